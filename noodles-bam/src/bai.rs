@@ -29,11 +29,9 @@ mod writer;
 
 pub use self::{index::Index, reader::Reader, writer::Writer};
 
-use std::{fs::File, io, path::Path};
-
-use noodles_bgzf::VirtualPosition;
+pub use noodles_tabix::{merge_chunks, optimize_chunks};
 
-use self::index::reference_sequence::{bin::Chunk, Bin};
+use std::{fs::File, io, path::Path};
 
 static MAGIC_NUMBER: &[u8] = b"BAI\x01";
 
@@ -82,101 +80,11 @@ where
     writer.write_index(index)
 }
 
-/// Merges a list of chunks into a list of non-overlapping chunks.
-///
-/// This is the same as calling [`optimize_chunks`] with a `min_offset` of 0.
-///
-/// # Examples
-///
-/// ```
-/// use noodles_bam::bai::{self, index::reference_sequence::bin::Chunk};
-/// use noodles_bgzf as bgzf;
-///
-/// let chunks = [
-///     Chunk::new(bgzf::VirtualPosition::from(2), bgzf::VirtualPosition::from(3)),
-///     Chunk::new(bgzf::VirtualPosition::from(5), bgzf::VirtualPosition::from(8)),
-///     Chunk::new(bgzf::VirtualPosition::from(7), bgzf::VirtualPosition::from(13)),
-///     Chunk::new(bgzf::VirtualPosition::from(21), bgzf::VirtualPosition::from(34)),
-/// ];
-///
-/// let actual = bai::merge_chunks(&chunks);
-///
-/// let expected = [
-///     Chunk::new(bgzf::VirtualPosition::from(2), bgzf::VirtualPosition::from(3)),
-///     Chunk::new(bgzf::VirtualPosition::from(5), bgzf::VirtualPosition::from(13)),
-///     Chunk::new(bgzf::VirtualPosition::from(21), bgzf::VirtualPosition::from(34)),
-/// ];
-///
-/// assert_eq!(actual, expected);
-/// ```
-pub fn merge_chunks(chunks: &[Chunk]) -> Vec<Chunk> {
-    optimize_chunks(chunks, VirtualPosition::default())
-}
-
-/// Optimizes a list of chunks into a list of non-overlapping chunks.
-///
-/// Unlike [`merge_chunks`], `min_offset` (typically from the linear index) is given to remove
-/// chunks that cannot be in the query.
-///
-/// # Examples
-///
-/// ```
-/// use noodles_bam::bai::{self, index::reference_sequence::bin::Chunk};
-/// use noodles_bgzf as bgzf;
-///
-/// let chunks = [
-///     Chunk::new(bgzf::VirtualPosition::from(2), bgzf::VirtualPosition::from(3)),
-///     Chunk::new(bgzf::VirtualPosition::from(5), bgzf::VirtualPosition::from(8)),
-///     Chunk::new(bgzf::VirtualPosition::from(7), bgzf::VirtualPosition::from(13)),
-///     Chunk::new(bgzf::VirtualPosition::from(21), bgzf::VirtualPosition::from(34)),
-/// ];
-/// let min_offset = bgzf::VirtualPosition::from(5);
-///
-/// let actual = bai::optimize_chunks(&chunks, min_offset);
-///
-/// let expected = [
-///     Chunk::new(bgzf::VirtualPosition::from(5), bgzf::VirtualPosition::from(13)),
-///     Chunk::new(bgzf::VirtualPosition::from(21), bgzf::VirtualPosition::from(34)),
-/// ];
-///
-/// assert_eq!(actual, expected);
-/// ```
-pub fn optimize_chunks(chunks: &[Chunk], min_offset: VirtualPosition) -> Vec<Chunk> {
-    let mut chunks: Vec<_> = chunks
-        .iter()
-        .filter(|c| c.end() > min_offset)
-        .copied()
-        .collect();
-
-    if chunks.is_empty() {
-        return chunks;
-    }
-
-    chunks.sort_unstable_by_key(|c| c.start());
-
-    // At worst, no chunks are merged, and the resulting list will be the same size as the input.
-    let mut merged_chunks = Vec::with_capacity(chunks.len());
-
-    // `chunks` is guaranteed to be non-empty.
-    let mut current_chunk = chunks[0];
-
-    for next_chunk in chunks.iter().skip(1) {
-        if next_chunk.start() > current_chunk.end() {
-            merged_chunks.push(current_chunk);
-            current_chunk = *next_chunk;
-        } else if current_chunk.end() < next_chunk.end() {
-            current_chunk = Chunk::new(current_chunk.start(), next_chunk.end());
-        }
-    }
-
-    merged_chunks.push(current_chunk);
-
-    merged_chunks
-}
-
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use noodles_bgzf::VirtualPosition;
+
+    use super::{index::reference_sequence::bin::Chunk, *};
 
     fn build_chunks() -> Vec<Chunk> {
         vec![
@@ -8,8 +8,8 @@ pub(crate) use self::builder::Builder;
 
 pub use self::{bin::Bin, metadata::Metadata};
 
-use bit_vec::BitVec;
 use noodles_bgzf as bgzf;
+use noodles_tabix as tabix;
 
 const WINDOW_SIZE: i32 = 16384;
 
@@ -112,12 +112,7 @@ impl ReferenceSequence {
     /// assert!(query_bins.is_empty());
     /// ```
     pub fn query(&self, start: i32, end: i32) -> Vec<&Bin> {
-        let region_bins = region_to_bins((start - 1) as usize, end as usize);
-
-        self.bins()
-            .iter()
-            .filter(|b| region_bins[b.id() as usize])
-            .collect()
+        tabix::index::reference_sequence::query_bins(self.bins(), start, end, Bin::id)
     }
 
     /// Finds in minimum start offset in the linear index for a given start position.
@@ -138,56 +133,24 @@ impl ReferenceSequence {
     }
 }
 
-// 0-based, [start, end)
-fn region_to_bins(start: usize, mut end: usize) -> BitVec {
-    end -= 1;
-
-    let mut bins = BitVec::from_elem(bin::MAX_ID, false);
-    bins.set(0, true);
-
-    for k in (1 + (start >> 26))..=(1 + (end >> 26)) {
-        bins.set(k, true);
-    }
-
-    for k in (9 + (start >> 23))..=(9 + (end >> 23)) {
-        bins.set(k, true);
-    }
-
-    for k in (73 + (start >> 20))..=(73 + (end >> 20)) {
-        bins.set(k, true);
-    }
-
-    for k in (585 + (start >> 17))..=(585 + (end >> 17)) {
-        bins.set(k, true);
-    }
-
-    for k in (4681 + (start >> 14))..=(4681 + (end >> 14)) {
-        bins.set(k, true);
-    }
-
-    bins
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_region_to_bins() {
-        // [8, 13]
-        let actual = region_to_bins(7, 13);
-        let mut expected = BitVec::from_elem(bin::MAX_ID, false);
-        for &k in &[0, 1, 9, 73, 585, 4681] {
-            expected.set(k, true);
-        }
-        assert_eq!(actual, expected);
-
-        // [63245986, 63245986]
-        let actual = region_to_bins(63245985, 63255986);
-        let mut expected = BitVec::from_elem(bin::MAX_ID, false);
-        for &k in &[0, 1, 16, 133, 1067, 8541] {
-            expected.set(k, true);
-        }
-        assert_eq!(actual, expected);
+    fn test_query() {
+        let reference_sequence = ReferenceSequence::new(
+            vec![
+                Bin::new(0, Vec::new()),
+                Bin::new(4681, Vec::new()),
+                Bin::new(4682, Vec::new()),
+            ],
+            Vec::new(),
+            None,
+        );
+
+        let query_bins = reference_sequence.query(8, 13);
+        let ids: Vec<_> = query_bins.iter().map(|bin| bin.id()).collect();
+        assert_eq!(ids, [0, 4681]);
     }
 }
@@ -8,10 +8,10 @@ use noodles_bgzf as bgzf;
 
 use super::{
     index::{
-        reference_sequence::{self, Metadata},
+        reference_sequence::{self, bin::Chunk, Bin, Metadata},
         ReferenceSequence,
     },
-    Bin, Chunk, Index, MAGIC_NUMBER,
+    Index, MAGIC_NUMBER,
 };
 
 /// A BAM index (BAI) reader.
@@ -0,0 +1,327 @@
+//! BAM mate fixing.
+//!
+//! This is the BAM equivalent of [`sam::fixmate`]. Mate fields are updated directly on the raw
+//! records, i.e., records are not converted to and from SAM records.
+
+use std::{convert::TryFrom, io};
+
+use noodles_sam::{
+    self as sam,
+    fixmate::MateRecord,
+    record::{
+        data::field::{Tag, Value},
+        Flags, Position,
+    },
+};
+
+use super::{
+    record::{data, reference_sequence_id, UNMAPPED_POSITION},
+    writer::record::{region_to_bin, write_data, UNMAPPED_BIN},
+    Record,
+};
+
+impl MateRecord for Record {
+    type Error = io::Error;
+
+    fn position(&self) -> Option<Position> {
+        self.position()
+    }
+
+    fn has_same_reference_sequence(&self, other: &Self) -> bool {
+        self.reference_sequence_id() == other.reference_sequence_id()
+    }
+
+    fn reference_len(&self) -> io::Result<u32> {
+        self.cigar().reference_len()
+    }
+
+    fn set_flags(&mut self, flags: Flags) {
+        self.set_flags(flags);
+    }
+
+    fn set_placement(&mut self, other: Option<&Self>) {
+        match other {
+            Some(other) => {
+                let pos = raw_position(other);
+
+                self.set_reference_sequence_id(raw_reference_sequence_id(other));
+                self.set_position(pos);
+
+                // § 4.2.1 BIN field calculation (2021-01-07): an unmapped read is given the bin of
+                // a 1-base region at its position.
+                self.set_bin(region_to_bin(pos, pos + 1) as u16);
+            }
+            None => {
+                self.set_reference_sequence_id(reference_sequence_id::UNMAPPED);
+                self.set_position(UNMAPPED_POSITION);
+                self.set_bin(UNMAPPED_BIN);
+            }
+        }
+    }
+
+    fn set_mate_placement(&mut self, mate: &Self) {
+        self.set_mate_reference_sequence_id(raw_reference_sequence_id(mate));
+        self.set_mate_position(raw_position(mate));
+    }
+
+    fn set_template_length(&mut self, template_length: i32) {
+        self.set_template_length(template_length);
+    }
+
+    fn set_mate_data(&mut self, mate: Option<&Self>) -> io::Result<()> {
+        let mut raw_data = remove_mate_fields(&self.data())?;
+
+        if let Some(mate) = mate {
+            let cigar = sam::record::Cigar::try_from(mate.cigar())?;
+            let mapping_quality = u8::from(mate.mapping_quality());
+
+            let mate_data = sam::record::Data::builder()
+                .insert(Tag::MateCigar, Value::String(cigar.to_string()))
+                .insert(
+                    Tag::MateMappingQuality,
+                    Value::Int32(i32::from(mapping_quality)),
+                )
+                .build();
+
+            write_data(&mut raw_data, &mate_data)?;
+        }
+
+        self.set_raw_data(&raw_data);
+
+        Ok(())
+    }
+}
+
+/// Fixes the mate fields of all read pairs in a list of name-grouped BAM records.
+///
+/// Records with the same read name must be adjacent. See [`sam::fixmate::fix_mates`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam as bam;
+///
+/// let mut records = vec![bam::Record::default()];
+/// bam::fixmate::fix_mates(&mut records)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn fix_mates(records: &mut [Record]) -> io::Result<()> {
+    sam::fixmate::try_fix_mates(records)
+}
+
+/// Fixes the mate fields of the primary read pair in a template.
+///
+/// See [`sam::fixmate::fix_template`].
+pub fn fix_template(records: &mut [Record]) -> io::Result<()> {
+    sam::fixmate::try_fix_template(records)
+}
+
+/// Fixes the mate fields of a pair of BAM records.
+///
+/// See [`sam::fixmate::fix_mate_pair`] for the fields that are updated.
+pub fn fix_mate_pair(record: &mut Record, mate: &mut Record) -> io::Result<()> {
+    sam::fixmate::try_fix_mate_pair(record, mate)
+}
+
+fn raw_reference_sequence_id(record: &Record) -> i32 {
+    record
+        .reference_sequence_id()
+        .map(i32::from)
+        .unwrap_or(reference_sequence_id::UNMAPPED)
+}
+
+fn raw_position(record: &Record) -> i32 {
+    record
+        .position()
+        .map(|position| i32::from(position) - 1)
+        .unwrap_or(UNMAPPED_POSITION)
+}
+
+// Returns a copy of the raw data without the mate CIGAR (`MC`) and mate mapping quality (`MQ`)
+// fields. Other fields are copied as is.
+fn remove_mate_fields(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dst = Vec::with_capacity(data.len());
+    let mut src = data;
+
+    loop {
+        let start = src;
+
+        let field = match data::Reader::new(&mut src).read_field()? {
+            Some(field) => field,
+            None => break,
+        };
+
+        let len = start.len() - src.len();
+
+        if !matches!(field.tag(), Tag::MateCigar | Tag::MateMappingQuality) {
+            dst.extend_from_slice(&start[..len]);
+        }
+    }
+
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use noodles_sam::{header::ReferenceSequence, record::Position};
+
+    use super::*;
+
+    #[test]
+    fn test_fix_mates() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 100))
+            .build();
+        let reference_sequences = header.reference_sequences();
+
+        let build = |flags: Flags, position: i32| -> Result<Record, Box<dyn std::error::Error>> {
+            let sam_record = sam::Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(flags)
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(position)?)
+                .set_cigar("4M".parse()?)
+                .set_mapping_quality(sam::record::MappingQuality::from(30))
+                .build();
+
+            Ok(Record::try_from_sam_record(
+                reference_sequences,
+                &sam_record,
+            )?)
+        };
+
+        let mut records = [
+            build(Flags::PAIRED | Flags::READ_1, 1)?,
+            build(
+                Flags::PAIRED | Flags::READ_2 | Flags::REVERSE_COMPLEMENTED,
+                9,
+            )?,
+        ];
+
+        fix_mates(&mut records)?;
+
+        assert_eq!(
+            records[0].flags(),
+            Flags::PAIRED | Flags::READ_1 | Flags::MATE_REVERSE_COMPLEMENTED
+        );
+        assert_eq!(
+            records[0].mate_reference_sequence_id().map(i32::from),
+            Some(0)
+        );
+        assert_eq!(records[0].mate_position().map(i32::from), Some(9));
+        assert_eq!(records[0].template_length(), 12);
+
+        let sam_record = records[1].try_into_sam_record(reference_sequences)?;
+        assert_eq!(sam_record.template_length(), -12);
+        assert_eq!(sam_record.data().to_string(), "MC:Z:4M\tMQ:i:30");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_mate_pair_keeps_other_data_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 100))
+            .build();
+        let reference_sequences = header.reference_sequences();
+
+        let build = |flags: Flags, position: Option<i32>, data: &str| {
+            let mut builder = sam::Record::builder()
+                .set_read_name("r0".parse()?)
+                .set_flags(flags)
+                .set_mapping_quality(sam::record::MappingQuality::from(60))
+                .set_data(data.parse()?);
+
+            if let Some(position) = position {
+                builder = builder
+                    .set_reference_sequence_name("sq0".parse()?)
+                    .set_position(Position::try_from(position)?)
+                    .set_cigar("4M".parse()?);
+            }
+
+            Record::try_from_sam_record(reference_sequences, &builder.build())
+                .map_err(Box::<dyn std::error::Error>::from)
+        };
+
+        let mut record = build(
+            Flags::PAIRED | Flags::READ_1,
+            Some(5),
+            "NM:i:0\tMC:Z:8M\tRG:Z:rg0",
+        )?;
+        let mut mate = build(
+            Flags::PAIRED | Flags::READ_2 | Flags::UNMAPPED,
+            None,
+            "MQ:i:1",
+        )?;
+
+        fix_mate_pair(&mut record, &mut mate)?;
+
+        assert_eq!(mate.reference_sequence_id().map(i32::from), Some(0));
+        assert_eq!(mate.position().map(i32::from), Some(5));
+        assert_eq!(mate.bin(), 4681);
+
+        let sam_record = record.try_into_sam_record(reference_sequences)?;
+        assert!(sam_record.flags().is_mate_unmapped());
+        assert_eq!(sam_record.data().to_string(), "NM:i:0\tRG:Z:rg0");
+
+        let sam_mate = mate.try_into_sam_record(reference_sequences)?;
+        assert_eq!(sam_mate.data().to_string(), "MC:Z:4M\tMQ:i:60");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_mate_pair_matches_sam_fixmate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 100))
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq1"), 100))
+            .build();
+        let reference_sequences = header.reference_sequences();
+
+        let pairs = [
+            (
+                "r0\t67\tsq0\t8\t60\t4M\t*\t0\t0\tACGT\t*",
+                "r0\t147\tsq0\t1\t30\t2S2M\t*\t0\t0\tACGT\t*",
+            ),
+            (
+                "r0\t65\tsq0\t8\t60\t4M\t*\t0\t0\tACGT\t*",
+                "r0\t129\tsq0\t8\t30\t2M2D2M\t*\t0\t0\tACGT\t*",
+            ),
+            (
+                "r0\t129\tsq0\t8\t60\t4M\t*\t0\t0\tACGT\t*",
+                "r0\t65\tsq0\t8\t30\t4M\t*\t0\t0\tACGT\t*",
+            ),
+            (
+                "r0\t67\tsq0\t8\t60\t4M\t*\t0\t0\tACGT\t*",
+                "r0\t133\t*\t0\t0\t*\t*\t0\t0\tACGT\t*",
+            ),
+            (
+                "r0\t65\tsq0\t8\t60\t4M\t*\t0\t0\tACGT\t*\tMC:Z:8M",
+                "r0\t145\tsq1\t1\t30\t4M\t*\t0\t0\tACGT\t*",
+            ),
+            (
+                "r0\t69\t*\t0\t0\t*\t*\t0\t0\tACGT\t*",
+                "r0\t137\t*\t0\t0\t*\t*\t0\t0\tACGT\t*",
+            ),
+        ];
+
+        for (raw_record, raw_mate) in &pairs {
+            let mut sam_record: sam::Record = raw_record.parse()?;
+            let mut sam_mate: sam::Record = raw_mate.parse()?;
+
+            let mut record = Record::try_from_sam_record(reference_sequences, &sam_record)?;
+            let mut mate = Record::try_from_sam_record(reference_sequences, &sam_mate)?;
+
+            sam::fixmate::fix_mate_pair(&mut sam_record, &mut sam_mate);
+            fix_mate_pair(&mut record, &mut mate)?;
+
+            assert_eq!(record.try_into_sam_record(reference_sequences)?, sam_record);
+            assert_eq!(mate.try_into_sam_record(reference_sequences)?, sam_mate);
+        }
+
+        Ok(())
+    }
+}
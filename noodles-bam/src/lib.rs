@@ -50,8 +50,14 @@
 //! ```
 
 pub mod bai;
+pub mod fixmate;
 pub mod reader;
 pub mod record;
+pub mod split;
+pub mod subsample;
+pub mod template;
+pub mod unaligned;
+pub mod validation;
 mod writer;
 
 pub use self::{reader::Reader, record::Record, writer::Writer};
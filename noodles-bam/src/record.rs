@@ -3,6 +3,7 @@
 pub mod cigar;
 mod convert;
 pub mod data;
+pub mod mismatched_positions;
 pub mod quality_scores;
 pub mod reference_sequence_id;
 pub mod sequence;
@@ -15,13 +16,15 @@ pub use self::{
 use std::{
     convert::TryFrom,
     ffi::{self, CStr},
-    fmt, mem,
+    fmt, io, mem,
     ops::{Deref, DerefMut},
 };
 
 use byteorder::{ByteOrder, LittleEndian};
 use noodles_sam as sam;
 
+use crate::writer;
+
 pub(crate) const UNMAPPED_POSITION: i32 = -1;
 
 /// A BAM record.
@@ -56,6 +59,40 @@ impl Record {
         self.0.len() as u32
     }
 
+    pub(crate) fn set_reference_sequence_id(&mut self, id: i32) {
+        LittleEndian::write_i32(&mut self.0[0..], id);
+    }
+
+    pub(crate) fn set_position(&mut self, pos: i32) {
+        LittleEndian::write_i32(&mut self.0[4..], pos);
+    }
+
+    pub(crate) fn set_bin(&mut self, bin: u16) {
+        LittleEndian::write_u16(&mut self.0[10..], bin);
+    }
+
+    pub(crate) fn set_flags(&mut self, flags: sam::record::Flags) {
+        LittleEndian::write_u16(&mut self.0[14..], u16::from(flags));
+    }
+
+    pub(crate) fn set_mate_reference_sequence_id(&mut self, id: i32) {
+        LittleEndian::write_i32(&mut self.0[20..], id);
+    }
+
+    pub(crate) fn set_mate_position(&mut self, pos: i32) {
+        LittleEndian::write_i32(&mut self.0[24..], pos);
+    }
+
+    pub(crate) fn set_template_length(&mut self, template_length: i32) {
+        LittleEndian::write_i32(&mut self.0[28..], template_length);
+    }
+
+    pub(crate) fn set_raw_data(&mut self, data: &[u8]) {
+        let offset = self.data_offset();
+        self.0.truncate(offset);
+        self.0.extend_from_slice(data);
+    }
+
     /// Returns the reference sequence ID of this record.
     ///
     /// The reference sequence ID is the index of the associated reference sequence in the SAM
@@ -307,17 +344,49 @@ impl Record {
     /// assert!(record.data().is_empty());
     /// ```
     pub fn data(&self) -> Data<'_> {
+        let offset = self.data_offset();
+        let len = self.block_size() as usize;
+
+        let bytes = &self.0[offset..len];
+        Data::new(bytes)
+    }
+
+    fn data_offset(&self) -> usize {
         let l_seq = self.l_seq();
 
-        let offset = 32
-            + (self.l_read_name() as usize)
+        32 + (self.l_read_name() as usize)
             + mem::size_of::<u32>() * (self.n_cigar_op() as usize)
             + ((self.l_seq() + 1) / 2) as usize
-            + l_seq as usize;
-        let len = self.block_size() as usize;
+            + l_seq as usize
+    }
 
-        let bytes = &self.0[offset..len];
-        Data::new(bytes)
+    /// Replaces the data fields of this record.
+    ///
+    /// Each [`sam::record::data::field::Value::Int32`] is encoded using the smallest integer type
+    /// that holds it. Arrays keep their subtype.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::{data::field::{Tag, Value}, Data};
+    ///
+    /// let data = Data::builder()
+    ///     .insert(Tag::AlignmentHitCount, Value::Int32(1))
+    ///     .build();
+    ///
+    /// let mut record = bam::Record::default();
+    /// record.set_data(&data)?;
+    ///
+    /// // NH:C:1
+    /// assert_eq!(&record.data()[..], [0x4e, 0x48, 0x43, 0x01]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn set_data(&mut self, data: &sam::record::Data) -> io::Result<()> {
+        let offset = self.data_offset();
+        self.0.truncate(offset);
+        writer::record::write_data(&mut self.0, data)
     }
 }
 
@@ -573,4 +642,26 @@ mod tests {
         assert_eq!(*record.data(), expected);
         Ok(())
     }
+
+    #[test]
+    fn test_set_data() -> io::Result<()> {
+        use sam::record::data::field::{Tag, Value};
+
+        let data = sam::record::Data::builder()
+            .insert(Tag::AlignmentHitCount, Value::Int32(1))
+            .insert_int_array(Tag::BaseModificationProbabilities, &[2, 255])
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            .build();
+
+        let mut record = Record::default();
+        record.set_data(&data)?;
+
+        let expected = [
+            0x4e, 0x48, 0x43, 0x01, // NH:C:1
+            0x4d, 0x4c, 0x42, 0x43, 0x02, 0x00, 0x00, 0x00, 0x02, 0xff, // ML:B:C,2,255
+        ];
+        assert_eq!(*record.data(), expected);
+
+        Ok(())
+    }
 }
@@ -5,9 +5,15 @@ mod ops;
 
 pub use self::{op::Op, ops::Ops};
 
-use std::{convert::TryFrom, fmt, io, ops::Deref};
+use std::{convert::TryFrom, fmt, io, ops::Deref, vec};
 
-use noodles_sam::{self as sam, record::cigar::op::Kind};
+use noodles_sam::{
+    self as sam,
+    record::{
+        cigar::{self, op::Kind, AlignedBlocks, AlignedPairs},
+        Position,
+    },
+};
 
 /// BAM record CIGAR.
 pub struct Cigar<'a>(&'a [u8]);
@@ -90,6 +96,179 @@ impl<'a> Cigar<'a> {
 
         Ok(len)
     }
+
+    /// Returns the read offset and reference position pairs of an alignment.
+    ///
+    /// See [`sam::record::Cigar::aligned_pairs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_bam::record::Cigar;
+    /// use noodles_sam::record::Position;
+    ///
+    /// // 1S2M
+    /// let data = [0x14, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// let pairs: Vec<_> = cigar.aligned_pairs(Position::try_from(8)?)?.collect();
+    ///
+    /// assert_eq!(pairs, [
+    ///     (Some(0), None),
+    ///     (Some(1), Some(Position::try_from(8)?)),
+    ///     (Some(2), Some(Position::try_from(9)?)),
+    /// ]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn aligned_pairs(
+        &self,
+        alignment_start: Position,
+    ) -> io::Result<AlignedPairs<vec::IntoIter<(Kind, u32)>>> {
+        self.kinds_and_lens()
+            .map(|ops| AlignedPairs::new(ops, alignment_start))
+    }
+
+    /// Returns the gapless aligned blocks of an alignment.
+    ///
+    /// See [`sam::record::Cigar::aligned_blocks`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_bam::record::Cigar;
+    /// use noodles_sam::record::Position;
+    ///
+    /// // 2M1D2M
+    /// let data = [0x20, 0x00, 0x00, 0x00, 0x12, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// let blocks: Vec<_> = cigar.aligned_blocks(Position::try_from(8)?)?.collect();
+    ///
+    /// assert_eq!(blocks.len(), 2);
+    /// assert_eq!(blocks[1].read_start(), 2);
+    /// assert_eq!(blocks[1].reference_start(), Position::try_from(11)?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn aligned_blocks(
+        &self,
+        alignment_start: Position,
+    ) -> io::Result<AlignedBlocks<vec::IntoIter<(Kind, u32)>>> {
+        self.kinds_and_lens()
+            .map(|ops| AlignedBlocks::new(ops, alignment_start))
+    }
+
+    /// Returns the reference position aligned to the given 0-based read offset.
+    ///
+    /// See [`sam::record::Cigar::reference_position`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_bam::record::Cigar;
+    /// use noodles_sam::record::Position;
+    ///
+    /// // 1S2M
+    /// let data = [0x14, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00];
+    /// let cigar = Cigar::new(&data);
+    /// let start = Position::try_from(8)?;
+    ///
+    /// assert_eq!(cigar.reference_position(start, 0)?, None);
+    /// assert_eq!(cigar.reference_position(start, 2)?, Some(Position::try_from(9)?));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reference_position(
+        &self,
+        alignment_start: Position,
+        read_offset: usize,
+    ) -> io::Result<Option<Position>> {
+        self.kinds_and_lens()
+            .map(|ops| cigar::reference_position(ops, alignment_start, read_offset))
+    }
+
+    /// Returns the 0-based read offset aligned to the given reference position.
+    ///
+    /// See [`sam::record::Cigar::read_offset`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_bam::record::Cigar;
+    /// use noodles_sam::record::Position;
+    ///
+    /// // 1S2M
+    /// let data = [0x14, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00];
+    /// let cigar = Cigar::new(&data);
+    /// let start = Position::try_from(8)?;
+    ///
+    /// assert_eq!(cigar.read_offset(start, Position::try_from(9)?)?, Some(2));
+    /// assert_eq!(cigar.read_offset(start, Position::try_from(10)?)?, None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn read_offset(
+        &self,
+        alignment_start: Position,
+        reference_position: Position,
+    ) -> io::Result<Option<usize>> {
+        self.kinds_and_lens()
+            .map(|ops| cigar::read_offset(ops, alignment_start, reference_position))
+    }
+
+    /// Calculates the unclipped start position of an alignment.
+    ///
+    /// See [`sam::record::Cigar::unclipped_start`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_bam::record::Cigar;
+    /// use noodles_sam::record::Position;
+    ///
+    /// // 1S2M
+    /// let data = [0x14, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// assert_eq!(cigar.unclipped_start(Position::try_from(8)?)?, 7);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn unclipped_start(&self, alignment_start: Position) -> io::Result<i32> {
+        self.kinds_and_lens()
+            .map(|ops| cigar::unclipped_start(ops, alignment_start))
+    }
+
+    /// Calculates the unclipped end position of an alignment.
+    ///
+    /// See [`sam::record::Cigar::unclipped_end`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_bam::record::Cigar;
+    /// use noodles_sam::record::Position;
+    ///
+    /// // 2M1S
+    /// let data = [0x20, 0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00];
+    /// let cigar = Cigar::new(&data);
+    ///
+    /// assert_eq!(cigar.unclipped_end(Position::try_from(8)?)?, 10);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn unclipped_end(&self, alignment_start: Position) -> io::Result<i32> {
+        self.kinds_and_lens()
+            .map(|ops| cigar::unclipped_end(ops, alignment_start))
+    }
+
+    fn kinds_and_lens(&self) -> io::Result<vec::IntoIter<(Kind, u32)>> {
+        self.ops()
+            .map(|result| result.map(|op| (op.kind(), op.len())))
+            .collect::<io::Result<Vec<_>>>()
+            .map(|ops| ops.into_iter())
+    }
 }
 
 impl<'a> fmt::Debug for Cigar<'a> {
@@ -167,4 +346,55 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_alignment_walk_matches_sam_record_cigar() -> Result<(), Box<dyn std::error::Error>> {
+        let start = Position::try_from(8)?;
+
+        for raw_cigar in &["1S2M1I1D1M", "2H3S3=1X2D4M1S2H", "2M0I3M1N2M", "4P2M", "3S"] {
+            let sam_cigar: sam::record::Cigar = raw_cigar.parse()?;
+
+            let mut data = Vec::new();
+
+            for op in sam_cigar.iter() {
+                let op = Op::new(op.kind(), op.len())?;
+                data.extend_from_slice(&u32::from(op).to_le_bytes());
+            }
+
+            let cigar = Cigar::new(&data);
+
+            assert_eq!(
+                cigar.aligned_pairs(start)?.collect::<Vec<_>>(),
+                sam_cigar.aligned_pairs(start).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                cigar.aligned_blocks(start)?.collect::<Vec<_>>(),
+                sam_cigar.aligned_blocks(start).collect::<Vec<_>>()
+            );
+
+            for read_offset in 0..16 {
+                assert_eq!(
+                    cigar.reference_position(start, read_offset)?,
+                    sam_cigar.reference_position(start, read_offset)
+                );
+            }
+
+            for n in 1..24 {
+                let position = Position::try_from(n)?;
+
+                assert_eq!(
+                    cigar.read_offset(start, position)?,
+                    sam_cigar.read_offset(start, position)
+                );
+            }
+
+            assert_eq!(
+                cigar.unclipped_start(start)?,
+                sam_cigar.unclipped_start(start)
+            );
+            assert_eq!(cigar.unclipped_end(start)?, sam_cigar.unclipped_end(start));
+        }
+
+        Ok(())
+    }
 }
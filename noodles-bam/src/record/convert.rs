@@ -119,7 +119,11 @@ impl Record {
             builder = builder.set_sequence(self.sequence().into());
         }
 
-        if !self.quality_scores().is_empty() {
+        // § 4.2.3 SEQ and QUAL encoding (2020-04-30): missing quality scores are stored as a list
+        // of 0xff.
+        let is_missing_quality_scores = self.quality_scores().iter().all(|&n| n == 0xff);
+
+        if !self.quality_scores().is_empty() && !is_missing_quality_scores {
             let quality_scores = self
                 .quality_scores()
                 .try_into()
@@ -188,6 +192,10 @@ mod tests {
     }
 
     fn build_record() -> io::Result<Record> {
+        build_record_with_quality_scores(&[0x1f, 0x1d, 0x1e, 0x20])
+    }
+
+    fn build_record_with_quality_scores(quality_scores: &[u8]) -> io::Result<Record> {
         let read_name =
             CString::new("r0").map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
         let flag = u16::from(sam::record::Flags::PAIRED | sam::record::Flags::READ_1);
@@ -223,7 +231,7 @@ mod tests {
         // seq
         writer.write_all(&[0x18, 0x42])?;
         // qual
-        writer.write_all(&[0x1f, 0x1d, 0x1e, 0x20])?;
+        writer.write_all(quality_scores)?;
         // data
         writer.write_all(&[
             0x4e, 0x4d, 0x43, 0x00, 0x50, 0x47, 0x5a, 0x53, 0x4e, 0x41, 0x50, 0x00,
@@ -271,4 +279,16 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_try_into_sam_record_with_missing_quality_scores(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let bam_record = build_record_with_quality_scores(&[0xff, 0xff, 0xff, 0xff])?;
+        let reference_sequences = build_reference_sequences();
+        let sam_record = bam_record.try_into_sam_record(&reference_sequences)?;
+
+        assert!(sam_record.quality_scores().is_empty());
+
+        Ok(())
+    }
 }
@@ -5,11 +5,14 @@ pub mod reader;
 
 pub use self::{field::Field, reader::Reader};
 
-use std::{convert::TryFrom, error, fmt, ops::Deref};
+use std::{convert::TryFrom, error, fmt, io, ops::Deref};
 
-use noodles_sam as sam;
+use noodles_sam::{
+    self as sam,
+    record::data::field::{value::Type, Tag},
+};
 
-use self::reader::Fields;
+use self::{field::Value, reader::Fields};
 
 /// BAM record data.
 ///
@@ -66,6 +69,380 @@ impl<'a> Data<'a> {
         let reader = Reader::new(self.0);
         reader.fields()
     }
+
+    /// Returns the field with the given tag.
+    ///
+    /// This reads fields until a field with the given tag is found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::record::{data::{field::Value, Field}, Data};
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// // NH:i:1  RG:Z:rg0
+    /// let raw_data = [
+    ///     0x4e, 0x48, 0x69, 0x01, 0x00, 0x00, 0x00,
+    ///     0x52, 0x47, 0x5a, 0x72, 0x67, 0x30, 0x00,
+    /// ];
+    /// let data = Data::new(&raw_data);
+    ///
+    /// let field = data.get(&Tag::ReadGroup).transpose()?;
+    /// assert_eq!(field, Some(Field::new(Tag::ReadGroup, Value::String(String::from("rg0")))));
+    ///
+    /// assert!(data.get(&Tag::EditDistance).is_none());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn get(&self, tag: &Tag) -> Option<io::Result<Field>> {
+        for result in self.fields() {
+            match result {
+                Ok(field) if field.tag() == tag => return Some(Ok(field)),
+                Ok(_) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None
+    }
+
+    /// Returns the character value of the field with the given tag.
+    ///
+    /// See [`sam::record::Data::get_char`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the data is invalid, the tag is declared with another type, or the
+    /// field value is not a character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// // TS:A:+
+    /// let data = Data::new(&[0x54, 0x53, 0x41, 0x2b]);
+    ///
+    /// assert_eq!(data.get_char(&Tag::TranscriptStrand)?, Some('+'));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn get_char(&self, tag: &Tag) -> Result<Option<char>, GetError> {
+        self.get_typed(tag, Type::Char)
+            .map(|value| value.and_then(|v| v.as_char()))
+    }
+
+    /// Returns the integer value of the field with the given tag.
+    ///
+    /// Any BAM integer type is widened to a 32-bit integer. See [`sam::record::Data::get_int32`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the data is invalid, the tag is declared with another type, the
+    /// field value is not an integer, or an unsigned 32-bit integer is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// // NM:c:1
+    /// let data = Data::new(&[0x4e, 0x4d, 0x63, 0x01]);
+    ///
+    /// assert_eq!(data.get_int32(&Tag::EditDistance)?, Some(1));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn get_int32(&self, tag: &Tag) -> Result<Option<i32>, GetError> {
+        match self.get_typed(tag, Type::Int32)? {
+            Some(Value::Int8(n)) => Ok(Some(i32::from(n))),
+            Some(Value::UInt8(n)) => Ok(Some(i32::from(n))),
+            Some(Value::Int16(n)) => Ok(Some(i32::from(n))),
+            Some(Value::UInt16(n)) => Ok(Some(i32::from(n))),
+            Some(Value::Int32(n)) => Ok(Some(n)),
+            Some(Value::UInt32(n)) => i32::try_from(n)
+                .map(Some)
+                .map_err(|_| GetError::OutOfRange(n)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the single-precision floating-point value of the field with the given tag.
+    ///
+    /// See [`sam::record::Data::get_float`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the data is invalid, the tag is declared with another type, or the
+    /// field value is not a single-precision floating-point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// // ZF:f:1.5
+    /// let data = Data::new(&[0x5a, 0x46, 0x66, 0x00, 0x00, 0xc0, 0x3f]);
+    ///
+    /// assert_eq!(data.get_float(&Tag::Other(String::from("ZF")))?, Some(1.5));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn get_float(&self, tag: &Tag) -> Result<Option<f32>, GetError> {
+        self.get_typed(tag, Type::Float)
+            .map(|value| value.and_then(|v| v.as_float()))
+    }
+
+    /// Returns the string value of the field with the given tag.
+    ///
+    /// See [`sam::record::Data::get_str`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the data is invalid, the tag is declared with another type, or the
+    /// field value is not a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// // RG:Z:rg0
+    /// let data = Data::new(&[0x52, 0x47, 0x5a, 0x72, 0x67, 0x30, 0x00]);
+    ///
+    /// assert_eq!(data.get_str(&Tag::ReadGroup)?, Some(String::from("rg0")));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn get_str(&self, tag: &Tag) -> Result<Option<String>, GetError> {
+        self.get_typed(tag, Type::String)
+            .map(|value| value.and_then(|v| v.as_str().map(String::from)))
+    }
+
+    /// Returns the hex string value of the field with the given tag.
+    ///
+    /// See [`sam::record::Data::get_hex`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the data is invalid, the tag is declared with another type, or the
+    /// field value is not a hex string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// // ZH:H:CAFE
+    /// let data = Data::new(&[0x5a, 0x48, 0x48, 0x43, 0x41, 0x46, 0x45, 0x00]);
+    ///
+    /// assert_eq!(data.get_hex(&Tag::Other(String::from("ZH")))?, Some(String::from("CAFE")));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn get_hex(&self, tag: &Tag) -> Result<Option<String>, GetError> {
+        self.get_typed(tag, Type::Hex)
+            .map(|value| value.and_then(|v| v.as_hex().map(String::from)))
+    }
+
+    /// Returns the array value of the field with the given tag.
+    ///
+    /// See [`sam::record::Data::get_array`].
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the data is invalid, the tag is declared with another type, or the
+    /// field value is not an array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::{data::field::Value, Data};
+    /// use noodles_sam::record::data::field::Tag;
+    ///
+    /// // ML:B:C,1,2
+    /// let data = Data::new(&[0x4d, 0x4c, 0x42, 0x43, 0x02, 0x00, 0x00, 0x00, 0x01, 0x02]);
+    ///
+    /// assert_eq!(
+    ///     data.get_array(&Tag::BaseModificationProbabilities)?,
+    ///     Some(Value::UInt8Array(vec![1, 2]))
+    /// );
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn get_array(&self, tag: &Tag) -> Result<Option<Value>, GetError> {
+        self.get_typed(tag, Type::Array)
+    }
+
+    /// Returns the read group (`RG`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// // RG:Z:rg0
+    /// let data = Data::new(&[0x52, 0x47, 0x5a, 0x72, 0x67, 0x30, 0x00]);
+    /// assert_eq!(data.read_group()?, Some(String::from("rg0")));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn read_group(&self) -> Result<Option<String>, GetError> {
+        self.get_str(&Tag::ReadGroup)
+    }
+
+    /// Returns the edit distance (`NM`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// // NM:c:1
+    /// let data = Data::new(&[0x4e, 0x4d, 0x63, 0x01]);
+    /// assert_eq!(data.edit_distance()?, Some(1));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn edit_distance(&self) -> Result<Option<i32>, GetError> {
+        self.get_int32(&Tag::EditDistance)
+    }
+
+    /// Returns the number of reported alignments (`NH`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// // NH:C:2
+    /// let data = Data::new(&[0x4e, 0x48, 0x43, 0x02]);
+    /// assert_eq!(data.alignment_hit_count()?, Some(2));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn alignment_hit_count(&self) -> Result<Option<i32>, GetError> {
+        self.get_int32(&Tag::AlignmentHitCount)
+    }
+
+    /// Returns the mismatched positions (`MD`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// // MD:Z:4
+    /// let data = Data::new(&[0x4d, 0x44, 0x5a, 0x34, 0x00]);
+    /// assert_eq!(data.mismatched_positions()?, Some(String::from("4")));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn mismatched_positions(&self) -> Result<Option<String>, GetError> {
+        self.get_str(&Tag::MismatchedPositions)
+    }
+
+    /// Returns the CIGAR of the mate (`MC`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// // MC:Z:4M
+    /// let data = Data::new(&[0x4d, 0x43, 0x5a, 0x34, 0x4d, 0x00]);
+    /// assert_eq!(data.mate_cigar()?, Some(String::from("4M")));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn mate_cigar(&self) -> Result<Option<String>, GetError> {
+        self.get_str(&Tag::MateCigar)
+    }
+
+    /// Returns the mapping quality of the mate (`MQ`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// // MQ:C:60
+    /// let data = Data::new(&[0x4d, 0x51, 0x43, 0x3c]);
+    /// assert_eq!(data.mate_mapping_quality()?, Some(60));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn mate_mapping_quality(&self) -> Result<Option<i32>, GetError> {
+        self.get_int32(&Tag::MateMappingQuality)
+    }
+
+    /// Returns the original quality scores (`OQ`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// // OQ:Z:NDLS
+    /// let data = Data::new(&[0x4f, 0x51, 0x5a, 0x4e, 0x44, 0x4c, 0x53, 0x00]);
+    /// assert_eq!(data.original_quality_scores()?, Some(String::from("NDLS")));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn original_quality_scores(&self) -> Result<Option<String>, GetError> {
+        self.get_str(&Tag::OriginalQualityScores)
+    }
+
+    /// Returns the sample barcode sequence (`BC`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// // BC:Z:ACGT
+    /// let data = Data::new(&[0x42, 0x43, 0x5a, 0x41, 0x43, 0x47, 0x54, 0x00]);
+    /// assert_eq!(data.sample_barcode_sequence()?, Some(String::from("ACGT")));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn sample_barcode_sequence(&self) -> Result<Option<String>, GetError> {
+        self.get_str(&Tag::SampleBarcodeSequence)
+    }
+
+    /// Returns the cell barcode ID (`CB`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// // CB:Z:cb0
+    /// let data = Data::new(&[0x43, 0x42, 0x5a, 0x63, 0x62, 0x30, 0x00]);
+    /// assert_eq!(data.cell_barcode_id()?, Some(String::from("cb0")));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn cell_barcode_id(&self) -> Result<Option<String>, GetError> {
+        self.get_str(&Tag::CellBarcodeId)
+    }
+
+    /// Returns the UMI sequence (`RX`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::record::Data;
+    /// // RX:Z:ACGT
+    /// let data = Data::new(&[0x52, 0x58, 0x5a, 0x41, 0x43, 0x47, 0x54, 0x00]);
+    /// assert_eq!(data.umi_sequence()?, Some(String::from("ACGT")));
+    /// # Ok::<(), noodles_bam::record::data::GetError>(())
+    /// ```
+    pub fn umi_sequence(&self) -> Result<Option<String>, GetError> {
+        self.get_str(&Tag::UmiSequence)
+    }
+
+    fn get_typed(&self, tag: &Tag, ty: Type) -> Result<Option<Value>, GetError> {
+        if let Some(declared_ty) = tag.ty() {
+            if declared_ty != ty {
+                return Err(GetError::UnexpectedType(tag.clone(), declared_ty, ty));
+            }
+        }
+
+        match self.get(tag).transpose().map_err(GetError::Invalid)? {
+            Some(field) => {
+                let actual_ty = Type::from(field.value().ty());
+
+                if actual_ty == ty {
+                    Ok(Some(field.value().clone()))
+                } else {
+                    Err(GetError::UnexpectedType(tag.clone(), ty, actual_ty))
+                }
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 impl<'a> Deref for Data<'a> {
@@ -82,6 +459,42 @@ impl<'a> fmt::Debug for Data<'a> {
     }
 }
 
+/// An error returned when a typed BAM data field value fails to be read.
+#[derive(Debug)]
+pub enum GetError {
+    /// The data is invalid.
+    Invalid(io::Error),
+    /// The type is unexpected for the tag.
+    ///
+    /// This holds the tag, the expected type, and the actual type.
+    UnexpectedType(Tag, Type, Type),
+    /// A BAM u32 value is out of range for a SAM i32 value.
+    OutOfRange(u32),
+}
+
+impl error::Error for GetError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(e) => write!(f, "invalid data: {}", e),
+            Self::UnexpectedType(tag, expected, actual) => write!(
+                f,
+                "unexpected type for {}: expected {}, got {}",
+                tag, expected, actual
+            ),
+            Self::OutOfRange(value) => write!(f, "value is out of range: {}", value),
+        }
+    }
+}
+
 /// An error returned when BAM data fails to convert to SAM data.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TryFromDataError {
@@ -175,6 +588,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_get_typed() -> Result<(), GetError> {
+        let raw_data = [
+            0x52, 0x47, 0x5a, 0x72, 0x67, 0x30, 0x00, // RG:Z:rg0
+            0x4e, 0x4d, 0x5a, 0x32, 0x00, // NM:Z:2
+            0x5a, 0x4e, 0x53, 0x08, 0x00, // ZN:S:8
+            0x5a, 0x49, 0x49, 0xff, 0xff, 0xff, 0xff, // ZI:I:4294967295
+        ];
+        let data = Data::new(&raw_data);
+
+        assert_eq!(data.read_group()?, Some(String::from("rg0")));
+        assert_eq!(data.mate_cigar()?, None);
+
+        assert!(matches!(
+            data.edit_distance(),
+            Err(GetError::UnexpectedType(
+                Tag::EditDistance,
+                Type::Int32,
+                Type::String
+            ))
+        ));
+
+        let tag = Tag::Other(String::from("ZN"));
+        assert_eq!(data.get_int32(&tag)?, Some(8));
+        assert!(matches!(
+            data.get_str(&tag),
+            Err(GetError::UnexpectedType(_, Type::String, Type::Int32))
+        ));
+
+        let tag = Tag::Other(String::from("ZI"));
+        assert!(matches!(
+            data.get_int32(&tag),
+            Err(GetError::OutOfRange(u32::MAX))
+        ));
+
+        let data = Data::new(&[0x52, 0x47, 0x3f]);
+        assert!(matches!(data.read_group(), Err(GetError::Invalid(_))));
+
+        Ok(())
+    }
+
     #[test]
     fn test_try_from_data_for_sam_record_data_with_out_of_range_u32_value() {
         let raw_data = [
@@ -2,6 +2,8 @@
 
 use std::{convert::TryFrom, error, fmt};
 
+use noodles_sam::record::data::field::value::Type as SamType;
+
 /// A BAM record data field value type.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Type {
@@ -86,6 +88,21 @@ impl From<Type> for char {
     }
 }
 
+impl From<Type> for SamType {
+    fn from(ty: Type) -> Self {
+        match ty {
+            Type::Char => Self::Char,
+            Type::Int8 | Type::Int16 | Type::Int32 | Type::UInt8 | Type::UInt16 | Type::UInt32 => {
+                Self::Int32
+            }
+            Type::Float => Self::Float,
+            Type::String => Self::String,
+            Type::Hex => Self::Hex,
+            Type::Array => Self::Array,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +153,19 @@ mod tests {
         assert_eq!(char::from(Type::Hex), 'H');
         assert_eq!(char::from(Type::Array), 'B');
     }
+
+    #[test]
+    fn test_from_type_for_sam_type() {
+        assert_eq!(SamType::from(Type::Char), SamType::Char);
+        assert_eq!(SamType::from(Type::Int8), SamType::Int32);
+        assert_eq!(SamType::from(Type::UInt8), SamType::Int32);
+        assert_eq!(SamType::from(Type::Int16), SamType::Int32);
+        assert_eq!(SamType::from(Type::UInt16), SamType::Int32);
+        assert_eq!(SamType::from(Type::Int32), SamType::Int32);
+        assert_eq!(SamType::from(Type::UInt32), SamType::Int32);
+        assert_eq!(SamType::from(Type::Float), SamType::Float);
+        assert_eq!(SamType::from(Type::String), SamType::String);
+        assert_eq!(SamType::from(Type::Hex), SamType::Hex);
+        assert_eq!(SamType::from(Type::Array), SamType::Array);
+    }
 }
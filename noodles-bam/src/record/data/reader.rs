@@ -103,7 +103,7 @@ where
         Fields::new(self)
     }
 
-    fn read_field(&mut self) -> io::Result<Option<Field>> {
+    pub(crate) fn read_field(&mut self) -> io::Result<Option<Field>> {
         let tag = match read_tag(&mut self.inner) {
             Ok(ref data) => str::from_utf8(data)
                 .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
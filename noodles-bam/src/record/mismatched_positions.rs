@@ -0,0 +1,151 @@
+//! BAM record mismatched positions (`MD`) and edit distance (`NM`) calculation.
+
+use std::{convert::TryFrom, io};
+
+use noodles_sam::{
+    self as sam,
+    record::mismatched_positions::{self, Discrepancy, MismatchedPositions},
+};
+
+use super::Record;
+
+/// Calculates the mismatched positions (`MD`) and edit distance (`NM`) of a record.
+///
+/// `reference_sequence` is the entire sequence of the reference the record is mapped to.
+///
+/// See [`noodles_sam::record::mismatched_positions::calculate`].
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bam::{self as bam, record::mismatched_positions};
+///
+/// let record = bam::Record::default();
+/// assert!(mismatched_positions::calculate(&record, b"ACGT").is_err());
+/// ```
+pub fn calculate(
+    record: &Record,
+    reference_sequence: &[u8],
+) -> io::Result<(MismatchedPositions, u32)> {
+    let position = record
+        .position()
+        .ok_or_else(|| invalid_input(mismatched_positions::CalculateError::Unmapped))?;
+
+    let cigar = sam::record::Cigar::try_from(record.cigar())?;
+    let sequence = sam::record::Sequence::from(record.sequence());
+
+    mismatched_positions::calculate_alignment(position, &cigar, &sequence, reference_sequence)
+        .map_err(invalid_input)
+}
+
+/// Compares the mismatched positions (`MD`) and edit distance (`NM`) fields of a record to
+/// calculated values.
+///
+/// An empty list is returned if both fields are present and equal to the calculated values.
+///
+/// See [`noodles_sam::record::mismatched_positions::validate`].
+pub fn validate(record: &Record, reference_sequence: &[u8]) -> io::Result<Vec<Discrepancy>> {
+    let (mismatched_positions, edit_distance) = calculate(record, reference_sequence)?;
+    let data = sam::record::Data::try_from(record.data()).map_err(invalid_data)?;
+
+    Ok(mismatched_positions::validate(
+        &data,
+        &mismatched_positions,
+        edit_distance,
+    ))
+}
+
+/// Calculates and sets the mismatched positions (`MD`) and edit distance (`NM`) fields of a
+/// record.
+///
+/// Existing fields are replaced in place. Missing fields are appended.
+pub fn update(record: &mut Record, reference_sequence: &[u8]) -> io::Result<()> {
+    let (mismatched_positions, edit_distance) = calculate(record, reference_sequence)?;
+    let mut data = sam::record::Data::try_from(record.data()).map_err(invalid_data)?;
+    mismatched_positions::update(&mut data, mismatched_positions, edit_distance);
+    record.set_data(&data)
+}
+
+fn invalid_input<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidInput, e)
+}
+
+fn invalid_data<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use sam::{header::ReferenceSequences, record::Position};
+
+    use super::*;
+
+    fn build_record() -> Result<Record, Box<dyn std::error::Error>> {
+        let reference_sequences: ReferenceSequences = vec![(
+            String::from("sq0"),
+            sam::header::ReferenceSequence::new(String::from("sq0"), 8),
+        )]
+        .into_iter()
+        .collect();
+
+        let sam_record = sam::Record::builder()
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(2)?)
+            .set_cigar("3M1D2M".parse()?)
+            .set_sequence("ATGTA".parse()?)
+            .set_data("NM:i:0\tRG:Z:rg0".parse()?)
+            .build();
+
+        let record = Record::try_from_sam_record(&reference_sequences, &sam_record)?;
+
+        Ok(record)
+    }
+
+    #[test]
+    fn test_calculate() -> Result<(), Box<dyn std::error::Error>> {
+        let record = build_record()?;
+        let (mismatched_positions, edit_distance) = calculate(&record, b"NACGTTA")?;
+        assert_eq!(mismatched_positions.to_string(), "1C1^T2");
+        assert_eq!(edit_distance, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::record::data::field::Value;
+
+        let record = build_record()?;
+        let discrepancies = validate(&record, b"NACGTTA")?;
+
+        assert_eq!(
+            discrepancies,
+            [
+                Discrepancy::MissingMismatchedPositions("1C1^T2".parse()?),
+                Discrepancy::EditDistance {
+                    actual: Value::Int32(0),
+                    expected: 2
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = build_record()?;
+        update(&mut record, b"NACGTTA")?;
+
+        let data = sam::record::Data::try_from(record.data())?;
+        assert_eq!(data.to_string(), "NM:i:2\tRG:Z:rg0\tMD:Z:1C1^T2");
+        assert!(validate(&record, b"NACGTTA")?.is_empty());
+
+        Ok(())
+    }
+}
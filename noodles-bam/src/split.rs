@@ -0,0 +1,584 @@
+//! BAM record splitting by read group.
+//!
+//! A [`Splitter`] routes each record to an output per read group, library, or sample, using the
+//! read group (`RG`) data field of the record and the read groups (`@RG`) in the SAM header. Each
+//! output gets a header with all reference sequences (`@SQ`) but only the read groups in its
+//! group.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io;
+//! use noodles_bam::{self as bam, split::{Builder, GroupBy, Mode}};
+//! use noodles_sam::{
+//!     self as sam,
+//!     header::ReadGroup,
+//!     record::data::field::{Tag, Value},
+//! };
+//!
+//! let header = sam::Header::builder()
+//!     .add_read_group(ReadGroup::new(String::from("rg0")))
+//!     .add_read_group(ReadGroup::new(String::from("rg1")))
+//!     .build();
+//!
+//! let mut splitter = Builder::default()
+//!     .set_group_by(GroupBy::ReadGroup)
+//!     .build(&header, |_: &str, _: Mode| Ok(Vec::new()));
+//!
+//! let data = sam::record::Data::builder()
+//!     .insert(Tag::ReadGroup, Value::String(String::from("rg1")))
+//!     .build();
+//! let mut record = bam::Record::default();
+//! record.set_data(&data)?;
+//!
+//! assert_eq!(splitter.write_record(&record)?, Some("rg1"));
+//! # Ok::<(), io::Error>(())
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use noodles_sam::{self as sam, header::ReadGroup, record::data::field::Tag};
+
+use super::{Record, Writer};
+
+const DEFAULT_MAX_OPEN_WRITERS: usize = 64;
+const DEFAULT_FALLBACK_GROUP: &str = "unassigned";
+
+/// The read group attribute records are grouped by.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum GroupBy {
+    /// Read group ID (`RG:ID`).
+    #[default]
+    ReadGroup,
+    /// Library (`RG:LB`).
+    Library,
+    /// Sample (`RG:SM`).
+    Sample,
+}
+
+/// The handling of records that cannot be assigned to a group.
+///
+/// A record is unassigned when it has no read group data field, its read group is not in the
+/// header, or its read group does not have the grouping attribute.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Fallback {
+    /// Writes the record to the given group.
+    Group(String),
+    /// Skips the record.
+    Skip,
+    /// Returns an error.
+    Error,
+}
+
+impl Default for Fallback {
+    fn default() -> Self {
+        Self::Group(String::from(DEFAULT_FALLBACK_GROUP))
+    }
+}
+
+/// The mode an output is opened with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// The output is opened for the first time and is to be created.
+    Create,
+    /// The output was previously closed and is to be appended to.
+    ///
+    /// A header is not written again.
+    Append,
+}
+
+/// A BAM splitter builder.
+#[derive(Debug)]
+pub struct Builder {
+    group_by: GroupBy,
+    fallback: Fallback,
+    max_open_writers: usize,
+}
+
+impl Builder {
+    /// Sets the read group attribute records are grouped by.
+    ///
+    /// By default, records are grouped by read group ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::split::{Builder, GroupBy};
+    /// let builder = Builder::default().set_group_by(GroupBy::Sample);
+    /// ```
+    pub fn set_group_by(mut self, group_by: GroupBy) -> Self {
+        self.group_by = group_by;
+        self
+    }
+
+    /// Sets the handling of records that cannot be assigned to a group.
+    ///
+    /// By default, these are written to the group `unassigned`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::split::{Builder, Fallback};
+    /// let builder = Builder::default().set_fallback(Fallback::Skip);
+    /// ```
+    pub fn set_fallback(mut self, fallback: Fallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Sets the maximum number of outputs that are open at once.
+    ///
+    /// When the limit is reached, the least recently written output is finished and closed. It is
+    /// reopened in [`Mode::Append`] when it receives another record. The default is 64. A value
+    /// of 0 is treated as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::split::Builder;
+    /// let builder = Builder::default().set_max_open_writers(16);
+    /// ```
+    pub fn set_max_open_writers(mut self, max_open_writers: usize) -> Self {
+        self.max_open_writers = max_open_writers.max(1);
+        self
+    }
+
+    /// Builds a splitter.
+    ///
+    /// `open` is called with a group name and mode to open the output of a group. See
+    /// [`file_opener`] to write to files in a directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::split::{Builder, Mode};
+    /// use noodles_sam as sam;
+    ///
+    /// let header = sam::Header::default();
+    /// let splitter = Builder::default().build(&header, |_: &str, _: Mode| Ok(Vec::new()));
+    /// ```
+    pub fn build<W, F>(self, header: &sam::Header, open: F) -> Splitter<W, F>
+    where
+        W: Write,
+        F: FnMut(&str, Mode) -> io::Result<W>,
+    {
+        let group_by = self.group_by;
+
+        let groups = header
+            .read_groups()
+            .iter()
+            .filter_map(|(id, read_group)| {
+                group_name(read_group, group_by).map(|name| (id.clone(), name.into()))
+            })
+            .collect();
+
+        Splitter {
+            header: header.clone(),
+            group_by,
+            fallback: self.fallback,
+            max_open_writers: self.max_open_writers,
+            open,
+            groups,
+            writers: HashMap::new(),
+            lru: VecDeque::new(),
+            opened: HashSet::new(),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            group_by: GroupBy::default(),
+            fallback: Fallback::default(),
+            max_open_writers: DEFAULT_MAX_OPEN_WRITERS,
+        }
+    }
+}
+
+/// A BAM splitter.
+///
+/// Outputs are finished when the splitter is dropped. Use [`Splitter::finish`] to handle errors.
+pub struct Splitter<W, F>
+where
+    W: Write,
+{
+    header: sam::Header,
+    group_by: GroupBy,
+    fallback: Fallback,
+    max_open_writers: usize,
+    open: F,
+    groups: HashMap<String, String>,
+    writers: HashMap<String, Writer<W>>,
+    lru: VecDeque<String>,
+    opened: HashSet<String>,
+}
+
+impl<W, F> Splitter<W, F>
+where
+    W: Write,
+    F: FnMut(&str, Mode) -> io::Result<W>,
+{
+    /// Returns the name of the group the given record is assigned to.
+    ///
+    /// This returns `None` if the record is unassigned and the fallback is not a group.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the record data is invalid, or the record is unassigned and the
+    /// fallback is [`Fallback::Error`].
+    pub fn group(&self, record: &Record) -> io::Result<Option<&str>> {
+        let read_group_id = match record.data().get(&Tag::ReadGroup).transpose()? {
+            Some(field) => Some(field.value().as_str().map(|s| s.to_string()).ok_or_else(
+                || io::Error::new(io::ErrorKind::InvalidData, "invalid RG field type"),
+            )?),
+            None => None,
+        };
+
+        if let Some(name) = read_group_id.and_then(|id| self.groups.get(&id)) {
+            return Ok(Some(name));
+        }
+
+        match &self.fallback {
+            Fallback::Group(name) => Ok(Some(name)),
+            Fallback::Skip => Ok(None),
+            Fallback::Error => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "record cannot be assigned to a read group",
+            )),
+        }
+    }
+
+    /// Returns the header written to the output of the given group.
+    ///
+    /// This is the input header with only the read groups in the group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::split::{Builder, GroupBy, Mode};
+    /// use noodles_sam::{self as sam, header::ReadGroup};
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_read_group(ReadGroup::builder().set_id("rg0").set_sample("sample0").build())
+    ///     .add_read_group(ReadGroup::builder().set_id("rg1").set_sample("sample1").build())
+    ///     .add_read_group(ReadGroup::builder().set_id("rg2").set_sample("sample0").build())
+    ///     .build();
+    ///
+    /// let splitter = Builder::default()
+    ///     .set_group_by(GroupBy::Sample)
+    ///     .build(&header, |_: &str, _: Mode| Ok(Vec::new()));
+    ///
+    /// let group_header = splitter.group_header("sample0");
+    /// let ids: Vec<_> = group_header.read_groups().keys().collect();
+    /// assert_eq!(ids, ["rg0", "rg2"]);
+    /// ```
+    pub fn group_header(&self, name: &str) -> sam::Header {
+        let mut header = self.header.clone();
+        let fallback_name = match &self.fallback {
+            Fallback::Group(fallback_name) => Some(fallback_name.as_str()),
+            _ => None,
+        };
+
+        let group_by = self.group_by;
+
+        header.read_groups_mut().retain(|_, read_group| {
+            group_name(read_group, group_by).or(fallback_name) == Some(name)
+        });
+
+        header
+    }
+
+    /// Writes a record to the output of its group.
+    ///
+    /// This returns the name of the group the record was written to or `None` if it was skipped.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the record cannot be assigned (see [`Self::group`]) or the output
+    /// fails to open or write.
+    pub fn write_record(&mut self, record: &Record) -> io::Result<Option<&str>> {
+        let name = match self.group(record)? {
+            Some(name) => name.to_string(),
+            None => return Ok(None),
+        };
+
+        if self.writers.contains_key(&name) {
+            if let Some(i) = self.lru.iter().position(|n| n == &name) {
+                self.lru.remove(i);
+            }
+        } else {
+            self.open_writer(&name)?;
+        }
+
+        self.lru.push_back(name.clone());
+
+        let writer = self.writers.get_mut(&name).expect("missing writer");
+        writer.write_record(record)?;
+
+        Ok(self.lru.back().map(|n| n.as_str()))
+    }
+
+    /// Finishes and closes all outputs.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if any output fails to finish.
+    pub fn finish(mut self) -> io::Result<()> {
+        for (_, mut writer) in self.writers.drain() {
+            writer.try_finish()?;
+        }
+
+        Ok(())
+    }
+
+    fn open_writer(&mut self, name: &str) -> io::Result<()> {
+        while self.writers.len() >= self.max_open_writers {
+            match self.lru.pop_front() {
+                Some(evicted_name) => {
+                    if let Some(mut writer) = self.writers.remove(&evicted_name) {
+                        writer.try_finish()?;
+                    }
+                }
+                None => break,
+            }
+        }
+
+        let mode = if self.opened.contains(name) {
+            Mode::Append
+        } else {
+            Mode::Create
+        };
+
+        let mut writer = (self.open)(name, mode).map(Writer::new)?;
+
+        if mode == Mode::Create {
+            let header = self.group_header(name);
+            writer.write_header(&header)?;
+            writer.write_reference_sequences(header.reference_sequences())?;
+            self.opened.insert(name.into());
+        }
+
+        self.writers.insert(name.into(), writer);
+
+        Ok(())
+    }
+}
+
+/// Returns an output opener that writes each group to `<dst>/<group>.bam`.
+///
+/// Group names are used as file names as is. An error is returned when a group is opened if its
+/// name is not a plain file name, e.g., if it is empty, `.` or `..`, or contains a path separator.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_bam::split::{self, Builder};
+/// use noodles_sam as sam;
+///
+/// let header = sam::Header::default();
+/// let splitter = Builder::default().build(&header, split::file_opener("out"));
+/// ```
+pub fn file_opener<P>(dst: P) -> impl FnMut(&str, Mode) -> io::Result<File>
+where
+    P: AsRef<Path>,
+{
+    let dst = dst.as_ref().to_path_buf();
+
+    move |name, mode| {
+        let path = build_path(&dst, name)?;
+
+        match mode {
+            Mode::Create => File::create(path),
+            Mode::Append => OpenOptions::new().append(true).open(path),
+        }
+    }
+}
+
+fn build_path(dst: &Path, name: &str) -> io::Result<PathBuf> {
+    let mut components = Path::new(name).components();
+
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) if !name.contains(&['/', '\\'][..]) => {
+            Ok(dst.join(format!("{}.bam", name)))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid group name: {}", name),
+        )),
+    }
+}
+
+fn group_name(read_group: &ReadGroup, group_by: GroupBy) -> Option<&str> {
+    match group_by {
+        GroupBy::ReadGroup => Some(read_group.id()),
+        GroupBy::Library => read_group.library(),
+        GroupBy::Sample => read_group.sample(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use noodles_sam::record::data::field::Value;
+
+    use super::*;
+    use crate::Reader;
+
+    fn build_record(read_group_id: Option<&str>) -> io::Result<Record> {
+        let mut builder = sam::record::Data::builder();
+
+        if let Some(id) = read_group_id {
+            builder = builder.insert(Tag::ReadGroup, Value::String(id.into()));
+        }
+
+        let mut record = Record::default();
+        record.set_data(&builder.build())?;
+
+        Ok(record)
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_read_group(
+                ReadGroup::builder()
+                    .set_id("rg0")
+                    .set_library("lib0")
+                    .build(),
+            )
+            .add_read_group(
+                ReadGroup::builder()
+                    .set_id("rg1")
+                    .set_library("lib1")
+                    .build(),
+            )
+            .add_read_group(ReadGroup::builder().set_id("rg2").build())
+            .build();
+
+        let outputs: Rc<RefCell<HashMap<String, SharedBuffer>>> = Rc::default();
+        let modes = Rc::new(RefCell::new(Vec::new()));
+
+        let mut splitter = {
+            let outputs = outputs.clone();
+            let modes = modes.clone();
+
+            Builder::default()
+                .set_group_by(GroupBy::Library)
+                .set_max_open_writers(1)
+                .build(&header, move |name: &str, mode| {
+                    modes.borrow_mut().push((name.to_string(), mode));
+                    let mut outputs = outputs.borrow_mut();
+                    Ok(outputs.entry(name.into()).or_default().clone())
+                })
+        };
+
+        assert_eq!(
+            splitter.write_record(&build_record(Some("rg0"))?)?,
+            Some("lib0")
+        );
+        assert_eq!(
+            splitter.write_record(&build_record(Some("rg1"))?)?,
+            Some("lib1")
+        );
+        assert_eq!(
+            splitter.write_record(&build_record(Some("rg0"))?)?,
+            Some("lib0")
+        );
+        assert_eq!(
+            splitter.write_record(&build_record(Some("rg2"))?)?,
+            Some(DEFAULT_FALLBACK_GROUP)
+        );
+        assert_eq!(
+            splitter.write_record(&build_record(None)?)?,
+            Some(DEFAULT_FALLBACK_GROUP)
+        );
+
+        splitter.finish()?;
+
+        assert_eq!(
+            *modes.borrow(),
+            [
+                (String::from("lib0"), Mode::Create),
+                (String::from("lib1"), Mode::Create),
+                (String::from("lib0"), Mode::Append),
+                (String::from(DEFAULT_FALLBACK_GROUP), Mode::Create),
+            ]
+        );
+
+        let outputs = outputs.borrow();
+
+        let data = outputs["lib0"].0.borrow();
+        let mut reader = Reader::new(&data[..]);
+        let group_header: sam::Header = reader.read_header()?.parse()?;
+        reader.read_reference_sequences()?;
+        let ids: Vec<_> = group_header.read_groups().keys().collect();
+        assert_eq!(ids, ["rg0"]);
+        assert_eq!(reader.records().count(), 2);
+
+        let data = outputs[DEFAULT_FALLBACK_GROUP].0.borrow();
+        let mut reader = Reader::new(&data[..]);
+        let group_header: sam::Header = reader.read_header()?.parse()?;
+        reader.read_reference_sequences()?;
+        let ids: Vec<_> = group_header.read_groups().keys().collect();
+        assert_eq!(ids, ["rg2"]);
+        assert_eq!(reader.records().count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_fallback() -> io::Result<()> {
+        let header = sam::Header::builder()
+            .add_read_group(ReadGroup::new(String::from("rg0")))
+            .build();
+
+        let mut splitter = Builder::default()
+            .set_fallback(Fallback::Skip)
+            .build(&header, |_: &str, _: Mode| Ok(Vec::new()));
+
+        assert_eq!(splitter.write_record(&build_record(Some("rg1"))?)?, None);
+        assert_eq!(splitter.write_record(&build_record(None)?)?, None);
+
+        let mut splitter = Builder::default()
+            .set_fallback(Fallback::Error)
+            .build(&header, |_: &str, _: Mode| Ok(Vec::new()));
+
+        assert!(splitter.write_record(&build_record(None)?).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_path() -> io::Result<()> {
+        assert_eq!(
+            build_path(Path::new("out"), "rg0")?,
+            PathBuf::from("out").join("rg0.bam")
+        );
+
+        for name in &["", ".", "..", "../rg0", "rg0/..", "/rg0", "a/b", "a\\b"] {
+            assert!(build_path(Path::new("out"), name).is_err(), "{}", name);
+        }
+
+        Ok(())
+    }
+}
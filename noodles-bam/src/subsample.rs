@@ -0,0 +1,194 @@
+//! BAM record subsampling by template.
+//!
+//! This wraps [`noodles_sam::subsample::Subsampler`] for BAM records and estimates input sizes from the
+//! metadata of a BAM index.
+
+use std::io;
+
+use noodles_sam::{header::ReferenceSequences, subsample::Subsampler};
+
+use super::{bai, Record};
+
+/// Returns whether the given record is kept.
+///
+/// # Errors
+///
+/// An error is returned if the read name is invalid.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam::{self as bam, subsample};
+/// use noodles_sam::subsample::Subsampler;
+///
+/// let subsampler = Subsampler::new(8, 1.0);
+/// assert!(subsample::keep(&subsampler, &bam::Record::default())?);
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn keep(subsampler: &Subsampler, record: &Record) -> io::Result<bool> {
+    record
+        .read_name()
+        .map(|read_name| subsampler.keep_read_name(read_name.to_bytes()))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Returns the number of records in the indexed BAM.
+///
+/// This is the sum of the mapped and unmapped record counts in the reference sequence metadata
+/// and the unplaced unmapped record count. Reference sequences without metadata are counted as
+/// having no records.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bam::{bai, subsample};
+/// let index = bai::Index::new(Vec::new(), Some(13));
+/// assert_eq!(subsample::record_count(&index), 13);
+/// ```
+pub fn record_count(index: &bai::Index) -> u64 {
+    let placed_record_count: u64 = index
+        .reference_sequences()
+        .iter()
+        .filter_map(|reference_sequence| reference_sequence.metadata())
+        .map(|metadata| metadata.mapped_record_count() + metadata.unmapped_record_count())
+        .sum();
+
+    placed_record_count + index.unplaced_unmapped_read_count().unwrap_or_default()
+}
+
+/// Returns the number of mapped records in the indexed BAM.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bam::{bai, subsample};
+/// let index = bai::Index::default();
+/// assert_eq!(subsample::mapped_record_count(&index), 0);
+/// ```
+pub fn mapped_record_count(index: &bai::Index) -> u64 {
+    index
+        .reference_sequences()
+        .iter()
+        .filter_map(|reference_sequence| reference_sequence.metadata())
+        .map(|metadata| metadata.mapped_record_count())
+        .sum()
+}
+
+/// Creates a subsampler that keeps about the given number of records of the indexed BAM.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bam::{bai, subsample};
+/// let index = bai::Index::new(Vec::new(), Some(400));
+/// let subsampler = subsample::with_target_count(8, 100, &index);
+/// assert_eq!(subsampler.fraction(), 0.25);
+/// ```
+pub fn with_target_count(seed: u64, target_count: u64, index: &bai::Index) -> Subsampler {
+    Subsampler::with_target_count(seed, target_count, record_count(index))
+}
+
+/// Creates a subsampler that keeps about enough records of the indexed BAM for the given mean
+/// depth.
+///
+/// The reference length is the sum of the reference sequence lengths.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bam::{bai, subsample};
+/// use noodles_sam::header::ReferenceSequences;
+///
+/// let subsampler = subsample::with_target_coverage(
+///     8,
+///     5.0,
+///     &ReferenceSequences::default(),
+///     &bai::Index::default(),
+///     100.0,
+/// );
+///
+/// assert_eq!(subsampler.fraction(), 1.0);
+/// ```
+pub fn with_target_coverage(
+    seed: u64,
+    target_depth: f64,
+    reference_sequences: &ReferenceSequences,
+    index: &bai::Index,
+    mean_read_length: f64,
+) -> Subsampler {
+    let reference_length = reference_sequences
+        .values()
+        .map(|reference_sequence| reference_sequence.len().max(0) as u64)
+        .sum();
+
+    Subsampler::with_target_coverage(
+        seed,
+        target_depth,
+        reference_length,
+        mapped_record_count(index),
+        mean_read_length,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf::VirtualPosition;
+    use noodles_sam::{self as sam, header::ReferenceSequence};
+
+    use super::*;
+    use bai::index::{reference_sequence::Metadata, ReferenceSequence as IndexReferenceSequence};
+
+    fn build_index() -> bai::Index {
+        let metadata = Metadata::new(VirtualPosition::from(0), VirtualPosition::from(0), 1000, 8);
+        let reference_sequences = vec![
+            IndexReferenceSequence::new(Vec::new(), Vec::new(), Some(metadata)),
+            IndexReferenceSequence::new(Vec::new(), Vec::new(), None),
+        ];
+
+        bai::Index::new(reference_sequences, Some(13))
+    }
+
+    #[test]
+    fn test_record_count() {
+        let index = build_index();
+        assert_eq!(record_count(&index), 1021);
+        assert_eq!(mapped_record_count(&index), 1000);
+    }
+
+    #[test]
+    fn test_with_target_coverage() {
+        let index = build_index();
+
+        let mut reference_sequences = ReferenceSequences::default();
+        reference_sequences.insert(
+            String::from("sq0"),
+            ReferenceSequence::new(String::from("sq0"), 6000),
+        );
+        reference_sequences.insert(
+            String::from("sq1"),
+            ReferenceSequence::new(String::from("sq1"), 4000),
+        );
+
+        // 1000 records * 100 bases / 10000 bases = 10x
+        let subsampler = with_target_coverage(8, 5.0, &reference_sequences, &index, 100.0);
+        assert_eq!(subsampler.fraction(), 0.5);
+    }
+
+    #[test]
+    fn test_keep() -> io::Result<()> {
+        let header = sam::Header::default();
+        let subsampler = Subsampler::new(8, 0.5);
+
+        for i in 0..100 {
+            let sam_record = sam::Record::builder()
+                .set_read_name(format!("r{}", i).parse().unwrap())
+                .build();
+
+            let record = Record::try_from_sam_record(header.reference_sequences(), &sam_record)?;
+            assert_eq!(keep(&subsampler, &record)?, subsampler.keep(&sam_record));
+        }
+
+        Ok(())
+    }
+}
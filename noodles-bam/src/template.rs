@@ -0,0 +1,174 @@
+//! BAM record grouping by template.
+//!
+//! BAM records implement [`noodles_sam::template::TemplateRecord`], so they are grouped directly, i.e.,
+//! without converting them to SAM records. See [`noodles_sam::template`].
+
+use std::io;
+
+use noodles_sam::{
+    record::{data::field::Tag, other_alignments::OtherAlignments, Flags},
+    template::{CoordinateSortedTemplates, TemplateRecord, Templates},
+};
+
+use super::Record;
+
+const MISSING_READ_NAME: &str = "*";
+
+impl TemplateRecord for Record {
+    fn read_name(&self) -> Option<&str> {
+        self.read_name()
+            .ok()
+            .and_then(|read_name| read_name.to_str().ok())
+            .filter(|read_name| *read_name != MISSING_READ_NAME)
+    }
+
+    fn flags(&self) -> Flags {
+        self.flags()
+    }
+
+    fn other_alignment_count(&self) -> usize {
+        self.data()
+            .get_str(&Tag::OtherAlignments)
+            .ok()
+            .flatten()
+            .and_then(|s| s.parse::<OtherAlignments>().ok())
+            .map(|other_alignments| other_alignments.len())
+            .unwrap_or_default()
+    }
+}
+
+/// Groups adjacent BAM records with the same read name into templates.
+///
+/// See [`noodles_sam::template::Templates`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam as bam;
+///
+/// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+/// reader.read_header()?;
+/// reader.read_reference_sequences()?;
+///
+/// for result in bam::template::templates(reader.records()) {
+///     let template = result?;
+///     println!("{:?}", template.read_name());
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn templates<I>(records: I) -> Templates<I::IntoIter>
+where
+    I: IntoIterator<Item = io::Result<Record>>,
+{
+    Templates::new(records)
+}
+
+/// Groups coordinate-sorted BAM records into templates.
+///
+/// See [`noodles_sam::template::CoordinateSortedTemplates`].
+pub fn coordinate_sorted_templates<I>(records: I) -> CoordinateSortedTemplates<I::IntoIter, Record>
+where
+    I: IntoIterator<Item = io::Result<Record>>,
+{
+    CoordinateSortedTemplates::new(records)
+}
+
+/// Groups coordinate-sorted BAM records into templates, buffering at most `max_pending_len`
+/// incomplete templates.
+///
+/// See [`noodles_sam::template::CoordinateSortedTemplates::with_max_pending_len`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam as bam;
+///
+/// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+/// reader.read_header()?;
+/// reader.read_reference_sequences()?;
+///
+/// let templates =
+///     bam::template::coordinate_sorted_templates_with_max_pending_len(reader.records(), 1 << 16);
+///
+/// for result in templates {
+///     let template = result?;
+///     println!("{:?}", template.read_name());
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn coordinate_sorted_templates_with_max_pending_len<I>(
+    records: I,
+    max_pending_len: usize,
+) -> CoordinateSortedTemplates<I::IntoIter, Record>
+where
+    I: IntoIterator<Item = io::Result<Record>>,
+{
+    CoordinateSortedTemplates::with_max_pending_len(records, max_pending_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::{self as sam, header::ReferenceSequences};
+
+    use super::*;
+
+    #[test]
+    fn test_templates() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequences = ReferenceSequences::default();
+
+        let build = |read_name: &str, flags: Flags| -> Result<Record, Box<dyn std::error::Error>> {
+            let sam_record = sam::Record::builder()
+                .set_read_name(read_name.parse()?)
+                .set_flags(flags)
+                .build();
+
+            Ok(Record::try_from_sam_record(
+                &reference_sequences,
+                &sam_record,
+            )?)
+        };
+
+        let records = vec![
+            build("r0", Flags::PAIRED | Flags::READ_1)?,
+            build("r1", Flags::empty())?,
+            build("r0", Flags::PAIRED | Flags::READ_2)?,
+            Record::default(),
+        ];
+
+        let templates: Vec<_> =
+            templates(records.iter().cloned().map(Ok)).collect::<io::Result<_>>()?;
+        assert_eq!(templates.len(), 4);
+        assert!(templates[3].read_name().is_none());
+
+        let templates: Vec<_> = coordinate_sorted_templates(records.iter().cloned().map(Ok))
+            .collect::<io::Result<_>>()?;
+        assert_eq!(templates.len(), 3);
+        assert_eq!(templates[0].read_name(), Some("r1"));
+        assert!(templates[1].read_1().is_some());
+        assert!(templates[1].read_2().is_some());
+        assert!(templates[2].read_name().is_none());
+
+        let templates: Vec<_> =
+            coordinate_sorted_templates_with_max_pending_len(records.into_iter().map(Ok), 0)
+                .collect::<io::Result<_>>()?;
+        assert_eq!(templates.len(), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_other_alignment_count() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequences = ReferenceSequences::default();
+
+        let sam_record: sam::Record =
+            "r0\t0\t*\t0\t255\t*\t*\t0\t0\t*\t*\tSA:Z:sq0,8,+,4M,60,0;sq0,13,-,4M,60,0;".parse()?;
+        let record = Record::try_from_sam_record(&reference_sequences, &sam_record)?;
+
+        assert_eq!(TemplateRecord::other_alignment_count(&record), 2);
+        assert_eq!(TemplateRecord::other_alignment_count(&Record::default()), 0);
+
+        Ok(())
+    }
+}
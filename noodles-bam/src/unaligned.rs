@@ -0,0 +1,117 @@
+//! Unaligned BAM record creation from FASTQ.
+
+use std::io;
+
+use noodles_fastq as fastq;
+use noodles_sam::{self as sam, header::ReferenceSequences, unaligned::Converter};
+
+use super::Record;
+
+/// Converts single-end FASTQ records to unmapped BAM records.
+///
+/// See [`sam::unaligned::single`].
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bam as bam;
+/// use noodles_fastq as fastq;
+/// use noodles_sam::unaligned::Converter;
+///
+/// let data = b"@r0\nACGT\n+\nNDLS\n";
+/// let mut reader = fastq::Reader::new(&data[..]);
+///
+/// let converter = Converter::default();
+/// let records: Vec<_> = bam::unaligned::single(&converter, reader.records())
+///     .collect::<Result<_, _>>()?;
+///
+/// assert_eq!(records.len(), 1);
+/// assert!(records[0].flags().is_unmapped());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn single<'a, I>(
+    converter: &'a Converter,
+    records: I,
+) -> impl Iterator<Item = io::Result<Record>> + 'a
+where
+    I: IntoIterator<Item = io::Result<fastq::Record>>,
+    I::IntoIter: 'a,
+{
+    sam::unaligned::single(converter, records).map(into_bam_record)
+}
+
+/// Converts paired-end FASTQ records to unmapped BAM records.
+///
+/// See [`sam::unaligned::paired`].
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bam as bam;
+/// use noodles_fastq as fastq;
+/// use noodles_sam::unaligned::Converter;
+///
+/// let data_1 = b"@r0/1 1:N:0:ACGT\nACGT\n+\nNDLS\n";
+/// let mut reader_1 = fastq::Reader::new(&data_1[..]);
+///
+/// let data_2 = b"@r0/2 2:N:0:ACGT\nTTGG\n+\nSLDN\n";
+/// let mut reader_2 = fastq::Reader::new(&data_2[..]);
+///
+/// let converter = Converter::default();
+/// let records: Vec<_> =
+///     bam::unaligned::paired(&converter, reader_1.records(), reader_2.records())
+///         .collect::<Result<_, _>>()?;
+///
+/// assert_eq!(records.len(), 2);
+/// assert!(records[0].flags().is_read_1());
+/// assert!(records[1].flags().is_read_2());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn paired<'a, I, J>(
+    converter: &'a Converter,
+    read_1_records: I,
+    read_2_records: J,
+) -> impl Iterator<Item = io::Result<Record>> + 'a
+where
+    I: IntoIterator<Item = io::Result<fastq::Record>>,
+    I::IntoIter: 'a,
+    J: IntoIterator<Item = io::Result<fastq::Record>>,
+    J::IntoIter: 'a,
+{
+    sam::unaligned::paired(converter, read_1_records, read_2_records).map(into_bam_record)
+}
+
+fn into_bam_record(result: io::Result<sam::Record>) -> io::Result<Record> {
+    let reference_sequences = ReferenceSequences::default();
+    result.and_then(|record| Record::try_from_sam_record(&reference_sequences, &record))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paired() -> io::Result<()> {
+        let converter = Converter::builder()
+            .set_read_group(sam::header::ReadGroup::new(String::from("rg0")))
+            .build();
+
+        let read_1_records = vec![Ok(fastq::Record::new("r0/1 1:N:0:ACGT", "ACGT", "NDLS"))];
+        let read_2_records = vec![Ok(fastq::Record::new("r0/2 2:N:0:ACGT", "TTGG", "SLDN"))];
+
+        let records: Vec<_> =
+            paired(&converter, read_1_records, read_2_records).collect::<io::Result<_>>()?;
+
+        assert_eq!(records.len(), 2);
+
+        let reference_sequences = ReferenceSequences::default();
+        let record = records[1].try_into_sam_record(&reference_sequences)?;
+
+        assert_eq!(u16::from(record.flags()), 0x8d);
+        assert_eq!(record.read_name().map(|name| name.as_str()), Some("r0"));
+        assert_eq!(record.sequence().to_string(), "TTGG");
+        assert_eq!(record.data().to_string(), "BC:Z:ACGT\tRG:Z:rg0");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+//! BAM record validation.
+
+use std::{convert::TryFrom, io};
+
+use noodles_sam::{
+    header::ReferenceSequences,
+    validation::{Diagnostic, Kind, Severity, Validator},
+};
+
+use super::{record::ReferenceSequenceId, Record};
+
+/// Validates the header and all the BAM records from the given iterator.
+///
+/// Each record is converted to a SAM record using the reference sequences of the validator
+/// header and validated by [`Validator::validate_record`].
+///
+/// A reference sequence ID or mate reference sequence ID that is not in the header is reported
+/// as a diagnostic of kind [`Kind::InvalidReferenceSequenceName`] or
+/// [`Kind::InvalidMateReferenceSequenceName`], respectively, with the ID as the name. Any other
+/// record that fails to convert is reported as a diagnostic of kind [`Kind::InvalidRecord`]. This
+/// stops at and returns the first I/O error when reading records.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_bam as bam;
+/// use noodles_sam::{self as sam, validation::{Strictness, Validator}};
+///
+/// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+/// let header: sam::Header = reader.read_header()?.parse().unwrap();
+/// reader.read_reference_sequences()?;
+///
+/// let validator = Validator::new(&header, Strictness::default());
+///
+/// for diagnostic in bam::validation::validate(&validator, reader.records())? {
+///     eprintln!("{}", diagnostic);
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn validate<I>(validator: &Validator<'_>, records: I) -> io::Result<Vec<Diagnostic>>
+where
+    I: IntoIterator<Item = io::Result<Record>>,
+{
+    let reference_sequences = validator.header().reference_sequences();
+    let mut diagnostics = validator.validate_header();
+
+    for (i, result) in records.into_iter().enumerate() {
+        let record = result?;
+
+        let kinds = validate_reference_sequence_ids(reference_sequences, &record);
+
+        if !kinds.is_empty() {
+            diagnostics.extend(
+                kinds
+                    .into_iter()
+                    .map(|kind| Diagnostic::new(Severity::Error, Some(i), kind)),
+            );
+
+            continue;
+        }
+
+        match record.try_into_sam_record(reference_sequences) {
+            Ok(sam_record) => diagnostics.extend(validator.validate_record(i, &sam_record)),
+            Err(e) => diagnostics.push(Diagnostic::new(
+                Severity::Error,
+                Some(i),
+                Kind::InvalidRecord(e.to_string()),
+            )),
+        }
+    }
+
+    Ok(diagnostics)
+}
+
+fn validate_reference_sequence_ids(
+    reference_sequences: &ReferenceSequences,
+    record: &Record,
+) -> Vec<Kind> {
+    let invalid_id = |id: Option<ReferenceSequenceId>| {
+        id.map(i32::from)
+            .filter(|&id| {
+                usize::try_from(id)
+                    .map(|i| i >= reference_sequences.len())
+                    .unwrap_or(true)
+            })
+            .map(|id| id.to_string())
+    };
+
+    let mut kinds = Vec::new();
+
+    if let Some(id) = invalid_id(record.reference_sequence_id()) {
+        kinds.push(Kind::InvalidReferenceSequenceName(id));
+    }
+
+    if let Some(id) = invalid_id(record.mate_reference_sequence_id()) {
+        kinds.push(Kind::InvalidMateReferenceSequenceName(id));
+    }
+
+    kinds
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use noodles_sam::{
+        self as sam,
+        header::ReferenceSequence,
+        record::{Flags, Position},
+        validation::{Kind, Strictness},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .build();
+
+        let sam_record = sam::Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(1)?)
+            .set_cigar("4M".parse()?)
+            .set_sequence("ACG".parse()?)
+            .build();
+
+        let record = Record::try_from_sam_record(header.reference_sequences(), &sam_record)?;
+
+        let validator = Validator::new(&header, Strictness::default());
+        let diagnostics = validate(&validator, vec![Ok(Record::default()), Ok(record)])?;
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].record_index(), Some(1));
+        assert_eq!(diagnostics[0].kind(), &Kind::CigarReadLengthMismatch(4, 3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_invalid_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .build();
+
+        let sam_record = sam::Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(1)?)
+            .set_cigar("4M".parse()?)
+            .set_sequence("ACGT".parse()?)
+            .build();
+
+        let record = Record::try_from_sam_record(header.reference_sequences(), &sam_record)?;
+
+        let header = sam::Header::default();
+        let validator = Validator::new(&header, Strictness::default());
+        let diagnostics = validate(&validator, vec![Ok(record), Ok(Record::default())])?;
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].record_index(), Some(0));
+        assert_eq!(
+            diagnostics[0].kind(),
+            &Kind::InvalidReferenceSequenceName(String::from("0"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_invalid_mate_reference_sequence_id(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq1"), 8))
+            .build();
+
+        let sam_record = sam::Record::builder()
+            .set_flags(Flags::PAIRED | Flags::READ_1)
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(1)?)
+            .set_cigar("4M".parse()?)
+            .set_mate_reference_sequence_name("sq1".parse()?)
+            .set_mate_position(Position::try_from(1)?)
+            .set_sequence("ACGT".parse()?)
+            .build();
+
+        let record = Record::try_from_sam_record(header.reference_sequences(), &sam_record)?;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .build();
+        let validator = Validator::new(&header, Strictness::default());
+        let diagnostics = validate(&validator, vec![Ok(record)])?;
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind(),
+            &Kind::InvalidMateReferenceSequenceName(String::from("1"))
+        );
+
+        Ok(())
+    }
+}
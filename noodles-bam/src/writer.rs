@@ -248,6 +248,29 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_write_header_with_other_records() -> Result<(), Box<dyn std::error::Error>> {
+        let expected = "\
+@HD\tVN:1.6\tzn:noodles
+@SQ\tSN:sq0\tLN:8\tzb:2\tza:1
+@CO\tnoodles\tbam
+@XY\tzn:noodles
+";
+
+        let header: sam::Header = expected.parse()?;
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.try_finish()?;
+
+        let mut reader = Reader::new(writer.get_ref().as_slice());
+        let actual = reader.read_header()?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
     #[test]
     fn test_write_reference_sequences() -> io::Result<()> {
         let mut writer = Writer::new(Vec::new());
@@ -21,7 +21,7 @@ use crate::record::sequence::Base;
 const BLOCK_HEADER_SIZE: usize = 32;
 
 // § 4.2.1 BIN field calculation (2020-04-30)
-const UNMAPPED_BIN: u16 = 4680;
+pub(crate) const UNMAPPED_BIN: u16 = 4680;
 
 // § 4.2.3 SEQ and QUAL encoding (2020-04-30)
 const NULL_QUALITY_SCORE: u8 = 255;
@@ -279,7 +279,7 @@ fn calculate_data_len(data: &Data) -> usize {
     len
 }
 
-fn write_data<W>(writer: &mut W, data: &Data) -> io::Result<()>
+pub(crate) fn write_data<W>(writer: &mut W, data: &Data) -> io::Result<()>
 where
     W: Write,
 {
@@ -401,7 +401,7 @@ where
 // § 5.3 C source code for computing bin number and overlapping bins (2020-04-30)
 // 0-based, [start, end)
 #[allow(clippy::eq_op)]
-fn region_to_bin(start: i32, mut end: i32) -> i32 {
+pub(crate) fn region_to_bin(start: i32, mut end: i32) -> i32 {
     end -= 1;
 
     if start >> 14 == end >> 14 {
@@ -0,0 +1,205 @@
+//! BCF header string maps.
+
+pub mod string_map;
+
+pub use self::string_map::StringMap;
+
+use std::{convert::TryFrom, error, fmt, num, str::FromStr};
+
+use noodles_vcf::{
+    self as vcf,
+    header::record::{Key, Value},
+};
+
+const PASS: &str = "PASS";
+
+const ID: &str = "ID";
+const IDX: &str = "IDX";
+
+/// BCF header string maps.
+///
+/// The string map is the dictionary of filter, info, and format IDs. `PASS` is always the first
+/// entry. The contig string map is the dictionary of contig IDs. Both are built from the order of
+/// the records in the raw header, unless a record sets an explicit index (`IDX`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StringMaps {
+    strings: StringMap,
+    contigs: StringMap,
+}
+
+impl StringMaps {
+    /// Returns the string map of filter, info, and format IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::header::StringMaps;
+    ///
+    /// let raw_header = "##fileformat=VCFv4.3\n##FILTER=<ID=q10,Description=\"Quality below 10\">\n";
+    ///
+    /// let string_maps: StringMaps = raw_header.parse()?;
+    /// assert_eq!(string_maps.strings().get_index_of("q10"), Some(1));
+    /// # Ok::<(), noodles_bcf::header::ParseError>(())
+    /// ```
+    pub fn strings(&self) -> &StringMap {
+        &self.strings
+    }
+
+    /// Returns the string map of contig IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::header::StringMaps;
+    ///
+    /// let raw_header = "##fileformat=VCFv4.3\n##contig=<ID=sq0,IDX=1>\n";
+    ///
+    /// let string_maps: StringMaps = raw_header.parse()?;
+    /// assert_eq!(string_maps.contigs().get_index_of("sq0"), Some(1));
+    /// # Ok::<(), noodles_bcf::header::ParseError>(())
+    /// ```
+    pub fn contigs(&self) -> &StringMap {
+        &self.contigs
+    }
+}
+
+impl Default for StringMaps {
+    fn default() -> Self {
+        let mut strings = StringMap::default();
+        // The map is empty, so the insert cannot conflict.
+        strings.insert(String::from(PASS), None).unwrap();
+
+        Self {
+            strings,
+            contigs: StringMap::default(),
+        }
+    }
+}
+
+/// An error returned when a raw VCF header fails to parse as BCF string maps.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// A header record is invalid.
+    InvalidRecord(vcf::header::record::ParseError),
+    /// A header record is missing an ID.
+    MissingId(Key),
+    /// A header record index (`IDX`) is invalid.
+    InvalidIdx(num::ParseIntError),
+    /// An ID is assigned to more than one index, or an index to more than one ID.
+    DuplicateEntry(String),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord(e) => write!(f, "invalid record: {}", e),
+            Self::MissingId(key) => write!(f, "missing ID in {} record", key),
+            Self::InvalidIdx(e) => write!(f, "invalid IDX: {}", e),
+            Self::DuplicateEntry(id) => write!(f, "duplicate entry: {}", id),
+        }
+    }
+}
+
+impl FromStr for StringMaps {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut string_maps = Self::default();
+
+        for line in s.lines().take_while(|line| line.starts_with("##")) {
+            let record: vcf::header::Record = line.parse().map_err(ParseError::InvalidRecord)?;
+
+            let string_map = match record.key() {
+                Key::Filter | Key::Info | Key::Format => &mut string_maps.strings,
+                Key::Contig => &mut string_maps.contigs,
+                _ => continue,
+            };
+
+            let fields = match record.value() {
+                Value::Struct(fields) => fields,
+                Value::String(_) => return Err(ParseError::MissingId(record.key().clone())),
+            };
+
+            let id = find_field(fields, ID)
+                .ok_or_else(|| ParseError::MissingId(record.key().clone()))?;
+
+            let idx = find_field(fields, IDX)
+                .map(|s| s.parse().map_err(ParseError::InvalidIdx))
+                .transpose()?;
+
+            string_map
+                .insert(id.into(), idx)
+                .map_err(ParseError::DuplicateEntry)?;
+        }
+
+        Ok(string_maps)
+    }
+}
+
+impl TryFrom<&vcf::Header> for StringMaps {
+    type Error = ParseError;
+
+    /// Builds the string maps of the header as it is written by [`crate::Writer::write_header`].
+    fn try_from(header: &vcf::Header) -> Result<Self, Self::Error> {
+        header.to_string().parse()
+    }
+}
+
+fn find_field<'a>(fields: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    fields
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() -> Result<(), ParseError> {
+        let s = r#"##fileformat=VCFv4.3
+##contig=<ID=sq0,length=8>
+##contig=<ID=sq1,length=13>
+##INFO=<ID=NS,Number=1,Type=Integer,Description="Number of samples with data">
+##FILTER=<ID=q10,Description="Quality below 10">
+##FILTER=<ID=PASS,Description="All filters passed">
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth",IDX=5>
+##FORMAT=<ID=GT,Number=1,Type=String,Description="Genotype">
+##FORMAT=<ID=DP,Number=1,Type=Integer,Description="Read depth",IDX=5>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#;
+
+        let string_maps: StringMaps = s.parse()?;
+
+        let strings = string_maps.strings();
+        assert_eq!(strings.len(), 7);
+        assert_eq!(strings.get_index(0), Some("PASS"));
+        assert_eq!(strings.get_index(1), Some("NS"));
+        assert_eq!(strings.get_index(2), Some("q10"));
+        assert_eq!(strings.get_index(3), None);
+        assert_eq!(strings.get_index(5), Some("DP"));
+        assert_eq!(strings.get_index_of("GT"), Some(6));
+
+        let contigs = string_maps.contigs();
+        assert_eq!(contigs.get_index_of("sq0"), Some(0));
+        assert_eq!(contigs.get_index_of("sq1"), Some(1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str_with_duplicate_idx() {
+        let s = r#"##fileformat=VCFv4.3
+##INFO=<ID=NS,Number=1,Type=Integer,Description="Number of samples with data",IDX=1>
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth",IDX=1>
+"#;
+
+        assert_eq!(
+            s.parse::<StringMaps>(),
+            Err(ParseError::DuplicateEntry(String::from("DP")))
+        );
+    }
+}
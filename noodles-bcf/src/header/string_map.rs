@@ -0,0 +1,139 @@
+//! BCF header string map.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// A BCF header string map.
+///
+/// This is an indexed dictionary of header IDs. BCF records reference IDs by their position in
+/// the map.
+///
+/// Explicit indices (`IDX`) can be sparse, so only assigned positions are stored.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StringMap {
+    entries: BTreeMap<usize, String>,
+    indices: HashMap<String, usize>,
+}
+
+impl StringMap {
+    /// Returns the number of entries in the map, including unassigned positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::header::StringMap;
+    /// let string_map = StringMap::default();
+    /// assert_eq!(string_map.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.entries
+            .keys()
+            .next_back()
+            .map(|&i| i.saturating_add(1))
+            .unwrap_or(0)
+    }
+
+    /// Returns whether the map has any entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::header::StringMap;
+    /// let string_map = StringMap::default();
+    /// assert!(string_map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the string at the given index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::header::StringMaps;
+    ///
+    /// let string_maps: StringMaps = "##fileformat=VCFv4.3\n".parse()?;
+    /// assert_eq!(string_maps.strings().get_index(0), Some("PASS"));
+    /// assert!(string_maps.strings().get_index(1).is_none());
+    /// # Ok::<(), noodles_bcf::header::ParseError>(())
+    /// ```
+    pub fn get_index(&self, i: usize) -> Option<&str> {
+        self.entries.get(&i).map(|entry| entry.as_str())
+    }
+
+    /// Returns the index of the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::header::StringMaps;
+    ///
+    /// let string_maps: StringMaps = "##fileformat=VCFv4.3\n".parse()?;
+    /// assert_eq!(string_maps.strings().get_index_of("PASS"), Some(0));
+    /// assert!(string_maps.strings().get_index_of("q10").is_none());
+    /// # Ok::<(), noodles_bcf::header::ParseError>(())
+    /// ```
+    pub fn get_index_of(&self, value: &str) -> Option<usize> {
+        self.indices.get(value).copied()
+    }
+
+    // Adds the string at the next position or, if given, at an explicit index (`IDX`).
+    //
+    // Strings already in the map are not added again.
+    pub(crate) fn insert(&mut self, value: String, idx: Option<usize>) -> Result<(), String> {
+        if let Some(&i) = self.indices.get(&value) {
+            return match idx {
+                Some(j) if i != j => Err(value),
+                _ => Ok(()),
+            };
+        }
+
+        let i = idx.unwrap_or_else(|| self.len());
+
+        if self.entries.contains_key(&i) {
+            return Err(value);
+        }
+
+        self.entries.insert(i, value.clone());
+        self.indices.insert(value, i);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let mut string_map = StringMap::default();
+
+        assert!(string_map.insert(String::from("PASS"), None).is_ok());
+        assert!(string_map.insert(String::from("NS"), Some(3)).is_ok());
+        assert!(string_map.insert(String::from("PASS"), None).is_ok());
+        assert!(string_map.insert(String::from("DP"), None).is_ok());
+
+        assert_eq!(string_map.len(), 5);
+        assert_eq!(string_map.get_index(0), Some("PASS"));
+        assert!(string_map.get_index(1).is_none());
+        assert_eq!(string_map.get_index(3), Some("NS"));
+        assert_eq!(string_map.get_index_of("DP"), Some(4));
+
+        assert_eq!(
+            string_map.insert(String::from("AF"), Some(0)),
+            Err(String::from("AF"))
+        );
+        assert_eq!(
+            string_map.insert(String::from("NS"), Some(1)),
+            Err(String::from("NS"))
+        );
+
+        let mut string_map = StringMap::default();
+        assert!(string_map
+            .insert(String::from("NS"), Some(usize::MAX - 1))
+            .is_ok());
+        assert_eq!(string_map.len(), usize::MAX);
+        assert_eq!(string_map.get_index(usize::MAX - 1), Some("NS"));
+    }
+}
@@ -0,0 +1,43 @@
+#![deny(missing_docs)]
+
+//! **noodles-bcf** handles the reading and writing of the BCF (binary VCF) file format.
+//!
+//! A BCF file is a BGZF-compressed stream of a VCF header followed by a list of binary records.
+//! Header entries (filters, info and format keys, and contigs) are referenced by index into the
+//! dictionaries of the header ([`header::StringMaps`]).
+//!
+//! # Examples
+//!
+//! ## Read all records
+//!
+//! ```no_run
+//! # use std::{fs::File, io};
+//! use noodles_bcf::{self as bcf, header::StringMaps};
+//! use noodles_vcf as vcf;
+//!
+//! let mut reader = File::open("sample.bcf").map(bcf::Reader::new)?;
+//!
+//! let raw_header = reader.read_header()?;
+//! let header: vcf::Header = raw_header
+//!     .parse()
+//!     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+//! let string_maps: StringMaps = raw_header
+//!     .parse()
+//!     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+//!
+//! for result in reader.records() {
+//!     let record = result?;
+//!     let vcf_record = record.try_into_vcf_record(&header, &string_maps)?;
+//!     println!("{:?}", vcf_record);
+//! }
+//! # Ok::<(), io::Error>(())
+//! ```
+
+pub mod header;
+pub mod reader;
+pub mod record;
+mod writer;
+
+pub use self::{reader::Reader, record::Record, writer::Writer};
+
+static MAGIC_NUMBER: &[u8] = b"BCF\x02\x02";
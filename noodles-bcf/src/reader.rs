@@ -0,0 +1,320 @@
+//! BCF reader and iterators.
+
+mod records;
+
+pub use self::records::Records;
+
+use std::io::{self, Read};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use noodles_bgzf as bgzf;
+
+use super::{record::SITE_FIELDS_LEN, Record, MAGIC_NUMBER};
+
+/// A BCF reader.
+///
+/// The BCF format is comprised of two parts: 1) a VCF header and 2) a list of records.
+pub struct Reader<R>
+where
+    R: Read,
+{
+    inner: bgzf::Reader<R>,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    /// Creates a BCF reader.
+    ///
+    /// The given reader must be a raw BGZF stream, as the underlying reader wraps it in a
+    /// decoder.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bcf as bcf;
+    /// let mut reader = File::open("sample.bcf").map(bcf::Reader::new)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn new(reader: R) -> Self {
+        Self {
+            inner: bgzf::Reader::new(reader),
+        }
+    }
+
+    /// Reads the raw VCF header.
+    ///
+    /// The BCF magic number and version are checked.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// This returns the raw VCF header as a [`String`]. It can subsequently be parsed as a
+    /// [`noodles_vcf::Header`] and as [`crate::header::StringMaps`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bcf as bcf;
+    /// let mut reader = File::open("sample.bcf").map(bcf::Reader::new)?;
+    /// let header = reader.read_header()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_header(&mut self) -> io::Result<String> {
+        let mut magic = [0; 5];
+        self.inner.read_exact(&mut magic)?;
+
+        if magic != MAGIC_NUMBER {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid BCF header",
+            ));
+        }
+
+        read_header(&mut self.inner)
+    }
+
+    /// Reads a single record.
+    ///
+    /// The record buffers are overwritten. This returns the number of bytes read from the
+    /// stream. If 0, the stream reached EOF.
+    ///
+    /// The stream is expected to be directly after the header or at the start of another
+    /// record.
+    ///
+    /// It is more ergonomic to read records using an iterator (see [`Self::records`]), but
+    /// using this method allows control of the record buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bcf as bcf;
+    ///
+    /// let mut reader = File::open("sample.bcf").map(bcf::Reader::new)?;
+    /// reader.read_header()?;
+    ///
+    /// let mut record = bcf::Record::default();
+    /// reader.read_record(&mut record)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_record(&mut self, record: &mut Record) -> io::Result<usize> {
+        let l_shared = match self.inner.read_u32::<LittleEndian>() {
+            Ok(n) => n as usize,
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(0),
+            Err(e) => return Err(e),
+        };
+
+        if l_shared < SITE_FIELDS_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "invalid l_shared: expected >= {}, got {}",
+                    SITE_FIELDS_LEN, l_shared
+                ),
+            ));
+        }
+
+        let l_indiv = self.inner.read_u32::<LittleEndian>()? as usize;
+
+        read_buf(&mut self.inner, record.site_buf_mut(), l_shared)?;
+        read_buf(&mut self.inner, record.genotypes_buf_mut(), l_indiv)?;
+
+        Ok(8 + l_shared + l_indiv)
+    }
+
+    /// Returns an iterator over records starting from the current stream position.
+    ///
+    /// The stream is expected to be directly after the header or at the start of another
+    /// record.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bcf as bcf;
+    ///
+    /// let mut reader = File::open("sample.bcf").map(bcf::Reader::new)?;
+    /// reader.read_header()?;
+    ///
+    /// for result in reader.records() {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records::new(self)
+    }
+}
+
+fn read_header<R>(reader: &mut R) -> io::Result<String>
+where
+    R: Read,
+{
+    let l_text = reader.read_u32::<LittleEndian>().map(|n| n as usize)?;
+
+    let mut buf = Vec::new();
+    read_buf(reader, &mut buf, l_text)?;
+
+    let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    buf.truncate(end);
+
+    String::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// Reads exactly `len` bytes into `buf`.
+//
+// The length is untrusted, so the buffer only grows as data is actually read rather than being
+// allocated up front.
+fn read_buf<R>(reader: &mut R, buf: &mut Vec<u8>, len: usize) -> io::Result<()>
+where
+    R: Read,
+{
+    buf.clear();
+
+    let n = reader.take(len as u64).read_to_end(buf)?;
+
+    if n < len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use byteorder::WriteBytesExt;
+    use noodles_vcf as vcf;
+
+    use super::*;
+    use crate::header::StringMaps;
+
+    fn build_bcf(records: &[u8]) -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(MAGIC_NUMBER)?;
+        writer.write_u32::<LittleEndian>(0)?;
+        writer.write_all(records)?;
+        writer.finish()
+    }
+
+    // `bcftools view --no-version -Ob` of
+    //
+    // ```
+    // sq0  1  rs0  A  C  30  PASS  DP=8;AF=0.5;DB  GT:GQ  0/1:13  1|1:.
+    // ```
+    //
+    // htslib adds an `IDX` to each header record, includes the trailing NUL in `l_text`, encodes
+    // the flag as a typed missing value, and sets the phase bit on the second allele.
+    static HTSLIB_HEADER: &str = r#"##fileformat=VCFv4.3
+##FILTER=<ID=PASS,Description="All filters passed",IDX=0>
+##contig=<ID=sq0,length=8,IDX=0>
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth across samples",IDX=1>
+##INFO=<ID=AF,Number=A,Type=Float,Description="Allele frequency",IDX=2>
+##INFO=<ID=DB,Number=0,Type=Flag,Description="dbSNP membership",IDX=3>
+##FORMAT=<ID=GT,Number=1,Type=String,Description="Genotype",IDX=4>
+##FORMAT=<ID=GQ,Number=1,Type=Integer,Description="Genotype quality",IDX=5>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO	FORMAT	sample0	sample1
+"#;
+
+    static HTSLIB_RECORD: [u8; 68] = [
+        0x30, 0x00, 0x00, 0x00, // l_shared = 48
+        0x0c, 0x00, 0x00, 0x00, // l_indiv = 12
+        0x00, 0x00, 0x00, 0x00, // CHROM = 0 (sq0)
+        0x00, 0x00, 0x00, 0x00, // POS = 0 (1)
+        0x01, 0x00, 0x00, 0x00, // rlen = 1
+        0x00, 0x00, 0xf0, 0x41, // QUAL = 30.0
+        0x03, 0x00, 0x02, 0x00, // n_info = 3, n_allele = 2
+        0x02, 0x00, 0x00, 0x02, // n_sample = 2, n_fmt = 2
+        0x37, b'r', b's', b'0', // ID = "rs0"
+        0x17, b'A', // REF = "A"
+        0x17, b'C', // ALT = "C"
+        0x11, 0x00, // FILTER = [0] (PASS)
+        0x11, 0x01, 0x11, 0x08, // DP = 8
+        0x11, 0x02, 0x15, 0x00, 0x00, 0x00, 0x3f, // AF = 0.5
+        0x11, 0x03, 0x00, // DB
+        0x11, 0x04, 0x21, 0x02, 0x04, 0x04, 0x05, // GT = [0/1, 1|1]
+        0x11, 0x05, 0x11, 0x0d, 0x80, // GQ = [13, .]
+    ];
+
+    #[test]
+    fn test_read_record_with_htslib_output() -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(MAGIC_NUMBER)?;
+        writer.write_u32::<LittleEndian>(HTSLIB_HEADER.len() as u32 + 1)?;
+        writer.write_all(HTSLIB_HEADER.as_bytes())?;
+        writer.write_u8(0)?;
+        writer.write_all(&HTSLIB_RECORD)?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(&data[..]);
+        let raw_header = reader.read_header()?;
+        assert_eq!(raw_header, HTSLIB_HEADER);
+
+        let header: vcf::Header = raw_header.parse()?;
+        let string_maps: StringMaps = raw_header.parse()?;
+
+        let mut record = Record::default();
+        assert_eq!(reader.read_record(&mut record)?, HTSLIB_RECORD.len());
+
+        let actual = record.try_into_vcf_record(&header, &string_maps)?;
+        let expected = vcf::Record::try_from_str_header(
+            "sq0\t1\trs0\tA\tC\t30\tPASS\tDP=8;AF=0.5;DB\tGT:GQ\t0/1:13\t1|1:.",
+            &header,
+        )?;
+        assert_eq!(actual, expected);
+
+        assert_eq!(reader.read_record(&mut record)?, 0);
+
+        let round_trip = Record::try_from_vcf_record(&string_maps, &expected)?;
+        assert_eq!(round_trip.site_buf(), &HTSLIB_RECORD[8..56]);
+        assert_eq!(round_trip.genotypes_buf(), &HTSLIB_RECORD[56..]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_with_invalid_l_shared() -> io::Result<()> {
+        let data = build_bcf(&[
+            0x10, 0x00, 0x00, 0x00, // l_shared = 16
+            0x00, 0x00, 0x00, 0x00, // l_indiv = 0
+        ])?;
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_header()?;
+
+        let mut record = Record::default();
+
+        assert!(matches!(
+            reader.read_record(&mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_with_truncated_fields() -> io::Result<()> {
+        let data = build_bcf(&[
+            0xff, 0xff, 0xff, 0xff, // l_shared = 4294967295
+            0x00, 0x00, 0x00, 0x00, // l_indiv = 0
+            0x00, 0x00, 0x00, 0x00,
+        ])?;
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_header()?;
+
+        let mut record = Record::default();
+
+        assert!(matches!(
+            reader.read_record(&mut record),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+}
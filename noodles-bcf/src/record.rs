@@ -0,0 +1,890 @@
+//! BCF record and fields.
+
+pub mod genotypes;
+mod value;
+
+pub use self::genotypes::Genotypes;
+
+use std::{
+    convert::TryFrom,
+    io::{self, Write},
+};
+
+use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
+use noodles_vcf::{
+    self as vcf,
+    header::{info::Type as InfoType, Number},
+    record::{info, AlternateBases, FilterStatus, Info, QualityScore},
+};
+
+use crate::header::StringMaps;
+
+use self::value::{Value, FLOAT_MISSING};
+
+// CHROM, POS, rlen, QUAL, n_allele_info, n_fmt_sample
+pub(crate) const SITE_FIELDS_LEN: usize = 24;
+
+const PASS: &str = "PASS";
+const ALLELE_DELIMITER: char = ',';
+const VALUE_DELIMITER: char = ',';
+const MISSING_VALUE: &str = ".";
+
+/// A BCF record.
+///
+/// This is a lazy record. The site fields and the per-sample values are kept in their binary
+/// representations and are only decoded on request.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    site_buf: Vec<u8>,
+    genotypes_buf: Vec<u8>,
+}
+
+impl Record {
+    pub(crate) fn site_buf_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.site_buf
+    }
+
+    pub(crate) fn site_buf(&self) -> &[u8] {
+        &self.site_buf
+    }
+
+    pub(crate) fn genotypes_buf_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.genotypes_buf
+    }
+
+    pub(crate) fn genotypes_buf(&self) -> &[u8] {
+        &self.genotypes_buf
+    }
+
+    /// Returns the chromosome ID of the record.
+    ///
+    /// This is the index of the chromosome in the contig string map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let record = bcf::Record::default();
+    /// assert_eq!(record.chromosome_id(), 0);
+    /// ```
+    pub fn chromosome_id(&self) -> i32 {
+        LittleEndian::read_i32(&self.site_buf[0..4])
+    }
+
+    /// Returns the start position of the record.
+    ///
+    /// The position is 1-based.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the stored 0-based position cannot be represented as a 1-based
+    /// position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf as bcf;
+    /// let record = bcf::Record::default();
+    /// assert_eq!(record.position()?, 1);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn position(&self) -> io::Result<i32> {
+        let pos = LittleEndian::read_i32(&self.site_buf[4..8]);
+
+        pos.checked_add(1)
+            .ok_or_else(|| invalid_data(format!("invalid position: {}", pos)))
+    }
+
+    /// Returns the length of the reference allele or, if set, the span to the end position
+    /// (`END`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let record = bcf::Record::default();
+    /// assert_eq!(record.rlen(), 1);
+    /// ```
+    pub fn rlen(&self) -> i32 {
+        LittleEndian::read_i32(&self.site_buf[8..12])
+    }
+
+    /// Returns the quality score of the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let record = bcf::Record::default();
+    /// assert!(record.quality_score().is_none());
+    /// ```
+    pub fn quality_score(&self) -> Option<f32> {
+        match LittleEndian::read_u32(&self.site_buf[12..16]) {
+            FLOAT_MISSING => None,
+            n => Some(f32::from_bits(n)),
+        }
+    }
+
+    /// Returns the number of alleles, including the reference allele.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let record = bcf::Record::default();
+    /// assert_eq!(record.allele_count(), 1);
+    /// ```
+    pub fn allele_count(&self) -> usize {
+        (LittleEndian::read_u32(&self.site_buf[16..20]) >> 16) as usize
+    }
+
+    /// Returns the number of info fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let record = bcf::Record::default();
+    /// assert_eq!(record.info_count(), 0);
+    /// ```
+    pub fn info_count(&self) -> usize {
+        (LittleEndian::read_u32(&self.site_buf[16..20]) & 0xffff) as usize
+    }
+
+    /// Returns the number of format keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let record = bcf::Record::default();
+    /// assert_eq!(record.format_count(), 0);
+    /// ```
+    pub fn format_count(&self) -> usize {
+        (LittleEndian::read_u32(&self.site_buf[20..24]) >> 24) as usize
+    }
+
+    /// Returns the number of samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let record = bcf::Record::default();
+    /// assert_eq!(record.sample_count(), 0);
+    /// ```
+    pub fn sample_count(&self) -> usize {
+        (LittleEndian::read_u32(&self.site_buf[20..24]) & 0x00ff_ffff) as usize
+    }
+
+    /// Returns a lazy view of the genotypes of the record.
+    ///
+    /// Only the field descriptors are read. Genotypes are decoded per sample using
+    /// [`Genotypes::get`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf as bcf;
+    /// let record = bcf::Record::default();
+    /// assert!(record.genotypes()?.is_empty());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn genotypes(&self) -> io::Result<Genotypes<'_>> {
+        Genotypes::new(
+            &self.genotypes_buf,
+            self.format_count(),
+            self.sample_count(),
+        )
+    }
+
+    /// Converts a VCF record to a BCF record.
+    ///
+    /// Filter, info, and format keys and the chromosome name are resolved to their indices in
+    /// the string maps.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a key or the chromosome is not in the string maps or a value
+    /// cannot be encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::{convert::TryFrom, io};
+    /// use noodles_bcf::{self as bcf, header::StringMaps};
+    /// use noodles_vcf::{self as vcf, header::Contig};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_contig(Contig::new(String::from("sq0")))
+    ///     .build();
+    /// let string_maps = StringMaps::try_from(&header)
+    ///     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    ///
+    /// let vcf_record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse().unwrap())
+    ///     .set_position(8)
+    ///     .set_reference_bases("A".parse().unwrap())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let record = bcf::Record::try_from_vcf_record(&string_maps, &vcf_record)?;
+    /// assert_eq!(record.position()?, 8);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_from_vcf_record(string_maps: &StringMaps, record: &vcf::Record) -> io::Result<Self> {
+        let mut site_buf = Vec::new();
+        write_site(&mut site_buf, string_maps, record)?;
+
+        let mut genotypes_buf = Vec::new();
+
+        if let Some(format) = record.format() {
+            genotypes::write_genotypes(
+                &mut genotypes_buf,
+                string_maps,
+                format,
+                record.genotypes(),
+            )?;
+        }
+
+        Ok(Self {
+            site_buf,
+            genotypes_buf,
+        })
+    }
+
+    /// Converts the BCF record to a VCF record.
+    ///
+    /// Indices are resolved using the string maps, and info and format keys using their
+    /// definitions in the header.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if an index is not in the string maps or a field is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::{convert::TryFrom, io};
+    /// use noodles_bcf::{self as bcf, header::StringMaps};
+    /// use noodles_vcf::{self as vcf, header::Contig};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_contig(Contig::new(String::from("sq0")))
+    ///     .build();
+    /// let string_maps = StringMaps::try_from(&header)
+    ///     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    ///
+    /// let record = bcf::Record::default();
+    /// let vcf_record = record.try_into_vcf_record(&header, &string_maps)?;
+    /// assert_eq!(vcf_record.position(), 1);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_into_vcf_record(
+        &self,
+        header: &vcf::Header,
+        string_maps: &StringMaps,
+    ) -> io::Result<vcf::Record> {
+        let mut reader = &self.site_buf[SITE_FIELDS_LEN..];
+
+        let chromosome = usize::try_from(self.chromosome_id())
+            .ok()
+            .and_then(|i| string_maps.contigs().get_index(i))
+            .ok_or_else(|| invalid_data(format!("invalid chromosome ID: {}", self.chromosome_id())))
+            .and_then(|name| name.parse().map_err(invalid_data))?;
+
+        let ids = match value::read_value(&mut reader)? {
+            Value::String(s) if !s.is_empty() => s.parse().map_err(invalid_data)?,
+            _ => vcf::record::Ids::default(),
+        };
+
+        let mut alleles = Vec::with_capacity(self.allele_count());
+
+        for _ in 0..self.allele_count() {
+            match value::read_value(&mut reader)? {
+                Value::String(s) => alleles.push(s),
+                value => return Err(invalid_data(format!("invalid allele: {:?}", value))),
+            }
+        }
+
+        let reference_bases = alleles
+            .first()
+            .ok_or_else(|| invalid_data("missing reference bases"))
+            .and_then(|s| s.parse().map_err(invalid_data))?;
+
+        let alternate_bases = if alleles.len() > 1 {
+            alleles[1..]
+                .join(&ALLELE_DELIMITER.to_string())
+                .parse()
+                .map_err(invalid_data)?
+        } else {
+            AlternateBases::default()
+        };
+
+        let quality_score = match self.quality_score() {
+            Some(n) => QualityScore::try_from(n).map_err(invalid_data)?,
+            None => QualityScore::default(),
+        };
+
+        let filter_status = read_filter_status(&mut reader, string_maps)?;
+        let info = read_info(&mut reader, header, string_maps, self.info_count())?;
+
+        let mut builder = vcf::Record::builder()
+            .set_chromosome(chromosome)
+            .set_position(self.position()?)
+            .set_ids(ids)
+            .set_reference_bases(reference_bases)
+            .set_alternate_bases(alternate_bases)
+            .set_quality_score(quality_score)
+            .set_filter_status(filter_status)
+            .set_info(info);
+
+        if self.format_count() > 0 {
+            let genotypes = self.genotypes()?;
+            let format = genotypes.format(header, string_maps)?;
+            let vcf_genotypes = genotypes.try_into_vcf_genotypes(&format)?;
+
+            builder = builder.set_format(format).set_genotypes(vcf_genotypes);
+        }
+
+        builder.build().map_err(invalid_data)
+    }
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        let mut site_buf = Vec::new();
+
+        // Writing to a `Vec<u8>` does not fail.
+        write_site_fields(&mut site_buf, 0, 1, 1, None, 1, 0, 0, 0).unwrap();
+        value::write_string(&mut site_buf, "").unwrap();
+        value::write_string(&mut site_buf, "N").unwrap();
+        value::write_int_vector(&mut site_buf, &[]).unwrap();
+
+        Self {
+            site_buf,
+            genotypes_buf: Vec::new(),
+        }
+    }
+}
+
+fn invalid_data<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+fn invalid_input<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidInput, e)
+}
+
+fn read_filter_status(reader: &mut &[u8], string_maps: &StringMaps) -> io::Result<FilterStatus> {
+    let indices = match value::read_value(reader)? {
+        Value::Int(indices) => indices,
+        Value::Missing => Vec::new(),
+        value => return Err(invalid_data(format!("invalid filter: {:?}", value))),
+    };
+
+    let ids = indices
+        .into_iter()
+        .map(|i| {
+            i.and_then(|n| usize::try_from(n).ok())
+                .and_then(|j| string_maps.strings().get_index(j))
+                .map(String::from)
+                .ok_or_else(|| invalid_data(format!("invalid filter index: {:?}", i)))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    if ids.is_empty() {
+        Ok(FilterStatus::Missing)
+    } else if ids.len() == 1 && ids[0] == PASS {
+        Ok(FilterStatus::Pass)
+    } else {
+        Ok(FilterStatus::Fail(ids))
+    }
+}
+
+fn read_info(
+    reader: &mut &[u8],
+    header: &vcf::Header,
+    string_maps: &StringMaps,
+    info_count: usize,
+) -> io::Result<Info> {
+    let mut fields = Vec::with_capacity(info_count);
+
+    for _ in 0..info_count {
+        let i = value::read_int(reader)?;
+
+        let id = usize::try_from(i)
+            .ok()
+            .and_then(|j| string_maps.strings().get_index(j))
+            .ok_or_else(|| invalid_data(format!("invalid info key index: {}", i)))?;
+
+        let declared_key = header
+            .infos()
+            .iter()
+            .find(|info| info.id().as_ref() == id)
+            .map(|info| match info.id() {
+                info::field::Key::Other(id, ..) => {
+                    info::field::Key::Other(id.clone(), info.number(), info.ty())
+                }
+                id => id.clone(),
+            });
+
+        let value = value::read_value(reader)?;
+
+        let field = match declared_key {
+            Some(key) => {
+                let value = decode_info_value(&key, value)?;
+                info::Field::new(key, value)
+            }
+            None => {
+                let key: info::field::Key = id.parse().map_err(invalid_data)?;
+
+                let value = if let info::field::Key::Other(..) = key {
+                    decode_undeclared_info_value(value)?
+                } else {
+                    decode_info_value(&key, value)?
+                };
+
+                info::Field::new(key, value)
+            }
+        };
+
+        fields.push(field);
+    }
+
+    Ok(Info::from(fields))
+}
+
+// Decodes a typed value using the number and type of its info key. A value with only missing
+// elements is `None`.
+fn decode_info_value(
+    key: &info::field::Key,
+    value: Value,
+) -> io::Result<Option<info::field::Value>> {
+    use info::field::Value as InfoValue;
+
+    let is_scalar = key.number() == Number::Count(1);
+
+    match (key.ty(), value) {
+        (InfoType::Flag, _) => Ok(Some(InfoValue::Flag)),
+        (InfoType::Integer, Value::Int(values)) => {
+            if is_missing(&values) {
+                Ok(None)
+            } else if is_scalar {
+                single_value(key, values).map(|n| n.map(InfoValue::Integer))
+            } else {
+                Ok(Some(InfoValue::IntegerArray(values)))
+            }
+        }
+        (InfoType::Float, Value::Float(values)) => {
+            if is_missing(&values) {
+                Ok(None)
+            } else if is_scalar {
+                single_value(key, values).map(|n| n.map(InfoValue::Float))
+            } else {
+                Ok(Some(InfoValue::FloatArray(values)))
+            }
+        }
+        (InfoType::Character, Value::String(s)) => {
+            if s == MISSING_VALUE {
+                return Ok(None);
+            }
+
+            let values = s
+                .split(VALUE_DELIMITER)
+                .map(|t| {
+                    let mut chars = t.chars();
+
+                    match (chars.next(), chars.next()) {
+                        _ if t == MISSING_VALUE => Ok(None),
+                        (Some(c), None) => Ok(Some(c)),
+                        _ => Err(invalid_data(format!("invalid character value for {}", key))),
+                    }
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+
+            if is_scalar {
+                single_value(key, values).map(|c| c.map(InfoValue::Character))
+            } else {
+                Ok(Some(InfoValue::CharacterArray(values)))
+            }
+        }
+        (InfoType::String, Value::String(s)) => {
+            if s == MISSING_VALUE {
+                Ok(None)
+            } else if is_scalar {
+                Ok(Some(InfoValue::String(s)))
+            } else {
+                Ok(Some(InfoValue::StringArray(
+                    s.split(VALUE_DELIMITER)
+                        .map(|t| {
+                            if t == MISSING_VALUE {
+                                None
+                            } else {
+                                Some(t.into())
+                            }
+                        })
+                        .collect(),
+                )))
+            }
+        }
+        (_, value) => Err(invalid_data(format!(
+            "invalid info value for {}: {:?}",
+            key, value
+        ))),
+    }
+}
+
+// Decodes a typed value of a key without a definition. Its type is taken from the value.
+fn decode_undeclared_info_value(value: Value) -> io::Result<Option<info::field::Value>> {
+    use info::field::Value as InfoValue;
+
+    match value {
+        Value::Missing => Ok(Some(InfoValue::Flag)),
+        Value::Int(values) if is_missing(&values) => Ok(None),
+        Value::Int(values) => match values[..] {
+            [Some(n)] => Ok(Some(InfoValue::Integer(n))),
+            _ => Ok(Some(InfoValue::IntegerArray(values))),
+        },
+        Value::Float(values) if is_missing(&values) => Ok(None),
+        Value::Float(values) => match values[..] {
+            [Some(n)] => Ok(Some(InfoValue::Float(n))),
+            _ => Ok(Some(InfoValue::FloatArray(values))),
+        },
+        Value::String(s) if s == MISSING_VALUE => Ok(None),
+        Value::String(s) => Ok(Some(InfoValue::String(s))),
+    }
+}
+
+fn is_missing<T>(values: &[Option<T>]) -> bool {
+    values.iter().all(Option::is_none)
+}
+
+fn single_value<T>(key: &info::field::Key, values: Vec<T>) -> io::Result<T> {
+    let mut iter = values.into_iter();
+
+    match (iter.next(), iter.next()) {
+        (Some(value), None) => Ok(value),
+        _ => Err(invalid_data(format!(
+            "invalid info value count for {}: expected 1",
+            key
+        ))),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_site_fields<W>(
+    writer: &mut W,
+    chromosome_id: i32,
+    position: i32,
+    rlen: i32,
+    quality_score: Option<f32>,
+    allele_count: usize,
+    info_count: usize,
+    format_count: usize,
+    sample_count: usize,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    if allele_count > 0xffff || info_count > 0xffff {
+        return Err(invalid_input("too many alleles or info fields"));
+    }
+
+    if format_count > 0xff || sample_count > 0x00ff_ffff {
+        return Err(invalid_input("too many format keys or samples"));
+    }
+
+    writer.write_i32::<LittleEndian>(chromosome_id)?;
+    writer.write_i32::<LittleEndian>(position - 1)?;
+    writer.write_i32::<LittleEndian>(rlen)?;
+
+    let qual = quality_score.map(f32::to_bits).unwrap_or(FLOAT_MISSING);
+    writer.write_u32::<LittleEndian>(qual)?;
+
+    let n_allele_info = (allele_count as u32) << 16 | info_count as u32;
+    writer.write_u32::<LittleEndian>(n_allele_info)?;
+
+    let n_fmt_sample = (format_count as u32) << 24 | sample_count as u32;
+    writer.write_u32::<LittleEndian>(n_fmt_sample)?;
+
+    Ok(())
+}
+
+fn write_site<W>(writer: &mut W, string_maps: &StringMaps, record: &vcf::Record) -> io::Result<()>
+where
+    W: Write,
+{
+    let chromosome = record.chromosome().to_string();
+    let chromosome_id = string_maps
+        .contigs()
+        .get_index_of(&chromosome)
+        .ok_or_else(|| invalid_input(format!("chromosome not in string map: {}", chromosome)))?;
+
+    let reference_bases = record.reference_bases().to_string();
+
+    let end = record
+        .info()
+        .iter()
+        .find(|field| field.key() == &info::field::Key::EndPosition)
+        .and_then(|field| match field.value() {
+            Some(info::field::Value::Integer(n)) => Some(*n),
+            _ => None,
+        });
+
+    let rlen = match end {
+        Some(n) => n - record.position() + 1,
+        None => reference_bases.len() as i32,
+    };
+
+    let alternate_bases = record.alternate_bases();
+    let format_count = record
+        .format()
+        .map(|format| format.len())
+        .unwrap_or_default();
+
+    write_site_fields(
+        writer,
+        chromosome_id as i32,
+        record.position(),
+        rlen,
+        *record.quality_score(),
+        1 + alternate_bases.len(),
+        record.info().len(),
+        format_count,
+        record.genotypes().len(),
+    )?;
+
+    let ids = record.ids();
+
+    if ids.is_empty() {
+        value::write_string(writer, "")?;
+    } else {
+        value::write_string(writer, &ids.to_string())?;
+    }
+
+    value::write_string(writer, &reference_bases)?;
+
+    for allele in alternate_bases.iter() {
+        value::write_string(writer, &allele.to_string())?;
+    }
+
+    write_filter_status(writer, string_maps, record.filter_status())?;
+
+    for field in record.info().iter() {
+        let key = field.key().as_ref();
+
+        let i = string_maps
+            .strings()
+            .get_index_of(key)
+            .ok_or_else(|| invalid_input(format!("info key not in string map: {}", key)))?;
+
+        value::write_int(writer, i as i32)?;
+
+        let value = match field.value() {
+            Some(value) => Value::from(value),
+            None => missing_info_value(field.key()),
+        };
+
+        match value {
+            Value::Missing => value::write_type(writer, value::Type::Missing, 0)?,
+            Value::Int(values) => value::write_int_vector(writer, &values)?,
+            Value::Float(values) => value::write_float_vector(writer, &values)?,
+            Value::String(s) => value::write_string(writer, &s)?,
+        }
+    }
+
+    Ok(())
+}
+
+// Returns the encoded missing value of an info key.
+fn missing_info_value(key: &info::field::Key) -> Value {
+    match key.ty() {
+        InfoType::Integer => Value::Int(vec![None]),
+        InfoType::Float => Value::Float(vec![None]),
+        InfoType::Flag => Value::Missing,
+        InfoType::Character | InfoType::String => Value::String(MISSING_VALUE.into()),
+    }
+}
+
+fn write_filter_status<W>(
+    writer: &mut W,
+    string_maps: &StringMaps,
+    filter_status: &FilterStatus,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let ids = match filter_status {
+        FilterStatus::Missing => Vec::new(),
+        FilterStatus::Pass => vec![String::from(PASS)],
+        FilterStatus::Fail(ids) => ids.clone(),
+    };
+
+    let indices = ids
+        .iter()
+        .map(|id| {
+            string_maps
+                .strings()
+                .get_index_of(id)
+                .map(|i| Some(i as i32))
+                .ok_or_else(|| invalid_input(format!("filter not in string map: {}", id)))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    value::write_int_vector(writer, &indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_vcf::header::{Contig, Filter, Format, Info as InfoHeader, Number};
+
+    use super::*;
+
+    fn build_header() -> vcf::Header {
+        vcf::Header::builder()
+            .add_contig(Contig::new(String::from("sq0")))
+            .add_contig(Contig::new(String::from("sq1")))
+            .add_filter(Filter::new(
+                String::from("q10"),
+                String::from("Quality below 10"),
+            ))
+            .add_info(InfoHeader::new(
+                info::field::Key::SamplesWithDataCount,
+                Number::Count(1),
+                InfoType::Integer,
+                String::from("Number of samples with data"),
+            ))
+            .add_info(InfoHeader::new(
+                info::field::Key::AlleleFrequencies,
+                Number::A,
+                InfoType::Float,
+                String::from("Allele frequency"),
+            ))
+            .add_info(InfoHeader::new(
+                info::field::Key::IsInDbSnp,
+                Number::Count(0),
+                InfoType::Flag,
+                String::from("dbSNP membership"),
+            ))
+            .add_format(Format::new(
+                vcf::record::genotype::field::Key::Genotype,
+                Number::Count(1),
+                vcf::header::format::Type::String,
+                String::from("Genotype"),
+            ))
+            .add_format(Format::new(
+                vcf::record::genotype::field::Key::ConditionalGenotypeQuality,
+                Number::Count(1),
+                vcf::header::format::Type::Integer,
+                String::from("Conditional genotype quality"),
+            ))
+            .add_format(Format::new(
+                vcf::record::genotype::field::Key::ReadDepths,
+                Number::R,
+                vcf::header::format::Type::Integer,
+                String::from("Read depth for each allele"),
+            ))
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .add_sample_name("sample2")
+            .build()
+    }
+
+    #[test]
+    fn test_vcf_record_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let string_maps = StringMaps::try_from(&header)?;
+
+        let s = "sq1\t8\trs13;rs21\tA\tC,TG\t13.5\tq10\tNS=3;AF=0.5,0.25;DB\tGT:GQ:AD\t0|1:13:5,300,0\t./.:.:.\t1/2:-1:2,1,0";
+        let vcf_record = vcf::Record::try_from_str_header(s, &header)?;
+
+        let record = Record::try_from_vcf_record(&string_maps, &vcf_record)?;
+        assert_eq!(record.chromosome_id(), 1);
+        assert_eq!(record.position()?, 8);
+        assert_eq!(record.rlen(), 1);
+        assert_eq!(record.quality_score(), Some(13.5));
+        assert_eq!(record.allele_count(), 3);
+        assert_eq!(record.info_count(), 3);
+        assert_eq!(record.format_count(), 3);
+        assert_eq!(record.sample_count(), 3);
+
+        let actual = record.try_into_vcf_record(&header, &string_maps)?;
+        assert_eq!(actual, vcf_record);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vcf_record_round_trip_with_missing_info_values(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let string_maps = StringMaps::try_from(&header)?;
+
+        let s = "sq1\t8\t.\tA\tC,TG\t.\tPASS\tNS=.;AF=0.5,.";
+        let vcf_record = vcf::Record::try_from_str_header(s, &header)?;
+
+        let record = Record::try_from_vcf_record(&string_maps, &vcf_record)?;
+        let actual = record.try_into_vcf_record(&header, &string_maps)?;
+        assert_eq!(actual, vcf_record);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_genotypes_get() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let string_maps = StringMaps::try_from(&header)?;
+
+        let s = "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:GQ\t0/1:8\t1|1:13\t0:21";
+        let vcf_record = vcf::Record::try_from_str_header(s, &header)?;
+        let record = Record::try_from_vcf_record(&string_maps, &vcf_record)?;
+
+        let genotypes = record.genotypes()?;
+        assert_eq!(genotypes.len(), 3);
+
+        let format = genotypes.format(&header, &string_maps)?;
+        assert_eq!(&format, vcf_record.format().unwrap());
+
+        let genotype = genotypes.get(&format, 2).transpose()?;
+        assert_eq!(genotype.as_ref(), vcf_record.genotypes().get(2));
+
+        assert!(genotypes.get(&format, 3).is_none());
+
+        let actual = record.try_into_vcf_record(&header, &string_maps)?;
+        assert_eq!(actual, vcf_record);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_vcf_record_with_undeclared_key() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let string_maps = StringMaps::try_from(&header)?;
+
+        let vcf_record = vcf::Record::try_from_str_header("sq0\t1\t.\tA\t.\t.\tq20\t.", &header)?;
+        assert!(Record::try_from_vcf_record(&string_maps, &vcf_record).is_err());
+
+        let vcf_record = vcf::Record::try_from_str_header("sq2\t1\t.\tA\t.\t.\t.\t.", &header)?;
+        assert!(Record::try_from_vcf_record(&string_maps, &vcf_record).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_position() {
+        let mut record = Record::default();
+        assert_eq!(record.position().ok(), Some(1));
+
+        record.site_buf_mut()[4..8].copy_from_slice(&i32::MAX.to_le_bytes());
+        assert!(record.position().is_err());
+    }
+}
@@ -0,0 +1,471 @@
+//! BCF record genotypes.
+
+use std::{
+    convert::TryFrom,
+    io::{self, Write},
+};
+
+use noodles_vcf::{
+    self as vcf,
+    header::{format::Type as FormatType, Number},
+    record::{
+        genotype::{
+            self,
+            field::{
+                value::genotype::{allele::Phasing, Allele},
+                Value as GenotypeValue,
+            },
+        },
+        Format, Genotype,
+    },
+};
+
+use crate::header::StringMaps;
+
+use super::value::{self, read_type, read_values, Type, Value};
+
+const MISSING_VALUE: &str = ".";
+const VALUE_DELIMITER: char = ',';
+
+/// Lazily decoded BCF record genotypes.
+///
+/// The per-sample values of a BCF record are stored field by field, i.e., all samples of the
+/// first format key, then all samples of the next, etc. This only reads the field descriptors
+/// up front. Each genotype is decoded on request, reading only the values of that sample.
+#[derive(Debug)]
+pub struct Genotypes<'a> {
+    buf: &'a [u8],
+    sample_count: usize,
+    fields: Vec<FieldDescriptor>,
+}
+
+#[derive(Debug)]
+struct FieldDescriptor {
+    key: usize,
+    ty: Type,
+    len: usize,
+    offset: usize,
+}
+
+impl<'a> Genotypes<'a> {
+    pub(crate) fn new(buf: &'a [u8], format_count: usize, sample_count: usize) -> io::Result<Self> {
+        let mut reader = buf;
+        let mut fields = Vec::with_capacity(format_count);
+
+        for _ in 0..format_count {
+            let key = value::read_int(&mut reader).and_then(|n| {
+                usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })?;
+
+            let (ty, len) = read_type(&mut reader)?;
+            let offset = buf.len() - reader.len();
+
+            let size = ty
+                .size()
+                .checked_mul(len)
+                .and_then(|n| n.checked_mul(sample_count))
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid genotype value length")
+                })?;
+
+            if size > reader.len() {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+
+            reader = &reader[size..];
+
+            fields.push(FieldDescriptor {
+                key,
+                ty,
+                len,
+                offset,
+            });
+        }
+
+        Ok(Self {
+            buf,
+            sample_count,
+            fields,
+        })
+    }
+
+    /// Returns the number of samples.
+    pub fn len(&self) -> usize {
+        self.sample_count
+    }
+
+    /// Returns whether there are any samples.
+    pub fn is_empty(&self) -> bool {
+        self.sample_count == 0
+    }
+
+    /// Returns the format keys of the genotypes.
+    ///
+    /// Keys are resolved using the string map and their definitions in the header.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a key is not in the string map or the list of keys is invalid.
+    pub fn format(&self, header: &vcf::Header, string_maps: &StringMaps) -> io::Result<Format> {
+        let keys = self
+            .fields
+            .iter()
+            .map(|field| {
+                string_maps.strings().get_index(field.key).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid format key index: {}", field.key),
+                    )
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Format::try_from_str_header(&keys.join(":"), header)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Decodes the genotype of the sample at the given index.
+    ///
+    /// `format` is the list of format keys of the genotypes, typically from [`Self::format`].
+    ///
+    /// This returns `None` if the index is out of bounds.
+    pub fn get(&self, format: &Format, i: usize) -> Option<io::Result<Genotype>> {
+        if i >= self.sample_count {
+            return None;
+        }
+
+        Some(self.decode(format, i))
+    }
+
+    /// Decodes the genotypes of all samples.
+    pub fn try_into_vcf_genotypes(&self, format: &Format) -> io::Result<Vec<Genotype>> {
+        (0..self.sample_count)
+            .map(|i| self.decode(format, i))
+            .collect()
+    }
+
+    fn decode(&self, format: &Format, i: usize) -> io::Result<Genotype> {
+        if format.len() != self.fields.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "format key count mismatch",
+            ));
+        }
+
+        let mut fields = Vec::with_capacity(self.fields.len());
+
+        for (field, key) in self.fields.iter().zip(format.iter()) {
+            let size = field.ty.size() * field.len;
+            let start = field.offset + i * size;
+            let mut reader = &self.buf[start..start + size];
+
+            let value = read_values(&mut reader, field.ty, field.len)?;
+            let value = decode_value(key, value)?;
+
+            fields.push(genotype::Field::new(key.clone(), value));
+        }
+
+        // A sample with a single missing value is written as a missing sample (`.`).
+        if let [field] = &fields[..] {
+            if is_missing_field(field) {
+                return Ok(Genotype::default());
+            }
+        }
+
+        Genotype::try_from(fields).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+pub(crate) fn write_genotypes<W>(
+    writer: &mut W,
+    string_maps: &StringMaps,
+    format: &Format,
+    genotypes: &[Genotype],
+) -> io::Result<()>
+where
+    W: Write,
+{
+    for key in format.iter() {
+        let i = string_maps
+            .strings()
+            .get_index_of(key.as_ref())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("format key not in string map: {}", key.as_ref()),
+                )
+            })?;
+
+        value::write_int(writer, i as i32)?;
+
+        let values = genotypes
+            .iter()
+            .map(|genotype| {
+                let value = genotype
+                    .iter()
+                    .find(|field| field.key() == key)
+                    .and_then(|field| field.value());
+
+                match (key, value) {
+                    (genotype::field::Key::Genotype, Some(GenotypeValue::Genotype(genotype))) => {
+                        encode_genotype(genotype).map(Value::Int)
+                    }
+                    (genotype::field::Key::Genotype, Some(v)) => v
+                        .to_string()
+                        .parse()
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                        .and_then(|genotype| encode_genotype(&genotype))
+                        .map(Value::Int),
+                    (genotype::field::Key::Genotype, None) => {
+                        encode_genotype(&decode_genotype(&[])).map(Value::Int)
+                    }
+                    (_, Some(v)) => Ok(Value::from(v)),
+                    (_, None) => Ok(Value::Missing),
+                }
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        write_values(writer, key, &values)?;
+    }
+
+    Ok(())
+}
+
+// Writes the values of all samples of a field, padded to the longest value.
+fn write_values<W>(writer: &mut W, key: &genotype::field::Key, values: &[Value]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mismatched_types_error = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("mismatched value types for key: {}", key.as_ref()),
+        )
+    };
+
+    let ty = match values.iter().find(|v| !matches!(v, Value::Missing)) {
+        Some(Value::Int(_)) | None => Type::for_ints(values.iter().flat_map(|value| match value {
+            Value::Int(values) => &values[..],
+            _ => &[],
+        })),
+        Some(Value::Float(_)) => Type::Float,
+        Some(Value::String(_)) => Type::Char,
+        Some(Value::Missing) => unreachable!(),
+    };
+
+    let len = values
+        .iter()
+        .map(|value| match value {
+            Value::Missing => 1,
+            Value::Int(values) => values.len(),
+            Value::Float(values) => values.len(),
+            Value::String(s) => s.len(),
+        })
+        .max()
+        .unwrap_or_default();
+
+    value::write_type(writer, ty, len)?;
+
+    for value in values {
+        match (ty, value) {
+            (Type::Float, Value::Float(values)) => value::write_float_values(writer, values, len)?,
+            (Type::Float, Value::Missing) => value::write_float_values(writer, &[None], len)?,
+            (Type::Char, Value::String(s)) => value::write_char_values(writer, s, len)?,
+            (Type::Char, Value::Missing) => value::write_char_values(writer, "", len)?,
+            (_, Value::Int(values)) => value::write_int_values(writer, ty, values, len)?,
+            (Type::Int8, Value::Missing)
+            | (Type::Int16, Value::Missing)
+            | (Type::Int32, Value::Missing) => value::write_int_values(writer, ty, &[None], len)?,
+            _ => return Err(mismatched_types_error()),
+        }
+    }
+
+    Ok(())
+}
+
+// Decodes a typed value using the number and type of its format key.
+fn decode_value(key: &genotype::field::Key, value: Value) -> io::Result<Option<GenotypeValue>> {
+    let invalid_value = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid genotype value for {}", key.as_ref()),
+        )
+    };
+
+    if key == &genotype::field::Key::Genotype {
+        return match value {
+            Value::Int(values) => Ok(Some(GenotypeValue::Genotype(decode_genotype(&values)))),
+            Value::Missing => Ok(Some(GenotypeValue::Genotype(decode_genotype(&[])))),
+            _ => Err(invalid_value()),
+        };
+    }
+
+    let is_scalar = match key.number() {
+        Number::Count(0) => return Err(invalid_value()),
+        Number::Count(1) => true,
+        _ => false,
+    };
+
+    match (key.ty(), value) {
+        (_, Value::Missing) => Ok(None),
+        (FormatType::Integer, Value::Int(values)) => match values[..] {
+            [] | [None] => Ok(None),
+            [Some(n)] if is_scalar => Ok(Some(GenotypeValue::Integer(n))),
+            _ if is_scalar => Err(invalid_value()),
+            _ => Ok(Some(GenotypeValue::IntegerArray(values))),
+        },
+        (FormatType::Float, Value::Float(values)) => match values[..] {
+            [] | [None] => Ok(None),
+            [Some(n)] if is_scalar => Ok(Some(GenotypeValue::Float(n))),
+            _ if is_scalar => Err(invalid_value()),
+            _ => Ok(Some(GenotypeValue::FloatArray(values))),
+        },
+        (_, Value::String(s)) if s.is_empty() || s == MISSING_VALUE => Ok(None),
+        (FormatType::Character, Value::String(s)) => {
+            let values = s
+                .split(VALUE_DELIMITER)
+                .map(|t| {
+                    if t == MISSING_VALUE {
+                        return Ok(None);
+                    }
+
+                    let mut chars = t.chars();
+
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Ok(Some(c)),
+                        _ => Err(invalid_value()),
+                    }
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+
+            match values[..] {
+                [Some(c)] if is_scalar => Ok(Some(GenotypeValue::Character(c))),
+                _ if is_scalar => Err(invalid_value()),
+                _ => Ok(Some(GenotypeValue::CharacterArray(values))),
+            }
+        }
+        (FormatType::String, Value::String(s)) => {
+            if is_scalar {
+                Ok(Some(GenotypeValue::String(s)))
+            } else {
+                let values = s
+                    .split(VALUE_DELIMITER)
+                    .map(|t| {
+                        if t == MISSING_VALUE {
+                            None
+                        } else {
+                            Some(t.into())
+                        }
+                    })
+                    .collect();
+
+                Ok(Some(GenotypeValue::StringArray(values)))
+            }
+        }
+        _ => Err(invalid_value()),
+    }
+}
+
+fn is_missing_field(field: &genotype::Field) -> bool {
+    match field.value() {
+        None => true,
+        Some(GenotypeValue::Genotype(genotype)) => {
+            genotype.len() == 1 && genotype[0].position().is_none()
+        }
+        Some(_) => false,
+    }
+}
+
+// Encodes a VCF genotype (`GT`) value.
+//
+// Each allele is stored as `(i + 1) << 1 | is_phased`, where a missing allele is 0. The phase of
+// the first allele is implicit and not stored.
+fn encode_genotype(genotype: &genotype::field::value::Genotype) -> io::Result<Vec<Option<i32>>> {
+    genotype
+        .iter()
+        .enumerate()
+        .map(|(i, allele)| {
+            let n = match allele.position() {
+                Some(position) => i32::try_from(position)
+                    .ok()
+                    .and_then(|n| n.checked_add(1))
+                    .and_then(|n| n.checked_mul(2))
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("invalid genotype: {}", genotype),
+                        )
+                    })?,
+                None => 0,
+            };
+
+            let is_phased = i > 0 && allele.phasing() == Phasing::Phased;
+
+            Ok(Some(n | i32::from(is_phased)))
+        })
+        .collect()
+}
+
+// Decodes a BCF genotype (`GT`) value.
+//
+// The first allele is phased if all other alleles are phased (see § 1.6.2 "Genotype fields" of
+// VCF 4.4). An empty value is a single missing allele.
+fn decode_genotype(values: &[Option<i32>]) -> genotype::field::value::Genotype {
+    let values = if values.is_empty() {
+        &[Some(0)][..]
+    } else {
+        values
+    };
+
+    let is_phased = |n: i32| n & 1 == 1;
+
+    let first_phasing = if values[1..].iter().all(|n| is_phased(n.unwrap_or(0))) {
+        Phasing::Phased
+    } else {
+        Phasing::Unphased
+    };
+
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let n = value.unwrap_or(0);
+            let position = usize::try_from((n >> 1) - 1).ok();
+
+            let phasing = if i == 0 {
+                first_phasing
+            } else if is_phased(n) {
+                Phasing::Phased
+            } else {
+                Phasing::Unphased
+            };
+
+            Allele::new(position, phasing)
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_genotype() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(encode_genotype(&"0/1".parse()?)?, [Some(2), Some(4)]);
+        assert_eq!(encode_genotype(&"1|2".parse()?)?, [Some(4), Some(7)]);
+        assert_eq!(encode_genotype(&"./.".parse()?)?, [Some(0), Some(0)]);
+        assert_eq!(encode_genotype(&"1".parse()?)?, [Some(4)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_genotype() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(decode_genotype(&[Some(2), Some(4)]), "0/1".parse()?);
+        assert_eq!(decode_genotype(&[Some(4), Some(7)]), "1|2".parse()?);
+        assert_eq!(decode_genotype(&[Some(0), Some(1)]), ".|.".parse()?);
+        assert_eq!(decode_genotype(&[Some(4)]), "1".parse()?);
+        assert_eq!(decode_genotype(&[]), ".".parse()?);
+        Ok(())
+    }
+}
@@ -0,0 +1,563 @@
+//! BCF record typed values.
+
+use std::{
+    convert::TryFrom,
+    fmt,
+    io::{self, Read, Write},
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use noodles_vcf::record::{genotype, info};
+
+// A type descriptor length of 15 means the length is stored in a following typed integer.
+const OVERFLOW_LEN: usize = 15;
+
+const MISSING_VALUE: &str = ".";
+const DELIMITER: char = ',';
+
+const INT8_MISSING: i8 = i8::MIN;
+const INT8_END_OF_VECTOR: i8 = i8::MIN + 1;
+const INT8_MIN: i32 = i8::MIN as i32 + 8;
+
+const INT16_MISSING: i16 = i16::MIN;
+const INT16_END_OF_VECTOR: i16 = i16::MIN + 1;
+const INT16_MIN: i32 = i16::MIN as i32 + 8;
+
+const INT32_MISSING: i32 = i32::MIN;
+const INT32_END_OF_VECTOR: i32 = i32::MIN + 1;
+
+pub(crate) const FLOAT_MISSING: u32 = 0x7f80_0001;
+const FLOAT_END_OF_VECTOR: u32 = 0x7f80_0002;
+
+/// A BCF typed value type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Type {
+    Missing,
+    Int8,
+    Int16,
+    Int32,
+    Float,
+    Char,
+}
+
+impl Type {
+    pub(crate) fn size(self) -> usize {
+        match self {
+            Self::Missing => 0,
+            Self::Int8 | Self::Char => 1,
+            Self::Int16 => 2,
+            Self::Int32 | Self::Float => 4,
+        }
+    }
+
+    // Returns the smallest integer type that can hold all of the given values.
+    pub(crate) fn for_ints<'a, I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Option<i32>>,
+    {
+        values.into_iter().flatten().fold(Self::Int8, |ty, &n| {
+            let min_ty = if n >= INT8_MIN && n <= i32::from(i8::MAX) {
+                Self::Int8
+            } else if n >= INT16_MIN && n <= i32::from(i16::MAX) {
+                Self::Int16
+            } else {
+                Self::Int32
+            };
+
+            if min_ty.size() > ty.size() {
+                min_ty
+            } else {
+                ty
+            }
+        })
+    }
+}
+
+impl TryFrom<u8> for Type {
+    type Error = io::Error;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        match n {
+            0 => Ok(Self::Missing),
+            1 => Ok(Self::Int8),
+            2 => Ok(Self::Int16),
+            3 => Ok(Self::Int32),
+            5 => Ok(Self::Float),
+            7 => Ok(Self::Char),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid type: {}", n),
+            )),
+        }
+    }
+}
+
+impl From<Type> for u8 {
+    fn from(ty: Type) -> Self {
+        match ty {
+            Type::Missing => 0,
+            Type::Int8 => 1,
+            Type::Int16 => 2,
+            Type::Int32 => 3,
+            Type::Float => 5,
+            Type::Char => 7,
+        }
+    }
+}
+
+/// A decoded BCF typed value.
+///
+/// Missing elements are `None`. End-of-vector padding is removed.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Missing,
+    Int(Vec<Option<i32>>),
+    Float(Vec<Option<f32>>),
+    String(String),
+}
+
+impl fmt::Display for Value {
+    // Formats the value as a raw VCF value.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => Ok(()),
+            Self::Int(values) => fmt_values(f, values),
+            Self::Float(values) => fmt_values(f, values),
+            Self::String(s) if s.is_empty() => f.write_str(MISSING_VALUE),
+            Self::String(s) => f.write_str(s),
+        }
+    }
+}
+
+impl From<&info::field::Value> for Value {
+    fn from(value: &info::field::Value) -> Self {
+        use info::field::Value as InfoValue;
+
+        match value {
+            InfoValue::Integer(n) => Self::Int(vec![Some(*n)]),
+            InfoValue::Float(n) => Self::Float(vec![Some(*n)]),
+            InfoValue::Flag => Self::Missing,
+            InfoValue::IntegerArray(values) => Self::Int(values.clone()),
+            InfoValue::FloatArray(values) => Self::Float(values.clone()),
+            InfoValue::Character(_)
+            | InfoValue::String(_)
+            | InfoValue::CharacterArray(_)
+            | InfoValue::StringArray(_) => Self::String(value.to_string()),
+        }
+    }
+}
+
+impl From<&genotype::field::Value> for Value {
+    fn from(value: &genotype::field::Value) -> Self {
+        use genotype::field::Value as GenotypeValue;
+
+        match value {
+            GenotypeValue::Integer(n) => Self::Int(vec![Some(*n)]),
+            GenotypeValue::Float(n) => Self::Float(vec![Some(*n)]),
+            GenotypeValue::IntegerArray(values) => Self::Int(values.clone()),
+            GenotypeValue::FloatArray(values) => Self::Float(values.clone()),
+            GenotypeValue::Character(_)
+            | GenotypeValue::String(_)
+            | GenotypeValue::CharacterArray(_)
+            | GenotypeValue::StringArray(_)
+            | GenotypeValue::Genotype(_) => Self::String(value.to_string()),
+        }
+    }
+}
+
+fn fmt_values<T>(f: &mut fmt::Formatter<'_>, values: &[Option<T>]) -> fmt::Result
+where
+    T: fmt::Display,
+{
+    if values.is_empty() {
+        return f.write_str(MISSING_VALUE);
+    }
+
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", DELIMITER)?;
+        }
+
+        match value {
+            Some(v) => write!(f, "{}", v)?,
+            None => f.write_str(MISSING_VALUE)?,
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn read_type(reader: &mut &[u8]) -> io::Result<(Type, usize)> {
+    let descriptor = reader.read_u8()?;
+    let ty = Type::try_from(descriptor & 0x0f)?;
+    let mut len = usize::from(descriptor >> 4);
+
+    if len == OVERFLOW_LEN {
+        len = read_int(reader).and_then(|n| {
+            usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })?;
+    }
+
+    Ok((ty, len))
+}
+
+pub(crate) fn read_value(reader: &mut &[u8]) -> io::Result<Value> {
+    let (ty, len) = read_type(reader)?;
+    read_values(reader, ty, len)
+}
+
+// Reads a typed integer scalar, e.g., a dictionary key or an overflowed length.
+pub(crate) fn read_int(reader: &mut &[u8]) -> io::Result<i32> {
+    match read_value(reader)? {
+        Value::Int(values) if values.len() == 1 => values[0].ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "missing typed integer value")
+        }),
+        value => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("expected typed integer, got {:?}", value),
+        )),
+    }
+}
+
+// Reads `len` values of the given type.
+//
+// The length is untrusted, so it is checked against the remaining buffer before anything is
+// allocated.
+pub(crate) fn read_values(reader: &mut &[u8], ty: Type, len: usize) -> io::Result<Value> {
+    let size = ty
+        .size()
+        .checked_mul(len)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid value length"))?;
+
+    if size > reader.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "value length out of bounds: expected {} bytes, got {}",
+                size,
+                reader.len()
+            ),
+        ));
+    }
+
+    match ty {
+        Type::Missing => Ok(Value::Missing),
+        Type::Int8 => read_ints(reader, len, |r| {
+            r.read_i8().map(|n| match n {
+                INT8_MISSING => Int::Missing,
+                INT8_END_OF_VECTOR => Int::EndOfVector,
+                _ => Int::Value(i32::from(n)),
+            })
+        }),
+        Type::Int16 => read_ints(reader, len, |r| {
+            r.read_i16::<LittleEndian>().map(|n| match n {
+                INT16_MISSING => Int::Missing,
+                INT16_END_OF_VECTOR => Int::EndOfVector,
+                _ => Int::Value(i32::from(n)),
+            })
+        }),
+        Type::Int32 => read_ints(reader, len, |r| {
+            r.read_i32::<LittleEndian>().map(|n| match n {
+                INT32_MISSING => Int::Missing,
+                INT32_END_OF_VECTOR => Int::EndOfVector,
+                _ => Int::Value(n),
+            })
+        }),
+        Type::Float => {
+            let mut values = Vec::with_capacity(len);
+            let mut is_end_of_vector = false;
+
+            for _ in 0..len {
+                let n = reader.read_u32::<LittleEndian>()?;
+
+                if is_end_of_vector || n == FLOAT_END_OF_VECTOR {
+                    is_end_of_vector = true;
+                } else if n == FLOAT_MISSING {
+                    values.push(None);
+                } else {
+                    values.push(Some(f32::from_bits(n)));
+                }
+            }
+
+            Ok(Value::Float(values))
+        }
+        Type::Char => {
+            let mut buf = vec![0; len];
+            reader.read_exact(&mut buf)?;
+
+            let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+            buf.truncate(end);
+
+            String::from_utf8(buf)
+                .map(Value::String)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+    }
+}
+
+enum Int {
+    Value(i32),
+    Missing,
+    EndOfVector,
+}
+
+fn read_ints<R, F>(reader: &mut R, len: usize, mut read_int: F) -> io::Result<Value>
+where
+    R: Read,
+    F: FnMut(&mut R) -> io::Result<Int>,
+{
+    let mut values = Vec::with_capacity(len);
+    let mut is_end_of_vector = false;
+
+    for _ in 0..len {
+        // End-of-vector values are still read to consume the entire vector.
+        match read_int(reader)? {
+            _ if is_end_of_vector => {}
+            Int::Value(n) => values.push(Some(n)),
+            Int::Missing => values.push(None),
+            Int::EndOfVector => is_end_of_vector = true,
+        }
+    }
+
+    Ok(Value::Int(values))
+}
+
+pub(crate) fn write_type<W>(writer: &mut W, ty: Type, len: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    let ty = u8::from(ty);
+
+    if len < OVERFLOW_LEN {
+        writer.write_u8((len as u8) << 4 | ty)
+    } else {
+        writer.write_u8((OVERFLOW_LEN as u8) << 4 | ty)?;
+
+        let n = i32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        write_int(writer, n)
+    }
+}
+
+pub(crate) fn write_int<W>(writer: &mut W, n: i32) -> io::Result<()>
+where
+    W: Write,
+{
+    write_int_vector(writer, &[Some(n)])
+}
+
+pub(crate) fn write_int_vector<W>(writer: &mut W, values: &[Option<i32>]) -> io::Result<()>
+where
+    W: Write,
+{
+    let ty = Type::for_ints(values);
+    write_type(writer, ty, values.len())?;
+    write_int_values(writer, ty, values, values.len())
+}
+
+pub(crate) fn write_float_vector<W>(writer: &mut W, values: &[Option<f32>]) -> io::Result<()>
+where
+    W: Write,
+{
+    write_type(writer, Type::Float, values.len())?;
+    write_float_values(writer, values, values.len())
+}
+
+pub(crate) fn write_string<W>(writer: &mut W, s: &str) -> io::Result<()>
+where
+    W: Write,
+{
+    write_type(writer, Type::Char, s.len())?;
+    writer.write_all(s.as_bytes())
+}
+
+// Writes integer values as the given type, padded to `len` with end-of-vector values.
+pub(crate) fn write_int_values<W>(
+    writer: &mut W,
+    ty: Type,
+    values: &[Option<i32>],
+    len: usize,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let padding = len - values.len();
+
+    match ty {
+        Type::Int8 => {
+            for value in values {
+                writer.write_i8(value.map(|n| n as i8).unwrap_or(INT8_MISSING))?;
+            }
+
+            for _ in 0..padding {
+                writer.write_i8(INT8_END_OF_VECTOR)?;
+            }
+        }
+        Type::Int16 => {
+            for value in values {
+                let n = value.map(|n| n as i16).unwrap_or(INT16_MISSING);
+                writer.write_i16::<LittleEndian>(n)?;
+            }
+
+            for _ in 0..padding {
+                writer.write_i16::<LittleEndian>(INT16_END_OF_VECTOR)?;
+            }
+        }
+        Type::Int32 => {
+            for value in values {
+                writer.write_i32::<LittleEndian>(value.unwrap_or(INT32_MISSING))?;
+            }
+
+            for _ in 0..padding {
+                writer.write_i32::<LittleEndian>(INT32_END_OF_VECTOR)?;
+            }
+        }
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid integer type: {:?}", ty),
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+// Writes float values, padded to `len` with end-of-vector values.
+pub(crate) fn write_float_values<W>(
+    writer: &mut W,
+    values: &[Option<f32>],
+    len: usize,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    for value in values {
+        let n = value.map(f32::to_bits).unwrap_or(FLOAT_MISSING);
+        writer.write_u32::<LittleEndian>(n)?;
+    }
+
+    for _ in values.len()..len {
+        writer.write_u32::<LittleEndian>(FLOAT_END_OF_VECTOR)?;
+    }
+
+    Ok(())
+}
+
+// Writes a string, padded to `len` with NULs.
+pub(crate) fn write_char_values<W>(writer: &mut W, s: &str, len: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(s.as_bytes())?;
+
+    for _ in s.len()..len {
+        writer.write_u8(0)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_ints() {
+        assert_eq!(Type::for_ints(&[]), Type::Int8);
+        assert_eq!(Type::for_ints(&[Some(-120), Some(127), None]), Type::Int8);
+        assert_eq!(Type::for_ints(&[Some(-121)]), Type::Int16);
+        assert_eq!(Type::for_ints(&[Some(8), Some(128)]), Type::Int16);
+        assert_eq!(Type::for_ints(&[Some(-32761)]), Type::Int32);
+        assert_eq!(Type::for_ints(&[Some(32768), Some(0)]), Type::Int32);
+    }
+
+    #[test]
+    fn test_write_int_vector() -> io::Result<()> {
+        let mut buf = Vec::new();
+        write_int_vector(&mut buf, &[Some(1), None, Some(300)])?;
+        assert_eq!(buf, [0x32, 0x01, 0x00, 0x00, 0x80, 0x2c, 0x01]);
+
+        let mut reader = &buf[..];
+        assert_eq!(
+            read_value(&mut reader)?,
+            Value::Int(vec![Some(1), None, Some(300)])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_values_with_end_of_vector() -> io::Result<()> {
+        let data = [0x05, 0x80, 0x81, 0x81];
+        let mut reader = &data[..];
+        assert_eq!(
+            read_values(&mut reader, Type::Int8, 4)?,
+            Value::Int(vec![Some(5), None])
+        );
+        assert!(reader.is_empty());
+
+        let mut buf = Vec::new();
+        write_float_values(&mut buf, &[Some(0.5), None], 3)?;
+        let mut reader = &buf[..];
+        assert_eq!(
+            read_values(&mut reader, Type::Float, 3)?,
+            Value::Float(vec![Some(0.5), None])
+        );
+
+        let data = *b"ab\0\0";
+        let mut reader = &data[..];
+        assert_eq!(
+            read_values(&mut reader, Type::Char, 4)?,
+            Value::String(String::from("ab"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_values_with_out_of_bounds_length() {
+        let data = [0x01, 0x02];
+
+        let mut reader = &data[..];
+        assert!(matches!(
+            read_values(&mut reader, Type::Int32, 1),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let mut reader = &data[..];
+        assert!(matches!(
+            read_values(&mut reader, Type::Float, usize::MAX),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        // A char vector with an overflowed length of i32::MAX.
+        let data = [0xf7, 0x13, 0xff, 0xff, 0xff, 0x7f];
+        let mut reader = &data[..];
+        assert!(matches!(
+            read_value(&mut reader),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_write_type_with_overflowed_length() -> io::Result<()> {
+        let mut buf = Vec::new();
+        write_type(&mut buf, Type::Char, 20)?;
+        assert_eq!(buf, [0xf7, 0x11, 0x14]);
+
+        let mut reader = &buf[..];
+        assert_eq!(read_type(&mut reader)?, (Type::Char, 20));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Value::Missing.to_string(), "");
+        assert_eq!(Value::Int(vec![Some(1), None]).to_string(), "1,.");
+        assert_eq!(Value::Int(Vec::new()).to_string(), ".");
+        assert_eq!(Value::Float(vec![Some(0.5)]).to_string(), "0.5");
+        assert_eq!(Value::String(String::new()).to_string(), ".");
+        assert_eq!(Value::String(String::from("a,b")).to_string(), "a,b");
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    convert::TryFrom,
+    io::{self, Write},
+};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use noodles_bgzf as bgzf;
+use noodles_vcf as vcf;
+
+use super::{header::StringMaps, Record, MAGIC_NUMBER};
+
+/// A BCF writer.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{convert::TryFrom, io};
+/// use noodles_bcf::{self as bcf, header::StringMaps};
+/// use noodles_vcf::{self as vcf, header::Contig};
+///
+/// let mut writer = bcf::Writer::new(Vec::new());
+///
+/// let header = vcf::Header::builder()
+///     .add_contig(Contig::new(String::from("sq0")))
+///     .build();
+/// writer.write_header(&header)?;
+///
+/// let string_maps = StringMaps::try_from(&header)
+///     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+///
+/// let record = vcf::Record::builder()
+///     .set_chromosome("sq0".parse().unwrap())
+///     .set_position(1)
+///     .set_reference_bases("A".parse().unwrap())
+///     .build()
+///     .unwrap();
+///
+/// writer.write_vcf_record(&string_maps, &record)?;
+/// writer.try_finish()?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct Writer<W>
+where
+    W: Write,
+{
+    inner: bgzf::Writer<W>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a BCF writer.
+    ///
+    /// The underlying writer is wrapped in a BGZF encoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let writer = bcf::Writer::new(Vec::new());
+    /// ```
+    pub fn new(writer: W) -> Self {
+        Self {
+            inner: bgzf::Writer::new(writer),
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let writer = bcf::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Attempts to finish the output stream.
+    ///
+    /// This is typically only manually called if the underlying stream is needed before the
+    /// writer is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf as bcf;
+    /// let mut writer = bcf::Writer::new(Vec::new());
+    /// writer.try_finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<()> {
+        self.inner.try_finish()
+    }
+
+    /// Writes the BCF magic number and version and a VCF header.
+    ///
+    /// The string maps of the written header can be built using
+    /// `StringMaps::try_from(&header)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf as bcf;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut writer = bcf::Writer::new(Vec::new());
+    /// writer.write_header(&vcf::Header::default())?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        self.inner.write_all(MAGIC_NUMBER)?;
+
+        let text = header.to_string();
+        let l_text = u32::try_from(text.len() + 1)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        self.inner.write_u32::<LittleEndian>(l_text)?;
+
+        self.inner.write_all(text.as_bytes())?;
+        self.inner.write_u8(0)?;
+
+        Ok(())
+    }
+
+    /// Writes a BCF record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf as bcf;
+    /// let mut writer = bcf::Writer::new(Vec::new());
+    /// writer.write_record(&bcf::Record::default())?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        let site_buf = record.site_buf();
+        let genotypes_buf = record.genotypes_buf();
+
+        let l_shared = u32::try_from(site_buf.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let l_indiv = u32::try_from(genotypes_buf.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        self.inner.write_u32::<LittleEndian>(l_shared)?;
+        self.inner.write_u32::<LittleEndian>(l_indiv)?;
+        self.inner.write_all(site_buf)?;
+        self.inner.write_all(genotypes_buf)?;
+
+        Ok(())
+    }
+
+    /// Converts a VCF record to a BCF record and writes it.
+    ///
+    /// See [`Record::try_from_vcf_record`].
+    pub fn write_vcf_record(
+        &mut self,
+        string_maps: &StringMaps,
+        record: &vcf::Record,
+    ) -> io::Result<()> {
+        let record = Record::try_from_vcf_record(string_maps, record)?;
+        self.write_record(&record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_vcf::{
+        header::{format::Type, Contig, Format, Number},
+        record::genotype::field::Key,
+    };
+
+    use crate::Reader;
+
+    use super::*;
+
+    #[test]
+    fn test_write_header_and_records() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::builder()
+            .add_contig(Contig::new(String::from("sq0")))
+            .add_format(Format::new(
+                Key::Genotype,
+                Number::Count(1),
+                Type::String,
+                String::from("Genotype"),
+            ))
+            .add_sample_name("sample0")
+            .build();
+
+        let string_maps = StringMaps::try_from(&header)?;
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_header(&header)?;
+
+        let raw_records = [
+            "sq0\t1\t.\tA\t.\t.\t.\t.\tGT\t0/0",
+            "sq0\t8\trs13\tC\tG,T\t21\tPASS\t.\tGT\t1|2",
+        ];
+
+        let mut records = Vec::new();
+
+        for s in raw_records.iter() {
+            let record = vcf::Record::try_from_str_header(s, &header)?;
+            writer.write_vcf_record(&string_maps, &record)?;
+            records.push(record);
+        }
+
+        writer.try_finish()?;
+
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        let raw_header = reader.read_header()?;
+        assert_eq!(raw_header, header.to_string());
+
+        let actual_header: vcf::Header = raw_header.parse()?;
+        let actual_string_maps: StringMaps = raw_header.parse()?;
+        assert_eq!(actual_string_maps, string_maps);
+
+        let actual_records = reader
+            .records()
+            .map(|result| {
+                result.and_then(|r| r.try_into_vcf_record(&actual_header, &actual_string_maps))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(actual_records, records);
+
+        Ok(())
+    }
+}
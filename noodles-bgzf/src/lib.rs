@@ -6,8 +6,8 @@
 //! streams. Each stream is called a block, with its uncompressed data size being constrained to
 //! less than 64 KiB. This multistream gzip allows random access using [`virtual positions`].
 //!
-//! noodles-bgzf abstracts away the concept of blocks, implementing [`std::io::Read`] for the
-//! reader and [`std::io::Write`] for the writer.
+//! noodles-bgzf abstracts away the concept of blocks, implementing [`std::io::Read`] and
+//! [`std::io::BufRead`] for the reader and [`std::io::Write`] for the writer.
 //!
 //! [`virtual positions`]: VirtualPosition
 //!
@@ -1,4 +1,4 @@
-use std::io::{self, Read, Seek, SeekFrom};
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
 
 use byteorder::{ByteOrder, LittleEndian};
 use flate2::read::DeflateDecoder;
@@ -10,7 +10,10 @@ use super::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
 /// Due to the static structure of a BGZF block, gzip headers are mostly discarded. CRC32
 /// validation is also disabled when decompressing data.
 ///
-/// This implements [`std::io::Read`], consuming compressed data and emitting uncompressed data.
+/// This implements [`std::io::Read`] and [`std::io::BufRead`], consuming compressed data and
+/// emitting uncompressed data. The buffer of the latter is the uncompressed data of the current
+/// block, so a reader can be used directly as a buffered reader without losing track of its
+/// virtual position.
 ///
 /// # Examples
 ///
@@ -103,9 +106,10 @@ where
         let (compressed_pos, uncompressed_pos) = pos.into();
 
         self.inner.seek(SeekFrom::Start(compressed_pos))?;
-        self.position = compressed_pos;
 
-        read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
+        let block_size = read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
+        self.block.set_position(compressed_pos);
+        self.position = compressed_pos + block_size as u64;
 
         self.block
             .data_mut()
@@ -136,6 +140,29 @@ where
     }
 }
 
+impl<R> BufRead for Reader<R>
+where
+    R: Read,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.block.is_eof() {
+            match read_block(&mut self.inner, &mut self.cdata, &mut self.block)? {
+                0 => break,
+                bs => {
+                    self.block.set_position(self.position);
+                    self.position += bs as u64;
+                }
+            }
+        }
+
+        self.block.data_mut().fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.block.data_mut().consume(amt);
+    }
+}
+
 fn read_block_size<R>(reader: &mut R) -> io::Result<u16>
 where
     R: Read,
@@ -196,3 +223,66 @@ where
 
     Ok(block_size)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use crate::Writer;
+
+    use super::*;
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(Cursor::new(data));
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodlesbgzf");
+
+        reader.seek(VirtualPosition::from(2))?;
+        assert_eq!(reader.virtual_position(), VirtualPosition::from(2));
+
+        let mut buf = [0; 5];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"odles");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fill_buf() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(Cursor::new(data));
+
+        assert_eq!(reader.fill_buf()?, b"noodles");
+        reader.consume(7);
+
+        let block_1_position = reader.virtual_position();
+        assert_eq!(block_1_position.uncompressed(), 0);
+        assert!(block_1_position.compressed() > 0);
+
+        assert_eq!(reader.fill_buf()?, b"bgzf");
+        reader.consume(2);
+        assert_eq!(
+            reader.virtual_position(),
+            VirtualPosition::from(u64::from(block_1_position) | 2)
+        );
+
+        reader.consume(2);
+        assert!(reader.fill_buf()?.is_empty());
+
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ use std::{
 use byteorder::{LittleEndian, WriteBytesExt};
 use flate2::{write::DeflateEncoder, Compression, Crc};
 
-use super::{block, gz, BGZF_HEADER_SIZE};
+use super::{block, gz, VirtualPosition, BGZF_HEADER_SIZE};
 
 const MAX_COMPRESSED_DATA_LENGTH: usize = block::MAX_LENGTH + 1; // bytes
 
@@ -46,6 +46,7 @@ where
     W: Write,
 {
     inner: Option<W>,
+    position: u64,
     encoder: DeflateEncoder<Vec<u8>>,
     crc: Crc,
 }
@@ -65,6 +66,7 @@ where
     pub fn new(inner: W) -> Self {
         Self {
             inner: Some(inner),
+            position: 0,
             encoder: DeflateEncoder::new(Vec::new(), Compression::default()),
             crc: Crc::new(),
         }
@@ -83,6 +85,31 @@ where
         self.inner.as_ref().unwrap()
     }
 
+    /// Returns the current virtual position of the stream.
+    ///
+    /// The compressed position is the number of compressed bytes written, and the uncompressed
+    /// position is the number of bytes written to the current (unflushed) block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::from(0));
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::from(7));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn virtual_position(&self) -> VirtualPosition {
+        // A full block is always flushed on write, so the uncompressed position of the current
+        // block fits in 16 bits.
+        let uncompressed_position = u64::from(self.crc.amount());
+        VirtualPosition::from(self.position << 16 | uncompressed_position)
+    }
+
     fn flush_block(&mut self) -> io::Result<()> {
         self.encoder.try_finish()?;
 
@@ -93,6 +120,8 @@ where
         inner.write_all(&data[..])?;
         write_trailer(inner, self.crc.sum(), self.crc.amount())?;
 
+        self.position += (BGZF_HEADER_SIZE + data.len() + gz::TRAILER_SIZE) as u64;
+
         self.encoder.reset(Vec::new())?;
         self.crc.reset();
 
@@ -176,6 +205,10 @@ where
         let bytes_written = self.encoder.write(&buf[..bytes_to_be_written])?;
         self.crc.update(&buf[..bytes_written]);
 
+        if self.crc.amount() as usize >= MAX_COMPRESSED_DATA_LENGTH {
+            self.flush()?;
+        }
+
         Ok(bytes_written)
     }
 
@@ -235,4 +268,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_virtual_position() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        assert_eq!(writer.virtual_position(), VirtualPosition::from(0));
+
+        writer.write_all(b"noodles")?;
+        assert_eq!(writer.virtual_position(), VirtualPosition::from(7));
+
+        writer.flush()?;
+        let compressed_position = writer.get_ref().len() as u64;
+        assert_eq!(
+            writer.virtual_position(),
+            VirtualPosition::from(compressed_position << 16)
+        );
+
+        writer.write_all(&[0; MAX_COMPRESSED_DATA_LENGTH])?;
+        let compressed_position = writer.get_ref().len() as u64;
+        assert_eq!(
+            writer.virtual_position(),
+            VirtualPosition::from(compressed_position << 16)
+        );
+
+        Ok(())
+    }
 }
@@ -9,6 +9,7 @@ mod num;
 mod rans;
 pub mod reader;
 pub mod record;
+pub mod subsample;
 pub mod writer;
 
 pub use self::{
@@ -0,0 +1,50 @@
+//! CRAM record subsampling by template.
+
+use noodles_sam::subsample::Subsampler;
+
+use super::Record;
+
+/// Returns whether the given record is kept.
+///
+/// Read names are required to keep or drop the records of a template together. If the read names
+/// are not preserved in the CRAM, all records are either kept or dropped.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::{self as cram, subsample};
+/// use noodles_sam::subsample::Subsampler;
+///
+/// let record = cram::Record::builder().set_read_name(b"r0".to_vec()).build();
+/// assert!(subsample::keep(&Subsampler::new(8, 1.0), &record));
+/// ```
+pub fn keep(subsampler: &Subsampler, record: &Record) -> bool {
+    let read_name = record.read_name();
+    let read_name = read_name.strip_suffix(&[0]).unwrap_or(read_name);
+    subsampler.keep_read_name(read_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep() {
+        let subsampler = Subsampler::new(8, 0.5);
+
+        for i in 0..100 {
+            let read_name = format!("r{}", i).into_bytes();
+            let expected = subsampler.keep_read_name(&read_name);
+
+            let record = Record::builder().set_read_name(read_name.clone()).build();
+            assert_eq!(keep(&subsampler, &record), expected);
+
+            let mut nul_terminated_read_name = read_name;
+            nul_terminated_read_name.push(0);
+            let record = Record::builder()
+                .set_read_name(nul_terminated_read_name)
+                .build();
+            assert_eq!(keep(&subsampler, &record), expected);
+        }
+    }
+}
@@ -7,6 +7,9 @@ pub use self::records::Records;
 use std::io::{self, BufRead, Seek, SeekFrom};
 
 use memchr::memchr;
+use noodles::Region;
+
+use super::{fai, record::Definition, Record};
 
 pub(crate) const DEFINITION_PREFIX: u8 = b'>';
 pub(crate) const NEWLINE: u8 = b'\n';
@@ -193,6 +196,107 @@ where
     }
 }
 
+impl<R> Reader<R>
+where
+    R: BufRead + Seek,
+{
+    /// Reads the sequence of the given region.
+    ///
+    /// The region's reference sequence name is resolved using the FASTA index. The returned
+    /// record uses the region as its reference sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles::Region;
+    /// use noodles_fasta::{self as fasta, fai};
+    ///
+    /// let data = b">sq0\nACGT\n>sq1\nNNNN\nACGT\nNN\n";
+    /// let index = vec![
+    ///     fai::Record::new(String::from("sq0"), 4, 5, 4, 5),
+    ///     fai::Record::new(String::from("sq1"), 10, 15, 4, 5),
+    /// ];
+    ///
+    /// let mut reader = fasta::Reader::new(Cursor::new(&data[..]));
+    /// let record = reader.query(&index, &Region::mapped("sq1", 4, 7))?;
+    ///
+    /// assert_eq!(record.reference_sequence_name(), "sq1:4-7");
+    /// assert_eq!(record.sequence(), b"NACG");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn query(&mut self, index: &[fai::Record], region: &Region) -> io::Result<Record> {
+        let (name, start, end) = match region {
+            Region::Mapped { name, start, end } => (name, *start, *end),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "region is not mapped",
+                ))
+            }
+        };
+
+        let index_record = index
+            .iter()
+            .find(|r| r.reference_sequence_name() == name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "region reference sequence does not exist in index: {:?}",
+                        region
+                    ),
+                )
+            })?;
+
+        if start < 1 || end < start || end as u64 > index_record.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid region interval: {}", region),
+            ));
+        }
+
+        let start = (start - 1) as u64;
+        let len = (end as u64) - start;
+
+        let pos = index_record.offset()
+            + start / index_record.line_bases() * index_record.line_width()
+            + start % index_record.line_bases();
+
+        self.inner.seek(SeekFrom::Start(pos))?;
+
+        let mut sequence = Vec::with_capacity(len as usize);
+
+        while (sequence.len() as u64) < len {
+            let reader_buf = self.inner.fill_buf()?;
+
+            if reader_buf.is_empty() {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+
+            let mut consumed = 0;
+
+            for &b in reader_buf {
+                if sequence.len() as u64 == len {
+                    break;
+                }
+
+                if b != NEWLINE && b != CARRIAGE_RETURN as u8 {
+                    sequence.push(b);
+                }
+
+                consumed += 1;
+            }
+
+            self.inner.consume(consumed);
+        }
+
+        let definition = Definition::new(region.to_string(), None);
+
+        Ok(Record::new(definition, sequence))
+    }
+}
+
 // Reads all bytes until a line feed ('\n') or EOF is reached.
 //
 // The buffer will not include the trailing newline ('\n' or '\r\n').
@@ -298,6 +402,32 @@ mod tests {
         assert_eq!(buf, b"NNNN");
     }
 
+    #[test]
+    fn test_query() -> io::Result<()> {
+        let data = b">sq0\nACGT\n>sq1\r\nNNNN\r\nACGT\r\nNN\r\n";
+        let index = vec![
+            fai::Record::new(String::from("sq0"), 4, 5, 4, 5),
+            fai::Record::new(String::from("sq1"), 10, 16, 4, 6),
+        ];
+
+        let mut reader = Reader::new(Cursor::new(&data[..]));
+
+        let record = reader.query(&index, &Region::mapped("sq0", 1, 4))?;
+        assert_eq!(record.sequence(), b"ACGT");
+
+        let record = reader.query(&index, &Region::mapped("sq1", 3, 10))?;
+        assert_eq!(record.sequence(), b"NNACGTNN");
+
+        let record = reader.query(&index, &Region::mapped("sq1", 9, 9))?;
+        assert_eq!(record.sequence(), b"N");
+
+        assert!(reader.query(&index, &Region::mapped("sq2", 1, 4)).is_err());
+        assert!(reader.query(&index, &Region::mapped("sq0", 2, 5)).is_err());
+        assert!(reader.query(&index, &Region::Unmapped).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_line() -> io::Result<()> {
         let mut buf = String::new();
@@ -0,0 +1,543 @@
+//! SAM record conversion to FASTQ.
+//!
+//! [`Converter`] converts a SAM record to a FASTQ record, restoring the sequence and quality
+//! scores to the orientation of the original read. [`Writer`] writes the templates of a SAM file
+//! as interleaved or split FASTQ files, keeping read pairs in sync.
+//!
+//! BAM records can be converted after converting them to SAM records, e.g., with
+//! `noodles_bam::Record::try_into_sam_record`.
+//!
+//! # Examples
+//!
+//! ```
+//! use noodles_sam::{self as sam, fastq::{Converter, Writer}, template::Templates};
+//!
+//! let data = b"\
+//! r0\t67\tsq0\t1\t60\t4M\t=\t9\t12\tACGT\tNDLS
+//! r0\t147\tsq0\t9\t60\t4M\t=\t1\t-12\tAACC\tABCD
+//! ";
+//!
+//! let mut reader = sam::Reader::new(&data[..]);
+//! let mut writer = Writer::interleaved(Converter::default(), Vec::new());
+//!
+//! for result in Templates::new(reader.records()) {
+//!     let template = result?;
+//!     writer.write_template(&template)?;
+//! }
+//!
+//! assert_eq!(writer.get_ref(), b"@r0\nACGT\n+\nNDLS\n@r0\nGGTT\n+\nDCBA\n");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{self, Write};
+
+use noodles_fastq as fastq;
+
+use crate::{
+    record::{data::field::Tag, QualityScores, Sequence},
+    template::Template,
+    Record,
+};
+
+// The quality score used when a record has no quality scores, i.e., Phred 1.
+const DEFAULT_QUALITY_SCORE: u8 = b'"';
+
+/// A SAM to FASTQ record converter builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    tags: Vec<Tag>,
+    use_original_quality_scores: bool,
+    include_secondary: bool,
+    include_supplementary: bool,
+    append_segment_suffix: bool,
+}
+
+impl Builder {
+    /// Sets the data field tags to copy to the FASTQ read name line.
+    ///
+    /// Fields that are present are appended to the read name, separated by tabs, in the given
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{fastq::Converter, record::data::field::Tag};
+    /// let converter = Converter::builder().set_tags(vec![Tag::SampleBarcodeSequence]).build();
+    /// ```
+    pub fn set_tags(mut self, tags: Vec<Tag>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Sets whether the original quality scores (`OQ`) replace the quality scores, when present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::fastq::Converter;
+    /// let converter = Converter::builder().set_use_original_quality_scores(true).build();
+    /// ```
+    pub fn set_use_original_quality_scores(mut self, use_original_quality_scores: bool) -> Self {
+        self.use_original_quality_scores = use_original_quality_scores;
+        self
+    }
+
+    /// Sets whether secondary alignments are written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::fastq::Converter;
+    /// let converter = Converter::builder().set_include_secondary(true).build();
+    /// ```
+    pub fn set_include_secondary(mut self, include_secondary: bool) -> Self {
+        self.include_secondary = include_secondary;
+        self
+    }
+
+    /// Sets whether supplementary alignments are written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::fastq::Converter;
+    /// let converter = Converter::builder().set_include_supplementary(true).build();
+    /// ```
+    pub fn set_include_supplementary(mut self, include_supplementary: bool) -> Self {
+        self.include_supplementary = include_supplementary;
+        self
+    }
+
+    /// Sets whether `/1` and `/2` are appended to the read names of the first and last segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::fastq::Converter;
+    /// let converter = Converter::builder().set_append_segment_suffix(true).build();
+    /// ```
+    pub fn set_append_segment_suffix(mut self, append_segment_suffix: bool) -> Self {
+        self.append_segment_suffix = append_segment_suffix;
+        self
+    }
+
+    /// Builds a SAM to FASTQ record converter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::fastq::Converter;
+    /// let converter = Converter::builder().build();
+    /// ```
+    pub fn build(self) -> Converter {
+        Converter {
+            tags: self.tags,
+            use_original_quality_scores: self.use_original_quality_scores,
+            include_secondary: self.include_secondary,
+            include_supplementary: self.include_supplementary,
+            append_segment_suffix: self.append_segment_suffix,
+        }
+    }
+}
+
+/// A SAM to FASTQ record converter.
+///
+/// By default, no data fields are copied, the quality scores are used as is, and secondary and
+/// supplementary alignments are skipped.
+#[derive(Clone, Debug, Default)]
+pub struct Converter {
+    tags: Vec<Tag>,
+    use_original_quality_scores: bool,
+    include_secondary: bool,
+    include_supplementary: bool,
+    append_segment_suffix: bool,
+}
+
+impl Converter {
+    /// Returns a builder to create a converter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::fastq::Converter;
+    /// let builder = Converter::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns whether the record is written by this converter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, fastq::Converter, record::Flags};
+    ///
+    /// let converter = Converter::default();
+    /// assert!(converter.is_included(&sam::Record::default()));
+    ///
+    /// let record = sam::Record::builder().set_flags(Flags::SECONDARY).build();
+    /// assert!(!converter.is_included(&record));
+    /// ```
+    pub fn is_included(&self, record: &Record) -> bool {
+        let flags = record.flags();
+
+        if flags.is_secondary() {
+            self.include_secondary
+        } else if flags.is_supplementary() {
+            self.include_supplementary
+        } else {
+            true
+        }
+    }
+
+    /// Converts a SAM record to a FASTQ record.
+    ///
+    /// Reverse complemented records are reverse complemented back to the orientation of the
+    /// original read. Missing quality scores are set to Phred 1 (`"`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq as fastq;
+    /// use noodles_sam::{self as sam, fastq::Converter, record::data::field::Tag};
+    ///
+    /// let record: sam::Record = "r0\t16\t*\t0\t255\t*\t*\t0\t0\tAACG\tABCD\tBC:Z:TT".parse()?;
+    ///
+    /// let converter = Converter::builder()
+    ///     .set_tags(vec![Tag::SampleBarcodeSequence])
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     converter.convert(&record)?,
+    ///     fastq::Record::new("r0\tBC:Z:TT", "CGTT", "DCBA")
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn convert(&self, record: &Record) -> io::Result<fastq::Record> {
+        let read_name = self.build_read_name(record);
+
+        let mut sequence = sequence_to_bytes(record.sequence());
+        let mut quality_scores = self.quality_scores(record)?;
+
+        if quality_scores.is_empty() {
+            quality_scores = vec![DEFAULT_QUALITY_SCORE; sequence.len()];
+        } else if quality_scores.len() != sequence.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "quality scores length mismatch: expected {}, got {}",
+                    sequence.len(),
+                    quality_scores.len()
+                ),
+            ));
+        }
+
+        if record.flags().is_reverse_complemented() {
+            reverse_complement(&mut sequence);
+            quality_scores.reverse();
+        }
+
+        Ok(fastq::Record::new(read_name, sequence, quality_scores))
+    }
+
+    fn build_read_name(&self, record: &Record) -> String {
+        let mut read_name = record
+            .read_name()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| String::from("*"));
+
+        let flags = record.flags();
+
+        if self.append_segment_suffix && flags.is_paired() {
+            if flags.is_read_1() {
+                read_name.push_str("/1");
+            } else if flags.is_read_2() {
+                read_name.push_str("/2");
+            }
+        }
+
+        for tag in &self.tags {
+            if let Some(field) = record.data().get(tag) {
+                read_name.push('\t');
+                read_name.push_str(&field.to_string());
+            }
+        }
+
+        read_name
+    }
+
+    fn quality_scores(&self, record: &Record) -> io::Result<Vec<u8>> {
+        if self.use_original_quality_scores {
+            let original_quality_scores = record
+                .data()
+                .get(&Tag::OriginalQualityScores)
+                .map(|field| field.value());
+
+            if let Some(value) = original_quality_scores {
+                return value
+                    .as_str()
+                    .map(|s| s.as_bytes().to_vec())
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidData, "invalid OQ field type")
+                    });
+            }
+        }
+
+        Ok(quality_scores_to_bytes(record.quality_scores()))
+    }
+}
+
+/// A FASTQ writer for SAM templates.
+pub struct Writer<W>
+where
+    W: Write,
+{
+    converter: Converter,
+    read_1: fastq::Writer<W>,
+    read_2: Option<fastq::Writer<W>>,
+    singletons: Option<fastq::Writer<W>>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a writer that writes all records to a single interleaved FASTQ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::fastq::{Converter, Writer};
+    /// let writer = Writer::interleaved(Converter::default(), Vec::new());
+    /// ```
+    pub fn interleaved(converter: Converter, inner: W) -> Self {
+        Self {
+            converter,
+            read_1: fastq::Writer::new(inner),
+            read_2: None,
+            singletons: None,
+        }
+    }
+
+    /// Creates a writer that writes the first and last segments of read pairs to separate
+    /// FASTQs.
+    ///
+    /// Records without a mate in the template, i.e., unpaired reads, orphaned segments, and
+    /// included secondary and supplementary alignments, are written to `singletons`. If
+    /// `singletons` is `None`, they are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::fastq::{Converter, Writer};
+    /// let writer = Writer::split(Converter::default(), Vec::new(), Vec::new(), None);
+    /// ```
+    pub fn split(converter: Converter, read_1: W, read_2: W, singletons: Option<W>) -> Self {
+        Self {
+            converter,
+            read_1: fastq::Writer::new(read_1),
+            read_2: Some(fastq::Writer::new(read_2)),
+            singletons: singletons.map(fastq::Writer::new),
+        }
+    }
+
+    /// Returns a reference to the underlying writer of the interleaved or first segment output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::fastq::{Converter, Writer};
+    /// let writer = Writer::interleaved(Converter::default(), Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.read_1.get_ref()
+    }
+
+    /// Writes the records of a template.
+    ///
+    /// The primary alignments of the first and last segments are written as a pair. All other
+    /// included records are written as singletons.
+    pub fn write_template(&mut self, template: &Template) -> io::Result<()> {
+        let converter = &self.converter;
+
+        let mut singletons: Vec<&Record> = template
+            .others()
+            .filter(|record| converter.is_included(record))
+            .collect();
+
+        if let Some(record) = template.unpaired() {
+            singletons.insert(0, record);
+        }
+
+        match (template.read_1(), template.read_2()) {
+            (Some(read_1), Some(read_2)) => {
+                let read_1 = converter.convert(read_1)?;
+                let read_2 = converter.convert(read_2)?;
+
+                self.read_1.write_record(&read_1)?;
+
+                match self.read_2.as_mut() {
+                    Some(writer) => writer.write_record(&read_2)?,
+                    None => self.read_1.write_record(&read_2)?,
+                }
+            }
+            (Some(record), None) | (None, Some(record)) => singletons.insert(0, record),
+            (None, None) => {}
+        }
+
+        for record in singletons {
+            let fastq_record = self.converter.convert(record)?;
+
+            if let Some(writer) = self.singletons.as_mut() {
+                writer.write_record(&fastq_record)?;
+            } else if self.read_2.is_none() {
+                self.read_1.write_record(&fastq_record)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn sequence_to_bytes(sequence: &Sequence) -> Vec<u8> {
+    sequence
+        .iter()
+        .map(|&base| char::from(base) as u8)
+        .collect()
+}
+
+fn quality_scores_to_bytes(quality_scores: &QualityScores) -> Vec<u8> {
+    quality_scores
+        .iter()
+        .map(|&score| char::from(score) as u8)
+        .collect()
+}
+
+fn complement(base: u8) -> u8 {
+    match base {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' | b'U' => b'A',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        b => b,
+    }
+}
+
+fn reverse_complement(sequence: &mut [u8]) {
+    sequence.reverse();
+
+    for base in sequence.iter_mut() {
+        *base = complement(*base);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_records(data: &[u8]) -> Vec<Record> {
+        let mut reader = crate::Reader::new(data);
+        reader.records().collect::<io::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn test_reverse_complement() {
+        let mut sequence = b"ACGTNRYKMBVDHSW".to_vec();
+        reverse_complement(&mut sequence);
+        assert_eq!(sequence, b"WSDHBVKMRYNACGT");
+    }
+
+    #[test]
+    fn test_convert() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record =
+            "r0\t83\tsq0\t1\t60\t4M\t=\t9\t12\tAACG\tABCD\tOQ:Z:!!##\tRX:Z:AC\tBC:Z:GT".parse()?;
+
+        let converter = Converter::builder()
+            .set_tags(vec![
+                Tag::SampleBarcodeSequence,
+                Tag::UmiSequence,
+                Tag::ReadGroup,
+            ])
+            .set_use_original_quality_scores(true)
+            .set_append_segment_suffix(true)
+            .build();
+
+        assert_eq!(
+            converter.convert(&record)?,
+            fastq::Record::new("r0/1\tBC:Z:GT\tRX:Z:AC", "CGTT", "##!!")
+        );
+
+        let record: Record = "r0\t0\t*\t0\t255\t*\t*\t0\t0\tACGT\t*".parse()?;
+        assert_eq!(
+            Converter::default().convert(&record)?,
+            fastq::Record::new("r0", "ACGT", "\"\"\"\"")
+        );
+
+        let record: Record = "r0\t0\t*\t0\t255\t*\t*\t0\t0\tACGT\tNDL".parse()?;
+        assert!(Converter::default().convert(&record).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_template_split() -> io::Result<()> {
+        let records = parse_records(
+            b"\
+r0\t77\t*\t0\t255\t*\t*\t0\t0\tACGT\tNDLS
+r0\t141\t*\t0\t255\t*\t*\t0\t0\tTTGG\tSLDN
+r0\t2113\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS
+r1\t73\tsq0\t1\t60\t4M\t*\t0\t0\tGGCC\tABCD
+",
+        );
+
+        let converter = Converter::builder().set_include_supplementary(true).build();
+        let mut writer = Writer::split(converter, Vec::new(), Vec::new(), Some(Vec::new()));
+
+        writer.write_template(&Template::new(records[..3].to_vec()))?;
+        writer.write_template(&Template::new(records[3..].to_vec()))?;
+
+        assert_eq!(writer.read_1.get_ref(), b"@r0\nACGT\n+\nNDLS\n");
+
+        let read_2 = writer.read_2.as_ref().map(|w| w.get_ref().as_slice());
+        assert_eq!(read_2, Some(&b"@r0\nTTGG\n+\nSLDN\n"[..]));
+
+        let singletons = writer.singletons.as_ref().map(|w| w.get_ref().as_slice());
+        assert_eq!(
+            singletons,
+            Some(&b"@r0\nACGT\n+\nNDLS\n@r1\nGGCC\n+\nABCD\n"[..])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_template_interleaved() -> io::Result<()> {
+        let records = parse_records(
+            b"\
+r0\t77\t*\t0\t255\t*\t*\t0\t0\tACGT\tNDLS
+r0\t141\t*\t0\t255\t*\t*\t0\t0\tTTGG\tSLDN
+r0\t321\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS
+",
+        );
+
+        let mut writer = Writer::interleaved(Converter::default(), Vec::new());
+        writer.write_template(&Template::new(records))?;
+
+        assert_eq!(
+            writer.get_ref(),
+            b"@r0\nACGT\n+\nNDLS\n@r0\nTTGG\n+\nSLDN\n"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,665 @@
+//! SAM mate fixing.
+//!
+//! Mate fields (`RNEXT`, `PNEXT`, the mate flags, and `TLEN`) describe the other segment of a
+//! template and go stale when either segment is filtered or realigned. The functions in this module
+//! recompute them from the primary alignments of read pairs, similar to `samtools fixmate`.
+//!
+//! Each segment is also given the mate CIGAR (`MC`) and mate mapping quality (`MQ`) data fields,
+//! which are removed when the mate is unmapped.
+//!
+//! Records of other formats can be fixed by implementing [`MateRecord`] and using
+//! [`try_fix_mates`], [`try_fix_template`], or [`try_fix_mate_pair`].
+//!
+//! # Examples
+//!
+//! ```
+//! use noodles_sam as sam;
+//!
+//! let data = b"\
+//! r0\t65\tsq0\t8\t60\t4M\t*\t0\t0\tACGT\t*
+//! r0\t145\tsq0\t1\t30\t4M\t*\t0\t0\tACGT\t*
+//! ";
+//!
+//! let mut reader = sam::Reader::new(&data[..]);
+//! let mut records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+//!
+//! sam::fixmate::fix_mates(&mut records);
+//!
+//! let record = &records[0];
+//! assert_eq!(u16::from(record.flags()), 97);
+//! assert_eq!(record.mate_position().map(i32::from), Some(1));
+//! assert_eq!(record.template_length(), -11);
+//! assert_eq!(record.data().to_string(), "MC:Z:4M\tMQ:i:30");
+//!
+//! let mate = &records[1];
+//! assert_eq!(u16::from(mate.flags()), 145);
+//! assert_eq!(mate.mate_position().map(i32::from), Some(8));
+//! assert_eq!(mate.template_length(), 11);
+//! assert_eq!(mate.data().to_string(), "MC:Z:4M\tMQ:i:60");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::{cmp::Ordering, convert::Infallible};
+
+use crate::{
+    record::{
+        data::{
+            field::{Tag, Value},
+            Field,
+        },
+        Flags, Position,
+    },
+    template::TemplateRecord,
+    Record,
+};
+
+/// A record whose mate fields can be fixed.
+///
+/// The pairing of records is shared by all implementations. Only the reading and writing of the
+/// fields differ.
+pub trait MateRecord: TemplateRecord {
+    /// The error returned when a field fails to be read or written.
+    type Error;
+
+    /// Returns the alignment start.
+    fn position(&self) -> Option<Position>;
+
+    /// Returns whether the record is placed on the same reference sequence as the other record.
+    fn has_same_reference_sequence(&self, other: &Self) -> bool;
+
+    /// Returns the length of the alignment span over the reference sequence.
+    fn reference_len(&self) -> Result<u32, Self::Error>;
+
+    /// Sets the flags.
+    fn set_flags(&mut self, flags: Flags);
+
+    /// Places the record at the reference sequence and position of the other record.
+    ///
+    /// If the other record is `None`, the record is unplaced.
+    fn set_placement(&mut self, other: Option<&Self>);
+
+    /// Sets the mate reference sequence and mate position to the placement of the mate.
+    fn set_mate_placement(&mut self, mate: &Self);
+
+    /// Sets the template length.
+    fn set_template_length(&mut self, template_length: i32);
+
+    /// Sets the mate CIGAR (`MC`) and mate mapping quality (`MQ`) data fields from the mate.
+    ///
+    /// If the mate is `None`, the fields are removed.
+    fn set_mate_data(&mut self, mate: Option<&Self>) -> Result<(), Self::Error>;
+}
+
+impl MateRecord for Record {
+    type Error = Infallible;
+
+    fn position(&self) -> Option<Position> {
+        self.position()
+    }
+
+    fn has_same_reference_sequence(&self, other: &Self) -> bool {
+        self.reference_sequence_name() == other.reference_sequence_name()
+    }
+
+    fn reference_len(&self) -> Result<u32, Self::Error> {
+        Ok(self.cigar().reference_len())
+    }
+
+    fn set_flags(&mut self, flags: Flags) {
+        *self.flags_mut() = flags;
+    }
+
+    fn set_placement(&mut self, other: Option<&Self>) {
+        *self.reference_sequence_name_mut() =
+            other.and_then(|record| record.reference_sequence_name().cloned());
+        *self.position_mut() = other.and_then(|record| record.position());
+    }
+
+    fn set_mate_placement(&mut self, mate: &Self) {
+        *self.mate_reference_sequence_name_mut() = mate.reference_sequence_name().cloned();
+        *self.mate_position_mut() = mate.position();
+    }
+
+    fn set_template_length(&mut self, template_length: i32) {
+        *self.template_length_mut() = template_length;
+    }
+
+    fn set_mate_data(&mut self, mate: Option<&Self>) -> Result<(), Self::Error> {
+        let data = self.data_mut();
+
+        match mate {
+            Some(mate) => {
+                data.insert(Field::new(
+                    Tag::MateCigar,
+                    Value::String(mate.cigar().to_string()),
+                ));
+
+                let mapping_quality = u8::from(mate.mapping_quality());
+
+                data.insert(Field::new(
+                    Tag::MateMappingQuality,
+                    Value::Int32(i32::from(mapping_quality)),
+                ));
+            }
+            None => {
+                data.remove(&Tag::MateCigar);
+                data.remove(&Tag::MateMappingQuality);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Fixes the mate fields of all read pairs in a list of name-grouped records.
+///
+/// Records with the same read name must be adjacent, e.g., as in a queryname-sorted or
+/// name-collated file. Each run of records with the same read name is fixed using
+/// [`fix_template`].
+pub fn fix_mates(records: &mut [Record]) {
+    unwrap_infallible(try_fix_mates(records))
+}
+
+/// Fixes the mate fields of all read pairs in a list of name-grouped records of any
+/// [`MateRecord`] implementation.
+///
+/// See [`fix_mates`].
+///
+/// # Errors
+///
+/// An error is returned if a field of a record fails to be read or written.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{self as sam, fixmate};
+/// let mut records = vec![sam::Record::default()];
+/// assert!(fixmate::try_fix_mates(&mut records).is_ok());
+/// ```
+pub fn try_fix_mates<R>(records: &mut [R]) -> Result<(), R::Error>
+where
+    R: MateRecord,
+{
+    let mut start = 0;
+
+    while start < records.len() {
+        let read_name = records[start].read_name().map(String::from);
+
+        let len = records[start..]
+            .iter()
+            .take_while(|record| record.read_name() == read_name.as_deref())
+            .count();
+
+        let end = start + len;
+
+        if read_name.is_some() {
+            try_fix_template(&mut records[start..end])?;
+        }
+
+        start = end;
+    }
+
+    Ok(())
+}
+
+/// Fixes the mate fields of the primary read pair in a template.
+///
+/// The primary alignments (neither secondary nor supplementary) of the first and last segments
+/// are fixed using [`fix_mate_pair`]. Other records are left unchanged. If the template does not
+/// have exactly one primary alignment for each segment, no records are changed.
+pub fn fix_template(records: &mut [Record]) {
+    unwrap_infallible(try_fix_template(records))
+}
+
+/// Fixes the mate fields of the primary read pair in a template of any [`MateRecord`]
+/// implementation.
+///
+/// See [`fix_template`].
+///
+/// # Errors
+///
+/// An error is returned if a field of a record fails to be read or written.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{self as sam, fixmate};
+/// let mut records = vec![sam::Record::default()];
+/// assert!(fixmate::try_fix_template(&mut records).is_ok());
+/// ```
+pub fn try_fix_template<R>(records: &mut [R]) -> Result<(), R::Error>
+where
+    R: MateRecord,
+{
+    let find_unique = |is_segment: fn(Flags) -> bool| {
+        let mut indices = records
+            .iter()
+            .enumerate()
+            .filter(|(_, record)| is_primary(*record) && is_segment(record.flags()))
+            .map(|(i, _)| i);
+
+        match (indices.next(), indices.next()) {
+            (Some(i), None) => Some(i),
+            _ => None,
+        }
+    };
+
+    let (i, j) = match (find_unique(Flags::is_read_1), find_unique(Flags::is_read_2)) {
+        (Some(i), Some(j)) if i != j => (i, j),
+        _ => return Ok(()),
+    };
+
+    let (lo, hi) = if i < j { (i, j) } else { (j, i) };
+    let (left, right) = records.split_at_mut(hi);
+    try_fix_mate_pair(&mut left[lo], &mut right[0])
+}
+
+/// Fixes the mate fields of a pair of records.
+///
+/// The following fields are updated for both records:
+///
+///   * An unmapped record with a mapped mate is placed at its mate's reference sequence name and
+///     position. If both records are unmapped, both are unplaced.
+///   * The paired flag is set, and the mate unmapped and mate reverse complemented flags are set
+///     from the mate. The proper pair flag is cleared if either record is unmapped.
+///   * The mate reference sequence name and mate position are set to the mate's.
+///   * The template length is recomputed if both records are mapped to the same reference
+///     sequence. It spans from the leftmost start to the rightmost alignment end and is positive
+///     for the leftmost record (or read 1, if both start at the same position). Otherwise, it is
+///     set to 0.
+///   * The mate CIGAR (`MC`) and mate mapping quality (`MQ`) data fields are set if the mate is
+///     mapped and removed otherwise.
+pub fn fix_mate_pair(record: &mut Record, mate: &mut Record) {
+    unwrap_infallible(try_fix_mate_pair(record, mate))
+}
+
+/// Fixes the mate fields of a pair of records of any [`MateRecord`] implementation.
+///
+/// See [`fix_mate_pair`] for the fields that are updated.
+///
+/// # Errors
+///
+/// An error is returned if a field of either record fails to be read or written.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{self as sam, fixmate};
+///
+/// let mut record = sam::Record::default();
+/// let mut mate = sam::Record::default();
+/// assert!(fixmate::try_fix_mate_pair(&mut record, &mut mate).is_ok());
+/// ```
+pub fn try_fix_mate_pair<R>(record: &mut R, mate: &mut R) -> Result<(), R::Error>
+where
+    R: MateRecord,
+{
+    place_unmapped(record, mate);
+
+    let template_length = calculate_template_length(record, mate)?;
+
+    set_mate(record, mate, template_length)?;
+    set_mate(mate, record, -template_length)?;
+
+    Ok(())
+}
+
+fn unwrap_infallible(result: Result<(), Infallible>) {
+    match result {
+        Ok(()) => {}
+        Err(e) => match e {},
+    }
+}
+
+fn is_primary<R>(record: &R) -> bool
+where
+    R: MateRecord,
+{
+    let flags = record.flags();
+    !flags.is_secondary() && !flags.is_supplementary()
+}
+
+fn is_unmapped<R>(record: &R) -> bool
+where
+    R: MateRecord,
+{
+    record.flags().is_unmapped() || record.position().is_none()
+}
+
+fn place_unmapped<R>(record: &mut R, mate: &mut R)
+where
+    R: MateRecord,
+{
+    match (is_unmapped(record), is_unmapped(mate)) {
+        (true, false) => record.set_placement(Some(mate)),
+        (false, true) => mate.set_placement(Some(record)),
+        (true, true) => {
+            record.set_placement(None);
+            mate.set_placement(None);
+        }
+        (false, false) => {}
+    }
+}
+
+// Returns the template length of `record`. Its mate has the negated value.
+fn calculate_template_length<R>(record: &R, mate: &R) -> Result<i32, R::Error>
+where
+    R: MateRecord,
+{
+    if is_unmapped(record) || is_unmapped(mate) {
+        return Ok(0);
+    }
+
+    if !record.has_same_reference_sequence(mate) {
+        return Ok(0);
+    }
+
+    match (record.position(), mate.position()) {
+        (Some(record_start), Some(mate_start)) => Ok(template_length(
+            record_start,
+            record.reference_len()?,
+            record.flags(),
+            mate_start,
+            mate.reference_len()?,
+            mate.flags(),
+        )),
+        _ => Ok(0),
+    }
+}
+
+/// Calculates the template length of a segment mapped to the same reference sequence as its mate.
+///
+/// Each segment is given by its alignment start, the length of its alignment span over the
+/// reference sequence, and its flags. The template spans from the leftmost start to the rightmost
+/// alignment end and is positive for the leftmost segment (or read 1, if both start at the same
+/// position). The mate has the negated value.
+///
+/// This is used by [`fix_mate_pair`] and can be used by other record implementations to compute
+/// the same value.
+///
+/// # Examples
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// use noodles_sam::{fixmate, record::{Flags, Position}};
+///
+/// let template_length = fixmate::template_length(
+///     Position::try_from(8)?,
+///     4,
+///     Flags::READ_1,
+///     Position::try_from(1)?,
+///     4,
+///     Flags::READ_2,
+/// );
+///
+/// assert_eq!(template_length, -11);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn template_length(
+    record_start: Position,
+    record_reference_len: u32,
+    record_flags: Flags,
+    mate_start: Position,
+    mate_reference_len: u32,
+    mate_flags: Flags,
+) -> i32 {
+    let alignment_end = |start: i32, reference_len: u32| start + (reference_len as i32).max(1) - 1;
+
+    let record_start = i32::from(record_start);
+    let mate_start = i32::from(mate_start);
+
+    let start = record_start.min(mate_start);
+    let end = alignment_end(record_start, record_reference_len)
+        .max(alignment_end(mate_start, mate_reference_len));
+    let len = end - start + 1;
+
+    let is_leftmost = match record_start.cmp(&mate_start) {
+        Ordering::Less => true,
+        Ordering::Greater => false,
+        Ordering::Equal => !mate_flags.is_read_1() || record_flags.is_read_1(),
+    };
+
+    if is_leftmost {
+        len
+    } else {
+        -len
+    }
+}
+
+/// Returns the flags of a segment with its mate flags fixed.
+///
+/// The paired flag is set, and the mate unmapped and mate reverse complemented flags are set from
+/// the mate. The proper pair flag is cleared if either segment is unmapped.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{fixmate, record::Flags};
+///
+/// let flags = fixmate::mate_flags(Flags::PROPER_PAIR, false, Flags::REVERSE_COMPLEMENTED, true);
+/// assert_eq!(flags, Flags::PAIRED | Flags::MATE_UNMAPPED | Flags::MATE_REVERSE_COMPLEMENTED);
+/// ```
+pub fn mate_flags(
+    flags: Flags,
+    is_unmapped: bool,
+    mate_flags: Flags,
+    mate_is_unmapped: bool,
+) -> Flags {
+    let mut flags = flags;
+
+    flags.insert(Flags::PAIRED);
+    flags.set(Flags::MATE_UNMAPPED, mate_is_unmapped);
+    flags.set(
+        Flags::MATE_REVERSE_COMPLEMENTED,
+        mate_flags.is_reverse_complemented(),
+    );
+
+    if mate_is_unmapped || is_unmapped {
+        flags.remove(Flags::PROPER_PAIR);
+    }
+
+    flags
+}
+
+fn set_mate<R>(record: &mut R, mate: &R, template_length: i32) -> Result<(), R::Error>
+where
+    R: MateRecord,
+{
+    let mate_is_unmapped = is_unmapped(mate);
+
+    record.set_flags(mate_flags(
+        record.flags(),
+        is_unmapped(record),
+        mate.flags(),
+        mate_is_unmapped,
+    ));
+
+    record.set_mate_placement(mate);
+    record.set_template_length(template_length);
+
+    if mate_is_unmapped {
+        record.set_mate_data(None)
+    } else {
+        record.set_mate_data(Some(mate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    fn build_record(
+        read_name: &str,
+        flags: Flags,
+        position: i32,
+        cigar: &str,
+        mapping_quality: u8,
+    ) -> Result<Record, Box<dyn std::error::Error>> {
+        let mut builder = Record::builder()
+            .set_read_name(read_name.parse()?)
+            .set_flags(flags)
+            .set_mapping_quality(mapping_quality.into());
+
+        if position > 0 {
+            builder = builder
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(position)?)
+                .set_cigar(cigar.parse()?);
+        }
+
+        Ok(builder.build())
+    }
+
+    #[test]
+    fn test_fix_mate_pair() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = build_record("r0", Flags::PAIRED | Flags::READ_1, 5, "4M2D4M", 60)?;
+        let mut mate = build_record(
+            "r0",
+            Flags::PAIRED | Flags::READ_2 | Flags::REVERSE_COMPLEMENTED,
+            20,
+            "2S6M",
+            13,
+        )?;
+
+        fix_mate_pair(&mut record, &mut mate);
+
+        assert_eq!(
+            record.flags(),
+            Flags::PAIRED | Flags::READ_1 | Flags::MATE_REVERSE_COMPLEMENTED
+        );
+        assert_eq!(
+            record
+                .mate_reference_sequence_name()
+                .map(|name| name.as_str()),
+            Some("sq0")
+        );
+        assert_eq!(record.mate_position().map(i32::from), Some(20));
+        assert_eq!(record.template_length(), 21);
+        assert_eq!(record.data().to_string(), "MC:Z:2S6M\tMQ:i:13");
+
+        assert_eq!(
+            mate.flags(),
+            Flags::PAIRED | Flags::READ_2 | Flags::REVERSE_COMPLEMENTED
+        );
+        assert_eq!(mate.mate_position().map(i32::from), Some(5));
+        assert_eq!(mate.template_length(), -21);
+        assert_eq!(mate.data().to_string(), "MC:Z:4M2D4M\tMQ:i:60");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_mate_pair_with_same_start() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = build_record("r0", Flags::PAIRED | Flags::READ_2, 5, "4M", 60)?;
+        let mut mate = build_record("r0", Flags::PAIRED | Flags::READ_1, 5, "8M", 60)?;
+
+        fix_mate_pair(&mut record, &mut mate);
+
+        assert_eq!(record.template_length(), -8);
+        assert_eq!(mate.template_length(), 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_mate_pair_with_unmapped_mate() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = build_record(
+            "r0",
+            Flags::PAIRED | Flags::PROPER_PAIR | Flags::READ_1,
+            5,
+            "4M",
+            60,
+        )?;
+        record.data_mut().insert(Field::new(
+            Tag::MateCigar,
+            Value::String(String::from("4M")),
+        ));
+
+        let mut mate = build_record(
+            "r0",
+            Flags::PAIRED | Flags::READ_2 | Flags::UNMAPPED,
+            0,
+            "",
+            0,
+        )?;
+
+        fix_mate_pair(&mut record, &mut mate);
+
+        assert_eq!(
+            record.flags(),
+            Flags::PAIRED | Flags::READ_1 | Flags::MATE_UNMAPPED
+        );
+        assert_eq!(record.mate_position().map(i32::from), Some(5));
+        assert_eq!(record.template_length(), 0);
+        assert!(record.data().is_empty());
+
+        assert_eq!(
+            mate.reference_sequence_name().map(|name| name.as_str()),
+            Some("sq0")
+        );
+        assert_eq!(mate.position().map(i32::from), Some(5));
+        assert_eq!(mate.mate_position().map(i32::from), Some(5));
+        assert_eq!(mate.template_length(), 0);
+        assert_eq!(mate.data().to_string(), "MC:Z:4M\tMQ:i:60");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_mate_pair_with_both_unmapped() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = build_record("r0", Flags::PAIRED | Flags::READ_1, 5, "4M", 60)?;
+        *record.flags_mut() |= Flags::UNMAPPED;
+        let mut mate = build_record(
+            "r0",
+            Flags::PAIRED | Flags::READ_2 | Flags::UNMAPPED,
+            0,
+            "",
+            0,
+        )?;
+
+        fix_mate_pair(&mut record, &mut mate);
+
+        assert!(record.reference_sequence_name().is_none());
+        assert!(record.position().is_none());
+        assert!(record.mate_position().is_none());
+        assert!(record.flags().is_mate_unmapped());
+        assert!(mate.flags().is_mate_unmapped());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_mates() -> Result<(), Box<dyn std::error::Error>> {
+        let mut records = [
+            build_record("r0", Flags::PAIRED | Flags::READ_2, 30, "4M", 60)?,
+            build_record(
+                "r0",
+                Flags::PAIRED | Flags::READ_2 | Flags::SUPPLEMENTARY,
+                50,
+                "4M",
+                60,
+            )?,
+            build_record("r0", Flags::PAIRED | Flags::READ_1, 10, "4M", 60)?,
+            build_record("r1", Flags::PAIRED | Flags::READ_1, 10, "4M", 60)?,
+            build_record("r2", Flags::PAIRED | Flags::READ_1, 10, "4M", 60)?,
+            build_record("r2", Flags::PAIRED | Flags::READ_2, 1, "4M", 60)?,
+        ];
+
+        fix_mates(&mut records);
+
+        assert_eq!(records[0].template_length(), -24);
+        assert_eq!(records[1].template_length(), 0);
+        assert!(records[1].data().is_empty());
+        assert_eq!(records[2].template_length(), 24);
+
+        assert!(records[3].mate_position().is_none());
+        assert!(records[3].data().is_empty());
+
+        assert_eq!(records[4].template_length(), -13);
+        assert_eq!(records[5].template_length(), 13);
+
+        Ok(())
+    }
+}
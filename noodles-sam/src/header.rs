@@ -18,6 +18,9 @@
 //! type. Reference sequences must be ordered; whereas read groups, programs, and comments can be
 //! unordered. (`sam::Header` defines them to be ordered.)
 //!
+//! Records of a kind not defined by the specification are kept as generic records in the order
+//! they appear, and unknown tags in known records are kept in the order they appear.
+//!
 //! # Examples
 //!
 //! ## Parse a SAM header
@@ -62,7 +65,9 @@
 mod builder;
 #[allow(clippy::module_inception)]
 pub mod header;
+pub mod parser;
 pub mod program;
+pub mod programs;
 pub mod read_group;
 pub mod record;
 pub mod reference_sequence;
@@ -72,7 +77,7 @@ use std::{convert::TryFrom, error, fmt, str::FromStr};
 use indexmap::IndexMap;
 
 pub use self::{
-    builder::Builder, program::Program, read_group::ReadGroup,
+    builder::Builder, parser::ParseOptions, program::Program, read_group::ReadGroup,
     reference_sequence::ReferenceSequence,
 };
 
@@ -91,13 +96,32 @@ pub type Programs = IndexMap<String, Program>;
 ///
 /// Records are grouped by their types: header, reference seqeuence, read group, program, and
 /// comment.
-#[derive(Debug, Default)]
+///
+/// The order in which records of different types are added, e.g., when parsed, is kept and used
+/// when the header is formatted. Within a type, records are always written in collection order,
+/// i.e., reference sequences are written in the order of [`Self::reference_sequences`], which
+/// defines the reference sequence IDs. Records added through the mutable accessors (e.g.,
+/// [`Self::reference_sequences_mut`]) are written after, grouped by their types.
+#[derive(Clone, Debug, Default)]
 pub struct Header {
     header: Option<header::Header>,
     reference_sequences: ReferenceSequences,
     read_groups: ReadGroups,
     programs: Programs,
     comments: Vec<String>,
+    other_records: Vec<Record>,
+    record_refs: Vec<RecordRef>,
+}
+
+// The type of a record slot in a SAM header, used to keep the order in which records of different
+// types are added. The nth slot of a type is filled by the nth record of its collection.
+#[derive(Clone, Copy, Debug)]
+enum RecordRef {
+    ReferenceSequence,
+    ReadGroup,
+    Program,
+    Comment,
+    Other,
 }
 
 impl Header {
@@ -296,6 +320,45 @@ impl Header {
         &mut self.programs
     }
 
+    /// Adds a program to the end of each program chain.
+    ///
+    /// This is typically used by a tool to record itself in the header of its output. The program
+    /// is given a unique ID based on its ID (e.g., `noodles`, `noodles.1`, `noodles.2`, etc.), and
+    /// its previous program ID (`PP`) is set to the last program of the chain. If there are
+    /// multiple chains, a program is added to each. The IDs of the added programs are returned.
+    ///
+    /// See [`programs::add`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::Program};
+    ///
+    /// let mut header: sam::Header = "@PG\tID:noodles\tPN:noodles\n".parse()?;
+    ///
+    /// let program = Program::builder()
+    ///     .set_id("noodles")
+    ///     .set_name("noodles")
+    ///     .set_version("0.1.0")
+    ///     .set_command_line("noodles view sample.bam")
+    ///     .build();
+    ///
+    /// assert_eq!(header.add_program(program), ["noodles.1"]);
+    ///
+    /// let program = &header.programs()["noodles.1"];
+    /// assert_eq!(program.previous_id(), Some("noodles"));
+    /// assert_eq!(program.command_line(), Some("noodles view sample.bam"));
+    /// # Ok::<(), sam::header::ParseError>(())
+    /// ```
+    pub fn add_program(&mut self, program: Program) -> Vec<String> {
+        let ids = programs::add(&mut self.programs, program);
+
+        self.record_refs
+            .extend(ids.iter().map(|_| RecordRef::Program));
+
+        ids
+    }
+
     /// Returns the SAM header comments.
     ///
     /// # Examples
@@ -349,9 +412,73 @@ impl Header {
     where
         S: Into<String>,
     {
+        self.record_refs.push(RecordRef::Comment);
         self.comments.push(comment.into());
     }
 
+    /// Returns the records with a kind not defined by the SAM specification.
+    ///
+    /// These are kept in the order they were added. When parsing with a lenient
+    /// [`ParseOptions`], this also holds the invalid and duplicate records of a known kind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::record::Kind};
+    ///
+    /// let header: sam::Header = "@SQ\tSN:sq0\tLN:8\n@XY\tzn:noodles\n".parse()?;
+    ///
+    /// let other_records = header.other_records();
+    /// assert_eq!(other_records.len(), 1);
+    /// assert_eq!(other_records[0].kind(), Kind::Other(*b"XY"));
+    /// # Ok::<(), sam::header::ParseError>(())
+    /// ```
+    pub fn other_records(&self) -> &[Record] {
+        &self.other_records
+    }
+
+    /// Returns a mutable reference to the records with a kind not defined by the SAM
+    /// specification.
+    ///
+    /// To simply append a record, consider using [`Self::add_other_record`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::{record::{Kind, Value}, Record}};
+    ///
+    /// let mut header = sam::Header::default();
+    /// header.other_records_mut().push(Record::new(
+    ///     Kind::Other(*b"XY"),
+    ///     Value::String(String::from("noodles")),
+    /// ));
+    ///
+    /// assert_eq!(header.other_records().len(), 1);
+    /// ```
+    pub fn other_records_mut(&mut self) -> &mut Vec<Record> {
+        &mut self.other_records
+    }
+
+    /// Adds a record with a kind not defined by the SAM specification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::{record::{Kind, Value}, Record}};
+    ///
+    /// let mut header = sam::Header::default();
+    /// header.add_other_record(Record::new(
+    ///     Kind::Other(*b"XY"),
+    ///     Value::Map(vec![(String::from("zn"), String::from("noodles"))]),
+    /// ));
+    ///
+    /// assert_eq!(header.to_string(), "@XY\tzn:noodles\n");
+    /// ```
+    pub fn add_other_record(&mut self, record: Record) {
+        self.record_refs.push(RecordRef::Other);
+        self.other_records.push(record);
+    }
+
     /// Returns whether there are no records in this SAM header.
     ///
     /// # Examples
@@ -371,6 +498,7 @@ impl Header {
             && self.read_groups.is_empty()
             && self.programs.is_empty()
             && self.comments.is_empty()
+            && self.other_records.is_empty()
     }
 }
 
@@ -401,26 +529,62 @@ impl fmt::Display for Header {
             writeln!(f, "{}", header)?;
         }
 
-        for reference_sequence in self.reference_sequences.values() {
+        let mut reference_sequences = self.reference_sequences.values();
+        let mut read_groups = self.read_groups.values();
+        let mut programs = self.programs.values();
+        let mut comments = self.comments.iter();
+        let mut other_records = self.other_records.iter();
+
+        for record_ref in &self.record_refs {
+            match record_ref {
+                RecordRef::ReferenceSequence => write_next(f, &mut reference_sequences)?,
+                RecordRef::ReadGroup => write_next(f, &mut read_groups)?,
+                RecordRef::Program => write_next(f, &mut programs)?,
+                RecordRef::Comment => {
+                    if let Some(comment) = comments.next() {
+                        writeln!(f, "{}\t{}", record::Kind::Comment, comment)?;
+                    }
+                }
+                RecordRef::Other => write_next(f, &mut other_records)?,
+            }
+        }
+
+        for reference_sequence in reference_sequences {
             writeln!(f, "{}", reference_sequence)?;
         }
 
-        for read_group in self.read_groups.values() {
+        for read_group in read_groups {
             writeln!(f, "{}", read_group)?;
         }
 
-        for program in self.programs.values() {
+        for program in programs {
             writeln!(f, "{}", program)?;
         }
 
-        for comment in &self.comments {
+        for comment in comments {
             writeln!(f, "{}\t{}", record::Kind::Comment, comment)?;
         }
 
+        for record in other_records {
+            writeln!(f, "{}", record)?;
+        }
+
         Ok(())
     }
 }
 
+fn write_next<I, T>(f: &mut fmt::Formatter<'_>, records: &mut I) -> fmt::Result
+where
+    I: Iterator<Item = T>,
+    T: fmt::Display,
+{
+    if let Some(record) = records.next() {
+        writeln!(f, "{}", record)?;
+    }
+
+    Ok(())
+}
+
 /// An error returned when a raw SAM header fails to parse.
 #[derive(Debug)]
 pub enum ParseError {
@@ -438,6 +602,14 @@ pub enum ParseError {
     InvalidProgram(program::TryFromRecordError),
     /// A comment record is invalid.
     InvalidComment,
+    /// A reference sequence name is duplicated.
+    DuplicateReferenceSequenceName(String),
+    /// A read group ID is duplicated.
+    DuplicateReadGroupId(String),
+    /// A program ID is duplicated.
+    DuplicateProgramId(String),
+    /// A record kind is not defined by the SAM specification.
+    UnknownKind(record::Kind),
 }
 
 impl error::Error for ParseError {}
@@ -452,6 +624,12 @@ impl fmt::Display for ParseError {
             Self::InvalidReadGroup(e) => write!(f, "invalid read group: {}", e),
             Self::InvalidProgram(e) => write!(f, "invalid program: {}", e),
             Self::InvalidComment => f.write_str("invalid comment record"),
+            Self::DuplicateReferenceSequenceName(name) => {
+                write!(f, "duplicate reference sequence name: {}", name)
+            }
+            Self::DuplicateReadGroupId(id) => write!(f, "duplicate read group ID: {}", id),
+            Self::DuplicateProgramId(id) => write!(f, "duplicate program ID: {}", id),
+            Self::UnknownKind(kind) => write!(f, "unknown record kind: {}", kind),
         }
     }
 }
@@ -515,6 +693,7 @@ impl FromStr for Header {
                     record::Value::String(comment) => builder.add_comment(comment),
                     _ => return Err(ParseError::InvalidComment),
                 },
+                record::Kind::Other(_) => builder.add_other_record(record),
             };
         }
 
@@ -581,6 +760,94 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_from_str_with_other_records_and_tags() -> Result<(), ParseError> {
+        let s = "\
+@HD\tVN:1.6\tzb:2\tza:1
+@SQ\tSN:sq0\tLN:8\tzb:2\tza:1
+@RG\tID:rg0\tzb:2\tza:1
+@PG\tID:pg0\tzb:2\tza:1
+@CO\tnoodles\tsam
+@XY\tzb:2\tza:1
+@co\tnoodles
+";
+
+        let header: Header = s.parse()?;
+
+        assert_eq!(header.comments(), ["noodles\tsam"]);
+        assert_eq!(header.other_records().len(), 2);
+        assert_eq!(header.to_string(), s);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fmt_with_interleaved_records() -> Result<(), ParseError> {
+        let s = "\
+@HD\tVN:1.6
+@CO\tnoodles
+@SQ\tSN:sq0\tLN:8
+@PG\tID:pg0
+@XY\tzn:noodles
+@SQ\tSN:sq1\tLN:13
+@CO\tsam
+@RG\tID:rg0
+@PG\tID:pg1\tPP:pg0
+@co\tnoodles
+";
+
+        let header: Header = s.parse()?;
+        assert_eq!(header.to_string(), s);
+
+        let (header, _) = ParseOptions::new(crate::validation::Strictness::Lenient).parse(s)?;
+        assert_eq!(header.to_string(), s);
+
+        let mut header: Header = "@CO\tnoodles\n@SQ\tSN:sq0\tLN:8\n".parse()?;
+
+        header.reference_sequences_mut().insert(
+            String::from("sq1"),
+            ReferenceSequence::new(String::from("sq1"), 13),
+        );
+        header.add_comment("sam");
+
+        let expected = "\
+@CO\tnoodles
+@SQ\tSN:sq0\tLN:8
+@CO\tsam
+@SQ\tSN:sq1\tLN:13
+";
+
+        assert_eq!(header.to_string(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fmt_after_modifying_records() -> Result<(), ParseError> {
+        let mut header: Header = "\
+@SQ\tSN:sq0\tLN:8
+@CO\tnoodles
+@SQ\tSN:sq1\tLN:13
+@CO\tsam
+@SQ\tSN:sq2\tLN:21
+"
+        .parse()?;
+
+        header.reference_sequences_mut().swap_indices(0, 2);
+        header.reference_sequences_mut().shift_remove("sq1");
+        header.comments_mut().remove(0);
+
+        let expected = "\
+@SQ\tSN:sq2\tLN:21
+@CO\tsam
+@SQ\tSN:sq0\tLN:8
+";
+
+        assert_eq!(header.to_string(), expected);
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_str_with_empty_input() -> Result<(), ParseError> {
         let header: Header = "".parse()?;
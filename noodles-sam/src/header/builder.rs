@@ -1,5 +1,6 @@
 use super::{
-    header, Header, Program, Programs, ReadGroup, ReadGroups, ReferenceSequence, ReferenceSequences,
+    header, Header, Program, Programs, ReadGroup, ReadGroups, Record, RecordRef, ReferenceSequence,
+    ReferenceSequences,
 };
 
 /// A SAM header builder.
@@ -10,6 +11,8 @@ pub struct Builder {
     read_groups: ReadGroups,
     programs: Programs,
     comments: Vec<String>,
+    other_records: Vec<Record>,
+    record_refs: Vec<RecordRef>,
 }
 
 impl Builder {
@@ -62,6 +65,7 @@ impl Builder {
     /// ```
     pub fn add_reference_sequence(mut self, reference_sequence: ReferenceSequence) -> Self {
         let name = reference_sequence.name().into();
+        self.record_refs.push(RecordRef::ReferenceSequence);
         self.reference_sequences.insert(name, reference_sequence);
         self
     }
@@ -82,6 +86,7 @@ impl Builder {
     /// assert!(read_groups.contains_key("rg0"));
     /// ```
     pub fn add_read_group(mut self, read_group: ReadGroup) -> Self {
+        self.record_refs.push(RecordRef::ReadGroup);
         self.read_groups.insert(read_group.id().into(), read_group);
         self
     }
@@ -102,6 +107,7 @@ impl Builder {
     /// assert!(programs.contains_key("noodles-sam"));
     /// ```
     pub fn add_program(mut self, program: Program) -> Self {
+        self.record_refs.push(RecordRef::Program);
         self.programs.insert(program.id().into(), program);
         self
     }
@@ -121,10 +127,33 @@ impl Builder {
     where
         S: Into<String>,
     {
+        self.record_refs.push(RecordRef::Comment);
         self.comments.push(comment.into());
         self
     }
 
+    /// Adds a record with a kind not defined by the SAM specification to the SAM header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::{record::{Kind, Value}, Record}};
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_other_record(Record::new(
+    ///         Kind::Other(*b"XY"),
+    ///         Value::Map(vec![(String::from("zn"), String::from("noodles"))]),
+    ///     ))
+    ///     .build();
+    ///
+    /// assert_eq!(header.other_records().len(), 1);
+    /// ```
+    pub fn add_other_record(mut self, record: Record) -> Self {
+        self.record_refs.push(RecordRef::Other);
+        self.other_records.push(record);
+        self
+    }
+
     /// Builds a SAM header.
     ///
     /// # Example
@@ -141,6 +170,8 @@ impl Builder {
             read_groups: self.read_groups,
             programs: self.programs,
             comments: self.comments,
+            other_records: self.other_records,
+            record_refs: self.record_refs,
         }
     }
 }
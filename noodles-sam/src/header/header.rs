@@ -9,7 +9,9 @@ mod sort_order;
 mod subsort_order;
 mod tag;
 
-use std::{collections::HashMap, convert::TryFrom, error, fmt};
+use std::{convert::TryFrom, error, fmt};
+
+use indexmap::IndexMap;
 
 pub use self::{
     builder::Builder, group_order::GroupOrder, sort_order::SortOrder, subsort_order::SubsortOrder,
@@ -29,7 +31,7 @@ pub struct Header {
     sort_order: Option<SortOrder>,
     group_order: Option<GroupOrder>,
     subsort_order: Option<SubsortOrder>,
-    fields: HashMap<Tag, String>,
+    fields: IndexMap<Tag, String>,
 }
 
 impl Header {
@@ -136,6 +138,9 @@ impl Header {
     /// this will not include the version field, as it is parsed and available as
     /// [`Self::version`].
     ///
+    /// The fields are kept in the order they were added, e.g., as read, which is the order they
+    /// are written in.
+    ///
     /// # Examples
     ///
     /// ```
@@ -156,7 +161,7 @@ impl Header {
     /// assert_eq!(fields.get(&Tag::Version), None);
     /// assert_eq!(header.version(), "1.6");
     /// ```
-    pub fn fields(&self) -> &HashMap<Tag, String> {
+    pub fn fields(&self) -> &IndexMap<Tag, String> {
         &self.fields
     }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 use super::{GroupOrder, Header, SortOrder, SubsortOrder, Tag, VERSION};
 
@@ -9,7 +9,7 @@ pub struct Builder {
     sort_order: Option<SortOrder>,
     group_order: Option<GroupOrder>,
     subsort_order: Option<SubsortOrder>,
-    fields: HashMap<Tag, String>,
+    fields: IndexMap<Tag, String>,
 }
 
 impl Builder {
@@ -0,0 +1,401 @@
+//! SAM header parse options.
+//!
+//! [`Header::from_str`](std::str::FromStr::from_str) fails on the first invalid line.
+//! [`ParseOptions`] allows headers written by older or nonconforming tools to be read by
+//! downgrading these errors to warnings.
+
+use std::{convert::TryFrom, fmt};
+
+use crate::validation::Strictness;
+
+use super::{
+    header, record, Header, ParseError, Program, ReadGroup, Record, RecordRef, ReferenceSequence,
+};
+
+/// SAM header parse options.
+///
+/// The strictness determines how invalid lines are handled:
+///
+///   * [`Strictness::Strict`]: the first invalid line is returned as an error. Unlike
+///     [`Header::from_str`](std::str::FromStr::from_str), duplicate reference sequence names,
+///     read group IDs, and program IDs are also errors.
+///   * [`Strictness::Lenient`]: invalid lines are reported as warnings and parsing continues.
+///   * [`Strictness::Silent`]: invalid lines are skipped without any warnings.
+///
+/// When not strict, the first valid occurrence of a reference sequence name, read group ID, or
+/// program ID is used, and a misplaced header (`@HD`) record is used if there is no other. Invalid
+/// records of a known kind, later duplicates, and records of a kind not defined by the SAM
+/// specification are kept in [`Header::other_records`], so writing the header keeps their lines.
+/// Unknown tags in known records are always kept in the records' fields.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ParseOptions {
+    strictness: Strictness,
+}
+
+impl ParseOptions {
+    /// Creates SAM header parse options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{header::ParseOptions, validation::Strictness};
+    /// let options = ParseOptions::new(Strictness::Strict);
+    /// ```
+    pub fn new(strictness: Strictness) -> Self {
+        Self { strictness }
+    }
+
+    /// Returns the strictness used when parsing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{header::ParseOptions, validation::Strictness};
+    /// let options = ParseOptions::new(Strictness::Strict);
+    /// assert_eq!(options.strictness(), Strictness::Strict);
+    /// ```
+    pub fn strictness(&self) -> Strictness {
+        self.strictness
+    }
+
+    /// Parses a raw SAM header.
+    ///
+    /// This returns the header and a list of warnings. The list is always empty when the
+    /// strictness is [`Strictness::Strict`] or [`Strictness::Silent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     header::{reference_sequence::Tag, ParseError, ParseOptions},
+    ///     validation::Strictness,
+    /// };
+    ///
+    /// let s = "\
+    /// @HD\tVN:1.6
+    /// @SQ\tSN:sq0\tLN:8\tzn:noodles
+    /// @SQ\tSN:sq0\tLN:13
+    /// @SQ\tSN:sq1
+    /// @XY\tzn:noodles
+    /// ";
+    ///
+    /// let (header, warnings) = ParseOptions::new(Strictness::Lenient).parse(s)?;
+    ///
+    /// let reference_sequences = header.reference_sequences();
+    /// assert_eq!(reference_sequences.len(), 1);
+    /// assert_eq!(reference_sequences["sq0"].len(), 8);
+    /// assert_eq!(
+    ///     reference_sequences["sq0"].fields().get(&Tag::Other(String::from("zn"))),
+    ///     Some(&String::from("noodles"))
+    /// );
+    ///
+    /// assert_eq!(header.other_records().len(), 3);
+    /// assert_eq!(header.to_string(), s);
+    ///
+    /// let line_numbers: Vec<_> = warnings.iter().map(|w| w.line_number()).collect();
+    /// assert_eq!(line_numbers, [3, 4, 5]);
+    ///
+    /// assert!(matches!(
+    ///     ParseOptions::new(Strictness::Strict).parse(s),
+    ///     Err(ParseError::DuplicateReferenceSequenceName(_))
+    /// ));
+    /// # Ok::<(), ParseError>(())
+    /// ```
+    pub fn parse(&self, s: &str) -> Result<(Header, Vec<Warning>), ParseError> {
+        let mut header = Header::default();
+        let mut warnings = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line_number = i + 1;
+
+            let record: Record = match line.parse() {
+                Ok(record) => record,
+                Err(e) => {
+                    self.warn(&mut warnings, line_number, ParseError::InvalidRecord(e))?;
+                    continue;
+                }
+            };
+
+            let result = match record.kind() {
+                record::Kind::Header => {
+                    if i > 0 && header.header.is_some() {
+                        Err(ParseError::UnexpectedHeader)
+                    } else {
+                        if i > 0 {
+                            self.warn(&mut warnings, line_number, ParseError::UnexpectedHeader)?;
+                        }
+
+                        header::Header::try_from(record.clone())
+                            .map(|hd| header.header = Some(hd))
+                            .map_err(ParseError::InvalidHeader)
+                    }
+                }
+                record::Kind::ReferenceSequence => ReferenceSequence::try_from(record.clone())
+                    .map_err(ParseError::InvalidReferenceSequence)
+                    .and_then(|reference_sequence| {
+                        let name = reference_sequence.name();
+
+                        if header.reference_sequences.contains_key(name) {
+                            Err(ParseError::DuplicateReferenceSequenceName(name.into()))
+                        } else {
+                            header.record_refs.push(RecordRef::ReferenceSequence);
+                            header
+                                .reference_sequences
+                                .insert(name.into(), reference_sequence);
+                            Ok(())
+                        }
+                    }),
+                record::Kind::ReadGroup => ReadGroup::try_from(record.clone())
+                    .map_err(ParseError::InvalidReadGroup)
+                    .and_then(|read_group| {
+                        let id = read_group.id();
+
+                        if header.read_groups.contains_key(id) {
+                            Err(ParseError::DuplicateReadGroupId(id.into()))
+                        } else {
+                            header.record_refs.push(RecordRef::ReadGroup);
+                            header.read_groups.insert(id.into(), read_group);
+                            Ok(())
+                        }
+                    }),
+                record::Kind::Program => Program::try_from(record.clone())
+                    .map_err(ParseError::InvalidProgram)
+                    .and_then(|program| {
+                        let id = program.id();
+
+                        if header.programs.contains_key(id) {
+                            Err(ParseError::DuplicateProgramId(id.into()))
+                        } else {
+                            header.record_refs.push(RecordRef::Program);
+                            header.programs.insert(id.into(), program);
+                            Ok(())
+                        }
+                    }),
+                record::Kind::Comment => match record.value() {
+                    record::Value::String(comment) => {
+                        header.record_refs.push(RecordRef::Comment);
+                        header.comments.push(comment.clone());
+                        Ok(())
+                    }
+                    _ => Err(ParseError::InvalidComment),
+                },
+                kind @ record::Kind::Other(_) => Err(ParseError::UnknownKind(kind)),
+            };
+
+            // Records that are not otherwise kept are added as other records so that no line that
+            // parses as a record is lost.
+            if let Err(e) = result {
+                self.warn(&mut warnings, line_number, e)?;
+                header.add_other_record(record);
+            }
+        }
+
+        Ok((header, warnings))
+    }
+
+    fn warn(
+        &self,
+        warnings: &mut Vec<Warning>,
+        line_number: usize,
+        error: ParseError,
+    ) -> Result<(), ParseError> {
+        match self.strictness {
+            Strictness::Strict => Err(error),
+            Strictness::Lenient => {
+                warnings.push(Warning::new(line_number, error));
+                Ok(())
+            }
+            Strictness::Silent => Ok(()),
+        }
+    }
+}
+
+/// A warning raised when an invalid line is skipped while parsing a SAM header.
+#[derive(Debug)]
+pub struct Warning {
+    line_number: usize,
+    error: ParseError,
+}
+
+impl Warning {
+    /// Creates a SAM header parse warning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::{parser::Warning, ParseError};
+    /// let warning = Warning::new(2, ParseError::UnexpectedHeader);
+    /// ```
+    pub fn new(line_number: usize, error: ParseError) -> Self {
+        Self { line_number, error }
+    }
+
+    /// Returns the 1-based line number of the invalid line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::{parser::Warning, ParseError};
+    /// let warning = Warning::new(2, ParseError::UnexpectedHeader);
+    /// assert_eq!(warning.line_number(), 2);
+    /// ```
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Returns the error that was downgraded to this warning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::{parser::Warning, ParseError};
+    /// let warning = Warning::new(2, ParseError::UnexpectedHeader);
+    /// assert!(matches!(warning.error(), ParseError::UnexpectedHeader));
+    /// ```
+    pub fn error(&self) -> &ParseError {
+        &self.error
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line_number, self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::header::reference_sequence::{Tag, TryFromRecordError};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_with_lenient_strictness() -> Result<(), ParseError> {
+        let s = "\
+@SQ\tSN:sq0\tLN:8
+@HD\tVN:1.6
+@SQ\tSN:sq1\tln:13
+@RG\tID:rg0\tzn:noodles
+@RG\tID:rg0
+@PG\tID:pg0
+@PG\tID:pg0
+@CO
+@co\tnoodles
+bad
+@CO\tnoodles
+";
+
+        let (header, warnings) = ParseOptions::new(Strictness::Lenient).parse(s)?;
+
+        assert!(header.header().is_some());
+        assert_eq!(header.reference_sequences().len(), 1);
+        assert_eq!(header.read_groups().len(), 1);
+        assert_eq!(header.read_groups()["rg0"].fields().len(), 1);
+        assert_eq!(header.programs().len(), 1);
+        assert_eq!(header.comments(), ["noodles"]);
+        assert_eq!(header.other_records().len(), 4);
+
+        assert_eq!(
+            header.to_string(),
+            "\
+@HD\tVN:1.6
+@SQ\tSN:sq0\tLN:8
+@SQ\tSN:sq1\tln:13
+@RG\tID:rg0\tzn:noodles
+@RG\tID:rg0
+@PG\tID:pg0
+@PG\tID:pg0
+@co\tnoodles
+@CO\tnoodles
+"
+        );
+
+        let line_numbers: Vec<_> = warnings.iter().map(|w| w.line_number()).collect();
+        assert_eq!(line_numbers, [2, 3, 5, 7, 8, 9, 10]);
+
+        assert!(matches!(warnings[0].error(), ParseError::UnexpectedHeader));
+        assert!(matches!(
+            warnings[1].error(),
+            ParseError::InvalidReferenceSequence(TryFromRecordError::MissingRequiredTag(
+                Tag::Length
+            ))
+        ));
+        assert!(matches!(
+            warnings[2].error(),
+            ParseError::DuplicateReadGroupId(id) if id == "rg0"
+        ));
+        assert!(matches!(
+            warnings[3].error(),
+            ParseError::DuplicateProgramId(id) if id == "pg0"
+        ));
+        assert!(matches!(warnings[4].error(), ParseError::InvalidRecord(_)));
+        assert!(matches!(warnings[5].error(), ParseError::UnknownKind(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_with_lenient_strictness_and_multiple_hd() -> Result<(), ParseError> {
+        let s = "\
+@HD\tVN:1.6
+@HD\tVN:1.5
+";
+
+        let (header, warnings) = ParseOptions::new(Strictness::Lenient).parse(s)?;
+
+        assert_eq!(header.header().map(|hd| hd.version()), Some("1.6"));
+        assert_eq!(warnings.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_with_silent_strictness() -> Result<(), ParseError> {
+        let s = "\
+@SQ\tSN:sq0\tLN:8
+@SQ\tSN:sq0\tLN:13
+@XY\tzn:noodles
+";
+
+        let (header, warnings) = ParseOptions::new(Strictness::Silent).parse(s)?;
+
+        assert_eq!(header.reference_sequences().len(), 1);
+        assert_eq!(header.other_records().len(), 2);
+        assert!(warnings.is_empty());
+
+        assert_eq!(header.to_string(), s);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_with_strict_strictness() {
+        let options = ParseOptions::new(Strictness::Strict);
+
+        assert!(matches!(
+            options.parse("@SQ\tSN:sq0\tLN:8\n@SQ\tSN:sq0\tLN:13\n"),
+            Err(ParseError::DuplicateReferenceSequenceName(name)) if name == "sq0"
+        ));
+
+        assert!(matches!(
+            options.parse("@SQ\tSN:sq0\n"),
+            Err(ParseError::InvalidReferenceSequence(
+                TryFromRecordError::MissingRequiredTag(Tag::Length)
+            ))
+        ));
+
+        assert!(matches!(
+            options.parse("@XY\tzn:noodles\n"),
+            Err(ParseError::UnknownKind(_))
+        ));
+
+        assert!(matches!(
+            options.parse("@SQ\tSN:sq0\tLN:8\n@HD\tVN:1.6\n"),
+            Err(ParseError::UnexpectedHeader)
+        ));
+
+        assert!(options
+            .parse("@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:8\tzn:noodles\n")
+            .is_ok());
+    }
+}
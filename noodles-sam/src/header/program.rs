@@ -3,7 +3,9 @@
 mod builder;
 mod tag;
 
-use std::{collections::HashMap, convert::TryFrom, error, fmt};
+use std::{convert::TryFrom, error, fmt};
+
+use indexmap::IndexMap;
 
 pub use self::{builder::Builder, tag::Tag};
 
@@ -21,7 +23,7 @@ pub struct Program {
     previous_id: Option<String>,
     description: Option<String>,
     version: Option<String>,
-    fields: HashMap<Tag, String>,
+    fields: IndexMap<Tag, String>,
 }
 
 impl Program {
@@ -54,7 +56,7 @@ impl Program {
             previous_id: None,
             description: None,
             version: None,
-            fields: HashMap::new(),
+            fields: IndexMap::new(),
         }
     }
 
@@ -127,6 +129,23 @@ impl Program {
         self.previous_id.as_deref()
     }
 
+    /// Returns a mutable reference to the previous program ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::Program;
+    ///
+    /// let mut program = Program::new(String::from("pg1"));
+    /// assert!(program.previous_id().is_none());
+    ///
+    /// *program.previous_id_mut() = Some(String::from("pg0"));
+    /// assert_eq!(program.previous_id(), Some("pg0"));
+    /// ```
+    pub fn previous_id_mut(&mut self) -> &mut Option<String> {
+        &mut self.previous_id
+    }
+
     /// Returns the description.
     ///
     /// # Examples
@@ -158,6 +177,9 @@ impl Program {
     /// This includes any field that is not specially handled by the structure itself. For example,
     /// this will not include the ID field, as it is parsed and available as [`Self::id`].
     ///
+    /// The fields are kept in the order they were added, e.g., as read, which is the order they
+    /// are written in.
+    ///
     /// # Examples
     ///
     /// ```
@@ -178,7 +200,7 @@ impl Program {
     /// assert_eq!(fields.get(&Tag::Id), None);
     /// assert_eq!(program.id(), "pg0");
     /// ```
-    pub fn fields(&self) -> &HashMap<Tag, String> {
+    pub fn fields(&self) -> &IndexMap<Tag, String> {
         &self.fields
     }
 }
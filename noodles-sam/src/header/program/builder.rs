@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 use super::{Program, Tag};
 
@@ -11,7 +11,7 @@ pub struct Builder {
     previous_id: Option<String>,
     description: Option<String>,
     version: Option<String>,
-    fields: HashMap<Tag, String>,
+    fields: IndexMap<Tag, String>,
 }
 
 impl Builder {
@@ -0,0 +1,459 @@
+//! SAM header program chains.
+//!
+//! Programs (`@PG`) form chains through their previous program IDs (`PP`). Each program refers to
+//! the program that processed the data before it. The last program in a chain, i.e., a program
+//! that is not referred to by any other program, is a leaf.
+
+use std::{
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+use super::{Program, Programs};
+
+/// Returns the programs that are the last in their chains.
+///
+/// A leaf is a program that is not the previous program of any other program. Leaves are
+/// returned in header order.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::header::{programs, Program, Programs};
+///
+/// let programs: Programs = vec![
+///     Program::builder().set_id("pg0").build(),
+///     Program::builder().set_id("pg1").set_previous_id("pg0").build(),
+///     Program::builder().set_id("pg2").build(),
+/// ]
+/// .into_iter()
+/// .map(|program| (program.id().into(), program))
+/// .collect();
+///
+/// let ids: Vec<_> = programs::leaves(&programs).iter().map(|p| p.id()).collect();
+/// assert_eq!(ids, ["pg1", "pg2"]);
+/// ```
+pub fn leaves(programs: &Programs) -> Vec<&Program> {
+    let previous_ids: HashSet<_> = programs.values().filter_map(|p| p.previous_id()).collect();
+
+    programs
+        .values()
+        .filter(|program| !previous_ids.contains(program.id()))
+        .collect()
+}
+
+/// Returns an iterator over the chain of programs starting at the given program ID.
+///
+/// The iterator follows previous program IDs (`PP`) toward the first program in the chain. It
+/// stops when a previous program does not exist or when a program would be visited twice, i.e.,
+/// the chain has a cycle.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::header::{programs, Program, Programs};
+///
+/// let programs: Programs = vec![
+///     Program::builder().set_id("pg0").build(),
+///     Program::builder().set_id("pg1").set_previous_id("pg0").build(),
+///     Program::builder().set_id("pg2").set_previous_id("pg1").build(),
+/// ]
+/// .into_iter()
+/// .map(|program| (program.id().into(), program))
+/// .collect();
+///
+/// let ids: Vec<_> = programs::chain(&programs, "pg2").map(|p| p.id()).collect();
+/// assert_eq!(ids, ["pg2", "pg1", "pg0"]);
+/// ```
+pub fn chain<'a>(programs: &'a Programs, id: &str) -> Chain<'a> {
+    Chain {
+        programs,
+        next: programs.get(id),
+        visited: HashSet::new(),
+    }
+}
+
+/// An iterator over a chain of programs.
+///
+/// This is created by calling [`chain`].
+pub struct Chain<'a> {
+    programs: &'a Programs,
+    next: Option<&'a Program>,
+    visited: HashSet<&'a str>,
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a Program;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let program = self.next.take()?;
+
+        if !self.visited.insert(program.id()) {
+            return None;
+        }
+
+        self.next = program
+            .previous_id()
+            .and_then(|previous_id| self.programs.get(previous_id));
+
+        Some(program)
+    }
+}
+
+/// An error returned when a program chain is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    /// A program refers to a previous program that does not exist.
+    ///
+    /// The values are the program ID and previous program ID, respectively.
+    MissingPreviousProgram(String, String),
+    /// Programs refer to each other in a cycle.
+    ///
+    /// The program IDs are listed in chain order, i.e., each program's previous program is the
+    /// next in the list, and the last program's previous program is the first.
+    Cycle(Vec<String>),
+}
+
+impl error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPreviousProgram(id, previous_id) => write!(
+                f,
+                "program {} refers to a missing previous program: {}",
+                id, previous_id
+            ),
+            Self::Cycle(ids) => write!(f, "program chain cycle: {}", ids.join(" -> ")),
+        }
+    }
+}
+
+/// Validates the program chains.
+///
+/// This checks that all previous program IDs (`PP`) refer to existing programs and that no chain
+/// has a cycle. Each cycle is reported once.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::header::{programs::{self, ValidationError}, Program, Programs};
+///
+/// let programs: Programs = vec![
+///     Program::builder().set_id("pg0").set_previous_id("pg1").build(),
+///     Program::builder().set_id("pg1").set_previous_id("pg0").build(),
+///     Program::builder().set_id("pg2").set_previous_id("pg3").build(),
+/// ]
+/// .into_iter()
+/// .map(|program| (program.id().into(), program))
+/// .collect();
+///
+/// assert_eq!(
+///     programs::validate(&programs),
+///     [
+///         ValidationError::MissingPreviousProgram(String::from("pg2"), String::from("pg3")),
+///         ValidationError::Cycle(vec![String::from("pg0"), String::from("pg1")]),
+///     ]
+/// );
+/// ```
+pub fn validate(programs: &Programs) -> Vec<ValidationError> {
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    enum State {
+        Visiting,
+        Visited,
+    }
+
+    let mut errors = Vec::new();
+
+    for program in programs.values() {
+        if let Some(previous_id) = program.previous_id() {
+            if !programs.contains_key(previous_id) {
+                errors.push(ValidationError::MissingPreviousProgram(
+                    program.id().into(),
+                    previous_id.into(),
+                ));
+            }
+        }
+    }
+
+    let mut states: HashMap<&str, State> = HashMap::new();
+
+    for id in programs.keys() {
+        let mut path = Vec::new();
+        let mut next = Some(id.as_str());
+
+        while let Some(id) = next {
+            match states.get(id) {
+                Some(State::Visited) => break,
+                Some(State::Visiting) => {
+                    if let Some(i) = path.iter().position(|&p| p == id) {
+                        let ids = path[i..].iter().map(|&p| String::from(p)).collect();
+                        errors.push(ValidationError::Cycle(ids));
+                    }
+
+                    break;
+                }
+                None => {}
+            }
+
+            let program = match programs.get(id) {
+                Some(program) => program,
+                None => break,
+            };
+
+            states.insert(id, State::Visiting);
+            path.push(id);
+
+            next = program.previous_id();
+        }
+
+        for id in path {
+            states.insert(id, State::Visited);
+        }
+    }
+
+    errors
+}
+
+/// Returns a program ID based on the given ID that is not used by any program.
+///
+/// If the ID is not used, it is returned as is. Otherwise, a numeric suffix is appended, e.g.,
+/// `pg0.1`, `pg0.2`, etc.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::header::{programs, Program, Programs};
+///
+/// let mut programs = Programs::default();
+/// assert_eq!(programs::unique_id(&programs, "pg0"), "pg0");
+///
+/// programs.insert(String::from("pg0"), Program::new(String::from("pg0")));
+/// assert_eq!(programs::unique_id(&programs, "pg0"), "pg0.1");
+/// ```
+pub fn unique_id(programs: &Programs, id: &str) -> String {
+    if !programs.contains_key(id) {
+        return id.into();
+    }
+
+    (1..)
+        .map(|i| format!("{}.{}", id, i))
+        .find(|candidate| !programs.contains_key(candidate))
+        .expect("exhausted program ID suffixes")
+}
+
+/// Appends a program to the end of each program chain.
+///
+/// The program is added once per chain leaf (see [`leaves`]), with its previous program ID (`PP`)
+/// set to the leaf. If there are no leaves, it is added once without a previous program ID. If the
+/// program already has a previous program ID, it is added once as is.
+///
+/// Each added program is given a unique ID based on the program's ID (see [`unique_id`]). The
+/// IDs of the added programs are returned.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::header::{programs, Program, Programs};
+///
+/// let mut programs = Programs::default();
+///
+/// let program = Program::builder()
+///     .set_id("noodles")
+///     .set_version("0.1.0")
+///     .set_command_line("noodles view")
+///     .build();
+///
+/// assert_eq!(programs::add(&mut programs, program.clone()), ["noodles"]);
+/// assert_eq!(programs::add(&mut programs, program), ["noodles.1"]);
+///
+/// assert_eq!(programs["noodles.1"].previous_id(), Some("noodles"));
+/// ```
+pub fn add(programs: &mut Programs, program: Program) -> Vec<String> {
+    let previous_ids: Vec<Option<String>> = if program.previous_id().is_some() {
+        vec![program.previous_id().map(|id| id.into())]
+    } else {
+        let leaf_ids: Vec<_> = leaves(programs)
+            .into_iter()
+            .map(|leaf| Some(leaf.id().into()))
+            .collect();
+
+        if leaf_ids.is_empty() {
+            vec![None]
+        } else {
+            leaf_ids
+        }
+    };
+
+    let mut ids = Vec::with_capacity(previous_ids.len());
+
+    for previous_id in previous_ids {
+        let id = unique_id(programs, program.id());
+
+        let mut p = program.clone();
+        *p.id_mut() = id.clone();
+        *p.previous_id_mut() = previous_id;
+
+        programs.insert(id.clone(), p);
+        ids.push(id);
+    }
+
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_programs(programs: Vec<Program>) -> Programs {
+        programs
+            .into_iter()
+            .map(|program| (program.id().into(), program))
+            .collect()
+    }
+
+    #[test]
+    fn test_leaves() {
+        let programs = build_programs(vec![
+            Program::builder().set_id("pg0").build(),
+            Program::builder()
+                .set_id("pg1")
+                .set_previous_id("pg0")
+                .build(),
+            Program::builder()
+                .set_id("pg2")
+                .set_previous_id("pg0")
+                .build(),
+            Program::builder()
+                .set_id("pg3")
+                .set_previous_id("pg4")
+                .build(),
+            Program::builder()
+                .set_id("pg4")
+                .set_previous_id("pg3")
+                .build(),
+        ]);
+
+        let ids: Vec<_> = leaves(&programs).iter().map(|p| p.id()).collect();
+        assert_eq!(ids, ["pg1", "pg2"]);
+
+        assert!(leaves(&Programs::default()).is_empty());
+    }
+
+    #[test]
+    fn test_chain() {
+        let programs = build_programs(vec![
+            Program::builder()
+                .set_id("pg0")
+                .set_previous_id("pg9")
+                .build(),
+            Program::builder()
+                .set_id("pg1")
+                .set_previous_id("pg0")
+                .build(),
+            Program::builder()
+                .set_id("pg2")
+                .set_previous_id("pg3")
+                .build(),
+            Program::builder()
+                .set_id("pg3")
+                .set_previous_id("pg2")
+                .build(),
+        ]);
+
+        let ids: Vec<_> = chain(&programs, "pg1").map(|p| p.id()).collect();
+        assert_eq!(ids, ["pg1", "pg0"]);
+
+        let ids: Vec<_> = chain(&programs, "pg2").map(|p| p.id()).collect();
+        assert_eq!(ids, ["pg2", "pg3"]);
+
+        assert_eq!(chain(&programs, "pg9").count(), 0);
+    }
+
+    #[test]
+    fn test_validate() {
+        let programs = build_programs(vec![
+            Program::builder().set_id("pg0").build(),
+            Program::builder()
+                .set_id("pg1")
+                .set_previous_id("pg0")
+                .build(),
+        ]);
+
+        assert!(validate(&programs).is_empty());
+
+        let programs = build_programs(vec![
+            Program::builder()
+                .set_id("pg0")
+                .set_previous_id("pg0")
+                .build(),
+            Program::builder()
+                .set_id("pg1")
+                .set_previous_id("pg3")
+                .build(),
+            Program::builder()
+                .set_id("pg2")
+                .set_previous_id("pg1")
+                .build(),
+            Program::builder()
+                .set_id("pg3")
+                .set_previous_id("pg2")
+                .build(),
+            Program::builder()
+                .set_id("pg4")
+                .set_previous_id("pg3")
+                .build(),
+            Program::builder()
+                .set_id("pg5")
+                .set_previous_id("pg6")
+                .build(),
+        ]);
+
+        assert_eq!(
+            validate(&programs),
+            [
+                ValidationError::MissingPreviousProgram(String::from("pg5"), String::from("pg6")),
+                ValidationError::Cycle(vec![String::from("pg0")]),
+                ValidationError::Cycle(vec![
+                    String::from("pg1"),
+                    String::from("pg3"),
+                    String::from("pg2"),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        let mut programs = build_programs(vec![
+            Program::builder().set_id("pg0").build(),
+            Program::builder()
+                .set_id("pg1")
+                .set_previous_id("pg0")
+                .build(),
+            Program::builder().set_id("pg2").build(),
+        ]);
+
+        let program = Program::builder()
+            .set_id("pg0")
+            .set_name("noodles")
+            .set_version("0.1.0")
+            .build();
+
+        assert_eq!(add(&mut programs, program), ["pg0.1", "pg0.2"]);
+
+        assert_eq!(programs["pg0.1"].previous_id(), Some("pg1"));
+        assert_eq!(programs["pg0.1"].version(), Some("0.1.0"));
+        assert_eq!(programs["pg0.2"].previous_id(), Some("pg2"));
+
+        let program = Program::builder()
+            .set_id("pg0")
+            .set_previous_id("pg2")
+            .build();
+
+        assert_eq!(add(&mut programs, program), ["pg0.3"]);
+        assert_eq!(programs["pg0.3"].previous_id(), Some("pg2"));
+
+        assert!(validate(&programs).is_empty());
+    }
+}
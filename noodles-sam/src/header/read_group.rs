@@ -6,7 +6,9 @@ mod tag;
 
 pub use self::{builder::Builder, platform::Platform, tag::Tag};
 
-use std::{collections::HashMap, convert::TryFrom, error, fmt};
+use std::{convert::TryFrom, error, fmt};
+
+use indexmap::IndexMap;
 
 use super::{record, Record};
 
@@ -30,7 +32,7 @@ pub struct ReadGroup {
     platform_model: Option<String>,
     platform_unit: Option<String>,
     sample: Option<String>,
-    fields: HashMap<Tag, String>,
+    fields: IndexMap<Tag, String>,
 }
 
 impl ReadGroup {
@@ -71,7 +73,7 @@ impl ReadGroup {
             platform_model: None,
             platform_unit: None,
             sample: None,
-            fields: HashMap::new(),
+            fields: IndexMap::new(),
         }
     }
 
@@ -280,6 +282,9 @@ impl ReadGroup {
     /// This includes any field that is not specially handled by the structure itself. For example,
     /// this will not include the ID field, as it is parsed and available as [`Self::id`].
     ///
+    /// The fields are kept in the order they were added, e.g., as read, which is the order they
+    /// are written in.
+    ///
     /// # Examples
     ///
     /// ```
@@ -300,7 +305,7 @@ impl ReadGroup {
     /// assert_eq!(fields.get(&Tag::Id), None);
     /// assert_eq!(read_group.id(), "rg0");
     /// ```
-    pub fn fields(&self) -> &HashMap<Tag, String> {
+    pub fn fields(&self) -> &IndexMap<Tag, String> {
         &self.fields
     }
 }
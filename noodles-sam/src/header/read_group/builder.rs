@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 use super::{Platform, ReadGroup, Tag};
 
@@ -19,7 +19,7 @@ pub struct Builder {
     platform_model: Option<String>,
     platform_unit: Option<String>,
     sample: Option<String>,
-    fields: HashMap<Tag, String>,
+    fields: IndexMap<Tag, String>,
 }
 
 impl Builder {
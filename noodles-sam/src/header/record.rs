@@ -57,6 +57,37 @@ impl Record {
     }
 }
 
+impl fmt::Display for Record {
+    /// Formats the record as a raw SAM header line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::{record::{Kind, Value}, Record};
+    ///
+    /// let record = Record::new(
+    ///     Kind::Other(*b"XY"),
+    ///     Value::Map(vec![(String::from("zn"), String::from("noodles"))]),
+    /// );
+    ///
+    /// assert_eq!(record.to_string(), "@XY\tzn:noodles");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        match &self.value {
+            Value::String(s) => write!(f, "{}{}", DELIMITER, s)?,
+            Value::Map(fields) => {
+                for (tag, value) in fields {
+                    write!(f, "{}{}{}{}", DELIMITER, tag, DATA_FIELD_DELIMITER, value)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl From<Record> for (Kind, Value) {
     fn from(record: Record) -> Self {
         (record.kind, record.value)
@@ -95,42 +126,70 @@ impl FromStr for Record {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut pieces = s.split(DELIMITER);
+        let mut pieces = s.splitn(2, DELIMITER);
 
         let kind = pieces
             .next()
             .ok_or(ParseError::MissingKind)
             .and_then(|s| s.parse().map_err(ParseError::InvalidKind))?;
 
-        let value = if let Kind::Comment = kind {
-            pieces
+        let value = match kind {
+            // A comment is the remainder of the line, which may include tabs.
+            Kind::Comment => pieces
                 .next()
                 .map(|s| Value::String(s.into()))
-                .ok_or_else(|| ParseError::MissingValue(Kind::Comment.to_string()))?
-        } else {
-            pieces
-                .map(|field| {
-                    let mut field_pieces = field.splitn(2, DATA_FIELD_DELIMITER);
-
-                    let tag = field_pieces.next().ok_or(ParseError::MissingTag)?;
-                    let value = field_pieces
-                        .next()
-                        .ok_or_else(|| ParseError::MissingValue(tag.into()))?;
-
-                    Ok((tag.into(), value.into()))
-                })
-                .collect::<Result<_, _>>()
-                .map(Value::Map)?
+                .ok_or_else(|| ParseError::MissingValue(Kind::Comment.to_string()))?,
+            // Records of other kinds are not required to be lists of fields. If they are not, the
+            // remainder of the line is kept as is.
+            Kind::Other(_) => match pieces.next() {
+                Some(t) => parse_map(t).unwrap_or_else(|_| Value::String(t.into())),
+                None => Value::Map(Vec::new()),
+            },
+            _ => match pieces.next() {
+                Some(t) => parse_map(t)?,
+                None => Value::Map(Vec::new()),
+            },
         };
 
         Ok(Self::new(kind, value))
     }
 }
 
+fn parse_map(s: &str) -> Result<Value, ParseError> {
+    s.split(DELIMITER)
+        .map(|field| {
+            let mut field_pieces = field.splitn(2, DATA_FIELD_DELIMITER);
+
+            let tag = field_pieces.next().ok_or(ParseError::MissingTag)?;
+            let value = field_pieces
+                .next()
+                .ok_or_else(|| ParseError::MissingValue(tag.into()))?;
+
+            Ok((tag.into(), value.into()))
+        })
+        .collect::<Result<_, _>>()
+        .map(Value::Map)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fmt() {
+        let record = Record::new(Kind::Comment, Value::String(String::from("noodles\tsam")));
+        assert_eq!(record.to_string(), "@CO\tnoodles\tsam");
+
+        let record = Record::new(
+            Kind::ReferenceSequence,
+            Value::Map(vec![
+                (String::from("SN"), String::from("sq0")),
+                (String::from("LN"), String::from("8")),
+            ]),
+        );
+        assert_eq!(record.to_string(), "@SQ\tSN:sq0\tLN:8");
+    }
+
     #[test]
     fn test_from_str() {
         assert_eq!(
@@ -176,6 +235,38 @@ mod tests {
             ))
         );
 
+        assert_eq!(
+            "@CO\tnoodles\tsam".parse(),
+            Ok(Record::new(
+                Kind::Comment,
+                Value::String(String::from("noodles\tsam"))
+            ))
+        );
+
+        assert_eq!(
+            "@XY\tzn:noodles\tzs:sam".parse(),
+            Ok(Record::new(
+                Kind::Other(*b"XY"),
+                Value::Map(vec![
+                    (String::from("zn"), String::from("noodles")),
+                    (String::from("zs"), String::from("sam")),
+                ])
+            ))
+        );
+
+        assert_eq!(
+            "@co\tnoodles".parse(),
+            Ok(Record::new(
+                Kind::Other(*b"co"),
+                Value::String(String::from("noodles"))
+            ))
+        );
+
+        assert_eq!(
+            "@XY".parse(),
+            Ok(Record::new(Kind::Other(*b"XY"), Value::Map(Vec::new())))
+        );
+
         assert_eq!(
             "@CO".parse::<Record>(),
             Err(ParseError::MissingValue(String::from("@CO")))
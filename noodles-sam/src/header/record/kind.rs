@@ -1,9 +1,9 @@
 //! SAM header record kind.
 
-use std::{error, fmt, str::FromStr};
+use std::{error, fmt, str, str::FromStr};
 
 /// A SAM header record kind.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Kind {
     /// Header (`HD`).
     Header,
@@ -15,6 +15,11 @@ pub enum Kind {
     Program,
     /// Comment (`CO`).
     Comment,
+    /// Any other record kind.
+    ///
+    /// This is a two-letter record type code not defined by the SAM specification, e.g., a
+    /// user-defined or legacy record type. The code is two ASCII letters.
+    Other([u8; 2]),
 }
 
 impl AsRef<str> for Kind {
@@ -25,6 +30,8 @@ impl AsRef<str> for Kind {
             Self::ReadGroup => "RG",
             Self::Program => "PG",
             Self::Comment => "CO",
+            // A parsed code is always ASCII.
+            Self::Other(code) => str::from_utf8(code).unwrap_or_default(),
         }
     }
 }
@@ -66,7 +73,12 @@ impl FromStr for Kind {
             "@RG" => Ok(Self::ReadGroup),
             "@PG" => Ok(Self::Program),
             "@CO" => Ok(Self::Comment),
-            _ => Err(ParseError::Invalid),
+            _ => match s.as_bytes() {
+                [b'@', a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+                    Ok(Self::Other([*a, *b]))
+                }
+                _ => Err(ParseError::Invalid),
+            },
         }
     }
 }
@@ -75,6 +87,12 @@ impl FromStr for Kind {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Kind::Header.to_string(), "@HD");
+        assert_eq!(Kind::Other(*b"NO").to_string(), "@NO");
+    }
+
     #[test]
     fn test_from_str() {
         assert_eq!("@HD".parse(), Ok(Kind::Header));
@@ -84,7 +102,12 @@ mod tests {
         assert_eq!("@CO".parse(), Ok(Kind::Comment));
 
         assert_eq!("".parse::<Kind>(), Err(ParseError::Empty));
-        assert_eq!("@NO".parse::<Kind>(), Err(ParseError::Invalid));
+        assert_eq!("@NO".parse(), Ok(Kind::Other(*b"NO")));
+        assert_eq!("@co".parse(), Ok(Kind::Other(*b"co")));
+
         assert_eq!("HD".parse::<Kind>(), Err(ParseError::Invalid));
+        assert_eq!("@N".parse::<Kind>(), Err(ParseError::Invalid));
+        assert_eq!("@N0".parse::<Kind>(), Err(ParseError::Invalid));
+        assert_eq!("@NOO".parse::<Kind>(), Err(ParseError::Invalid));
     }
 }
@@ -5,7 +5,9 @@ mod md5_checksum;
 mod molecule_topology;
 mod tag;
 
-use std::{collections::HashMap, convert::TryFrom, error, fmt, num};
+use std::{convert::TryFrom, error, fmt, num};
+
+use indexmap::IndexMap;
 
 pub use self::{
     builder::Builder, md5_checksum::Md5Checksum, molecule_topology::MoleculeTopology, tag::Tag,
@@ -31,7 +33,7 @@ pub struct ReferenceSequence {
     species: Option<String>,
     molecule_topology: Option<MoleculeTopology>,
     uri: Option<String>,
-    fields: HashMap<Tag, String>,
+    fields: IndexMap<Tag, String>,
 }
 
 #[allow(clippy::len_without_is_empty)]
@@ -72,7 +74,7 @@ impl ReferenceSequence {
             species: None,
             molecule_topology: None,
             uri: None,
-            fields: HashMap::new(),
+            fields: IndexMap::new(),
         }
     }
 
@@ -246,6 +248,9 @@ impl ReferenceSequence {
     /// this will not include the name and length fields, as they are parsed and available as
     /// [`Self::name`] and [`Self::len`], respectively.
     ///
+    /// The fields are kept in the order they were added, e.g., as read, which is the order they
+    /// are written in.
+    ///
     /// # Examples
     ///
     /// ```
@@ -270,7 +275,7 @@ impl ReferenceSequence {
     /// assert_eq!(fields.get(&Tag::Length), None);
     /// assert_eq!(reference_sequence.len(), 13);
     /// ```
-    pub fn fields(&self) -> &HashMap<Tag, String> {
+    pub fn fields(&self) -> &IndexMap<Tag, String> {
         &self.fields
     }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 use super::{Md5Checksum, MoleculeTopology, ReferenceSequence, Tag};
 
@@ -15,7 +15,7 @@ pub struct Builder {
     species: Option<String>,
     molecule_topology: Option<MoleculeTopology>,
     uri: Option<String>,
-    fields: HashMap<Tag, String>,
+    fields: IndexMap<Tag, String>,
 }
 
 impl Builder {
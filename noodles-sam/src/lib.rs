@@ -31,9 +31,16 @@
 //! # Ok::<(), io::Error>(())
 //! ```
 
+pub mod fastq;
+pub mod fixmate;
 pub mod header;
 pub mod reader;
 pub mod record;
+pub mod split_reads;
+pub mod subsample;
+pub mod template;
+pub mod unaligned;
+pub mod validation;
 mod writer;
 
 pub use self::{header::Header, reader::Reader, record::Record, writer::Writer};
@@ -1,11 +1,14 @@
 //! SAM record and fields.
 
+pub mod base_modifications;
 mod builder;
 pub mod cigar;
 pub mod data;
 mod field;
 mod flags;
 mod mapping_quality;
+pub mod mismatched_positions;
+pub mod other_alignments;
 pub mod position;
 pub mod quality_scores;
 pub mod read_name;
@@ -122,6 +125,21 @@ impl Record {
         self.flags
     }
 
+    /// Returns a mutable reference to the SAM flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::Flags};
+    ///
+    /// let mut record = sam::Record::default();
+    /// *record.flags_mut() = Flags::PAIRED | Flags::READ_1;
+    /// assert_eq!(record.flags(), Flags::PAIRED | Flags::READ_1);
+    /// ```
+    pub fn flags_mut(&mut self) -> &mut Flags {
+        &mut self.flags
+    }
+
     /// Returns the reference sequence name of this record.
     ///
     /// # Examples
@@ -140,6 +158,22 @@ impl Record {
         self.reference_sequence_name.as_ref()
     }
 
+    /// Returns a mutable reference to the reference sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let mut record = sam::Record::default();
+    /// *record.reference_sequence_name_mut() = Some("sq0".parse()?);
+    /// assert_eq!(record.reference_sequence_name().map(|name| name.as_str()), Some("sq0"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reference_sequence_name_mut(&mut self) -> &mut Option<ReferenceSequenceName> {
+        &mut self.reference_sequence_name
+    }
+
     /// Returns the start position of this record.
     ///
     /// This value is 1-based.
@@ -163,6 +197,23 @@ impl Record {
         self.position
     }
 
+    /// Returns a mutable reference to the start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::{self as sam, record::Position};
+    ///
+    /// let mut record = sam::Record::default();
+    /// *record.position_mut() = Some(Position::try_from(13)?);
+    /// assert_eq!(record.position().map(i32::from), Some(13));
+    /// # Ok::<(), sam::record::position::TryFromIntError>(())
+    /// ```
+    pub fn position_mut(&mut self) -> &mut Option<Position> {
+        &mut self.position
+    }
+
     /// Returns the mapping quality of this record.
     ///
     /// Mapping quality ranges from 0 to 254, inclusive. A value of 255 means no mapping quality is
@@ -232,6 +283,26 @@ impl Record {
         self.mate_reference_sequence_name.as_ref()
     }
 
+    /// Returns a mutable reference to the mate reference sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let mut record = sam::Record::default();
+    /// *record.mate_reference_sequence_name_mut() = Some("sq0".parse()?);
+    ///
+    /// assert_eq!(
+    ///     record.mate_reference_sequence_name().map(|name| name.as_str()),
+    ///     Some("sq0")
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn mate_reference_sequence_name_mut(&mut self) -> &mut Option<ReferenceSequenceName> {
+        &mut self.mate_reference_sequence_name
+    }
+
     /// Returns the start position of the mate of this record.
     ///
     /// This value is 1-based.
@@ -255,6 +326,23 @@ impl Record {
         self.mate_position
     }
 
+    /// Returns a mutable reference to the start position of the mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::{self as sam, record::Position};
+    ///
+    /// let mut record = sam::Record::default();
+    /// *record.mate_position_mut() = Some(Position::try_from(21)?);
+    /// assert_eq!(record.mate_position().map(i32::from), Some(21));
+    /// # Ok::<(), sam::record::position::TryFromIntError>(())
+    /// ```
+    pub fn mate_position_mut(&mut self) -> &mut Option<Position> {
+        &mut self.mate_position
+    }
+
     /// Returns the template length of this record.
     ///
     /// # Examples
@@ -272,6 +360,21 @@ impl Record {
         self.template_length
     }
 
+    /// Returns a mutable reference to the template length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let mut record = sam::Record::default();
+    /// *record.template_length_mut() = 101;
+    /// assert_eq!(record.template_length(), 101);
+    /// ```
+    pub fn template_length_mut(&mut self) -> &mut i32 {
+        &mut self.template_length
+    }
+
     /// Returns the bases in the sequence of this record.
     ///
     /// # Examples
@@ -339,6 +442,26 @@ impl Record {
     pub fn data(&self) -> &Data {
         &self.data
     }
+
+    /// Returns a mutable reference to the optional data fields for this record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::data};
+    ///
+    /// let mut record = sam::Record::default();
+    ///
+    /// record.data_mut().insert(data::Field::new(
+    ///     data::field::Tag::AlignmentHitCount,
+    ///     data::field::Value::Int32(1),
+    /// ));
+    ///
+    /// assert_eq!(record.data().to_string(), "NH:i:1");
+    /// ```
+    pub fn data_mut(&mut self) -> &mut Data {
+        &mut self.data
+    }
 }
 
 impl Default for Record {
@@ -0,0 +1,899 @@
+//! SAM record base modifications (`MM`) and modification probabilities (`ML`).
+//!
+//! The base modifications data field (`MM`) lists groups of modifications, e.g., methylation
+//! calls. Each group is formatted as `<base><strand><codes>[.?],<skip>,...;`, where each skip
+//! count is the number of occurrences of the base to skip before the next modified base. Bases
+//! are counted in the orientation of the original read, i.e., as sequenced.
+//!
+//! The modification probabilities data field (`ML`) is an array of 8-bit probabilities, one for
+//! each code of each modified base, in the order they are listed in `MM`.
+//!
+//! # Examples
+//!
+//! ```
+//! use noodles_sam::{self as sam, record::base_modifications::{self, Code}};
+//!
+//! let record: sam::Record =
+//!     "r0\t0\tsq0\t8\t60\t8M\t*\t0\t0\tACGCACGC\t*\tMM:Z:C+m,1,0;\tML:B:C,200,10".parse()?;
+//!
+//! let calls: Vec<_> = base_modifications::calls(&record)?.collect::<Result<_, _>>()?;
+//!
+//! assert_eq!(calls.len(), 2);
+//!
+//! assert_eq!(calls[0].read_position(), 3);
+//! assert_eq!(calls[0].reference_position().map(i32::from), Some(11));
+//! assert_eq!(calls[0].code(), Code::Letter('m'));
+//! assert_eq!(calls[0].probability(), Some(200));
+//!
+//! assert_eq!(calls[1].read_position(), 5);
+//! assert_eq!(calls[1].probability(), Some(10));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::{convert::TryFrom, error, fmt, num, ops::Deref, str::FromStr};
+
+use super::{
+    data::field::{Tag, Value},
+    sequence::Base,
+    Position, Record,
+};
+
+const GROUP_DELIMITER: char = ';';
+const FIELD_DELIMITER: char = ',';
+
+/// The strand of a base modification relative to the original read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strand {
+    /// Same strand as the original read (`+`).
+    Forward,
+    /// Opposite strand of the original read (`-`).
+    Reverse,
+}
+
+impl From<Strand> for char {
+    fn from(strand: Strand) -> Self {
+        match strand {
+            Strand::Forward => '+',
+            Strand::Reverse => '-',
+        }
+    }
+}
+
+/// A base modification code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Code {
+    /// A single-letter code, e.g., `m` (5-methylcytosine).
+    Letter(char),
+    /// A ChEBI identifier.
+    Chebi(u32),
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Letter(c) => write!(f, "{}", c),
+            Self::Chebi(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+/// How bases not listed in a base modification group are interpreted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// Skipped bases are unmodified (`.` or no mode).
+    Implicit,
+    /// Skipped bases have an unknown modification status (`?`).
+    Explicit,
+}
+
+/// A group of base modifications.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Group {
+    base: Base,
+    strand: Strand,
+    codes: Vec<Code>,
+    mode: Mode,
+    skips: Vec<usize>,
+}
+
+impl Group {
+    /// Creates a base modification group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{
+    ///     base_modifications::{Code, Group, Mode, Strand},
+    ///     sequence::Base,
+    /// };
+    ///
+    /// let group = Group::new(
+    ///     Base::C,
+    ///     Strand::Forward,
+    ///     vec![Code::Letter('m')],
+    ///     Mode::Implicit,
+    ///     vec![1, 0],
+    /// );
+    ///
+    /// assert_eq!(group.to_string(), "C+m,1,0");
+    /// ```
+    pub fn new(
+        base: Base,
+        strand: Strand,
+        codes: Vec<Code>,
+        mode: Mode,
+        skips: Vec<usize>,
+    ) -> Self {
+        Self {
+            base,
+            strand,
+            codes,
+            mode,
+            skips,
+        }
+    }
+
+    /// Returns the unmodified base.
+    ///
+    /// `N` matches any base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{base_modifications::BaseModifications, sequence::Base};
+    /// let base_modifications: BaseModifications = "C-m?,1,0;".parse()?;
+    /// assert_eq!(base_modifications[0].base(), Base::C);
+    /// # Ok::<(), noodles_sam::record::base_modifications::ParseError>(())
+    /// ```
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Returns the strand of the modifications.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::base_modifications::{BaseModifications, Strand};
+    /// let base_modifications: BaseModifications = "C-m?,1,0;".parse()?;
+    /// assert_eq!(base_modifications[0].strand(), Strand::Reverse);
+    /// # Ok::<(), noodles_sam::record::base_modifications::ParseError>(())
+    /// ```
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the modification codes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::base_modifications::{BaseModifications, Code};
+    /// let base_modifications: BaseModifications = "C-m?,1,0;".parse()?;
+    /// assert_eq!(base_modifications[0].codes(), [Code::Letter('m')]);
+    /// # Ok::<(), noodles_sam::record::base_modifications::ParseError>(())
+    /// ```
+    pub fn codes(&self) -> &[Code] {
+        &self.codes
+    }
+
+    /// Returns the mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::base_modifications::{BaseModifications, Mode};
+    /// let base_modifications: BaseModifications = "C-m?,1,0;".parse()?;
+    /// assert_eq!(base_modifications[0].mode(), Mode::Explicit);
+    /// # Ok::<(), noodles_sam::record::base_modifications::ParseError>(())
+    /// ```
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Returns the number of bases to skip before each modified base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::base_modifications::BaseModifications;
+    /// let base_modifications: BaseModifications = "C-m?,1,0;".parse()?;
+    /// assert_eq!(base_modifications[0].skips(), [1, 0]);
+    /// # Ok::<(), noodles_sam::record::base_modifications::ParseError>(())
+    /// ```
+    pub fn skips(&self) -> &[usize] {
+        &self.skips
+    }
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.base, char::from(self.strand))?;
+
+        for code in &self.codes {
+            write!(f, "{}", code)?;
+        }
+
+        if self.mode == Mode::Explicit {
+            f.write_str("?")?;
+        }
+
+        for skip in &self.skips {
+            write!(f, "{}{}", FIELD_DELIMITER, skip)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// SAM record base modifications (`MM`).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BaseModifications(Vec<Group>);
+
+impl Deref for BaseModifications {
+    type Target = [Group];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<Group>> for BaseModifications {
+    fn from(groups: Vec<Group>) -> Self {
+        Self(groups)
+    }
+}
+
+impl fmt::Display for BaseModifications {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for group in &self.0 {
+            write!(f, "{}{}", group, GROUP_DELIMITER)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when raw base modifications fail to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The base is missing.
+    MissingBase,
+    /// The base is invalid.
+    InvalidBase(char),
+    /// The strand is missing.
+    MissingStrand,
+    /// The strand is invalid.
+    InvalidStrand(char),
+    /// The modification codes are missing.
+    MissingCodes,
+    /// A modification code is invalid.
+    InvalidCode(String),
+    /// A skip count is invalid.
+    InvalidSkip(num::ParseIntError),
+    /// The last group is missing the `;` terminator.
+    MissingTerminator,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingBase => f.write_str("missing base"),
+            Self::InvalidBase(c) => write!(f, "invalid base: {}", c),
+            Self::MissingStrand => f.write_str("missing strand"),
+            Self::InvalidStrand(c) => write!(f, "invalid strand: {}", c),
+            Self::MissingCodes => f.write_str("missing modification codes"),
+            Self::InvalidCode(s) => write!(f, "invalid modification code: {}", s),
+            Self::InvalidSkip(e) => write!(f, "invalid skip count: {}", e),
+            Self::MissingTerminator => f.write_str("missing group terminator"),
+        }
+    }
+}
+
+impl FromStr for BaseModifications {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let s = s
+            .strip_suffix(GROUP_DELIMITER)
+            .ok_or(ParseError::MissingTerminator)?;
+
+        s.split(GROUP_DELIMITER)
+            .map(parse_group)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+fn parse_group(s: &str) -> Result<Group, ParseError> {
+    let mut fields = s.split(FIELD_DELIMITER);
+
+    let header = fields.next().unwrap_or_default();
+    let mut chars = header.chars();
+
+    let base = match chars.next() {
+        Some(c @ ('A' | 'C' | 'G' | 'T' | 'U' | 'N')) => {
+            Base::try_from(c).map_err(|_| ParseError::InvalidBase(c))?
+        }
+        Some(c) => return Err(ParseError::InvalidBase(c)),
+        None => return Err(ParseError::MissingBase),
+    };
+
+    let strand = match chars.next() {
+        Some('+') => Strand::Forward,
+        Some('-') => Strand::Reverse,
+        Some(c) => return Err(ParseError::InvalidStrand(c)),
+        None => return Err(ParseError::MissingStrand),
+    };
+
+    let mut raw_codes = chars.as_str();
+    let mut mode = Mode::Implicit;
+
+    if let Some(t) = raw_codes.strip_suffix('.') {
+        raw_codes = t;
+    } else if let Some(t) = raw_codes.strip_suffix('?') {
+        raw_codes = t;
+        mode = Mode::Explicit;
+    }
+
+    let codes = parse_codes(raw_codes)?;
+
+    let skips = fields
+        .map(|t| t.parse().map_err(ParseError::InvalidSkip))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Group::new(base, strand, codes, mode, skips))
+}
+
+fn parse_codes(s: &str) -> Result<Vec<Code>, ParseError> {
+    if s.is_empty() {
+        Err(ParseError::MissingCodes)
+    } else if s.chars().all(|c| c.is_ascii_digit()) {
+        s.parse()
+            .map(|id| vec![Code::Chebi(id)])
+            .map_err(|_| ParseError::InvalidCode(s.into()))
+    } else if s.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(s.chars().map(Code::Letter).collect())
+    } else {
+        Err(ParseError::InvalidCode(s.into()))
+    }
+}
+
+/// A modified base of a record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Call {
+    read_position: usize,
+    reference_position: Option<Position>,
+    base: Base,
+    strand: Strand,
+    code: Code,
+    probability: Option<u8>,
+}
+
+impl Call {
+    /// Returns the 0-based position of the modified base in the record sequence.
+    ///
+    /// This is in the orientation of the stored sequence, i.e., reverse complemented for
+    /// reverse complemented records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::base_modifications::{self}};
+    ///
+    /// let record: sam::Record =
+    ///     "r0\t0\tsq0\t8\t60\t1S7M\t*\t0\t0\tCGCACGCA\t*\tMM:Z:C+m,0,1;\tML:B:C,200,10".parse()?;
+    /// let calls: Vec<_> = base_modifications::calls(&record)?.collect::<Result<_, _>>()?;
+    ///
+    /// assert_eq!(calls[0].read_position(), 0);
+    /// assert_eq!(calls[1].read_position(), 4);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn read_position(&self) -> usize {
+        self.read_position
+    }
+
+    /// Returns the reference position aligned to the modified base.
+    ///
+    /// This is `None` if the record is unmapped or the base is not aligned to the reference,
+    /// e.g., an insertion or soft clip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::base_modifications::{self}};
+    ///
+    /// let record: sam::Record =
+    ///     "r0\t0\tsq0\t8\t60\t1S7M\t*\t0\t0\tCGCACGCA\t*\tMM:Z:C+m,0,1;\tML:B:C,200,10".parse()?;
+    /// let calls: Vec<_> = base_modifications::calls(&record)?.collect::<Result<_, _>>()?;
+    ///
+    /// assert!(calls[0].reference_position().is_none());
+    /// assert_eq!(calls[1].reference_position().map(i32::from), Some(11));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reference_position(&self) -> Option<Position> {
+        self.reference_position
+    }
+
+    /// Returns the unmodified base of the group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::{base_modifications, sequence::Base}};
+    ///
+    /// let record: sam::Record =
+    ///     "r0\t0\tsq0\t8\t60\t1S7M\t*\t0\t0\tCGCACGCA\t*\tMM:Z:C+m,0,1;\tML:B:C,200,10".parse()?;
+    /// let calls: Vec<_> = base_modifications::calls(&record)?.collect::<Result<_, _>>()?;
+    ///
+    /// assert_eq!(calls[0].base(), Base::C);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Returns the strand of the modification relative to the original read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::base_modifications::{self, Strand}};
+    ///
+    /// let record: sam::Record =
+    ///     "r0\t0\tsq0\t8\t60\t1S7M\t*\t0\t0\tCGCACGCA\t*\tMM:Z:C+m,0,1;\tML:B:C,200,10".parse()?;
+    /// let calls: Vec<_> = base_modifications::calls(&record)?.collect::<Result<_, _>>()?;
+    ///
+    /// assert_eq!(calls[0].strand(), Strand::Forward);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the modification code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::base_modifications::{self, Code}};
+    ///
+    /// let record: sam::Record =
+    ///     "r0\t0\tsq0\t8\t60\t1S7M\t*\t0\t0\tCGCACGCA\t*\tMM:Z:C+m,0,1;\tML:B:C,200,10".parse()?;
+    /// let calls: Vec<_> = base_modifications::calls(&record)?.collect::<Result<_, _>>()?;
+    ///
+    /// assert_eq!(calls[0].code(), Code::Letter('m'));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn code(&self) -> Code {
+        self.code
+    }
+
+    /// Returns the modification probability, if modification probabilities (`ML`) are present.
+    ///
+    /// The probability that the base is modified is in the range [`p`/256, (`p` + 1)/256).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::base_modifications::{self}};
+    ///
+    /// let record: sam::Record =
+    ///     "r0\t0\tsq0\t8\t60\t1S7M\t*\t0\t0\tCGCACGCA\t*\tMM:Z:C+m,0,1;\tML:B:C,200,10".parse()?;
+    /// let calls: Vec<_> = base_modifications::calls(&record)?.collect::<Result<_, _>>()?;
+    ///
+    /// assert_eq!(calls[0].probability(), Some(200));
+    /// assert_eq!(calls[1].probability(), Some(10));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn probability(&self) -> Option<u8> {
+        self.probability
+    }
+}
+
+/// An error returned when base modification calls fail to be read from a record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CallsError {
+    /// The base modifications (`MM`) are not a string.
+    InvalidBaseModificationsType,
+    /// The base modifications (`MM`) are invalid.
+    InvalidBaseModifications(ParseError),
+    /// The modification probabilities (`ML`) are not an 8-bit unsigned integer array.
+    InvalidProbabilitiesType,
+    /// The number of modification probabilities (`ML`) does not match the number of calls.
+    ProbabilitiesLengthMismatch(usize, usize),
+    /// The sequence length does not match the base modification sequence length (`MN`).
+    SequenceLengthMismatch(usize, usize),
+    /// A skip count is past the end of the sequence.
+    SkipOutOfRange,
+}
+
+impl error::Error for CallsError {}
+
+impl fmt::Display for CallsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBaseModificationsType => f.write_str("invalid MM field type"),
+            Self::InvalidBaseModifications(e) => write!(f, "invalid MM field: {}", e),
+            Self::InvalidProbabilitiesType => f.write_str("invalid ML field type"),
+            Self::ProbabilitiesLengthMismatch(expected, actual) => write!(
+                f,
+                "ML length mismatch: expected {}, got {}",
+                expected, actual
+            ),
+            Self::SequenceLengthMismatch(expected, actual) => write!(
+                f,
+                "sequence length mismatch: expected {} (MN), got {}",
+                expected, actual
+            ),
+            Self::SkipOutOfRange => f.write_str("MM skip count out of range"),
+        }
+    }
+}
+
+/// Returns an iterator over the base modification calls of a record.
+///
+/// The base modifications (`MM`) and modification probabilities (`ML`) are read from the record
+/// data. If `MM` is missing, no calls are returned. Each call maps the modified base to its
+/// position in the record sequence and, through the CIGAR, to its reference position.
+///
+/// # Errors
+///
+/// An error is returned if the data fields are invalid, if `ML` does not have one value per call,
+/// or if the sequence length does not match `MN`, e.g., when the sequence is hard clipped. Skip
+/// counts past the end of the sequence are reported by the iterator.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{self as sam, record::base_modifications::{self}};
+///
+/// let record: sam::Record =
+///     "r0\t0\tsq0\t8\t60\t1S7M\t*\t0\t0\tCGCACGCA\t*\tMM:Z:C+m,0,1;\tML:B:C,200,10".parse()?;
+/// let calls: Vec<_> = base_modifications::calls(&record)?.collect::<Result<_, _>>()?;
+///
+/// assert_eq!(calls.len(), 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn calls(record: &Record) -> Result<Calls<'_>, CallsError> {
+    let find = |tag: Tag| record.data().get(&tag).map(|field| field.value());
+
+    let base_modifications = match find(Tag::BaseModifications) {
+        Some(Value::String(s)) => s
+            .parse::<BaseModifications>()
+            .map_err(CallsError::InvalidBaseModifications)?,
+        Some(_) => return Err(CallsError::InvalidBaseModificationsType),
+        None => BaseModifications::default(),
+    };
+
+    let probabilities = match find(Tag::BaseModificationProbabilities) {
+        Some(Value::UInt8Array(probabilities)) => Some(probabilities.clone()),
+        Some(_) => return Err(CallsError::InvalidProbabilitiesType),
+        None => None,
+    };
+
+    if let Some(probabilities) = probabilities.as_ref() {
+        let call_count: usize = base_modifications
+            .iter()
+            .map(|group| group.skips().len() * group.codes().len())
+            .sum();
+
+        if probabilities.len() != call_count {
+            return Err(CallsError::ProbabilitiesLengthMismatch(
+                call_count,
+                probabilities.len(),
+            ));
+        }
+    }
+
+    let sequence_len = record.sequence().len();
+
+    if let Some(n) = find(Tag::BaseModificationSequenceLength).and_then(|value| value.as_int32()) {
+        if n as usize != sequence_len {
+            return Err(CallsError::SequenceLengthMismatch(n as usize, sequence_len));
+        }
+    }
+
+    let is_reverse_complemented = record.flags().is_reverse_complemented();
+
+    let bases: Vec<_> = if is_reverse_complemented {
+        record
+            .sequence()
+            .iter()
+            .rev()
+            .map(|&b| complement(b))
+            .collect()
+    } else {
+        record.sequence().to_vec()
+    };
+
+    Ok(Calls {
+        record,
+        groups: base_modifications.0,
+        probabilities,
+        bases,
+        group_index: 0,
+        skip_index: 0,
+        code_index: 0,
+        cursor: 0,
+        position: None,
+        probability_index: 0,
+    })
+}
+
+/// An iterator over the base modification calls of a record.
+///
+/// This is created by [`calls`].
+pub struct Calls<'a> {
+    record: &'a Record,
+    groups: Vec<Group>,
+    probabilities: Option<Vec<u8>>,
+    // The sequence in the orientation of the original read.
+    bases: Vec<Base>,
+    group_index: usize,
+    skip_index: usize,
+    code_index: usize,
+    cursor: usize,
+    position: Option<usize>,
+    probability_index: usize,
+}
+
+impl<'a> Calls<'a> {
+    fn build_call(&self, group: &Group, position: usize, code: Code) -> Call {
+        let read_position = if self.record.flags().is_reverse_complemented() {
+            self.bases.len() - 1 - position
+        } else {
+            position
+        };
+
+        let reference_position = if self.record.flags().is_unmapped() {
+            None
+        } else {
+            self.record
+                .position()
+                .and_then(|start| self.record.cigar().reference_position(start, read_position))
+        };
+
+        let probability = self
+            .probabilities
+            .as_ref()
+            .and_then(|probabilities| probabilities.get(self.probability_index))
+            .copied();
+
+        Call {
+            read_position,
+            reference_position,
+            base: group.base(),
+            strand: group.strand(),
+            code,
+            probability,
+        }
+    }
+}
+
+impl<'a> Iterator for Calls<'a> {
+    type Item = Result<Call, CallsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let group = self.groups.get(self.group_index)?;
+
+            if let Some(position) = self.position {
+                if let Some(&code) = group.codes().get(self.code_index) {
+                    let call = self.build_call(group, position, code);
+                    self.code_index += 1;
+                    self.probability_index += 1;
+                    return Some(Ok(call));
+                }
+
+                self.position = None;
+                self.code_index = 0;
+                self.skip_index += 1;
+            }
+
+            let skip = match group.skips().get(self.skip_index) {
+                Some(&skip) => skip,
+                None => {
+                    self.group_index += 1;
+                    self.skip_index = 0;
+                    self.cursor = 0;
+                    continue;
+                }
+            };
+
+            let target = match group.strand() {
+                Strand::Forward => group.base(),
+                Strand::Reverse => complement(group.base()),
+            };
+
+            let position = self.bases[self.cursor..]
+                .iter()
+                .enumerate()
+                .filter(|(_, &base)| target == Base::N || base == target)
+                .nth(skip)
+                .map(|(i, _)| self.cursor + i);
+
+            match position {
+                Some(position) => {
+                    self.cursor = position + 1;
+                    self.position = Some(position);
+                }
+                None => {
+                    self.group_index = self.groups.len();
+                    return Some(Err(CallsError::SkipOutOfRange));
+                }
+            }
+        }
+    }
+}
+
+fn complement(base: Base) -> Base {
+    match base {
+        Base::A => Base::T,
+        Base::C => Base::G,
+        Base::G => Base::C,
+        Base::T | Base::U => Base::A,
+        b => b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), ParseError> {
+        let base_modifications: BaseModifications = "C+mh?,5,12;G-21839.,0;N+n,1;".parse()?;
+        assert_eq!(
+            base_modifications.to_string(),
+            "C+mh?,5,12;G-21839,0;N+n,1;"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("".parse(), Ok(BaseModifications::default()));
+
+        assert_eq!(
+            "C+mh?,5,12;A-a,;".parse::<BaseModifications>(),
+            Err(ParseError::InvalidSkip("".parse::<usize>().unwrap_err()))
+        );
+
+        assert_eq!(
+            "C+mh?,5,12;G-21839;".parse(),
+            Ok(BaseModifications::from(vec![
+                Group::new(
+                    Base::C,
+                    Strand::Forward,
+                    vec![Code::Letter('m'), Code::Letter('h')],
+                    Mode::Explicit,
+                    vec![5, 12],
+                ),
+                Group::new(
+                    Base::G,
+                    Strand::Reverse,
+                    vec![Code::Chebi(21839)],
+                    Mode::Implicit,
+                    Vec::new(),
+                ),
+            ]))
+        );
+
+        assert_eq!(
+            "C+m,5".parse::<BaseModifications>(),
+            Err(ParseError::MissingTerminator)
+        );
+        assert_eq!(
+            ";".parse::<BaseModifications>(),
+            Err(ParseError::MissingBase)
+        );
+        assert_eq!(
+            "X+m;".parse::<BaseModifications>(),
+            Err(ParseError::InvalidBase('X'))
+        );
+        assert_eq!(
+            "C;".parse::<BaseModifications>(),
+            Err(ParseError::MissingStrand)
+        );
+        assert_eq!(
+            "C*m;".parse::<BaseModifications>(),
+            Err(ParseError::InvalidStrand('*'))
+        );
+        assert_eq!(
+            "C+;".parse::<BaseModifications>(),
+            Err(ParseError::MissingCodes)
+        );
+        assert_eq!(
+            "C+m1;".parse::<BaseModifications>(),
+            Err(ParseError::InvalidCode(String::from("m1")))
+        );
+    }
+
+    #[test]
+    fn test_calls_with_multiple_codes() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record =
+            "r0\t4\t*\t0\t255\t*\t*\t0\t0\tCCGC\t*\tMM:Z:C+mh,0,1;\tML:B:C,1,2,3,4".parse()?;
+
+        let calls: Vec<_> = calls(&record)?
+            .map(|result| {
+                result.map(|call| (call.read_position(), call.code(), call.probability()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(
+            calls,
+            [
+                (0, Code::Letter('m'), Some(1)),
+                (0, Code::Letter('h'), Some(2)),
+                (3, Code::Letter('m'), Some(3)),
+                (3, Code::Letter('h'), Some(4)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calls_with_reverse_complemented_record() -> Result<(), Box<dyn std::error::Error>> {
+        // original read: CAGCT; stored (reverse complemented): AGCTG
+        let record: Record =
+            "r0\t16\tsq0\t10\t60\t1S4M\t*\t0\t0\tAGCTG\t*\tMM:Z:C+m,1;G-m,0;".parse()?;
+
+        let calls: Vec<_> = calls(&record)?
+            .map(|result| {
+                result.map(|call| {
+                    (
+                        call.read_position(),
+                        call.reference_position().map(i32::from),
+                        call.strand(),
+                        call.probability(),
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(
+            calls,
+            [
+                (1, Some(10), Strand::Forward, None),
+                (4, Some(13), Strand::Reverse, None)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calls_with_invalid_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "r0\t4\t*\t0\t255\t*\t*\t0\t0\tACGT\t*".parse()?;
+        assert_eq!(calls(&record)?.count(), 0);
+
+        let record: Record =
+            "r0\t4\t*\t0\t255\t*\t*\t0\t0\tACGT\t*\tMM:Z:C+m,0;\tML:B:C,1,2".parse()?;
+        assert!(matches!(
+            calls(&record),
+            Err(CallsError::ProbabilitiesLengthMismatch(1, 2))
+        ));
+
+        let record: Record =
+            "r0\t4\t*\t0\t255\t*\t*\t0\t0\tACGT\t*\tMM:Z:C+m,0;\tMN:i:8".parse()?;
+        assert!(matches!(
+            calls(&record),
+            Err(CallsError::SequenceLengthMismatch(8, 4))
+        ));
+
+        let record: Record = "r0\t4\t*\t0\t255\t*\t*\t0\t0\tACGT\t*\tMM:Z:C+m,1;".parse()?;
+        let mut calls = calls(&record)?;
+        assert_eq!(calls.next(), Some(Err(CallsError::SkipOutOfRange)));
+        assert!(calls.next().is_none());
+
+        Ok(())
+    }
+}
@@ -1,12 +1,18 @@
 //! SAM CIGAR and operations.
 
+mod aligned_blocks;
+mod aligned_pairs;
 pub mod op;
 
-use std::{error, fmt, ops::Deref, str::FromStr};
+use std::{convert::TryFrom, error, fmt, ops::Deref, str::FromStr};
 
-pub use self::op::Op;
+pub use self::{
+    aligned_blocks::{AlignedBlock, AlignedBlocks},
+    aligned_pairs::AlignedPairs,
+    op::Op,
+};
 
-use super::NULL_FIELD;
+use super::{Position, NULL_FIELD};
 
 use self::op::Kind;
 
@@ -44,6 +50,399 @@ impl Cigar {
             })
             .sum()
     }
+
+    /// Calculates the read length.
+    ///
+    /// This sums the lengths of the CIGAR operations that consume the read, i.e., alignment
+    /// matches (`M`), insertions into the reference (`I`), soft clips (`S`), sequence matches
+    /// (`=`), and sequence mismatches (`X`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{cigar::{op::Kind, Op}, Cigar};
+    ///
+    /// let cigar = Cigar::from(vec![
+    ///     Op::new(Kind::Match, 36),
+    ///     Op::new(Kind::Deletion, 4),
+    ///     Op::new(Kind::SoftClip, 8),
+    /// ]);
+    ///
+    /// assert_eq!(cigar.read_len(), 44);
+    /// ```
+    pub fn read_len(&self) -> u32 {
+        self.iter()
+            .filter_map(|op| match op.kind() {
+                Kind::Match
+                | Kind::Insertion
+                | Kind::SoftClip
+                | Kind::SeqMatch
+                | Kind::SeqMismatch => Some(op.len()),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Returns an iterator over the read offset and reference position pairs of an alignment.
+    ///
+    /// Read offsets are 0-based indices into the sequence, including soft clips. Aligned bases
+    /// (`M`, `=`, `X`) have both values; insertions (`I`) and soft clips (`S`) have no reference
+    /// position; and deletions (`D`) and skipped regions (`N`) have no read offset. Hard clips
+    /// (`H`) and padding (`P`) are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{Cigar, Position};
+    ///
+    /// let cigar: Cigar = "1S2M1I1D1M".parse()?;
+    /// let start = Position::try_from(8)?;
+    ///
+    /// let pairs: Vec<_> = cigar
+    ///     .aligned_pairs(start)
+    ///     .map(|(i, pos)| (i, pos.map(i32::from)))
+    ///     .collect();
+    ///
+    /// assert_eq!(pairs, [
+    ///     (Some(0), None),
+    ///     (Some(1), Some(8)),
+    ///     (Some(2), Some(9)),
+    ///     (Some(3), None),
+    ///     (None, Some(10)),
+    ///     (Some(4), Some(11)),
+    /// ]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn aligned_pairs(
+        &self,
+        alignment_start: Position,
+    ) -> AlignedPairs<impl Iterator<Item = (Kind, u32)> + '_> {
+        AlignedPairs::new(self.kinds_and_lens(), alignment_start)
+    }
+
+    /// Returns an iterator over the gapless aligned blocks of an alignment.
+    ///
+    /// Adjacent alignment matches (`M`), sequence matches (`=`), and sequence mismatches (`X`)
+    /// are merged into a single block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{Cigar, Position};
+    ///
+    /// let cigar: Cigar = "2S3=1X2D4M".parse()?;
+    /// let start = Position::try_from(8)?;
+    ///
+    /// let blocks: Vec<_> = cigar
+    ///     .aligned_blocks(start)
+    ///     .map(|block| {
+    ///         (
+    ///             block.read_start()..block.read_end(),
+    ///             i32::from(block.reference_start())..=i32::from(block.reference_end()),
+    ///         )
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(blocks, [(2..6, 8..=11), (6..10, 14..=17)]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn aligned_blocks(
+        &self,
+        alignment_start: Position,
+    ) -> AlignedBlocks<impl Iterator<Item = (Kind, u32)> + '_> {
+        AlignedBlocks::new(self.kinds_and_lens(), alignment_start)
+    }
+
+    /// Returns the reference position aligned to the given 0-based read offset.
+    ///
+    /// This returns `None` if the read base is an insertion (`I`), is soft clipped (`S`), or is
+    /// out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{Cigar, Position};
+    ///
+    /// let cigar: Cigar = "1S2M1I1D1M".parse()?;
+    /// let start = Position::try_from(8)?;
+    ///
+    /// assert_eq!(cigar.reference_position(start, 0), None);
+    /// assert_eq!(cigar.reference_position(start, 2), Some(Position::try_from(9)?));
+    /// assert_eq!(cigar.reference_position(start, 3), None);
+    /// assert_eq!(cigar.reference_position(start, 4), Some(Position::try_from(11)?));
+    /// assert_eq!(cigar.reference_position(start, 5), None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn reference_position(
+        &self,
+        alignment_start: Position,
+        read_offset: usize,
+    ) -> Option<Position> {
+        reference_position(self.kinds_and_lens(), alignment_start, read_offset)
+    }
+
+    /// Returns the 0-based read offset aligned to the given reference position.
+    ///
+    /// This returns `None` if the reference base is deleted (`D`), is skipped (`N`), or is not
+    /// covered by the alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{Cigar, Position};
+    ///
+    /// let cigar: Cigar = "1S2M1I1D1M".parse()?;
+    /// let start = Position::try_from(8)?;
+    ///
+    /// assert_eq!(cigar.read_offset(start, Position::try_from(7)?), None);
+    /// assert_eq!(cigar.read_offset(start, Position::try_from(9)?), Some(2));
+    /// assert_eq!(cigar.read_offset(start, Position::try_from(10)?), None);
+    /// assert_eq!(cigar.read_offset(start, Position::try_from(11)?), Some(4));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn read_offset(
+        &self,
+        alignment_start: Position,
+        reference_position: Position,
+    ) -> Option<usize> {
+        read_offset(self.kinds_and_lens(), alignment_start, reference_position)
+    }
+
+    /// Calculates the unclipped start position of an alignment.
+    ///
+    /// This is the alignment start moved left by the lengths of the leading soft clips (`S`) and
+    /// hard clips (`H`). It can be less than 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{Cigar, Position};
+    ///
+    /// let cigar: Cigar = "2H3S4M1S".parse()?;
+    /// assert_eq!(cigar.unclipped_start(Position::try_from(8)?), 3);
+    /// assert_eq!(cigar.unclipped_start(Position::try_from(2)?), -3);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn unclipped_start(&self, alignment_start: Position) -> i32 {
+        unclipped_start(self.kinds_and_lens(), alignment_start)
+    }
+
+    /// Calculates the unclipped end position of an alignment.
+    ///
+    /// This is the alignment end moved right by the lengths of the trailing soft clips (`S`) and
+    /// hard clips (`H`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{Cigar, Position};
+    ///
+    /// let cigar: Cigar = "2H3S4M1D1M1S2H".parse()?;
+    /// assert_eq!(cigar.unclipped_end(Position::try_from(8)?), 16);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn unclipped_end(&self, alignment_start: Position) -> i32 {
+        unclipped_end(self.kinds_and_lens(), alignment_start)
+    }
+
+    fn kinds_and_lens(&self) -> impl Iterator<Item = (Kind, u32)> + '_ {
+        self.iter().map(|op| (op.kind(), op.len()))
+    }
+}
+
+/// Returns the reference position aligned to the given 0-based read offset of the given CIGAR
+/// operation kinds and lengths.
+///
+/// This is the walk used by [`Cigar::reference_position`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// use noodles_sam::record::{cigar::{self, op::Kind}, Position};
+///
+/// let ops = [(Kind::SoftClip, 1), (Kind::Match, 2)];
+/// let start = Position::try_from(8)?;
+///
+/// assert_eq!(cigar::reference_position(ops.iter().copied(), start, 0), None);
+/// assert_eq!(
+///     cigar::reference_position(ops.iter().copied(), start, 2),
+///     Some(Position::try_from(9)?)
+/// );
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn reference_position<I>(
+    ops: I,
+    alignment_start: Position,
+    read_offset: usize,
+) -> Option<Position>
+where
+    I: IntoIterator<Item = (Kind, u32)>,
+{
+    let mut read_start = 0;
+    let mut reference_start = i32::from(alignment_start);
+
+    for (kind, op_len) in ops {
+        let len = op_len as usize;
+
+        match kind {
+            Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                if read_offset < read_start + len {
+                    let n = reference_start + (read_offset - read_start) as i32;
+                    return Position::try_from(n).ok();
+                }
+
+                read_start += len;
+                reference_start += op_len as i32;
+            }
+            Kind::Insertion | Kind::SoftClip => {
+                if read_offset < read_start + len {
+                    return None;
+                }
+
+                read_start += len;
+            }
+            Kind::Deletion | Kind::Skip => reference_start += op_len as i32,
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    None
+}
+
+/// Returns the 0-based read offset aligned to the given reference position of the given CIGAR
+/// operation kinds and lengths.
+///
+/// This is the walk used by [`Cigar::read_offset`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// use noodles_sam::record::{cigar::{self, op::Kind}, Position};
+///
+/// let ops = [(Kind::SoftClip, 1), (Kind::Match, 2)];
+/// let start = Position::try_from(8)?;
+///
+/// assert_eq!(cigar::read_offset(ops.iter().copied(), start, Position::try_from(9)?), Some(2));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn read_offset<I>(
+    ops: I,
+    alignment_start: Position,
+    reference_position: Position,
+) -> Option<usize>
+where
+    I: IntoIterator<Item = (Kind, u32)>,
+{
+    let reference_position = i32::from(reference_position);
+
+    let mut read_start = 0;
+    let mut reference_start = i32::from(alignment_start);
+
+    if reference_position < reference_start {
+        return None;
+    }
+
+    for (kind, op_len) in ops {
+        let len = op_len as i32;
+
+        match kind {
+            Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                if reference_position < reference_start + len {
+                    return Some(read_start + (reference_position - reference_start) as usize);
+                }
+
+                read_start += op_len as usize;
+                reference_start += len;
+            }
+            Kind::Insertion | Kind::SoftClip => read_start += op_len as usize,
+            Kind::Deletion | Kind::Skip => {
+                if reference_position < reference_start + len {
+                    return None;
+                }
+
+                reference_start += len;
+            }
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    None
+}
+
+/// Calculates the unclipped start position of an alignment with the given CIGAR operation kinds
+/// and lengths.
+///
+/// This is the walk used by [`Cigar::unclipped_start`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// use noodles_sam::record::{cigar::{self, op::Kind}, Position};
+///
+/// let ops = [(Kind::HardClip, 2), (Kind::SoftClip, 3), (Kind::Match, 4)];
+/// assert_eq!(cigar::unclipped_start(ops.iter().copied(), Position::try_from(8)?), 3);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn unclipped_start<I>(ops: I, alignment_start: Position) -> i32
+where
+    I: IntoIterator<Item = (Kind, u32)>,
+{
+    let clip_len: u32 = ops
+        .into_iter()
+        .take_while(|&(kind, _)| is_clip(kind))
+        .map(|(_, len)| len)
+        .sum();
+
+    i32::from(alignment_start) - clip_len as i32
+}
+
+/// Calculates the unclipped end position of an alignment with the given CIGAR operation kinds and
+/// lengths.
+///
+/// This is the walk used by [`Cigar::unclipped_end`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// use noodles_sam::record::{cigar::{self, op::Kind}, Position};
+///
+/// let ops = [(Kind::Match, 4), (Kind::Deletion, 1), (Kind::SoftClip, 2)];
+/// assert_eq!(cigar::unclipped_end(ops.iter().copied(), Position::try_from(8)?), 14);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn unclipped_end<I>(ops: I, alignment_start: Position) -> i32
+where
+    I: IntoIterator<Item = (Kind, u32)>,
+{
+    let mut reference_len = 0;
+    let mut clip_len = 0;
+
+    for (kind, len) in ops {
+        if is_clip(kind) {
+            clip_len += len;
+        } else {
+            clip_len = 0;
+
+            if matches!(
+                kind,
+                Kind::Match | Kind::Deletion | Kind::Skip | Kind::SeqMatch | Kind::SeqMismatch
+            ) {
+                reference_len += len;
+            }
+        }
+    }
+
+    i32::from(alignment_start) + reference_len as i32 - 1 + clip_len as i32
 }
 
 impl Deref for Cigar {
@@ -119,10 +518,102 @@ impl FromStr for Cigar {
     }
 }
 
+fn is_clip(kind: Kind) -> bool {
+    matches!(kind, Kind::SoftClip | Kind::HardClip)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_aligned_pairs() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar: Cigar = "1H1S1=1X1P1N1M1H".parse()?;
+        let start = Position::try_from(5)?;
+
+        let actual: Vec<_> = cigar
+            .aligned_pairs(start)
+            .map(|(i, pos)| (i, pos.map(i32::from)))
+            .collect();
+
+        let expected = [
+            (Some(0), None),
+            (Some(1), Some(5)),
+            (Some(2), Some(6)),
+            (None, Some(7)),
+            (Some(3), Some(8)),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let cigar = Cigar::default();
+        assert_eq!(cigar.aligned_pairs(start).count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_aligned_blocks() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar: Cigar = "1S2M1I3M2N1=1X1D0M1H".parse()?;
+        let start = Position::try_from(5)?;
+
+        let actual: Vec<_> = cigar
+            .aligned_blocks(start)
+            .map(|block| {
+                (
+                    block.read_start(),
+                    block.read_end(),
+                    i32::from(block.reference_start()),
+                    i32::from(block.reference_end()),
+                )
+            })
+            .collect();
+
+        let expected = [(1, 3, 5, 6), (4, 7, 7, 9), (7, 9, 12, 13)];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reference_position_and_read_offset() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar: Cigar = "2S3M2N2M1I2M".parse()?;
+        let start = Position::try_from(10)?;
+
+        for (read_offset, reference_position) in cigar.aligned_pairs(start) {
+            match (read_offset, reference_position) {
+                (Some(i), Some(pos)) => {
+                    assert_eq!(cigar.reference_position(start, i), Some(pos));
+                    assert_eq!(cigar.read_offset(start, pos), Some(i));
+                }
+                (Some(i), None) => assert_eq!(cigar.reference_position(start, i), None),
+                (None, Some(pos)) => assert_eq!(cigar.read_offset(start, pos), None),
+                (None, None) => unreachable!(),
+            }
+        }
+
+        assert_eq!(cigar.reference_position(start, 10), None);
+        assert_eq!(cigar.read_offset(start, Position::try_from(9)?), None);
+        assert_eq!(cigar.read_offset(start, Position::try_from(21)?), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unclipped_start_and_end() -> Result<(), Box<dyn std::error::Error>> {
+        let start = Position::try_from(10)?;
+
+        let cigar: Cigar = "4M".parse()?;
+        assert_eq!(cigar.unclipped_start(start), 10);
+        assert_eq!(cigar.unclipped_end(start), 13);
+
+        let cigar: Cigar = "1H2S4M1I2D3S".parse()?;
+        assert_eq!(cigar.unclipped_start(start), 7);
+        assert_eq!(cigar.unclipped_end(start), 18);
+
+        Ok(())
+    }
+
     #[test]
     fn test_is_empty() {
         let cigar = Cigar::default();
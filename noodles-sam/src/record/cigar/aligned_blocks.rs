@@ -0,0 +1,152 @@
+use std::{convert::TryFrom, iter::Peekable};
+
+use super::op::Kind;
+use crate::record::Position;
+
+/// A gapless aligned block.
+///
+/// An aligned block is a maximal run of alignment matches (`M`), sequence matches (`=`), and
+/// sequence mismatches (`X`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AlignedBlock {
+    read_start: usize,
+    reference_start: Position,
+    len: u32,
+}
+
+impl AlignedBlock {
+    /// Creates an aligned block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{cigar::AlignedBlock, Position};
+    ///
+    /// let block = AlignedBlock::new(2, Position::try_from(8)?, 4);
+    /// assert_eq!(block.read_end(), 6);
+    /// assert_eq!(block.reference_end(), Position::try_from(11)?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(read_start: usize, reference_start: Position, len: u32) -> Self {
+        Self {
+            read_start,
+            reference_start,
+            len,
+        }
+    }
+
+    /// Returns the 0-based offset of the first read base in the block.
+    pub fn read_start(&self) -> usize {
+        self.read_start
+    }
+
+    /// Returns the 0-based offset after the last read base in the block.
+    pub fn read_end(&self) -> usize {
+        self.read_start + self.len as usize
+    }
+
+    /// Returns the position of the first reference base in the block.
+    pub fn reference_start(&self) -> Position {
+        self.reference_start
+    }
+
+    /// Returns the position of the last reference base in the block.
+    pub fn reference_end(&self) -> Position {
+        let end = i32::from(self.reference_start) + self.len as i32 - 1;
+        Position::try_from(end).unwrap_or(self.reference_start)
+    }
+}
+
+/// An iterator over the gapless aligned blocks of an alignment.
+///
+/// This is created by calling [`super::Cigar::aligned_blocks`] or [`AlignedBlocks::new`] with the
+/// kinds and lengths of CIGAR operations.
+pub struct AlignedBlocks<I>
+where
+    I: Iterator<Item = (Kind, u32)>,
+{
+    ops: Peekable<I>,
+    read_offset: usize,
+    reference_position: i32,
+}
+
+impl<I> AlignedBlocks<I>
+where
+    I: Iterator<Item = (Kind, u32)>,
+{
+    /// Creates an iterator over the aligned blocks of CIGAR operation kinds and lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{
+    ///     cigar::{op::Kind, AlignedBlock, AlignedBlocks},
+    ///     Position,
+    /// };
+    ///
+    /// let ops = vec![(Kind::Match, 2), (Kind::Deletion, 1), (Kind::Match, 2)];
+    /// let mut blocks = AlignedBlocks::new(ops.into_iter(), Position::try_from(8)?);
+    ///
+    /// assert_eq!(blocks.next(), Some(AlignedBlock::new(0, Position::try_from(8)?, 2)));
+    /// assert_eq!(blocks.next(), Some(AlignedBlock::new(2, Position::try_from(11)?, 2)));
+    /// assert!(blocks.next().is_none());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(ops: I, alignment_start: Position) -> Self {
+        Self {
+            ops: ops.peekable(),
+            read_offset: 0,
+            reference_position: i32::from(alignment_start),
+        }
+    }
+}
+
+impl<I> Iterator for AlignedBlocks<I>
+where
+    I: Iterator<Item = (Kind, u32)>,
+{
+    type Item = AlignedBlock;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (kind, op_len) = self.ops.next()?;
+
+            match kind {
+                Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                    let read_start = self.read_offset;
+                    let reference_start = self.reference_position;
+                    let mut len = op_len;
+
+                    while let Some(&(kind, op_len)) = self.ops.peek() {
+                        if is_aligned(kind) {
+                            len += op_len;
+                            self.ops.next();
+                        } else {
+                            break;
+                        }
+                    }
+
+                    self.read_offset += len as usize;
+                    self.reference_position += len as i32;
+
+                    if len == 0 {
+                        continue;
+                    }
+
+                    let reference_start = Position::try_from(reference_start).ok()?;
+
+                    return Some(AlignedBlock::new(read_start, reference_start, len));
+                }
+                Kind::Insertion | Kind::SoftClip => self.read_offset += op_len as usize,
+                Kind::Deletion | Kind::Skip => self.reference_position += op_len as i32,
+                Kind::HardClip | Kind::Pad => {}
+            }
+        }
+    }
+}
+
+fn is_aligned(kind: Kind) -> bool {
+    matches!(kind, Kind::Match | Kind::SeqMatch | Kind::SeqMismatch)
+}
@@ -0,0 +1,96 @@
+use std::convert::TryFrom;
+
+use super::op::Kind;
+use crate::record::Position;
+
+/// An iterator over the read offset and reference position pairs of an alignment.
+///
+/// This is created by calling [`super::Cigar::aligned_pairs`] or [`AlignedPairs::new`] with the
+/// kinds and lengths of CIGAR operations.
+pub struct AlignedPairs<I> {
+    ops: I,
+    op: Option<(Kind, u32)>,
+    read_offset: usize,
+    reference_position: i32,
+}
+
+impl<I> AlignedPairs<I>
+where
+    I: Iterator<Item = (Kind, u32)>,
+{
+    /// Creates an iterator over the aligned pairs of CIGAR operation kinds and lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{
+    ///     cigar::{op::Kind, AlignedPairs},
+    ///     Position,
+    /// };
+    ///
+    /// let ops = vec![(Kind::SoftClip, 1), (Kind::Match, 1)];
+    /// let mut pairs = AlignedPairs::new(ops.into_iter(), Position::try_from(8)?);
+    ///
+    /// assert_eq!(pairs.next(), Some((Some(0), None)));
+    /// assert_eq!(pairs.next(), Some((Some(1), Some(Position::try_from(8)?))));
+    /// assert!(pairs.next().is_none());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(ops: I, alignment_start: Position) -> Self {
+        Self {
+            ops,
+            op: None,
+            read_offset: 0,
+            reference_position: i32::from(alignment_start),
+        }
+    }
+}
+
+impl<I> Iterator for AlignedPairs<I>
+where
+    I: Iterator<Item = (Kind, u32)>,
+{
+    type Item = (Option<usize>, Option<Position>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (kind, len) = match self.op {
+                Some((kind, len)) if len > 0 => (kind, len),
+                _ => {
+                    self.op = Some(self.ops.next()?);
+                    continue;
+                }
+            };
+
+            self.op = Some((kind, len - 1));
+
+            match kind {
+                Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                    let pair = (
+                        Some(self.read_offset),
+                        Position::try_from(self.reference_position).ok(),
+                    );
+
+                    self.read_offset += 1;
+                    self.reference_position += 1;
+
+                    return Some(pair);
+                }
+                Kind::Insertion | Kind::SoftClip => {
+                    let pair = (Some(self.read_offset), None);
+                    self.read_offset += 1;
+                    return Some(pair);
+                }
+                Kind::Deletion | Kind::Skip => {
+                    let pair = (None, Position::try_from(self.reference_position).ok());
+                    self.reference_position += 1;
+                    return Some(pair);
+                }
+                Kind::HardClip | Kind::Pad => {
+                    self.op = None;
+                }
+            }
+        }
+    }
+}
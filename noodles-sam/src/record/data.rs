@@ -1,10 +1,16 @@
 //! SAM record data and fields.
 
+mod builder;
 pub mod field;
 
-pub use self::field::Field;
+pub use self::{
+    builder::{Builder, InsertIntArrayError},
+    field::Field,
+};
 
-use std::{error, fmt, ops::Deref, str::FromStr};
+use std::{error, fmt, mem, ops::Deref, str::FromStr};
+
+use self::field::{value::Type, Tag, Value};
 
 const DELIMITER: char = '\t';
 
@@ -14,6 +20,379 @@ const DELIMITER: char = '\t';
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Data(Vec<Field>);
 
+impl Data {
+    /// Returns a builder to create data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let builder = Data::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the field with the given tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::{field::{Tag, Value}, Field}, Data};
+    ///
+    /// let rg = Field::new(Tag::ReadGroup, Value::String(String::from("rg0")));
+    /// let data = Data::from(vec![rg.clone()]);
+    ///
+    /// assert_eq!(data.get(&Tag::ReadGroup), Some(&rg));
+    /// assert!(data.get(&Tag::AlignmentHitCount).is_none());
+    /// ```
+    pub fn get(&self, tag: &Tag) -> Option<&Field> {
+        self.0.iter().find(|field| field.tag() == tag)
+    }
+
+    /// Returns the character value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the tag is declared with another type or the field value is not a
+    /// character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::Tag, Data};
+    /// let data: Data = "TS:A:+".parse()?;
+    /// assert_eq!(data.get_char(&Tag::TranscriptStrand), Ok(Some('+')));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_char(&self, tag: &Tag) -> Result<Option<char>, GetError> {
+        self.get_typed(tag, Type::Char)
+            .map(|value| value.and_then(|v| v.as_char()))
+    }
+
+    /// Returns the integer value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the tag is declared with another type or the field value is not
+    /// an integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{
+    ///     data::{field::{value::Type, Tag}, GetError},
+    ///     Data,
+    /// };
+    ///
+    /// let data: Data = "NM:i:2\tRG:Z:rg0".parse()?;
+    ///
+    /// assert_eq!(data.get_int32(&Tag::EditDistance), Ok(Some(2)));
+    /// assert_eq!(data.get_int32(&Tag::AlignmentHitCount), Ok(None));
+    ///
+    /// assert_eq!(
+    ///     data.get_int32(&Tag::ReadGroup),
+    ///     Err(GetError::UnexpectedType(Tag::ReadGroup, Type::String, Type::Int32))
+    /// );
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_int32(&self, tag: &Tag) -> Result<Option<i32>, GetError> {
+        self.get_typed(tag, Type::Int32)
+            .map(|value| value.and_then(|v| v.as_int32()))
+    }
+
+    /// Returns the floating-point value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the tag is declared with another type or the field value is not a
+    /// floating-point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::Tag, Data};
+    /// let data: Data = "XS:f:0.5".parse()?;
+    /// assert_eq!(data.get_float(&Tag::Other(String::from("XS"))), Ok(Some(0.5)));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_float(&self, tag: &Tag) -> Result<Option<f32>, GetError> {
+        self.get_typed(tag, Type::Float)
+            .map(|value| value.and_then(|v| v.as_float()))
+    }
+
+    /// Returns the string value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the tag is declared with another type or the field value is not a
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::Tag, Data};
+    /// let data: Data = "RG:Z:rg0".parse()?;
+    /// assert_eq!(data.get_str(&Tag::ReadGroup), Ok(Some("rg0")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_str(&self, tag: &Tag) -> Result<Option<&str>, GetError> {
+        self.get_typed(tag, Type::String)
+            .map(|value| value.and_then(|v| v.as_str()))
+    }
+
+    /// Returns the hex string value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the tag is declared with another type or the field value is not a
+    /// hex string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::Tag, Data};
+    /// let data: Data = "ZH:H:CAFE".parse()?;
+    /// assert_eq!(data.get_hex(&Tag::Other(String::from("ZH"))), Ok(Some("CAFE")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_hex(&self, tag: &Tag) -> Result<Option<&str>, GetError> {
+        self.get_typed(tag, Type::Hex)
+            .map(|value| value.and_then(|v| v.as_hex()))
+    }
+
+    /// Returns the array value of the field with the given tag.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the tag is declared with another type or the field value is not an
+    /// array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::{field::{Tag, Value}, Data};
+    /// let data: Data = "ML:B:C,1,2".parse()?;
+    ///
+    /// assert_eq!(
+    ///     data.get_array(&Tag::BaseModificationProbabilities),
+    ///     Ok(Some(&Value::UInt8Array(vec![1, 2])))
+    /// );
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn get_array(&self, tag: &Tag) -> Result<Option<&Value>, GetError> {
+        self.get_typed(tag, Type::Array)
+    }
+
+    /// Returns the read group (`RG`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data: Data = "RG:Z:rg0".parse()?;
+    /// assert_eq!(data.read_group(), Ok(Some("rg0")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn read_group(&self) -> Result<Option<&str>, GetError> {
+        self.get_str(&Tag::ReadGroup)
+    }
+
+    /// Returns the edit distance (`NM`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data: Data = "NM:i:1".parse()?;
+    /// assert_eq!(data.edit_distance(), Ok(Some(1)));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn edit_distance(&self) -> Result<Option<i32>, GetError> {
+        self.get_int32(&Tag::EditDistance)
+    }
+
+    /// Returns the number of reported alignments (`NH`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data: Data = "NH:i:2".parse()?;
+    /// assert_eq!(data.alignment_hit_count(), Ok(Some(2)));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn alignment_hit_count(&self) -> Result<Option<i32>, GetError> {
+        self.get_int32(&Tag::AlignmentHitCount)
+    }
+
+    /// Returns the mismatched positions (`MD`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data: Data = "MD:Z:4A3".parse()?;
+    /// assert_eq!(data.mismatched_positions(), Ok(Some("4A3")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn mismatched_positions(&self) -> Result<Option<&str>, GetError> {
+        self.get_str(&Tag::MismatchedPositions)
+    }
+
+    /// Returns the CIGAR of the mate (`MC`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data: Data = "MC:Z:8M".parse()?;
+    /// assert_eq!(data.mate_cigar(), Ok(Some("8M")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn mate_cigar(&self) -> Result<Option<&str>, GetError> {
+        self.get_str(&Tag::MateCigar)
+    }
+
+    /// Returns the mapping quality of the mate (`MQ`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data: Data = "MQ:i:60".parse()?;
+    /// assert_eq!(data.mate_mapping_quality(), Ok(Some(60)));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn mate_mapping_quality(&self) -> Result<Option<i32>, GetError> {
+        self.get_int32(&Tag::MateMappingQuality)
+    }
+
+    /// Returns the original quality scores (`OQ`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data: Data = "OQ:Z:NDLS".parse()?;
+    /// assert_eq!(data.original_quality_scores(), Ok(Some("NDLS")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn original_quality_scores(&self) -> Result<Option<&str>, GetError> {
+        self.get_str(&Tag::OriginalQualityScores)
+    }
+
+    /// Returns the sample barcode sequence (`BC`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data: Data = "BC:Z:ACGT".parse()?;
+    /// assert_eq!(data.sample_barcode_sequence(), Ok(Some("ACGT")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn sample_barcode_sequence(&self) -> Result<Option<&str>, GetError> {
+        self.get_str(&Tag::SampleBarcodeSequence)
+    }
+
+    /// Returns the cell barcode ID (`CB`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data: Data = "CB:Z:ACGT-1".parse()?;
+    /// assert_eq!(data.cell_barcode_id(), Ok(Some("ACGT-1")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn cell_barcode_id(&self) -> Result<Option<&str>, GetError> {
+        self.get_str(&Tag::CellBarcodeId)
+    }
+
+    /// Returns the UMI sequence (`RX`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data: Data = "RX:Z:ACGT".parse()?;
+    /// assert_eq!(data.umi_sequence(), Ok(Some("ACGT")));
+    /// # Ok::<(), noodles_sam::record::data::ParseError>(())
+    /// ```
+    pub fn umi_sequence(&self) -> Result<Option<&str>, GetError> {
+        self.get_str(&Tag::UmiSequence)
+    }
+
+    fn get_typed(&self, tag: &Tag, ty: Type) -> Result<Option<&Value>, GetError> {
+        if let Some(declared_ty) = tag.ty() {
+            if declared_ty != ty {
+                return Err(GetError::UnexpectedType(tag.clone(), declared_ty, ty));
+            }
+        }
+
+        match self.get(tag).map(|field| field.value()) {
+            Some(value) if value.ty() != ty => {
+                Err(GetError::UnexpectedType(tag.clone(), ty, value.ty()))
+            }
+            value => Ok(value),
+        }
+    }
+
+    /// Inserts a field into the data.
+    ///
+    /// If a field with the same tag exists, it is replaced in place, and the old field is
+    /// returned. Otherwise, the field is appended, and `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::{field::{Tag, Value}, Field}, Data};
+    ///
+    /// let nh = Field::new(Tag::AlignmentHitCount, Value::Int32(1));
+    /// let mut data = Data::from(vec![nh.clone()]);
+    ///
+    /// let rg = Field::new(Tag::ReadGroup, Value::String(String::from("rg0")));
+    /// assert!(data.insert(rg).is_none());
+    ///
+    /// let nh2 = Field::new(Tag::AlignmentHitCount, Value::Int32(2));
+    /// assert_eq!(data.insert(nh2), Some(nh));
+    ///
+    /// assert_eq!(data.to_string(), "NH:i:2\tRG:Z:rg0");
+    /// ```
+    pub fn insert(&mut self, field: Field) -> Option<Field> {
+        match self.0.iter_mut().find(|f| f.tag() == field.tag()) {
+            Some(f) => Some(mem::replace(f, field)),
+            None => {
+                self.0.push(field);
+                None
+            }
+        }
+    }
+
+    /// Removes the field with the given tag from the data.
+    ///
+    /// The removed field is returned, if it was present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::{field::{Tag, Value}, Field}, Data};
+    ///
+    /// let nh = Field::new(Tag::AlignmentHitCount, Value::Int32(1));
+    /// let rg = Field::new(Tag::ReadGroup, Value::String(String::from("rg0")));
+    /// let mut data = Data::from(vec![nh.clone(), rg]);
+    ///
+    /// assert_eq!(data.remove(&Tag::AlignmentHitCount), Some(nh));
+    /// assert!(data.remove(&Tag::AlignmentHitCount).is_none());
+    /// assert_eq!(data.to_string(), "RG:Z:rg0");
+    /// ```
+    pub fn remove(&mut self, tag: &Tag) -> Option<Field> {
+        let i = self.0.iter().position(|f| f.tag() == tag)?;
+        Some(self.0.remove(i))
+    }
+}
+
 impl Deref for Data {
     type Target = [Field];
 
@@ -42,6 +421,29 @@ impl From<Vec<Field>> for Data {
     }
 }
 
+/// An error returned when a typed data field value fails to be read.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GetError {
+    /// The type is unexpected for the tag.
+    ///
+    /// This holds the tag, the expected type, and the actual type.
+    UnexpectedType(Tag, Type, Type),
+}
+
+impl error::Error for GetError {}
+
+impl fmt::Display for GetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedType(tag, expected, actual) => write!(
+                f,
+                "unexpected type for {}: expected {}, got {}",
+                tag, expected, actual
+            ),
+        }
+    }
+}
+
 /// An error returned when raw SAM record data fails to parse.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseError {
@@ -76,8 +478,6 @@ impl FromStr for Data {
 
 #[cfg(test)]
 mod tests {
-    use super::field::{Tag, Value};
-
     use super::*;
 
     #[test]
@@ -104,4 +504,30 @@ mod tests {
 
         assert_eq!("".parse::<Data>(), Ok(Data::default()));
     }
+
+    #[test]
+    fn test_get_typed() -> Result<(), ParseError> {
+        let data: Data = "RG:Z:rg0\tNM:Z:2\tZN:i:8".parse()?;
+
+        assert_eq!(data.read_group(), Ok(Some("rg0")));
+        assert_eq!(data.mate_cigar(), Ok(None));
+
+        assert_eq!(
+            data.edit_distance(),
+            Err(GetError::UnexpectedType(
+                Tag::EditDistance,
+                Type::Int32,
+                Type::String
+            ))
+        );
+
+        let tag = Tag::Other(String::from("ZN"));
+        assert_eq!(data.get_int32(&tag), Ok(Some(8)));
+        assert_eq!(
+            data.get_str(&tag),
+            Err(GetError::UnexpectedType(tag, Type::String, Type::Int32))
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,196 @@
+use std::{convert::TryFrom, error, fmt};
+
+use super::{
+    field::{value::Subtype, Tag, Value},
+    Data, Field,
+};
+
+/// A SAM record data builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    data: Data,
+}
+
+impl Builder {
+    /// Inserts a field with the given tag and value.
+    ///
+    /// If a field with the same tag exists, it is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::{Tag, Value}, Data};
+    ///
+    /// let data = Data::builder()
+    ///     .insert(Tag::ReadGroup, Value::String(String::from("rg0")))
+    ///     .insert(Tag::AlignmentHitCount, Value::Int32(1))
+    ///     .build();
+    ///
+    /// assert_eq!(data.read_group(), Ok(Some("rg0")));
+    /// assert_eq!(data.alignment_hit_count(), Ok(Some(1)));
+    /// ```
+    pub fn insert(mut self, tag: Tag, value: Value) -> Self {
+        self.data.insert(Field::new(tag, value));
+        self
+    }
+
+    /// Inserts an integer array field with the given tag.
+    ///
+    /// If the tag has a declared subtype (e.g., `ML` is `B:C`), the array uses it, and it is an
+    /// error if a value does not fit. Otherwise, the array subtype is the smallest integer subtype
+    /// that holds all the values.
+    ///
+    /// Single integer values do not need this: the BAM writer already encodes a
+    /// [`Value::Int32`] using the smallest integer type that holds it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{data::field::{Tag, Value}, Data};
+    ///
+    /// let tag = Tag::Other(String::from("ZB"));
+    /// let data = Data::builder().insert_int_array(tag.clone(), &[-1, 300])?.build();
+    ///
+    /// assert_eq!(data.get_array(&tag), Ok(Some(&Value::Int16Array(vec![-1, 300]))));
+    /// # Ok::<(), noodles_sam::record::data::InsertIntArrayError>(())
+    /// ```
+    pub fn insert_int_array(self, tag: Tag, values: &[i32]) -> Result<Self, InsertIntArrayError> {
+        let value = if let Some(subtype) = tag.subtype() {
+            build_int_array(subtype, values)
+                .ok_or_else(|| InsertIntArrayError::OutOfRange(tag.clone(), subtype))?
+        } else {
+            [
+                Subtype::UInt8,
+                Subtype::Int8,
+                Subtype::UInt16,
+                Subtype::Int16,
+            ]
+            .iter()
+            .find_map(|&subtype| build_int_array(subtype, values))
+            .unwrap_or_else(|| Value::Int32Array(values.to_vec()))
+        };
+
+        Ok(self.insert(tag, value))
+    }
+
+    /// Builds SAM record data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::Data;
+    /// let data = Data::builder().build();
+    /// assert!(data.is_empty());
+    /// ```
+    pub fn build(self) -> Data {
+        self.data
+    }
+}
+
+/// An error returned when an integer array fails to be inserted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InsertIntArrayError {
+    /// A value is out of range for the subtype declared for the tag.
+    OutOfRange(Tag, Subtype),
+}
+
+impl error::Error for InsertIntArrayError {}
+
+impl fmt::Display for InsertIntArrayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange(tag, subtype) => write!(
+                f,
+                "value out of range for {} array subtype {}",
+                tag,
+                char::from(*subtype)
+            ),
+        }
+    }
+}
+
+fn build_int_array(subtype: Subtype, values: &[i32]) -> Option<Value> {
+    fn convert<T: TryFrom<i32>>(values: &[i32]) -> Option<Vec<T>> {
+        values.iter().map(|&n| T::try_from(n).ok()).collect()
+    }
+
+    match subtype {
+        Subtype::Int8 => convert(values).map(Value::Int8Array),
+        Subtype::UInt8 => convert(values).map(Value::UInt8Array),
+        Subtype::Int16 => convert(values).map(Value::Int16Array),
+        Subtype::UInt16 => convert(values).map(Value::UInt16Array),
+        Subtype::Int32 => Some(Value::Int32Array(values.to_vec())),
+        Subtype::UInt32 => convert(values).map(Value::UInt32Array),
+        Subtype::Float => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_int_array() -> Result<(), InsertIntArrayError> {
+        let tag = Tag::Other(String::from("ZB"));
+
+        let data = Builder::default()
+            .insert_int_array(tag.clone(), &[0, 255])?
+            .build();
+        assert_eq!(
+            data.get_array(&tag),
+            Ok(Some(&Value::UInt8Array(vec![0, 255])))
+        );
+
+        let data = Builder::default()
+            .insert_int_array(tag.clone(), &[-128, 127])?
+            .build();
+        assert_eq!(
+            data.get_array(&tag),
+            Ok(Some(&Value::Int8Array(vec![-128, 127])))
+        );
+
+        let data = Builder::default()
+            .insert_int_array(tag.clone(), &[0, 65535])?
+            .build();
+        assert_eq!(
+            data.get_array(&tag),
+            Ok(Some(&Value::UInt16Array(vec![0, 65535])))
+        );
+
+        let data = Builder::default()
+            .insert_int_array(tag.clone(), &[-1, 65535])?
+            .build();
+        assert_eq!(
+            data.get_array(&tag),
+            Ok(Some(&Value::Int32Array(vec![-1, 65535])))
+        );
+
+        let data = Builder::default()
+            .insert_int_array(Tag::Cigar, &[1])?
+            .build();
+        assert_eq!(
+            data.get_array(&Tag::Cigar),
+            Ok(Some(&Value::UInt32Array(vec![1])))
+        );
+
+        let data = Builder::default()
+            .insert_int_array(Tag::BaseModificationProbabilities, &[0, 255])?
+            .build();
+        assert_eq!(
+            data.get_array(&Tag::BaseModificationProbabilities),
+            Ok(Some(&Value::UInt8Array(vec![0, 255])))
+        );
+
+        assert_eq!(
+            Builder::default()
+                .insert_int_array(Tag::BaseModificationProbabilities, &[-1])
+                .map(|_| ()),
+            Err(InsertIntArrayError::OutOfRange(
+                Tag::BaseModificationProbabilities,
+                Subtype::UInt8
+            ))
+        );
+
+        Ok(())
+    }
+}
@@ -2,6 +2,8 @@
 
 use std::{error, fmt, str::FromStr};
 
+use super::value::{Subtype, Type};
+
 const LEN: usize = 2;
 
 /// A SAM record data field tag.
@@ -76,6 +78,12 @@ pub enum Tag {
     ReservedMF,
     /// (`MI`).
     UmiId,
+    /// (`ML`).
+    BaseModificationProbabilities,
+    /// (`MM`).
+    BaseModifications,
+    /// (`MN`).
+    BaseModificationSequenceLength,
     /// (`MQ`).
     MateMappingQuality,
     /// (`NH`).
@@ -134,6 +142,104 @@ pub enum Tag {
     Other(String),
 }
 
+impl Tag {
+    /// Returns the value type of a standard tag.
+    ///
+    /// This is the type declared by the specification. Reserved and other tags do not have a
+    /// declared type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::{value::Type, Tag};
+    ///
+    /// assert_eq!(Tag::ReadGroup.ty(), Some(Type::String));
+    /// assert_eq!(Tag::EditDistance.ty(), Some(Type::Int32));
+    /// assert_eq!(Tag::Other(String::from("ZN")).ty(), None);
+    /// ```
+    pub fn ty(&self) -> Option<Type> {
+        match self {
+            Self::TranscriptStrand => Some(Type::Char),
+            Self::MinMappingQuality
+            | Self::AlignmentScore
+            | Self::ColorEditDistance
+            | Self::NextHitPosition
+            | Self::SegmentIndex
+            | Self::OneDifferenceHitCount
+            | Self::TwoDifferenceHitCount
+            | Self::HitIndex
+            | Self::TotalHitCount
+            | Self::BaseModificationSequenceLength
+            | Self::MateMappingQuality
+            | Self::AlignmentHitCount
+            | Self::EditDistance
+            | Self::OriginalPosition
+            | Self::TemplateLikelihood
+            | Self::TemplateMappingQuality
+            | Self::SegmentCount
+            | Self::SegmentLikelihood => Some(Type::Int32),
+            Self::SampleBarcodeSequence
+            | Self::BaseAlignmentQualityOffsets
+            | Self::OriginalUmiQualityScores
+            | Self::CellBarcodeId
+            | Self::NextHitReferenceSequenceName
+            | Self::Comment
+            | Self::ColarQualityScores
+            | Self::CellBarcodeSequence
+            | Self::ColorSequence
+            | Self::CompleteReadAnnotations
+            | Self::CellBarcodeQualityScores
+            | Self::NextHitSequence
+            | Self::SegmentSuffix
+            | Self::Library
+            | Self::MateCigar
+            | Self::MismatchedPositions
+            | Self::UmiId
+            | Self::BaseModifications
+            | Self::OriginalAlignment
+            | Self::OriginalCigar
+            | Self::OriginalQualityScores
+            | Self::OriginalUmiBarcodeSequence
+            | Self::Program
+            | Self::PaddedReadAnnotations
+            | Self::PlatformUnit
+            | Self::MateQualityScores
+            | Self::SampleBarcodeQualityScores
+            | Self::UmiQualityScores
+            | Self::MateSequence
+            | Self::ReadGroup
+            | Self::UmiSequence
+            | Self::OtherAlignments
+            | Self::NextHitQualityScores => Some(Type::String),
+            Self::Cigar | Self::AlterantiveSequence | Self::BaseModificationProbabilities => {
+                Some(Type::Array)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the array value subtype of a standard tag.
+    ///
+    /// This is only set for array tags that declare a subtype.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::data::field::{value::Subtype, Tag};
+    ///
+    /// assert_eq!(Tag::BaseModificationProbabilities.subtype(), Some(Subtype::UInt8));
+    /// assert_eq!(Tag::ReadGroup.subtype(), None);
+    /// ```
+    pub fn subtype(&self) -> Option<Subtype> {
+        match self {
+            Self::Cigar => Some(Subtype::UInt32),
+            Self::AlterantiveSequence => Some(Subtype::UInt16),
+            Self::BaseModificationProbabilities => Some(Subtype::UInt8),
+            _ => None,
+        }
+    }
+}
+
 impl AsRef<str> for Tag {
     fn as_ref(&self) -> &str {
         match self {
@@ -170,6 +276,9 @@ impl AsRef<str> for Tag {
             Self::MismatchedPositions => "MD",
             Self::ReservedMF => "MF",
             Self::UmiId => "MI",
+            Self::BaseModificationProbabilities => "ML",
+            Self::BaseModifications => "MM",
+            Self::BaseModificationSequenceLength => "MN",
             Self::MateMappingQuality => "MQ",
             Self::AlignmentHitCount => "NH",
             Self::EditDistance => "NM",
@@ -258,6 +367,9 @@ impl FromStr for Tag {
             "MD" => Ok(Self::MismatchedPositions),
             "MF" => Ok(Self::ReservedMF),
             "MI" => Ok(Self::UmiId),
+            "ML" => Ok(Self::BaseModificationProbabilities),
+            "MM" => Ok(Self::BaseModifications),
+            "MN" => Ok(Self::BaseModificationSequenceLength),
             "MQ" => Ok(Self::MateMappingQuality),
             "NH" => Ok(Self::AlignmentHitCount),
             "NM" => Ok(Self::EditDistance),
@@ -335,6 +447,9 @@ mod tests {
         assert_eq!(Tag::MismatchedPositions.to_string(), "MD");
         assert_eq!(Tag::ReservedMF.to_string(), "MF");
         assert_eq!(Tag::UmiId.to_string(), "MI");
+        assert_eq!(Tag::BaseModificationProbabilities.to_string(), "ML");
+        assert_eq!(Tag::BaseModifications.to_string(), "MM");
+        assert_eq!(Tag::BaseModificationSequenceLength.to_string(), "MN");
         assert_eq!(Tag::MateMappingQuality.to_string(), "MQ");
         assert_eq!(Tag::AlignmentHitCount.to_string(), "NH");
         assert_eq!(Tag::EditDistance.to_string(), "NM");
@@ -400,6 +515,9 @@ mod tests {
         assert_eq!("MD".parse(), Ok(Tag::MismatchedPositions));
         assert_eq!("MF".parse(), Ok(Tag::ReservedMF));
         assert_eq!("MI".parse(), Ok(Tag::UmiId));
+        assert_eq!("ML".parse(), Ok(Tag::BaseModificationProbabilities));
+        assert_eq!("MM".parse(), Ok(Tag::BaseModifications));
+        assert_eq!("MN".parse(), Ok(Tag::BaseModificationSequenceLength));
         assert_eq!("MQ".parse(), Ok(Tag::MateMappingQuality));
         assert_eq!("NH".parse(), Ok(Tag::AlignmentHitCount));
         assert_eq!("NM".parse(), Ok(Tag::EditDistance));
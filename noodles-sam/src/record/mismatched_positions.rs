@@ -0,0 +1,720 @@
+//! SAM record mismatched positions (`MD`) and edit distance (`NM`) calculation.
+
+use std::{convert::TryFrom, error, fmt, ops::Deref, str::FromStr};
+
+use super::{
+    cigar::op::Kind,
+    data::{
+        field::{Tag, Value},
+        Field,
+    },
+    sequence::Base,
+    Cigar, Data, Position, Record, Sequence,
+};
+
+const DELETION_PREFIX: char = '^';
+
+/// An entry in a list of mismatched positions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Entry {
+    /// A number of consecutive bases that match the reference sequence.
+    Match(u32),
+    /// A reference base that differs from the read base.
+    Mismatch(Base),
+    /// Reference bases that are deleted from the read.
+    Deletion(Vec<Base>),
+}
+
+/// SAM record mismatched positions (`MD`).
+///
+/// Consecutive matches are merged, and zero-length matches are not stored. They are implied when
+/// formatting, i.e., a number is always written between mismatches and deletions.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MismatchedPositions(Vec<Entry>);
+
+impl MismatchedPositions {
+    fn push_match(&mut self, len: u32) {
+        if len == 0 {
+            return;
+        }
+
+        if let Some(Entry::Match(n)) = self.0.last_mut() {
+            *n += len;
+        } else {
+            self.0.push(Entry::Match(len));
+        }
+    }
+
+    fn push_mismatch(&mut self, base: Base) {
+        self.0.push(Entry::Mismatch(base));
+    }
+
+    fn push_deletion(&mut self, bases: Vec<Base>) {
+        if !bases.is_empty() {
+            self.0.push(Entry::Deletion(bases));
+        }
+    }
+}
+
+impl Deref for MismatchedPositions {
+    type Target = [Entry];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<Entry>> for MismatchedPositions {
+    fn from(entries: Vec<Entry>) -> Self {
+        let mut mismatched_positions = Self::default();
+
+        for entry in entries {
+            match entry {
+                Entry::Match(len) => mismatched_positions.push_match(len),
+                Entry::Mismatch(base) => mismatched_positions.push_mismatch(base),
+                Entry::Deletion(bases) => mismatched_positions.push_deletion(bases),
+            }
+        }
+
+        mismatched_positions
+    }
+}
+
+impl fmt::Display for MismatchedPositions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut needs_match = true;
+
+        for entry in self.iter() {
+            match entry {
+                Entry::Match(len) => {
+                    write!(f, "{}", len)?;
+                    needs_match = false;
+                    continue;
+                }
+                Entry::Mismatch(base) => {
+                    if needs_match {
+                        f.write_str("0")?;
+                    }
+
+                    write!(f, "{}", base)?;
+                }
+                Entry::Deletion(bases) => {
+                    if needs_match {
+                        f.write_str("0")?;
+                    }
+
+                    write!(f, "{}", DELETION_PREFIX)?;
+
+                    for base in bases {
+                        write!(f, "{}", base)?;
+                    }
+                }
+            }
+
+            needs_match = true;
+        }
+
+        if needs_match {
+            f.write_str("0")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when raw SAM record mismatched positions fail to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input has an invalid match length.
+    InvalidLength,
+    /// The input has an invalid base.
+    InvalidBase(char),
+    /// A deletion is empty.
+    EmptyDeletion,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::InvalidLength => f.write_str("invalid length"),
+            Self::InvalidBase(c) => write!(f, "invalid base: {}", c),
+            Self::EmptyDeletion => f.write_str("empty deletion"),
+        }
+    }
+}
+
+impl FromStr for MismatchedPositions {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut mismatched_positions = Self::default();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c.is_ascii_digit() {
+                let mut len = c.to_digit(10).unwrap_or_default();
+
+                while let Some(d) = chars.peek().and_then(|c| c.to_digit(10)) {
+                    len = len
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(d))
+                        .ok_or(ParseError::InvalidLength)?;
+
+                    chars.next();
+                }
+
+                mismatched_positions.push_match(len);
+            } else if c == DELETION_PREFIX {
+                let mut bases = Vec::new();
+
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_digit() {
+                        break;
+                    }
+
+                    let base = parse_base(d)?;
+                    bases.push(base);
+                    chars.next();
+                }
+
+                if bases.is_empty() {
+                    return Err(ParseError::EmptyDeletion);
+                }
+
+                mismatched_positions.push_deletion(bases);
+            } else {
+                let base = parse_base(c)?;
+                mismatched_positions.push_mismatch(base);
+            }
+        }
+
+        Ok(mismatched_positions)
+    }
+}
+
+fn parse_base(c: char) -> Result<Base, ParseError> {
+    Base::try_from(c.to_ascii_uppercase()).map_err(|_| ParseError::InvalidBase(c))
+}
+
+/// An error returned when the mismatched positions and edit distance of a record fail to
+/// calculate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CalculateError {
+    /// The record is unmapped.
+    Unmapped,
+    /// The record sequence is missing.
+    MissingSequence,
+    /// The sequence length does not match the read length described by the CIGAR.
+    SequenceLengthMismatch(usize, usize),
+    /// The alignment span exceeds the length of the reference sequence.
+    ReferenceSequenceOutOfBounds,
+    /// A reference sequence base is invalid.
+    InvalidReferenceBase(u8),
+}
+
+impl error::Error for CalculateError {}
+
+impl fmt::Display for CalculateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unmapped => f.write_str("record is unmapped"),
+            Self::MissingSequence => f.write_str("missing sequence"),
+            Self::SequenceLengthMismatch(actual, expected) => write!(
+                f,
+                "sequence length mismatch: expected {}, got {}",
+                expected, actual
+            ),
+            Self::ReferenceSequenceOutOfBounds => {
+                f.write_str("alignment is out of bounds of the reference sequence")
+            }
+            Self::InvalidReferenceBase(b) => {
+                write!(f, "invalid reference base: {:?}", char::from(*b))
+            }
+        }
+    }
+}
+
+/// Calculates the mismatched positions (`MD`) and edit distance (`NM`) of a record.
+///
+/// `reference_sequence` is the entire sequence of the reference the record is mapped to, e.g.,
+/// the sequence of a `fasta::Record` or one read from an indexed FASTA. Its bases are compared
+/// case-insensitively.
+///
+/// # Examples
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// use noodles_sam::{self as sam, record::{mismatched_positions, Position}};
+///
+/// let record = sam::Record::builder()
+///     .set_position(Position::try_from(2)?)
+///     .set_cigar("3M1D2M".parse()?)
+///     .set_sequence("ATGTA".parse()?)
+///     .build();
+///
+/// let reference_sequence = b"NACGTTA";
+/// let (mismatched_positions, edit_distance) =
+///     mismatched_positions::calculate(&record, reference_sequence)?;
+///
+/// assert_eq!(mismatched_positions.to_string(), "1C1^T2");
+/// assert_eq!(edit_distance, 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn calculate(
+    record: &Record,
+    reference_sequence: &[u8],
+) -> Result<(MismatchedPositions, u32), CalculateError> {
+    let position = record.position().ok_or(CalculateError::Unmapped)?;
+
+    calculate_alignment(
+        position,
+        record.cigar(),
+        record.sequence(),
+        reference_sequence,
+    )
+}
+
+/// Calculates the mismatched positions (`MD`) and edit distance (`NM`) of an alignment.
+///
+/// This is the same as [`calculate`] but takes the alignment fields individually.
+pub fn calculate_alignment(
+    position: Position,
+    cigar: &Cigar,
+    sequence: &Sequence,
+    reference_sequence: &[u8],
+) -> Result<(MismatchedPositions, u32), CalculateError> {
+    if sequence.is_empty() {
+        return Err(CalculateError::MissingSequence);
+    }
+
+    let read_len = cigar.read_len() as usize;
+
+    if sequence.len() != read_len {
+        return Err(CalculateError::SequenceLengthMismatch(
+            sequence.len(),
+            read_len,
+        ));
+    }
+
+    let mut mismatched_positions = MismatchedPositions::default();
+    let mut edit_distance = 0;
+
+    let mut reference_position = (i32::from(position) - 1) as usize;
+    let mut read_position = 0;
+
+    for op in cigar.iter() {
+        let len = op.len() as usize;
+
+        match op.kind() {
+            Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                let reference_bases =
+                    get_reference_bases(reference_sequence, reference_position, len)?;
+                let read_bases = &sequence[read_position..read_position + len];
+
+                for (&read_base, reference_base) in read_bases.iter().zip(reference_bases) {
+                    if is_match(read_base, reference_base) {
+                        mismatched_positions.push_match(1);
+                    } else {
+                        mismatched_positions.push_mismatch(reference_base);
+                        edit_distance += 1;
+                    }
+                }
+
+                reference_position += len;
+                read_position += len;
+            }
+            Kind::Insertion => {
+                edit_distance += op.len();
+                read_position += len;
+            }
+            Kind::Deletion => {
+                let bases = get_reference_bases(reference_sequence, reference_position, len)?;
+                mismatched_positions.push_deletion(bases);
+                edit_distance += op.len();
+                reference_position += len;
+            }
+            Kind::Skip => {
+                reference_position += len;
+            }
+            Kind::SoftClip => {
+                read_position += len;
+            }
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    Ok((mismatched_positions, edit_distance))
+}
+
+fn get_reference_bases(
+    reference_sequence: &[u8],
+    start: usize,
+    len: usize,
+) -> Result<Vec<Base>, CalculateError> {
+    let end = start + len;
+
+    reference_sequence
+        .get(start..end)
+        .ok_or(CalculateError::ReferenceSequenceOutOfBounds)?
+        .iter()
+        .map(|&b| {
+            Base::try_from(char::from(b.to_ascii_uppercase()))
+                .map_err(|_| CalculateError::InvalidReferenceBase(b))
+        })
+        .collect()
+}
+
+// An `N` never matches, even when it is in both the read and the reference sequence.
+fn is_match(read_base: Base, reference_base: Base) -> bool {
+    read_base == Base::Eq || (read_base == reference_base && read_base != Base::N)
+}
+
+/// A difference between calculated and stored mismatched positions or edit distance.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Discrepancy {
+    /// The mismatched positions (`MD`) field is missing.
+    MissingMismatchedPositions(MismatchedPositions),
+    /// The mismatched positions (`MD`) field differs from the calculated value.
+    MismatchedPositions {
+        /// The stored value.
+        actual: Value,
+        /// The calculated value.
+        expected: MismatchedPositions,
+    },
+    /// The edit distance (`NM`) field is missing.
+    MissingEditDistance(u32),
+    /// The edit distance (`NM`) field differs from the calculated value.
+    EditDistance {
+        /// The stored value.
+        actual: Value,
+        /// The calculated value.
+        expected: u32,
+    },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMismatchedPositions(expected) => {
+                write!(
+                    f,
+                    "missing {} field: expected {}",
+                    Tag::MismatchedPositions,
+                    expected
+                )
+            }
+            Self::MismatchedPositions { actual, expected } => write!(
+                f,
+                "{} field mismatch: expected {}, got {}",
+                Tag::MismatchedPositions,
+                expected,
+                actual
+            ),
+            Self::MissingEditDistance(expected) => {
+                write!(
+                    f,
+                    "missing {} field: expected {}",
+                    Tag::EditDistance,
+                    expected
+                )
+            }
+            Self::EditDistance { actual, expected } => write!(
+                f,
+                "{} field mismatch: expected {}, got {}",
+                Tag::EditDistance,
+                expected,
+                actual
+            ),
+        }
+    }
+}
+
+/// Compares the mismatched positions (`MD`) and edit distance (`NM`) fields in the given data to
+/// calculated values.
+///
+/// An empty list is returned if both fields are present and equal to the calculated values.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::record::{
+///     data::{field::{Tag, Value}, Field},
+///     mismatched_positions::{self, Discrepancy},
+///     Data,
+/// };
+///
+/// let data = Data::from(vec![Field::new(Tag::EditDistance, Value::Int32(1))]);
+/// let expected = "4A1".parse()?;
+///
+/// let discrepancies = mismatched_positions::validate(&data, &expected, 1);
+/// assert_eq!(discrepancies, [Discrepancy::MissingMismatchedPositions(expected)]);
+/// # Ok::<(), mismatched_positions::ParseError>(())
+/// ```
+pub fn validate(
+    data: &Data,
+    mismatched_positions: &MismatchedPositions,
+    edit_distance: u32,
+) -> Vec<Discrepancy> {
+    let mut discrepancies = Vec::new();
+
+    match data
+        .get(&Tag::MismatchedPositions)
+        .map(|field| field.value())
+    {
+        Some(value) => {
+            let is_equal = value
+                .as_str()
+                .and_then(|s| s.parse::<MismatchedPositions>().ok())
+                .map(|actual| &actual == mismatched_positions)
+                .unwrap_or(false);
+
+            if !is_equal {
+                discrepancies.push(Discrepancy::MismatchedPositions {
+                    actual: value.clone(),
+                    expected: mismatched_positions.clone(),
+                });
+            }
+        }
+        None => discrepancies.push(Discrepancy::MissingMismatchedPositions(
+            mismatched_positions.clone(),
+        )),
+    }
+
+    match data.get(&Tag::EditDistance).map(|field| field.value()) {
+        Some(value) => {
+            let is_equal = value
+                .as_int32()
+                .map(|n| i64::from(n) == i64::from(edit_distance))
+                .unwrap_or(false);
+
+            if !is_equal {
+                discrepancies.push(Discrepancy::EditDistance {
+                    actual: value.clone(),
+                    expected: edit_distance,
+                });
+            }
+        }
+        None => discrepancies.push(Discrepancy::MissingEditDistance(edit_distance)),
+    }
+
+    discrepancies
+}
+
+/// Sets the mismatched positions (`MD`) and edit distance (`NM`) fields in the given data.
+///
+/// Existing fields are replaced in place. Missing fields are appended.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::record::{mismatched_positions, Data};
+///
+/// let mut data = Data::default();
+/// mismatched_positions::update(&mut data, "4A1".parse()?, 1);
+/// assert_eq!(data.to_string(), "MD:Z:4A1\tNM:i:1");
+/// # Ok::<(), mismatched_positions::ParseError>(())
+/// ```
+pub fn update(data: &mut Data, mismatched_positions: MismatchedPositions, edit_distance: u32) {
+    data.insert(Field::new(
+        Tag::MismatchedPositions,
+        Value::String(mismatched_positions.to_string()),
+    ));
+
+    data.insert(Field::new(
+        Tag::EditDistance,
+        Value::Int32(edit_distance as i32),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_record(
+        position: i32,
+        cigar: &str,
+        sequence: &str,
+    ) -> Result<Record, Box<dyn std::error::Error>> {
+        Ok(Record::builder()
+            .set_position(Position::try_from(position)?)
+            .set_cigar(cigar.parse()?)
+            .set_sequence(sequence.parse()?)
+            .build())
+    }
+
+    #[test]
+    fn test_fmt() {
+        let mismatched_positions = MismatchedPositions::from(vec![
+            Entry::Match(10),
+            Entry::Mismatch(Base::A),
+            Entry::Deletion(vec![Base::A, Base::C]),
+            Entry::Match(5),
+        ]);
+
+        assert_eq!(mismatched_positions.to_string(), "10A0^AC5");
+
+        let mismatched_positions = MismatchedPositions::from(vec![Entry::Mismatch(Base::G)]);
+        assert_eq!(mismatched_positions.to_string(), "0G0");
+
+        assert_eq!(MismatchedPositions::default().to_string(), "0");
+    }
+
+    #[test]
+    fn test_from_str() {
+        let expected = MismatchedPositions::from(vec![
+            Entry::Match(10),
+            Entry::Mismatch(Base::A),
+            Entry::Deletion(vec![Base::A, Base::C]),
+            Entry::Match(5),
+        ]);
+
+        assert_eq!("10A0^AC5".parse(), Ok(expected.clone()));
+        assert_eq!("10a^ac5".parse(), Ok(expected));
+
+        assert_eq!("0".parse(), Ok(MismatchedPositions::default()));
+
+        assert_eq!("".parse::<MismatchedPositions>(), Err(ParseError::Empty));
+        assert_eq!(
+            "8^3".parse::<MismatchedPositions>(),
+            Err(ParseError::EmptyDeletion)
+        );
+        assert_eq!(
+            "8*3".parse::<MismatchedPositions>(),
+            Err(ParseError::InvalidBase('*'))
+        );
+        assert_eq!(
+            "99999999999".parse::<MismatchedPositions>(),
+            Err(ParseError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_calculate() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = b"ACGTACGTAC";
+
+        let record = build_record(1, "4M", "ACGT")?;
+        let (mismatched_positions, edit_distance) = calculate(&record, reference_sequence)?;
+        assert_eq!(mismatched_positions.to_string(), "4");
+        assert_eq!(edit_distance, 0);
+
+        let record = build_record(3, "2S2M1I3M2D2M", "TTGTATCGCG")?;
+        let (mismatched_positions, edit_distance) = calculate(&record, b"NNGTACGTACGT")?;
+        assert_eq!(mismatched_positions.to_string(), "2A2^TA2");
+        assert_eq!(edit_distance, 4);
+
+        let record = build_record(3, "2M4N2M", "gtac")?;
+        let (mismatched_positions, edit_distance) = calculate(&record, reference_sequence)?;
+        assert_eq!(mismatched_positions.to_string(), "4");
+        assert_eq!(edit_distance, 0);
+
+        let record = build_record(1, "4M", "AC=N")?;
+        let (mismatched_positions, edit_distance) = calculate(&record, b"ACGN")?;
+        assert_eq!(mismatched_positions.to_string(), "3N0");
+        assert_eq!(edit_distance, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_with_invalid_record() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = b"ACGT";
+
+        let record = Record::default();
+        assert_eq!(
+            calculate(&record, reference_sequence),
+            Err(CalculateError::Unmapped)
+        );
+
+        let record = Record::builder()
+            .set_position(Position::try_from(1)?)
+            .set_cigar("4M".parse()?)
+            .build();
+        assert_eq!(
+            calculate(&record, reference_sequence),
+            Err(CalculateError::MissingSequence)
+        );
+
+        let record = build_record(1, "4M", "ACG")?;
+        assert_eq!(
+            calculate(&record, reference_sequence),
+            Err(CalculateError::SequenceLengthMismatch(3, 4))
+        );
+
+        let record = build_record(2, "4M", "CGTA")?;
+        assert_eq!(
+            calculate(&record, reference_sequence),
+            Err(CalculateError::ReferenceSequenceOutOfBounds)
+        );
+
+        let record = build_record(1, "4M", "ACGT")?;
+        assert_eq!(
+            calculate(&record, b"AC-T"),
+            Err(CalculateError::InvalidReferenceBase(b'-'))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), ParseError> {
+        let expected: MismatchedPositions = "4A1".parse()?;
+
+        let data = Data::from(vec![
+            Field::new(Tag::MismatchedPositions, Value::String(String::from("4A1"))),
+            Field::new(Tag::EditDistance, Value::Int32(1)),
+        ]);
+        assert!(validate(&data, &expected, 1).is_empty());
+
+        let data = Data::from(vec![
+            Field::new(Tag::MismatchedPositions, Value::String(String::from("6"))),
+            Field::new(Tag::EditDistance, Value::Int32(0)),
+        ]);
+        assert_eq!(
+            validate(&data, &expected, 1),
+            [
+                Discrepancy::MismatchedPositions {
+                    actual: Value::String(String::from("6")),
+                    expected: expected.clone(),
+                },
+                Discrepancy::EditDistance {
+                    actual: Value::Int32(0),
+                    expected: 1,
+                },
+            ]
+        );
+
+        let data = Data::default();
+        assert_eq!(
+            validate(&data, &expected, 1),
+            [
+                Discrepancy::MissingMismatchedPositions(expected.clone()),
+                Discrepancy::MissingEditDistance(1),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update() -> Result<(), ParseError> {
+        let mut data = Data::from(vec![
+            Field::new(Tag::EditDistance, Value::Int32(0)),
+            Field::new(Tag::ReadGroup, Value::String(String::from("rg0"))),
+        ]);
+
+        update(&mut data, "4A1".parse()?, 1);
+
+        assert_eq!(data.to_string(), "NM:i:1\tRG:Z:rg0\tMD:Z:4A1");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,578 @@
+//! SAM record other alignments (`SA`) and alternative hits (`XA`).
+//!
+//! The other alignments data field (`SA`) lists the other canonical alignments of a chimeric
+//! alignment, e.g., the partners of a supplementary alignment. Each entry is formatted as
+//! `rname,pos,strand,CIGAR,mapQ,NM;`.
+//!
+//! The alternative hits data field (`XA`) is written by some aligners (e.g., BWA) to list
+//! alternative alignments. Each entry is formatted as `rname,[+-]pos,CIGAR,NM;`.
+
+use std::{error, fmt, num, ops::Deref, str::FromStr};
+
+use super::{
+    cigar, position, reference_sequence_name, Cigar, MappingQuality, Position,
+    ReferenceSequenceName,
+};
+
+const ENTRY_DELIMITER: char = ';';
+const FIELD_DELIMITER: char = ',';
+
+/// The strand of an alignment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strand {
+    /// Forward strand (`+`).
+    Forward,
+    /// Reverse strand (`-`).
+    Reverse,
+}
+
+impl AsRef<str> for Strand {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Forward => "+",
+            Self::Reverse => "-",
+        }
+    }
+}
+
+impl fmt::Display for Strand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+/// An other alignment entry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    reference_sequence_name: String,
+    position: Position,
+    strand: Strand,
+    cigar: Cigar,
+    mapping_quality: MappingQuality,
+    edit_distance: u32,
+}
+
+impl Entry {
+    /// Creates an other alignment entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{
+    ///     other_alignments::{Entry, Strand},
+    ///     MappingQuality, Position,
+    /// };
+    ///
+    /// let entry = Entry::new(
+    ///     String::from("sq0"),
+    ///     Position::try_from(8)?,
+    ///     Strand::Forward,
+    ///     "4M".parse()?,
+    ///     MappingQuality::from(60),
+    ///     0,
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(
+        reference_sequence_name: String,
+        position: Position,
+        strand: Strand,
+        cigar: Cigar,
+        mapping_quality: MappingQuality,
+        edit_distance: u32,
+    ) -> Self {
+        Self {
+            reference_sequence_name,
+            position,
+            strand,
+            cigar,
+            mapping_quality,
+            edit_distance,
+        }
+    }
+
+    /// Returns the reference sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::other_alignments::OtherAlignments;
+    /// let other_alignments: OtherAlignments = "sq0,8,-,2S4M,60,1;".parse()?;
+    /// assert_eq!(other_alignments[0].reference_sequence_name(), "sq0");
+    /// # Ok::<(), noodles_sam::record::other_alignments::ParseError>(())
+    /// ```
+    pub fn reference_sequence_name(&self) -> &str {
+        &self.reference_sequence_name
+    }
+
+    /// Returns the start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::convert::TryFrom;
+    /// use noodles_sam::record::{other_alignments::OtherAlignments, Position};
+    /// let other_alignments: OtherAlignments = "sq0,8,-,2S4M,60,1;".parse()?;
+    /// assert_eq!(other_alignments[0].position(), Position::try_from(8)?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the strand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::other_alignments::{OtherAlignments, Strand};
+    /// let other_alignments: OtherAlignments = "sq0,8,-,2S4M,60,1;".parse()?;
+    /// assert_eq!(other_alignments[0].strand(), Strand::Reverse);
+    /// # Ok::<(), noodles_sam::record::other_alignments::ParseError>(())
+    /// ```
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the CIGAR.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::other_alignments::OtherAlignments;
+    /// let other_alignments: OtherAlignments = "sq0,8,-,2S4M,60,1;".parse()?;
+    /// assert_eq!(other_alignments[0].cigar().to_string(), "2S4M");
+    /// # Ok::<(), noodles_sam::record::other_alignments::ParseError>(())
+    /// ```
+    pub fn cigar(&self) -> &Cigar {
+        &self.cigar
+    }
+
+    /// Returns the mapping quality.
+    ///
+    /// This is always missing for alternative hits (`XA`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::{other_alignments::OtherAlignments, MappingQuality};
+    /// let other_alignments: OtherAlignments = "sq0,8,-,2S4M,60,1;".parse()?;
+    /// assert_eq!(other_alignments[0].mapping_quality(), MappingQuality::from(60));
+    /// # Ok::<(), noodles_sam::record::other_alignments::ParseError>(())
+    /// ```
+    pub fn mapping_quality(&self) -> MappingQuality {
+        self.mapping_quality
+    }
+
+    /// Returns the edit distance (`NM`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::other_alignments::OtherAlignments;
+    /// let other_alignments: OtherAlignments = "sq0,8,-,2S4M,60,1;".parse()?;
+    /// assert_eq!(other_alignments[0].edit_distance(), 1);
+    /// # Ok::<(), noodles_sam::record::other_alignments::ParseError>(())
+    /// ```
+    pub fn edit_distance(&self) -> u32 {
+        self.edit_distance
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{},{},{}",
+            self.reference_sequence_name,
+            i32::from(self.position),
+            self.strand,
+            self.cigar,
+            u8::from(self.mapping_quality),
+            self.edit_distance
+        )
+    }
+}
+
+/// An error returned when a raw other alignment entry fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The reference sequence name is missing.
+    MissingReferenceSequenceName,
+    /// The reference sequence name is invalid.
+    InvalidReferenceSequenceName(reference_sequence_name::ParseError),
+    /// The position is missing.
+    MissingPosition,
+    /// The position is invalid.
+    InvalidPosition(position::ParseError),
+    /// The strand is missing.
+    MissingStrand,
+    /// The strand is invalid.
+    InvalidStrand(String),
+    /// The CIGAR is missing.
+    MissingCigar,
+    /// The CIGAR is invalid.
+    InvalidCigar(cigar::ParseError),
+    /// The mapping quality is missing.
+    MissingMappingQuality,
+    /// The mapping quality is invalid.
+    InvalidMappingQuality(num::ParseIntError),
+    /// The edit distance is missing.
+    MissingEditDistance,
+    /// The edit distance is invalid.
+    InvalidEditDistance(num::ParseIntError),
+    /// The entry has unexpected trailing fields.
+    UnexpectedFields,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::MissingReferenceSequenceName => f.write_str("missing reference sequence name"),
+            Self::InvalidReferenceSequenceName(e) => {
+                write!(f, "invalid reference sequence name: {}", e)
+            }
+            Self::MissingPosition => f.write_str("missing position"),
+            Self::InvalidPosition(e) => write!(f, "invalid position: {}", e),
+            Self::MissingStrand => f.write_str("missing strand"),
+            Self::InvalidStrand(s) => write!(f, "invalid strand: {}", s),
+            Self::MissingCigar => f.write_str("missing CIGAR"),
+            Self::InvalidCigar(e) => write!(f, "invalid CIGAR: {}", e),
+            Self::MissingMappingQuality => f.write_str("missing mapping quality"),
+            Self::InvalidMappingQuality(e) => write!(f, "invalid mapping quality: {}", e),
+            Self::MissingEditDistance => f.write_str("missing edit distance"),
+            Self::InvalidEditDistance(e) => write!(f, "invalid edit distance: {}", e),
+            Self::UnexpectedFields => f.write_str("unexpected fields"),
+        }
+    }
+}
+
+/// A list of other alignments.
+///
+/// This is parsed from and formatted as a raw other alignments (`SA`) field value. Each entry is
+/// formatted without the trailing delimiter.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::record::other_alignments::{OtherAlignments, Strand};
+///
+/// let s = "sq0,8,+,4M,60,0;sq1,13,-,2S2M,0,1;";
+/// let other_alignments: OtherAlignments = s.parse()?;
+///
+/// assert_eq!(other_alignments.len(), 2);
+/// assert_eq!(other_alignments[1].reference_sequence_name(), "sq1");
+/// assert_eq!(other_alignments[1].strand(), Strand::Reverse);
+/// assert_eq!(*other_alignments[1].mapping_quality(), Some(0));
+///
+/// assert_eq!(other_alignments.to_string(), s);
+/// assert_eq!(other_alignments[1].to_string(), "sq1,13,-,2S2M,0,1");
+/// # Ok::<(), noodles_sam::record::other_alignments::ParseError>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OtherAlignments(Vec<Entry>);
+
+impl OtherAlignments {
+    /// Parses a raw alternative hits (`XA`) field value.
+    ///
+    /// The mapping quality of each entry is missing, as it is not recorded in this field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::record::other_alignments::{OtherAlignments, Strand};
+    ///
+    /// let alternative_hits = OtherAlignments::parse_alternative_hits("sq0,-8,4M,1;sq1,+13,4M,2;")?;
+    ///
+    /// assert_eq!(alternative_hits.len(), 2);
+    /// assert_eq!(alternative_hits[0].reference_sequence_name(), "sq0");
+    /// assert_eq!(i32::from(alternative_hits[0].position()), 8);
+    /// assert_eq!(alternative_hits[0].strand(), Strand::Reverse);
+    /// assert!(alternative_hits[0].mapping_quality().is_none());
+    /// assert_eq!(alternative_hits[1].edit_distance(), 2);
+    /// # Ok::<(), noodles_sam::record::other_alignments::ParseError>(())
+    /// ```
+    pub fn parse_alternative_hits(s: &str) -> Result<Self, ParseError> {
+        parse_entries(s, parse_alternative_hit)
+    }
+}
+
+impl Deref for OtherAlignments {
+    type Target = [Entry];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<Entry>> for OtherAlignments {
+    fn from(entries: Vec<Entry>) -> Self {
+        Self(entries)
+    }
+}
+
+impl fmt::Display for OtherAlignments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.0 {
+            write!(f, "{}{}", entry, ENTRY_DELIMITER)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for OtherAlignments {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_entries(s, parse_other_alignment)
+    }
+}
+
+fn parse_entries<F>(s: &str, parse_entry: F) -> Result<OtherAlignments, ParseError>
+where
+    F: Fn(&str) -> Result<Entry, ParseError>,
+{
+    if s.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    s.split(ENTRY_DELIMITER)
+        .filter(|t| !t.is_empty())
+        .map(parse_entry)
+        .collect::<Result<_, _>>()
+        .map(OtherAlignments)
+}
+
+fn parse_other_alignment(s: &str) -> Result<Entry, ParseError> {
+    let mut fields = s.split(FIELD_DELIMITER);
+
+    let reference_sequence_name = parse_reference_sequence_name(&mut fields)?;
+
+    let position = fields
+        .next()
+        .ok_or(ParseError::MissingPosition)
+        .and_then(|t| t.parse().map_err(ParseError::InvalidPosition))?;
+
+    let strand = fields
+        .next()
+        .ok_or(ParseError::MissingStrand)
+        .and_then(|t| match t {
+            "+" => Ok(Strand::Forward),
+            "-" => Ok(Strand::Reverse),
+            _ => Err(ParseError::InvalidStrand(t.into())),
+        })?;
+
+    let cigar = parse_cigar(&mut fields)?;
+
+    let mapping_quality = fields
+        .next()
+        .ok_or(ParseError::MissingMappingQuality)
+        .and_then(|t| {
+            t.parse::<u8>()
+                .map(MappingQuality::from)
+                .map_err(ParseError::InvalidMappingQuality)
+        })?;
+
+    let edit_distance = parse_edit_distance(&mut fields)?;
+
+    if fields.next().is_some() {
+        return Err(ParseError::UnexpectedFields);
+    }
+
+    Ok(Entry::new(
+        reference_sequence_name,
+        position,
+        strand,
+        cigar,
+        mapping_quality,
+        edit_distance,
+    ))
+}
+
+fn parse_alternative_hit(s: &str) -> Result<Entry, ParseError> {
+    let mut fields = s.split(FIELD_DELIMITER);
+
+    let reference_sequence_name = parse_reference_sequence_name(&mut fields)?;
+
+    let raw_position = fields.next().ok_or(ParseError::MissingPosition)?;
+
+    let (strand, raw_position) = if let Some(t) = raw_position.strip_prefix('+') {
+        (Strand::Forward, t)
+    } else if let Some(t) = raw_position.strip_prefix('-') {
+        (Strand::Reverse, t)
+    } else {
+        return Err(ParseError::MissingStrand);
+    };
+
+    let position = raw_position.parse().map_err(ParseError::InvalidPosition)?;
+
+    let cigar = parse_cigar(&mut fields)?;
+    let edit_distance = parse_edit_distance(&mut fields)?;
+
+    if fields.next().is_some() {
+        return Err(ParseError::UnexpectedFields);
+    }
+
+    Ok(Entry::new(
+        reference_sequence_name,
+        position,
+        strand,
+        cigar,
+        MappingQuality::default(),
+        edit_distance,
+    ))
+}
+
+fn parse_reference_sequence_name<'a, I>(fields: &mut I) -> Result<String, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    fields
+        .next()
+        .ok_or(ParseError::MissingReferenceSequenceName)
+        .and_then(|t| {
+            t.parse::<ReferenceSequenceName>()
+                .map(|name| name.to_string())
+                .map_err(ParseError::InvalidReferenceSequenceName)
+        })
+}
+
+fn parse_cigar<'a, I>(fields: &mut I) -> Result<Cigar, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    fields
+        .next()
+        .ok_or(ParseError::MissingCigar)
+        .and_then(|t| t.parse().map_err(ParseError::InvalidCigar))
+}
+
+fn parse_edit_distance<'a, I>(fields: &mut I) -> Result<u32, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    fields
+        .next()
+        .ok_or(ParseError::MissingEditDistance)
+        .and_then(|t| t.parse().map_err(ParseError::InvalidEditDistance))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    #[test]
+    fn test_from_str() -> Result<(), Box<dyn std::error::Error>> {
+        let actual: OtherAlignments = "sq0,8,+,4M,60,0;sq1,13,-,2S2M,255,1".parse()?;
+
+        let expected = OtherAlignments::from(vec![
+            Entry::new(
+                String::from("sq0"),
+                Position::try_from(8)?,
+                Strand::Forward,
+                "4M".parse()?,
+                MappingQuality::from(60),
+                0,
+            ),
+            Entry::new(
+                String::from("sq1"),
+                Position::try_from(13)?,
+                Strand::Reverse,
+                "2S2M".parse()?,
+                MappingQuality::default(),
+                1,
+            ),
+        ]);
+
+        assert_eq!(actual, expected);
+
+        assert_eq!("".parse::<OtherAlignments>(), Err(ParseError::Empty));
+        assert_eq!(
+            "sq0".parse::<OtherAlignments>(),
+            Err(ParseError::MissingPosition)
+        );
+        assert!(matches!(
+            "sq0,0,+,4M,60,0;".parse::<OtherAlignments>(),
+            Err(ParseError::InvalidPosition(_))
+        ));
+        assert_eq!(
+            "sq0,8,.,4M,60,0;".parse::<OtherAlignments>(),
+            Err(ParseError::InvalidStrand(String::from(".")))
+        );
+        assert!(matches!(
+            "sq0,8,+,4Z,60,0;".parse::<OtherAlignments>(),
+            Err(ParseError::InvalidCigar(_))
+        ));
+        assert_eq!(
+            "sq0,8,+,4M;".parse::<OtherAlignments>(),
+            Err(ParseError::MissingMappingQuality)
+        );
+        assert!(matches!(
+            "sq0,8,+,4M,256,0;".parse::<OtherAlignments>(),
+            Err(ParseError::InvalidMappingQuality(_))
+        ));
+        assert_eq!(
+            "sq0,8,+,4M,60;".parse::<OtherAlignments>(),
+            Err(ParseError::MissingEditDistance)
+        );
+        assert_eq!(
+            "sq0,8,+,4M,60,0,1;".parse::<OtherAlignments>(),
+            Err(ParseError::UnexpectedFields)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_alternative_hits() -> Result<(), Box<dyn std::error::Error>> {
+        let actual = OtherAlignments::parse_alternative_hits("sq0,+8,4M,0;sq1,-13,2S2M,1;")?;
+
+        let expected = OtherAlignments::from(vec![
+            Entry::new(
+                String::from("sq0"),
+                Position::try_from(8)?,
+                Strand::Forward,
+                "4M".parse()?,
+                MappingQuality::default(),
+                0,
+            ),
+            Entry::new(
+                String::from("sq1"),
+                Position::try_from(13)?,
+                Strand::Reverse,
+                "2S2M".parse()?,
+                MappingQuality::default(),
+                1,
+            ),
+        ]);
+
+        assert_eq!(actual, expected);
+
+        assert_eq!(
+            OtherAlignments::parse_alternative_hits("sq0,8,4M,0;"),
+            Err(ParseError::MissingStrand)
+        );
+        assert_eq!(
+            OtherAlignments::parse_alternative_hits("sq0,+8,4M,0,60;"),
+            Err(ParseError::UnexpectedFields)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let other_alignments = OtherAlignments::parse_alternative_hits("sq0,-8,4M,1;")?;
+        assert_eq!(other_alignments.to_string(), "sq0,8,-,4M,255,1;");
+        Ok(())
+    }
+}
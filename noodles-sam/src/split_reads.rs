@@ -0,0 +1,402 @@
+//! SAM split read grouping.
+//!
+//! A split (or chimeric) read has multiple linear alignments: one primary and one or more
+//! supplementary alignments. Each alignment covers a segment of the read. The partners of each
+//! alignment are also listed in its other alignments data field (`SA`); see
+//! [`crate::record::other_alignments`].
+//!
+//! # Examples
+//!
+//! ```
+//! use noodles_sam as sam;
+//!
+//! let data = b"\
+//! r0\t0\tsq0\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*\tSA:Z:sq1,1,-,4M4S,60,0;
+//! r0\t2064\tsq1\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*\tSA:Z:sq0,1,+,4M4S,60,0;
+//! ";
+//!
+//! let mut reader = sam::Reader::new(&data[..]);
+//! let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+//!
+//! let split_reads = sam::split_reads::group(&records);
+//! assert_eq!(split_reads.len(), 1);
+//!
+//! let segments = split_reads[0].segments();
+//! assert_eq!(segments.len(), 2);
+//! assert_eq!((segments[0].read_start(), segments[0].read_end()), (0, 4));
+//! assert_eq!((segments[1].read_start(), segments[1].read_end()), (4, 8));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use indexmap::IndexMap;
+
+use crate::{
+    record::{cigar::op::Kind, Cigar},
+    Record,
+};
+
+/// An aligned segment of a read.
+#[derive(Clone, Copy, Debug)]
+pub struct Segment<'a> {
+    record: &'a Record,
+    read_start: usize,
+    read_end: usize,
+}
+
+impl<'a> Segment<'a> {
+    fn new(record: &'a Record) -> Self {
+        let (read_start, read_end) =
+            read_interval(record.cigar(), record.flags().is_reverse_complemented());
+
+        Self {
+            record,
+            read_start,
+            read_end,
+        }
+    }
+
+    /// Returns the record of the alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let data = b"\
+    /// r0\t0\tsq0\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// r0\t2064\tsq1\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// ";
+    ///
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+    /// let split_reads = sam::split_reads::group(&records);
+    /// let segments = split_reads[0].segments();
+    /// assert!(segments[1].record().flags().is_supplementary());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn record(&self) -> &'a Record {
+        self.record
+    }
+
+    /// Returns the 0-based offset of the first aligned base.
+    ///
+    /// The offset is in the orientation of the original read, i.e., as sequenced, and includes
+    /// hard clipped bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let data = b"\
+    /// r0\t0\tsq0\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// r0\t2064\tsq1\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// ";
+    ///
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+    /// let split_reads = sam::split_reads::group(&records);
+    /// let segments = split_reads[0].segments();
+    /// assert_eq!(segments[0].read_start(), 0);
+    /// assert_eq!(segments[1].read_start(), 4);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn read_start(&self) -> usize {
+        self.read_start
+    }
+
+    /// Returns the 0-based offset after the last aligned base.
+    ///
+    /// The offset is in the orientation of the original read, i.e., as sequenced, and includes
+    /// hard clipped bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let data = b"\
+    /// r0\t0\tsq0\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// r0\t2064\tsq1\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// ";
+    ///
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+    /// let split_reads = sam::split_reads::group(&records);
+    /// let segments = split_reads[0].segments();
+    /// assert_eq!(segments[0].read_end(), 4);
+    /// assert_eq!(segments[1].read_end(), 8);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn read_end(&self) -> usize {
+        self.read_end
+    }
+}
+
+/// The alignments of a read.
+#[derive(Clone, Debug)]
+pub struct SplitRead<'a> {
+    read_name: &'a str,
+    segments: Vec<Segment<'a>>,
+}
+
+impl<'a> SplitRead<'a> {
+    /// Returns the read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let data = b"\
+    /// r0\t0\tsq0\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// r0\t2064\tsq1\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// ";
+    ///
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+    /// let split_reads = sam::split_reads::group(&records);
+    /// assert_eq!(split_reads[0].read_name(), "r0");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn read_name(&self) -> &'a str {
+        self.read_name
+    }
+
+    /// Returns the aligned segments, ordered by their starts on the original read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let data = b"\
+    /// r0\t0\tsq0\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// r0\t2064\tsq1\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// ";
+    ///
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+    /// let split_reads = sam::split_reads::group(&records);
+    /// assert_eq!(split_reads[0].segments().len(), 2);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn segments(&self) -> &[Segment<'a>] {
+        &self.segments
+    }
+
+    /// Returns the primary alignment record, if present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let data = b"\
+    /// r0\t0\tsq0\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// r0\t2064\tsq1\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// ";
+    ///
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+    /// let split_reads = sam::split_reads::group(&records);
+    /// let primary = split_reads[0].primary().expect("missing primary record");
+    /// assert!(!primary.flags().is_supplementary());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn primary(&self) -> Option<&'a Record> {
+        self.segments
+            .iter()
+            .map(|segment| segment.record())
+            .find(|record| !record.flags().is_supplementary())
+    }
+
+    /// Returns whether the read has more than one aligned segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    ///
+    /// let data = b"\
+    /// r0\t0\tsq0\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// r0\t2064\tsq1\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+    /// ";
+    ///
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+    /// let split_reads = sam::split_reads::group(&records);
+    /// assert!(split_reads[0].is_split());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn is_split(&self) -> bool {
+        self.segments.len() > 1
+    }
+}
+
+/// Groups primary and supplementary alignments by read.
+///
+/// Records are grouped by read name and segment (first or last segment of the template), which
+/// means that each mate of a pair is its own group. Unmapped, secondary, and unnamed records are
+/// ignored.
+///
+/// Groups are returned in the order their first record appears. The segments of each group are
+/// ordered along the original read.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam as sam;
+///
+/// let data = b"\
+/// r0\t0\tsq0\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+/// r0\t2064\tsq1\t1\t60\t4M4S\t*\t0\t0\tACGTACGT\t*
+/// ";
+///
+/// let mut reader = sam::Reader::new(&data[..]);
+/// let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+/// let split_reads = sam::split_reads::group(&records);
+/// assert_eq!(split_reads.len(), 1);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn group(records: &[Record]) -> Vec<SplitRead<'_>> {
+    let mut groups: IndexMap<(&str, bool, bool), Vec<Segment<'_>>> = IndexMap::new();
+
+    for record in records {
+        let flags = record.flags();
+
+        if flags.is_unmapped() || flags.is_secondary() {
+            continue;
+        }
+
+        let read_name = match record.read_name() {
+            Some(read_name) => read_name.as_str(),
+            None => continue,
+        };
+
+        let key = (read_name, flags.is_read_1(), flags.is_read_2());
+        groups.entry(key).or_default().push(Segment::new(record));
+    }
+
+    groups
+        .into_iter()
+        .map(|((read_name, _, _), mut segments)| {
+            segments.sort_by_key(|segment| (segment.read_start, segment.read_end));
+            SplitRead {
+                read_name,
+                segments,
+            }
+        })
+        .collect()
+}
+
+// Returns the aligned interval of the original read, including hard clips.
+fn read_interval(cigar: &Cigar, is_reverse_complemented: bool) -> (usize, usize) {
+    let is_clip = |kind| matches!(kind, Kind::SoftClip | Kind::HardClip);
+
+    let leading_clip_len: u32 = cigar
+        .iter()
+        .take_while(|op| is_clip(op.kind()))
+        .map(|op| op.len())
+        .sum();
+
+    let trailing_clip_len: u32 = cigar
+        .iter()
+        .rev()
+        .take_while(|op| is_clip(op.kind()))
+        .map(|op| op.len())
+        .sum();
+
+    let aligned_len: u32 = cigar
+        .iter()
+        .filter(|op| {
+            matches!(
+                op.kind(),
+                Kind::Match | Kind::Insertion | Kind::SeqMatch | Kind::SeqMismatch
+            )
+        })
+        .map(|op| op.len())
+        .sum();
+
+    let start = if is_reverse_complemented {
+        trailing_clip_len
+    } else {
+        leading_clip_len
+    };
+
+    let start = start as usize;
+    (start, start + aligned_len as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar = "3H2S4M1I2M5S".parse()?;
+        assert_eq!(read_interval(&cigar, false), (5, 12));
+        assert_eq!(read_interval(&cigar, true), (5, 12));
+
+        let cigar = "2H4M6S".parse()?;
+        assert_eq!(read_interval(&cigar, false), (2, 6));
+        assert_eq!(read_interval(&cigar, true), (6, 10));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_group() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::record::{Flags, Position};
+        use std::convert::TryFrom;
+
+        let build =
+            |name: &str, flags: u16, cigar: &str| -> Result<Record, Box<dyn std::error::Error>> {
+                Ok(Record::builder()
+                    .set_read_name(name.parse()?)
+                    .set_flags(Flags::from(flags))
+                    .set_reference_sequence_name("sq0".parse()?)
+                    .set_position(Position::try_from(1)?)
+                    .set_cigar(cigar.parse()?)
+                    .build())
+            };
+
+        let records = [
+            // r0, first segment: primary (8..12) and supplementaries (0..4 reversed, 4..8)
+            build("r0", 0x41, "8S4M")?,
+            build("r0", 0x851, "8H4M")?,
+            build("r0", 0x841, "4H4M4H")?,
+            // r0, last segment
+            build("r0", 0x81, "12M")?,
+            // secondary and unmapped records are ignored
+            build("r1", 0x100, "12M")?,
+            build("r1", 0x04, "12M")?,
+            build("r2", 0x00, "12M")?,
+        ];
+
+        let split_reads = group(&records);
+        assert_eq!(split_reads.len(), 3);
+
+        let split_read = &split_reads[0];
+        assert_eq!(split_read.read_name(), "r0");
+        assert!(split_read.is_split());
+
+        let intervals: Vec<_> = split_read
+            .segments()
+            .iter()
+            .map(|segment| (segment.read_start(), segment.read_end()))
+            .collect();
+        assert_eq!(intervals, [(0, 4), (4, 8), (8, 12)]);
+
+        assert!(split_read
+            .primary()
+            .map(|record| !record.flags().is_supplementary())
+            .unwrap_or(false));
+
+        assert_eq!(split_reads[1].read_name(), "r0");
+        assert!(!split_reads[1].is_split());
+        assert_eq!(split_reads[2].read_name(), "r2");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+//! SAM record subsampling by template.
+//!
+//! Records are kept or dropped by a hash of their read name and a seed. All records of a
+//! template, i.e., mates and secondary and supplementary alignments, share a read name and are
+//! therefore kept or dropped together. The same seed and fraction always select the same
+//! templates.
+//!
+//! # Examples
+//!
+//! ```
+//! use noodles_sam::{self as sam, subsample::Subsampler};
+//!
+//! let subsampler = Subsampler::new(8, 0.5);
+//!
+//! let record = sam::Record::builder().set_read_name("r0".parse()?).build();
+//! let mate = record.clone();
+//! assert_eq!(subsampler.keep(&record), subsampler.keep(&mate));
+//! # Ok::<(), sam::record::read_name::ParseError>(())
+//! ```
+
+use crate::Record;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+// Unnamed records (`*`) are hashed as such.
+const MISSING_READ_NAME: &[u8] = b"*";
+
+/// A template subsampler.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Subsampler {
+    seed: u64,
+    fraction: f64,
+}
+
+impl Subsampler {
+    /// Creates a subsampler that keeps the given fraction of templates.
+    ///
+    /// The fraction is clamped to [0, 1].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::subsample::Subsampler;
+    /// let subsampler = Subsampler::new(8, 0.25);
+    /// assert_eq!(subsampler.fraction(), 0.25);
+    /// ```
+    pub fn new(seed: u64, fraction: f64) -> Self {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+
+        Self { seed, fraction }
+    }
+
+    /// Creates a subsampler that keeps about the given number of records.
+    ///
+    /// `record_count` is the (estimated) number of input records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::subsample::Subsampler;
+    /// let subsampler = Subsampler::with_target_count(8, 100, 400);
+    /// assert_eq!(subsampler.fraction(), 0.25);
+    /// ```
+    pub fn with_target_count(seed: u64, target_count: u64, record_count: u64) -> Self {
+        let fraction = if record_count == 0 {
+            1.0
+        } else {
+            target_count as f64 / record_count as f64
+        };
+
+        Self::new(seed, fraction)
+    }
+
+    /// Creates a subsampler that keeps about enough records for the given mean depth.
+    ///
+    /// The input depth is estimated as `mapped_record_count * mean_read_length /
+    /// reference_length`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::subsample::Subsampler;
+    ///
+    /// // 1000 records * 100 bases / 10000 bases = 10x
+    /// let subsampler = Subsampler::with_target_coverage(8, 5.0, 10000, 1000, 100.0);
+    /// assert_eq!(subsampler.fraction(), 0.5);
+    /// ```
+    pub fn with_target_coverage(
+        seed: u64,
+        target_depth: f64,
+        reference_length: u64,
+        mapped_record_count: u64,
+        mean_read_length: f64,
+    ) -> Self {
+        let mapped_base_count = mapped_record_count as f64 * mean_read_length;
+
+        let fraction = if mapped_base_count > 0.0 {
+            target_depth * reference_length as f64 / mapped_base_count
+        } else {
+            1.0
+        };
+
+        Self::new(seed, fraction)
+    }
+
+    /// Returns the seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::subsample::Subsampler;
+    /// let subsampler = Subsampler::new(8, 0.25);
+    /// assert_eq!(subsampler.seed(), 8);
+    /// ```
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the fraction of templates that are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::subsample::Subsampler;
+    /// let subsampler = Subsampler::new(8, 1.5);
+    /// assert_eq!(subsampler.fraction(), 1.0);
+    /// ```
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// Returns whether records with the given read name are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::subsample::Subsampler;
+    ///
+    /// assert!(Subsampler::new(8, 1.0).keep_read_name(b"r0"));
+    /// assert!(!Subsampler::new(8, 0.0).keep_read_name(b"r0"));
+    /// ```
+    pub fn keep_read_name(&self, read_name: &[u8]) -> bool {
+        if self.fraction >= 1.0 {
+            return true;
+        }
+
+        let n = mix(hash(read_name) ^ self.seed);
+        let x = (n >> 11) as f64 / (1u64 << 53) as f64;
+        x < self.fraction
+    }
+
+    /// Returns whether the given record is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, subsample::Subsampler};
+    /// let subsampler = Subsampler::new(8, 1.0);
+    /// assert!(subsampler.keep(&sam::Record::default()));
+    /// ```
+    pub fn keep(&self, record: &Record) -> bool {
+        let read_name = record
+            .read_name()
+            .map(|name| name.as_bytes())
+            .unwrap_or(MISSING_READ_NAME);
+
+        self.keep_read_name(read_name)
+    }
+}
+
+// 64-bit FNV-1a
+fn hash(buf: &[u8]) -> u64 {
+    buf.iter().fold(FNV_OFFSET_BASIS, |h, &b| {
+        (h ^ u64::from(b)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+// SplitMix64 finalizer
+fn mix(mut n: u64) -> u64 {
+    n = (n ^ (n >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    n = (n ^ (n >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    n ^ (n >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep_read_name() {
+        let names: Vec<_> = (0..10000).map(|i| format!("r{}", i)).collect();
+
+        let subsampler = Subsampler::new(8, 0.25);
+        let kept: Vec<_> = names
+            .iter()
+            .filter(|name| subsampler.keep_read_name(name.as_bytes()))
+            .collect();
+
+        assert!((2250..2750).contains(&kept.len()));
+
+        let kept_again = names
+            .iter()
+            .filter(|name| subsampler.keep_read_name(name.as_bytes()))
+            .count();
+        assert_eq!(kept_again, kept.len());
+
+        let other_subsampler = Subsampler::new(13, 0.25);
+        let overlap = kept
+            .iter()
+            .filter(|name| other_subsampler.keep_read_name(name.as_bytes()))
+            .count();
+        assert!(overlap < kept.len());
+    }
+
+    #[test]
+    fn test_keep_read_name_is_nested() {
+        let small = Subsampler::new(8, 0.1);
+        let large = Subsampler::new(8, 0.5);
+
+        for i in 0..1000 {
+            let name = format!("r{}", i);
+
+            if small.keep_read_name(name.as_bytes()) {
+                assert!(large.keep_read_name(name.as_bytes()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(Subsampler::new(0, -1.0).fraction(), 0.0);
+        assert_eq!(Subsampler::new(0, f64::NAN).fraction(), 0.0);
+    }
+
+    #[test]
+    fn test_with_target_count() {
+        assert_eq!(Subsampler::with_target_count(0, 10, 0).fraction(), 1.0);
+        assert_eq!(Subsampler::with_target_count(0, 10, 5).fraction(), 1.0);
+    }
+}
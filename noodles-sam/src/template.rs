@@ -0,0 +1,763 @@
+//! SAM record grouping by template.
+//!
+//! A template is the set of records that share a read name, i.e., the primary, secondary, and
+//! supplementary alignments of all segments of a sequenced fragment.
+//!
+//! [`Templates`] groups adjacent records, e.g., from queryname-sorted or name-collated input.
+//! [`CoordinateSortedTemplates`] groups records from coordinate-sorted input, buffering the
+//! records of each template until it is complete.
+//!
+//! Records of other formats can be grouped by implementing [`TemplateRecord`].
+//!
+//! # Examples
+//!
+//! ```
+//! use noodles_sam::{self as sam, template::Templates};
+//!
+//! let data = b"\
+//! r0\t67\tsq0\t1\t60\t4M\t=\t9\t12\tACGT\t*
+//! r0\t147\tsq0\t9\t60\t4M\t=\t1\t-12\tACGT\t*
+//! r1\t4\t*\t0\t255\t*\t*\t0\t0\tACGT\t*
+//! ";
+//!
+//! let mut reader = sam::Reader::new(&data[..]);
+//! let templates: Vec<_> = Templates::new(reader.records()).collect::<Result<_, _>>()?;
+//!
+//! assert_eq!(templates.len(), 2);
+//!
+//! let template = &templates[0];
+//! assert_eq!(template.read_name(), Some("r0"));
+//! assert!(template.read_1().is_some());
+//! assert!(template.read_2().is_some());
+//!
+//! assert_eq!(templates[1].read_name(), Some("r1"));
+//! assert!(templates[1].read_1().is_none());
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    io,
+    iter::Peekable,
+};
+
+use crate::{
+    record::{data::field::Tag, other_alignments::OtherAlignments, Flags},
+    Record,
+};
+
+/// A record that can be grouped into a template.
+pub trait TemplateRecord {
+    /// Returns the read name.
+    fn read_name(&self) -> Option<&str>;
+
+    /// Returns the flags.
+    fn flags(&self) -> Flags;
+
+    /// Returns the number of other alignments listed in the other alignments (`SA`) data field.
+    fn other_alignment_count(&self) -> usize;
+}
+
+impl TemplateRecord for Record {
+    fn read_name(&self) -> Option<&str> {
+        self.read_name().map(|read_name| read_name.as_str())
+    }
+
+    fn flags(&self) -> Flags {
+        self.flags()
+    }
+
+    fn other_alignment_count(&self) -> usize {
+        self.data()
+            .get(&Tag::OtherAlignments)
+            .and_then(|field| field.value().as_str())
+            .and_then(|s| s.parse::<OtherAlignments>().ok())
+            .map(|other_alignments| other_alignments.len())
+            .unwrap_or_default()
+    }
+}
+
+/// The records of a template.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Template<R = Record> {
+    records: Vec<R>,
+}
+
+impl<R> Template<R>
+where
+    R: TemplateRecord,
+{
+    /// Creates a template from a list of records.
+    ///
+    /// The records are expected to have the same read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, template::Template};
+    /// let template = Template::new(vec![sam::Record::default()]);
+    /// assert_eq!(template.records().len(), 1);
+    /// ```
+    pub fn new(records: Vec<R>) -> Self {
+        Self { records }
+    }
+
+    /// Returns the read name of the template.
+    ///
+    /// This is the read name of the first record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::template::Template;
+    /// let template: Template = Template::default();
+    /// assert!(template.read_name().is_none());
+    /// ```
+    pub fn read_name(&self) -> Option<&str> {
+        self.records.first().and_then(|record| record.read_name())
+    }
+
+    /// Returns all the records of the template.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::template::Template;
+    /// let template: Template = Template::default();
+    /// assert!(template.records().is_empty());
+    /// ```
+    pub fn records(&self) -> &[R] {
+        &self.records
+    }
+
+    /// Returns the primary alignment of the first segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::Flags, template::Template};
+    ///
+    /// let record = sam::Record::builder()
+    ///     .set_flags(Flags::PAIRED | Flags::READ_1)
+    ///     .build();
+    ///
+    /// let template = Template::new(vec![record.clone()]);
+    ///
+    /// assert_eq!(template.read_1(), Some(&record));
+    /// assert!(template.read_2().is_none());
+    /// ```
+    pub fn read_1(&self) -> Option<&R> {
+        self.primary(Flags::is_read_1)
+    }
+
+    /// Returns the primary alignment of the last segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::Flags, template::Template};
+    ///
+    /// let record = sam::Record::builder()
+    ///     .set_flags(Flags::PAIRED | Flags::READ_2)
+    ///     .build();
+    ///
+    /// let template = Template::new(vec![record.clone()]);
+    ///
+    /// assert!(template.read_1().is_none());
+    /// assert_eq!(template.read_2(), Some(&record));
+    /// ```
+    pub fn read_2(&self) -> Option<&R> {
+        self.primary(Flags::is_read_2)
+    }
+
+    /// Returns the primary alignment of an unpaired read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, template::Template};
+    /// let record = sam::Record::default();
+    /// let template = Template::new(vec![record.clone()]);
+    /// assert_eq!(template.unpaired(), Some(&record));
+    /// ```
+    pub fn unpaired(&self) -> Option<&R> {
+        self.primary(|flags| !flags.is_paired())
+    }
+
+    /// Returns an iterator over the secondary and supplementary alignments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, record::Flags, template::Template};
+    ///
+    /// let template = Template::new(vec![
+    ///     sam::Record::default(),
+    ///     sam::Record::builder().set_flags(Flags::SUPPLEMENTARY).build(),
+    /// ]);
+    ///
+    /// assert_eq!(template.others().count(), 1);
+    /// ```
+    pub fn others(&self) -> impl Iterator<Item = &R> {
+        self.records.iter().filter(|record| !is_primary(*record))
+    }
+
+    /// Returns the records of the template.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::template::Template;
+    /// let template: Template = Template::default();
+    /// assert!(template.into_records().is_empty());
+    /// ```
+    pub fn into_records(self) -> Vec<R> {
+        self.records
+    }
+
+    fn primary<F>(&self, is_segment: F) -> Option<&R>
+    where
+        F: Fn(Flags) -> bool,
+    {
+        self.records
+            .iter()
+            .find(|record| is_primary(*record) && is_segment(record.flags()))
+    }
+
+    // Returns whether the primary alignments of all segments and the supplementary alignments they
+    // list are present.
+    fn is_complete(&self) -> bool {
+        let mut has_unpaired = false;
+        let mut has_read_1 = false;
+        let mut has_read_2 = false;
+        let mut expected_supplementary_count = 0;
+        let mut supplementary_count = 0;
+
+        for record in &self.records {
+            let flags = record.flags();
+
+            if flags.is_supplementary() {
+                supplementary_count += 1;
+                continue;
+            } else if flags.is_secondary() {
+                continue;
+            }
+
+            if !flags.is_paired() {
+                has_unpaired = true;
+            } else if flags.is_read_1() {
+                has_read_1 = true;
+            } else if flags.is_read_2() {
+                has_read_2 = true;
+            }
+
+            expected_supplementary_count += record.other_alignment_count();
+        }
+
+        let has_primaries = has_unpaired || (has_read_1 && has_read_2);
+        has_primaries && supplementary_count >= expected_supplementary_count
+    }
+}
+
+fn is_primary<R>(record: &R) -> bool
+where
+    R: TemplateRecord,
+{
+    let flags = record.flags();
+    !flags.is_secondary() && !flags.is_supplementary()
+}
+
+/// An iterator over templates of adjacent records with the same read name.
+///
+/// This is typically used with queryname-sorted or name-collated input. Records without a read
+/// name are each their own template.
+pub struct Templates<I>
+where
+    I: Iterator,
+{
+    records: Peekable<I>,
+}
+
+impl<I, R> Templates<I>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: TemplateRecord,
+{
+    /// Creates a template iterator over records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, template::Templates};
+    ///
+    /// let data = [];
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let mut templates = Templates::new(reader.records());
+    ///
+    /// assert!(templates.next().is_none());
+    /// ```
+    pub fn new<J>(records: J) -> Self
+    where
+        J: IntoIterator<IntoIter = I>,
+    {
+        Self {
+            records: records.into_iter().peekable(),
+        }
+    }
+}
+
+impl<I, R> Iterator for Templates<I>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: TemplateRecord,
+{
+    type Item = io::Result<Template<R>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = match self.records.next()? {
+            Ok(record) => record,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let read_name = record.read_name().map(String::from);
+        let mut records = vec![record];
+
+        if read_name.is_some() {
+            while let Some(Ok(next_record)) = self.records.peek() {
+                if next_record.read_name() != read_name.as_deref() {
+                    break;
+                }
+
+                if let Some(Ok(next_record)) = self.records.next() {
+                    records.push(next_record);
+                }
+            }
+        }
+
+        Some(Ok(Template::new(records)))
+    }
+}
+
+/// An iterator over templates of coordinate-sorted records.
+///
+/// Records are buffered by read name until their template is complete, i.e., the primary
+/// alignments of all segments have been read, along with the supplementary alignments listed in
+/// their other alignments (`SA`) data fields. Only incomplete templates are held in memory.
+///
+/// By default, the number of buffered templates is not limited. In the worst case, e.g., when
+/// mates are missing or far apart, all records of the input are held in memory. Use
+/// [`Self::with_max_pending_len`] to bound the number of buffered templates. When the limit is
+/// exceeded, the oldest incomplete template is returned as is.
+///
+/// Templates are returned in the order they are completed. Records that arrive after their
+/// template is returned (e.g., secondary alignments downstream of both primary alignments) are
+/// returned right away as their own template. Templates that are still incomplete when the input
+/// ends are returned last, in the order their first record appears. Records without a read name
+/// are each their own template.
+///
+/// To recognize late records, the read names of returned templates are also kept. Secondary
+/// alignments are not listed by the other records of a template, so these names cannot be
+/// evicted when a template completes. By default, the read names of all returned templates are
+/// kept, i.e., memory grows with the number of templates in the input. With
+/// [`Self::with_max_pending_len`], only the most recent `max_pending_len` names are kept, and a
+/// late record of an evicted name starts a new template instead.
+pub struct CoordinateSortedTemplates<I, R = Record>
+where
+    I: Iterator<Item = io::Result<R>>,
+{
+    records: I,
+    pending: HashMap<String, (u64, Template<R>)>,
+    // The read names of pending templates, oldest first, keyed by when they were started. Entries
+    // of templates that are no longer pending are skipped and compacted lazily.
+    pending_order: VecDeque<(u64, String)>,
+    next_pending_id: u64,
+    completed: HashSet<String>,
+    completed_order: VecDeque<String>,
+    ready: VecDeque<Template<R>>,
+    max_pending_len: Option<usize>,
+}
+
+impl<I, R> CoordinateSortedTemplates<I, R>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: TemplateRecord,
+{
+    /// Creates a template iterator over coordinate-sorted records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, template::CoordinateSortedTemplates};
+    ///
+    /// let data = [];
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let mut templates = CoordinateSortedTemplates::new(reader.records());
+    ///
+    /// assert!(templates.next().is_none());
+    /// ```
+    pub fn new<J>(records: J) -> Self
+    where
+        J: IntoIterator<IntoIter = I>,
+    {
+        Self {
+            records: records.into_iter(),
+            pending: HashMap::new(),
+            pending_order: VecDeque::new(),
+            next_pending_id: 0,
+            completed: HashSet::new(),
+            completed_order: VecDeque::new(),
+            ready: VecDeque::new(),
+            max_pending_len: None,
+        }
+    }
+
+    /// Creates a template iterator over coordinate-sorted records that buffers at most
+    /// `max_pending_len` incomplete templates.
+    ///
+    /// When a record starts a new template and the limit is exceeded, the incomplete template
+    /// whose first record was read earliest is returned early. Its remaining primary alignments
+    /// start a new template, and its other remaining records are returned as their own templates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, template::CoordinateSortedTemplates};
+    ///
+    /// let data = [];
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let mut templates = CoordinateSortedTemplates::with_max_pending_len(reader.records(), 1024);
+    ///
+    /// assert!(templates.next().is_none());
+    /// ```
+    pub fn with_max_pending_len<J>(records: J, max_pending_len: usize) -> Self
+    where
+        J: IntoIterator<IntoIter = I>,
+    {
+        Self {
+            max_pending_len: Some(max_pending_len),
+            ..Self::new(records)
+        }
+    }
+
+    /// Returns the number of incomplete templates currently buffered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, template::CoordinateSortedTemplates};
+    ///
+    /// let data = [];
+    /// let mut reader = sam::Reader::new(&data[..]);
+    /// let templates = CoordinateSortedTemplates::new(reader.records());
+    ///
+    /// assert_eq!(templates.pending_len(), 0);
+    /// ```
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    fn push(&mut self, record: R) {
+        let read_name: String = match record.read_name() {
+            Some(read_name) => read_name.into(),
+            None => {
+                self.ready.push_back(Template::new(vec![record]));
+                return;
+            }
+        };
+
+        if !self.pending.contains_key(&read_name) {
+            if !is_primary(&record) && self.completed.contains(&read_name) {
+                self.ready.push_back(Template::new(vec![record]));
+                return;
+            }
+
+            let id = self.next_pending_id;
+            self.next_pending_id += 1;
+            self.pending_order.push_back((id, read_name.clone()));
+            self.pending
+                .insert(read_name.clone(), (id, Template::new(Vec::new())));
+        }
+
+        let is_complete = match self.pending.get_mut(&read_name) {
+            Some((_, template)) => {
+                template.records.push(record);
+                template.is_complete()
+            }
+            None => false,
+        };
+
+        if is_complete {
+            if let Some((_, template)) = self.pending.remove(&read_name) {
+                self.ready.push_back(template);
+                self.complete(read_name);
+            }
+
+            // Keep the stale entries bounded by the number of pending templates.
+            if self.pending_order.len() > 2 * self.pending.len() + 1 {
+                let pending = &self.pending;
+                self.pending_order
+                    .retain(|(id, read_name)| is_pending(pending, *id, read_name));
+            }
+        }
+
+        if let Some(max_pending_len) = self.max_pending_len {
+            while self.pending.len() > max_pending_len {
+                if let Some(template) = self.pop_oldest_pending() {
+                    if let Some(read_name) = template.read_name().map(String::from) {
+                        self.complete(read_name);
+                    }
+
+                    self.ready.push_back(template);
+                }
+            }
+        }
+    }
+
+    fn complete(&mut self, read_name: String) {
+        if let Some(max_pending_len) = self.max_pending_len {
+            if self.completed.contains(&read_name) {
+                return;
+            }
+
+            self.completed_order.push_back(read_name.clone());
+
+            while self.completed_order.len() > max_pending_len {
+                if let Some(name) = self.completed_order.pop_front() {
+                    self.completed.remove(&name);
+                }
+            }
+        }
+
+        self.completed.insert(read_name);
+    }
+
+    fn pop_oldest_pending(&mut self) -> Option<Template<R>> {
+        while let Some((id, read_name)) = self.pending_order.pop_front() {
+            if is_pending(&self.pending, id, &read_name) {
+                return self
+                    .pending
+                    .remove(&read_name)
+                    .map(|(_, template)| template);
+            }
+        }
+
+        None
+    }
+}
+
+fn is_pending<R>(pending: &HashMap<String, (u64, Template<R>)>, id: u64, read_name: &str) -> bool {
+    pending
+        .get(read_name)
+        .map(|(pending_id, _)| *pending_id == id)
+        .unwrap_or(false)
+}
+
+impl<I, R> Iterator for CoordinateSortedTemplates<I, R>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: TemplateRecord,
+{
+    type Item = io::Result<Template<R>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(template) = self.ready.pop_front() {
+                return Some(Ok(template));
+            }
+
+            match self.records.next() {
+                Some(Ok(record)) => self.push(record),
+                Some(Err(e)) => return Some(Err(e)),
+                None => return self.pop_oldest_pending().map(Ok),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_record(read_name: &str, flags: u16) -> Result<Record, Box<dyn std::error::Error>> {
+        Ok(Record::builder()
+            .set_read_name(read_name.parse()?)
+            .set_flags(Flags::from(flags))
+            .build())
+    }
+
+    fn read_names<I>(templates: I) -> io::Result<Vec<(String, usize)>>
+    where
+        I: Iterator<Item = io::Result<Template>>,
+    {
+        templates
+            .map(|result| {
+                result.map(|template| {
+                    let read_name = template.read_name().map(String::from).unwrap_or_default();
+                    (read_name, template.records().len())
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_template() -> Result<(), Box<dyn std::error::Error>> {
+        let template = Template::new(vec![
+            build_record("r0", 0x41)?,
+            build_record("r0", 0x141)?,
+            build_record("r0", 0x81)?,
+            build_record("r0", 0x881)?,
+        ]);
+
+        assert_eq!(template.read_name(), Some("r0"));
+        assert_eq!(template.read_1().map(|r| u16::from(r.flags())), Some(0x41));
+        assert_eq!(template.read_2().map(|r| u16::from(r.flags())), Some(0x81));
+        assert!(template.unpaired().is_none());
+
+        let others: Vec<_> = template.others().map(|r| u16::from(r.flags())).collect();
+        assert_eq!(others, [0x141, 0x881]);
+
+        assert!(template.is_complete());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_complete() -> Result<(), Box<dyn std::error::Error>> {
+        let template = Template::new(vec![build_record("r0", 0x41)?]);
+        assert!(!template.is_complete());
+
+        let template = Template::new(vec![build_record("r0", 0x00)?]);
+        assert!(template.is_complete());
+
+        let record: Record = "r0\t0\t*\t0\t255\t*\t*\t0\t0\t*\t*\tSA:Z:sq0,8,+,4M,60,0;".parse()?;
+        let mut template = Template::new(vec![record]);
+        assert!(!template.is_complete());
+
+        template.records.push(build_record("r0", 0x800)?);
+        assert!(template.is_complete());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_templates() -> Result<(), Box<dyn std::error::Error>> {
+        let records = vec![
+            Ok(build_record("r0", 0x41)?),
+            Ok(build_record("r0", 0x81)?),
+            Ok(build_record("r1", 0x00)?),
+            Ok(Record::default()),
+            Ok(Record::default()),
+            Ok(build_record("r0", 0x100)?),
+        ];
+
+        let actual = read_names(Templates::new(records))?;
+        let expected = [
+            (String::from("r0"), 2),
+            (String::from("r1"), 1),
+            (String::new(), 1),
+            (String::new(), 1),
+            (String::from("r0"), 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_coordinate_sorted_templates() -> Result<(), Box<dyn std::error::Error>> {
+        let records = vec![
+            Ok(build_record("r0", 0x41)?),
+            Ok(build_record("r1", 0x41)?),
+            Ok(build_record("r2", 0x00)?),
+            Ok(build_record("r1", 0x81)?),
+            Ok(build_record("r0", 0x141)?),
+            Ok(build_record("r0", 0x81)?),
+            Ok(build_record("r3", 0x41)?),
+        ];
+
+        let mut templates = CoordinateSortedTemplates::new(records);
+
+        assert_eq!(
+            templates
+                .next()
+                .transpose()?
+                .and_then(|t| t.read_name().map(String::from)),
+            Some(String::from("r2"))
+        );
+        assert_eq!(templates.pending_len(), 2);
+
+        let actual = read_names(templates)?;
+        let expected = [
+            (String::from("r1"), 2),
+            (String::from("r0"), 3),
+            (String::from("r3"), 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_coordinate_sorted_templates_with_late_records() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let records = vec![
+            Ok(build_record("r0", 0x41)?),
+            Ok(build_record("r0", 0x81)?),
+            Ok(build_record("r0", 0x141)?),
+            Ok(build_record("r0", 0x881)?),
+            Ok(build_record("r1", 0x41)?),
+        ];
+
+        let mut templates = CoordinateSortedTemplates::new(records);
+
+        let template = templates.next().transpose()?;
+        assert_eq!(template.map(|t| t.records().len()), Some(2));
+
+        let template = templates.next().transpose()?;
+        assert_eq!(template.map(|t| t.records().len()), Some(1));
+        assert_eq!(templates.pending_len(), 0);
+
+        let actual = read_names(templates)?;
+        let expected = [(String::from("r0"), 1), (String::from("r1"), 1)];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_coordinate_sorted_templates_with_max_pending_len(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let records = vec![
+            Ok(build_record("r0", 0x41)?),
+            Ok(build_record("r1", 0x41)?),
+            Ok(build_record("r2", 0x41)?),
+            Ok(build_record("r2", 0x81)?),
+            Ok(build_record("r0", 0x81)?),
+            Ok(build_record("r1", 0x81)?),
+        ];
+
+        let mut templates = CoordinateSortedTemplates::with_max_pending_len(records, 2);
+
+        assert_eq!(
+            templates
+                .next()
+                .transpose()?
+                .and_then(|t| t.read_name().map(String::from)),
+            Some(String::from("r0"))
+        );
+        assert!(templates.pending_len() <= 2);
+
+        let actual = read_names(templates)?;
+        let expected = [
+            (String::from("r2"), 2),
+            (String::from("r1"), 2),
+            (String::from("r0"), 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,512 @@
+//! Unaligned SAM record creation from FASTQ.
+//!
+//! [`Converter`] converts FASTQ records to unmapped SAM records, e.g., to store raw reads as an
+//! unaligned BAM. Single-end reads are converted with [`Converter::convert`] or [`single`], and
+//! paired-end reads with [`Converter::convert_pair`] or [`paired`].
+//!
+//! The read name is the first word of the FASTQ read name line, with a trailing `/1` or `/2`
+//! removed. If the rest of the line is an Illumina comment (`<read>:<is filtered>:<control
+//! number>:<index>`), the index is added as the sample barcode sequence (`BC`). Dual indices
+//! separated by `+` are joined by `-`.
+//!
+//! # Examples
+//!
+//! ```
+//! use noodles_fastq as fastq;
+//! use noodles_sam::{self as sam, header::ReadGroup, unaligned::Converter};
+//!
+//! let converter = Converter::builder()
+//!     .set_read_group(ReadGroup::new(String::from("rg0")))
+//!     .build();
+//!
+//! let read_1 = fastq::Record::new("r0/1 1:N:0:ACGT", "TTGG", "NDLS");
+//! let read_2 = fastq::Record::new("r0/2 2:N:0:ACGT", "CCAA", "SLDN");
+//!
+//! let (record_1, record_2) = converter.convert_pair(&read_1, &read_2)?;
+//!
+//! assert_eq!(u16::from(record_1.flags()), 0x4d);
+//! assert_eq!(record_1.read_name().map(|name| name.as_str()), Some("r0"));
+//! assert_eq!(record_1.data().to_string(), "BC:Z:ACGT\tRG:Z:rg0");
+//!
+//! assert_eq!(u16::from(record_2.flags()), 0x8d);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::{io, str};
+
+use noodles_fastq as fastq;
+
+use crate::{
+    header::ReadGroup,
+    record::{
+        data::{
+            field::{Tag, Value},
+            Field,
+        },
+        Flags,
+    },
+    Header, Record,
+};
+
+/// An unaligned record converter builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    read_group: Option<ReadGroup>,
+    parse_illumina_comments: Option<bool>,
+}
+
+impl Builder {
+    /// Sets the read group of the records.
+    ///
+    /// Each record is given a read group (`RG`) data field with the read group ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{header::ReadGroup, unaligned::Converter};
+    ///
+    /// let converter = Converter::builder()
+    ///     .set_read_group(ReadGroup::new(String::from("rg0")))
+    ///     .build();
+    ///
+    /// assert_eq!(converter.read_group().map(|rg| rg.id()), Some("rg0"));
+    /// ```
+    pub fn set_read_group(mut self, read_group: ReadGroup) -> Self {
+        self.read_group = Some(read_group);
+        self
+    }
+
+    /// Sets whether Illumina comments are parsed for sample barcodes.
+    ///
+    /// This is enabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::unaligned::Converter;
+    /// let converter = Converter::builder().set_parse_illumina_comments(false).build();
+    /// ```
+    pub fn set_parse_illumina_comments(mut self, parse_illumina_comments: bool) -> Self {
+        self.parse_illumina_comments = Some(parse_illumina_comments);
+        self
+    }
+
+    /// Builds an unaligned record converter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::unaligned::Converter;
+    /// let converter = Converter::builder().build();
+    /// ```
+    pub fn build(self) -> Converter {
+        Converter {
+            read_group: self.read_group,
+            parse_illumina_comments: self.parse_illumina_comments.unwrap_or(true),
+        }
+    }
+}
+
+/// A FASTQ to unaligned SAM record converter.
+#[derive(Clone, Debug)]
+pub struct Converter {
+    read_group: Option<ReadGroup>,
+    parse_illumina_comments: bool,
+}
+
+impl Converter {
+    /// Returns a builder to create a converter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::unaligned::Converter;
+    /// let builder = Converter::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the read group of the records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::unaligned::Converter;
+    /// let converter = Converter::default();
+    /// assert!(converter.read_group().is_none());
+    /// ```
+    pub fn read_group(&self) -> Option<&ReadGroup> {
+        self.read_group.as_ref()
+    }
+
+    /// Builds a SAM header for the records.
+    ///
+    /// The header lists the read group, if set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{header::ReadGroup, unaligned::Converter};
+    ///
+    /// let converter = Converter::builder()
+    ///     .set_read_group(ReadGroup::new(String::from("rg0")))
+    ///     .build();
+    ///
+    /// let header = converter.header();
+    /// assert!(header.read_groups().contains_key("rg0"));
+    /// ```
+    pub fn header(&self) -> Header {
+        let mut builder = Header::builder();
+
+        if let Some(read_group) = self.read_group.clone() {
+            builder = builder.add_read_group(read_group);
+        }
+
+        builder.build()
+    }
+
+    /// Converts a single-end FASTQ record to an unmapped SAM record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq as fastq;
+    /// use noodles_sam::{record::Flags, unaligned::Converter};
+    ///
+    /// let converter = Converter::default();
+    /// let record = converter.convert(&fastq::Record::new("r0", "ACGT", "NDLS"))?;
+    ///
+    /// assert_eq!(record.flags(), Flags::UNMAPPED);
+    /// assert_eq!(record.sequence().to_string(), "ACGT");
+    /// assert_eq!(record.quality_scores().to_string(), "NDLS");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn convert(&self, record: &fastq::Record) -> io::Result<Record> {
+        self.build_record(record, Flags::UNMAPPED)
+    }
+
+    /// Converts a pair of FASTQ records to unmapped SAM records.
+    ///
+    /// The records are flagged as the first and last segments of a pair, with both segments
+    /// unmapped.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the read names of the records differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq as fastq;
+    /// use noodles_sam::unaligned::Converter;
+    ///
+    /// let converter = Converter::default();
+    ///
+    /// let read_1 = fastq::Record::new("r0", "ACGT", "NDLS");
+    /// let read_2 = fastq::Record::new("r1", "ACGT", "NDLS");
+    ///
+    /// assert!(converter.convert_pair(&read_1, &read_2).is_err());
+    /// ```
+    pub fn convert_pair(
+        &self,
+        read_1: &fastq::Record,
+        read_2: &fastq::Record,
+    ) -> io::Result<(Record, Record)> {
+        let base_flags = Flags::PAIRED | Flags::UNMAPPED | Flags::MATE_UNMAPPED;
+
+        let record_1 = self.build_record(read_1, base_flags | Flags::READ_1)?;
+        let record_2 = self.build_record(read_2, base_flags | Flags::READ_2)?;
+
+        if record_1.read_name() != record_2.read_name() {
+            let name = |record: &Record| {
+                record
+                    .read_name()
+                    .map(|name| name.to_string())
+                    .unwrap_or_default()
+            };
+
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "read name mismatch: {} != {}",
+                    name(&record_1),
+                    name(&record_2)
+                ),
+            ));
+        }
+
+        Ok((record_1, record_2))
+    }
+
+    fn build_record(&self, record: &fastq::Record, flags: Flags) -> io::Result<Record> {
+        let read_name_line = str::from_utf8(record.read_name()).map_err(invalid_data)?;
+
+        let (read_name, comment) = split_read_name_line(read_name_line);
+
+        let mut builder = Record::builder()
+            .set_flags(flags)
+            .set_read_name(read_name.parse().map_err(invalid_data)?);
+
+        if !record.sequence().is_empty() {
+            let sequence = str::from_utf8(record.sequence()).map_err(invalid_data)?;
+            builder = builder.set_sequence(sequence.parse().map_err(invalid_data)?);
+        }
+
+        if !record.quality_scores().is_empty() {
+            let quality_scores = str::from_utf8(record.quality_scores()).map_err(invalid_data)?;
+            builder = builder.set_quality_scores(quality_scores.parse().map_err(invalid_data)?);
+        }
+
+        let mut fields = Vec::new();
+
+        if self.parse_illumina_comments {
+            if let Some(barcode) = comment.and_then(parse_illumina_barcode) {
+                fields.push(Field::new(
+                    Tag::SampleBarcodeSequence,
+                    Value::String(barcode),
+                ));
+            }
+        }
+
+        if let Some(read_group) = self.read_group.as_ref() {
+            fields.push(Field::new(
+                Tag::ReadGroup,
+                Value::String(read_group.id().into()),
+            ));
+        }
+
+        if !fields.is_empty() {
+            builder = builder.set_data(fields.into());
+        }
+
+        Ok(builder.build())
+    }
+}
+
+impl Default for Converter {
+    fn default() -> Self {
+        Builder::default().build()
+    }
+}
+
+/// Converts single-end FASTQ records to unmapped SAM records.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq as fastq;
+/// use noodles_sam::unaligned::{self, Converter};
+///
+/// let data = b"@r0\nACGT\n+\nNDLS\n@r1\nTTGG\n+\nSLDN\n";
+/// let mut reader = fastq::Reader::new(&data[..]);
+///
+/// let converter = Converter::default();
+/// let records: Vec<_> = unaligned::single(&converter, reader.records()).collect::<Result<_, _>>()?;
+///
+/// assert_eq!(records.len(), 2);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn single<'a, I>(
+    converter: &'a Converter,
+    records: I,
+) -> impl Iterator<Item = io::Result<Record>> + 'a
+where
+    I: IntoIterator<Item = io::Result<fastq::Record>>,
+    I::IntoIter: 'a,
+{
+    records
+        .into_iter()
+        .map(move |result| result.and_then(|record| converter.convert(&record)))
+}
+
+/// Converts paired-end FASTQ records to unmapped SAM records.
+///
+/// The first and last segments are read in parallel, and the records of each pair are returned
+/// adjacently, i.e., grouped by name. An error is returned if the read names of a pair differ or
+/// the inputs have a different number of records.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq as fastq;
+/// use noodles_sam::unaligned::{self, Converter};
+///
+/// let data_1 = b"@r0/1\nACGT\n+\nNDLS\n";
+/// let mut reader_1 = fastq::Reader::new(&data_1[..]);
+///
+/// let data_2 = b"@r0/2\nTTGG\n+\nSLDN\n";
+/// let mut reader_2 = fastq::Reader::new(&data_2[..]);
+///
+/// let converter = Converter::default();
+/// let records: Vec<_> = unaligned::paired(&converter, reader_1.records(), reader_2.records())
+///     .collect::<Result<_, _>>()?;
+///
+/// assert_eq!(records.len(), 2);
+/// assert!(records[0].flags().is_read_1());
+/// assert!(records[1].flags().is_read_2());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn paired<'a, I, J>(
+    converter: &'a Converter,
+    read_1_records: I,
+    read_2_records: J,
+) -> impl Iterator<Item = io::Result<Record>> + 'a
+where
+    I: IntoIterator<Item = io::Result<fastq::Record>>,
+    I::IntoIter: 'a,
+    J: IntoIterator<Item = io::Result<fastq::Record>>,
+    J::IntoIter: 'a,
+{
+    let mut read_1_records = read_1_records.into_iter();
+    let mut read_2_records = read_2_records.into_iter();
+    let mut pending_read_2 = None;
+
+    std::iter::from_fn(move || {
+        if let Some(record) = pending_read_2.take() {
+            return Some(Ok(record));
+        }
+
+        let result = match (read_1_records.next(), read_2_records.next()) {
+            (Some(read_1), Some(read_2)) => read_1.and_then(|read_1| {
+                read_2.and_then(|read_2| converter.convert_pair(&read_1, &read_2))
+            }),
+            (None, None) => return None,
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "paired FASTQs have a different number of records",
+            )),
+        };
+
+        match result {
+            Ok((record_1, record_2)) => {
+                pending_read_2 = Some(record_2);
+                Some(Ok(record_1))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    })
+}
+
+fn invalid_data<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+fn split_read_name_line(s: &str) -> (&str, Option<&str>) {
+    let mut components = s.splitn(2, |c: char| c.is_ascii_whitespace());
+
+    let name = components.next().unwrap_or_default();
+    let name = name
+        .strip_suffix("/1")
+        .or_else(|| name.strip_suffix("/2"))
+        .unwrap_or(name);
+
+    let comment = components
+        .next()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty());
+
+    (name, comment)
+}
+
+// Parses the index of an Illumina comment, e.g., `1:N:0:ATCACG`.
+fn parse_illumina_barcode(s: &str) -> Option<String> {
+    let mut components = s.split(':');
+
+    let read_number = components.next()?;
+    let is_filtered = components.next()?;
+    let control_number = components.next()?;
+    let index = components.next()?;
+
+    if components.next().is_some()
+        || read_number.parse::<u32>().is_err()
+        || !matches!(is_filtered, "Y" | "N")
+        || control_number.parse::<u32>().is_err()
+        || index.is_empty()
+        || index.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    Some(index.replace('+', "-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_read_name_line() {
+        assert_eq!(split_read_name_line("r0"), ("r0", None));
+        assert_eq!(split_read_name_line("r0/1"), ("r0", None));
+        assert_eq!(
+            split_read_name_line("r0/2 2:N:0:ACGT"),
+            ("r0", Some("2:N:0:ACGT"))
+        );
+        assert_eq!(split_read_name_line("r0/3\tx"), ("r0/3", Some("x")));
+    }
+
+    #[test]
+    fn test_parse_illumina_barcode() {
+        assert_eq!(
+            parse_illumina_barcode("1:N:0:ATCACG"),
+            Some(String::from("ATCACG"))
+        );
+        assert_eq!(
+            parse_illumina_barcode("2:Y:18:ATCACG+GTTACA"),
+            Some(String::from("ATCACG-GTTACA"))
+        );
+
+        // sample number instead of an index
+        assert!(parse_illumina_barcode("1:N:0:2").is_none());
+        assert!(parse_illumina_barcode("1:N:0:").is_none());
+        assert!(parse_illumina_barcode("1:X:0:ACGT").is_none());
+        assert!(parse_illumina_barcode("length=4").is_none());
+    }
+
+    #[test]
+    fn test_convert() -> io::Result<()> {
+        let converter = Converter::builder()
+            .set_read_group(ReadGroup::new(String::from("rg0")))
+            .set_parse_illumina_comments(false)
+            .build();
+
+        let record = converter.convert(&fastq::Record::new("r0 1:N:0:ACGT", "", ""))?;
+        assert_eq!(record.flags(), Flags::UNMAPPED);
+        assert!(record.sequence().is_empty());
+        assert!(record.quality_scores().is_empty());
+        assert_eq!(record.data().to_string(), "RG:Z:rg0");
+
+        assert!(converter
+            .convert(&fastq::Record::new("r0", "AC!T", "NDLS"))
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_paired() -> io::Result<()> {
+        let converter = Converter::default();
+
+        let read_1_records = vec![
+            Ok(fastq::Record::new("r0/1", "ACGT", "NDLS")),
+            Ok(fastq::Record::new("r1/1", "ACGT", "NDLS")),
+        ];
+        let read_2_records = vec![Ok(fastq::Record::new("r0/2", "ACGT", "NDLS"))];
+
+        let mut records = paired(&converter, read_1_records, read_2_records);
+
+        let record = records.next().transpose()?;
+        assert!(record.map(|r| r.flags().is_read_1()).unwrap_or(false));
+
+        let record = records.next().transpose()?;
+        assert!(record.map(|r| r.flags().is_read_2()).unwrap_or(false));
+
+        assert!(records.next().map(|r| r.is_err()).unwrap_or(false));
+
+        Ok(())
+    }
+}
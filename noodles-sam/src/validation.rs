@@ -0,0 +1,811 @@
+//! SAM header and record validation.
+//!
+//! The header and record parsers are permissive and accept data that does not conform to the SAM
+//! specification. A [`Validator`] checks a parsed header and its records for inconsistencies and
+//! reports each as a [`Diagnostic`].
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::io;
+//! use noodles_sam::{self as sam, validation::{Kind, Strictness, Validator}};
+//!
+//! let data = b"@SQ\tSN:sq0\tLN:8
+//! r0\t0\tsq0\t1\t60\t4M\t*\t0\t0\tACG\tNDL
+//! ";
+//!
+//! let mut reader = sam::Reader::new(&data[..]);
+//! let header: sam::Header = reader.read_header()?.parse().unwrap();
+//!
+//! let validator = Validator::new(&header, Strictness::default());
+//! let diagnostics = validator.validate(reader.records())?;
+//!
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].record_index(), Some(0));
+//! assert_eq!(diagnostics[0].kind(), &Kind::CigarReadLengthMismatch(4, 3));
+//! # Ok::<(), io::Error>(())
+//! ```
+
+use std::{fmt, io};
+
+use crate::{
+    record::{
+        data::field::{Tag, Value},
+        Flags,
+    },
+    Header, Record,
+};
+
+/// The severity of a validation diagnostic.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// The data is suspicious but may still be usable.
+    Warning,
+    /// The data violates the SAM specification.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => f.write_str("warning"),
+            Self::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// The strictness of a validator.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Strictness {
+    /// All diagnostics are reported as errors.
+    Strict,
+    /// Diagnostics are reported with their default severity.
+    #[default]
+    Lenient,
+    /// Only diagnostics with an error severity are reported.
+    Silent,
+}
+
+/// A validation diagnostic kind.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Kind {
+    /// A reference sequence length (`@SQ LN`) is not positive.
+    InvalidReferenceSequenceLength(String, i32),
+    /// A program previous ID (`@PG PP`) does not refer to a program in the header.
+    InvalidProgramPreviousId(String, String),
+    /// The read length described by the CIGAR does not match the sequence length.
+    ///
+    /// The values are the CIGAR read length and the sequence length, respectively.
+    CigarReadLengthMismatch(u32, usize),
+    /// The number of quality scores does not match the sequence length.
+    ///
+    /// The values are the number of quality scores and the sequence length, respectively.
+    QualityScoresLengthMismatch(usize, usize),
+    /// A mapped record is missing a reference sequence name.
+    MissingReferenceSequenceName,
+    /// A mapped record is missing a position.
+    MissingPosition,
+    /// A mapped record has no CIGAR operations.
+    MissingCigar,
+    /// The reference sequence name is not in the header reference sequence dictionary.
+    InvalidReferenceSequenceName(String),
+    /// The position is greater than the reference sequence length.
+    PositionOutOfBounds(i32, i32),
+    /// The alignment end is greater than the reference sequence length.
+    AlignmentEndOutOfBounds(i32, i32),
+    /// An unmapped record has a mapping quality other than 0 or missing (255).
+    UnmappedRecordWithMappingQuality(u8),
+    /// The mate reference sequence name is not in the header reference sequence dictionary.
+    InvalidMateReferenceSequenceName(String),
+    /// The mate position is greater than the mate reference sequence length.
+    MatePositionOutOfBounds(i32, i32),
+    /// An unpaired record has flags that are only valid for paired records.
+    InvalidFlagsForUnpairedRecord(Flags),
+    /// An unpaired record has mate fields.
+    MateFieldsForUnpairedRecord,
+    /// A paired record with a mapped mate is missing the mate reference sequence name or
+    /// position.
+    MissingMateFields,
+    /// A paired record is neither or both the first and last segment.
+    InvalidSegmentFlags(Flags),
+    /// The read group (`RG`) data field does not refer to a read group in the header.
+    InvalidReadGroup(String),
+    /// The read group (`RG`) data field is not a string.
+    InvalidReadGroupType(Value),
+    /// The record could not be decoded, e.g., when converting a BAM record.
+    ///
+    /// The value is the error message.
+    InvalidRecord(String),
+}
+
+impl Kind {
+    /// Returns the default severity of this kind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::validation::{Kind, Severity};
+    /// assert_eq!(Kind::MissingPosition.severity(), Severity::Error);
+    /// assert_eq!(Kind::MateFieldsForUnpairedRecord.severity(), Severity::Warning);
+    /// ```
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::InvalidProgramPreviousId(..)
+            | Self::MissingCigar
+            | Self::AlignmentEndOutOfBounds(..)
+            | Self::UnmappedRecordWithMappingQuality(_)
+            | Self::MateFieldsForUnpairedRecord
+            | Self::InvalidSegmentFlags(_) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidReferenceSequenceLength(name, len) => {
+                write!(f, "invalid reference sequence length for {}: {}", name, len)
+            }
+            Self::InvalidProgramPreviousId(id, previous_id) => write!(
+                f,
+                "program {} has an invalid previous program ID: {}",
+                id, previous_id
+            ),
+            Self::CigarReadLengthMismatch(cigar_len, sequence_len) => write!(
+                f,
+                "CIGAR read length ({}) does not match sequence length ({})",
+                cigar_len, sequence_len
+            ),
+            Self::QualityScoresLengthMismatch(quality_scores_len, sequence_len) => write!(
+                f,
+                "quality scores length ({}) does not match sequence length ({})",
+                quality_scores_len, sequence_len
+            ),
+            Self::MissingReferenceSequenceName => {
+                f.write_str("mapped record is missing a reference sequence name")
+            }
+            Self::MissingPosition => f.write_str("mapped record is missing a position"),
+            Self::MissingCigar => f.write_str("mapped record is missing a CIGAR"),
+            Self::InvalidReferenceSequenceName(name) => {
+                write!(f, "reference sequence {} is not in the header", name)
+            }
+            Self::PositionOutOfBounds(position, len) => write!(
+                f,
+                "position ({}) is greater than the reference sequence length ({})",
+                position, len
+            ),
+            Self::AlignmentEndOutOfBounds(end, len) => write!(
+                f,
+                "alignment end ({}) is greater than the reference sequence length ({})",
+                end, len
+            ),
+            Self::UnmappedRecordWithMappingQuality(mapq) => {
+                write!(f, "unmapped record has a mapping quality of {}", mapq)
+            }
+            Self::InvalidMateReferenceSequenceName(name) => {
+                write!(f, "mate reference sequence {} is not in the header", name)
+            }
+            Self::MatePositionOutOfBounds(position, len) => write!(
+                f,
+                "mate position ({}) is greater than the mate reference sequence length ({})",
+                position, len
+            ),
+            Self::InvalidFlagsForUnpairedRecord(flags) => {
+                write!(f, "unpaired record has paired flags: {:#06x}", flags.bits())
+            }
+            Self::MateFieldsForUnpairedRecord => f.write_str("unpaired record has mate fields"),
+            Self::MissingMateFields => f.write_str("paired record is missing mate fields"),
+            Self::InvalidSegmentFlags(flags) => write!(
+                f,
+                "paired record has invalid segment flags: {:#06x}",
+                flags.bits()
+            ),
+            Self::InvalidReadGroup(id) => write!(f, "read group {} is not in the header", id),
+            Self::InvalidReadGroupType(value) => {
+                write!(f, "invalid read group value type: {}", value.ty())
+            }
+            Self::InvalidRecord(message) => write!(f, "invalid record: {}", message),
+        }
+    }
+}
+
+/// A validation diagnostic.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    severity: Severity,
+    record_index: Option<usize>,
+    kind: Kind,
+}
+
+impl Diagnostic {
+    /// Creates a validation diagnostic.
+    ///
+    /// `record_index` is the 0-based index of the record the diagnostic refers to. It is `None`
+    /// for diagnostics about the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::validation::{Diagnostic, Kind, Severity};
+    /// let diagnostic = Diagnostic::new(Severity::Error, Some(0), Kind::MissingPosition);
+    /// ```
+    pub fn new(severity: Severity, record_index: Option<usize>, kind: Kind) -> Self {
+        Self {
+            severity,
+            record_index,
+            kind,
+        }
+    }
+
+    /// Returns the severity of the diagnostic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::validation::{Diagnostic, Kind, Severity};
+    /// let diagnostic = Diagnostic::new(Severity::Error, Some(0), Kind::MissingPosition);
+    /// assert_eq!(diagnostic.severity(), Severity::Error);
+    /// ```
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns the index of the record the diagnostic refers to.
+    ///
+    /// This is `None` if the diagnostic refers to the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::validation::{Diagnostic, Kind, Severity};
+    /// let diagnostic = Diagnostic::new(Severity::Error, Some(0), Kind::MissingPosition);
+    /// assert_eq!(diagnostic.record_index(), Some(0));
+    /// ```
+    pub fn record_index(&self) -> Option<usize> {
+        self.record_index
+    }
+
+    /// Returns the kind of the diagnostic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::validation::{Diagnostic, Kind, Severity};
+    /// let diagnostic = Diagnostic::new(Severity::Error, Some(0), Kind::MissingPosition);
+    /// assert_eq!(diagnostic.kind(), &Kind::MissingPosition);
+    /// ```
+    pub fn kind(&self) -> &Kind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.record_index {
+            Some(i) => write!(f, "{}: record {}: {}", self.severity, i, self.kind),
+            None => write!(f, "{}: header: {}", self.severity, self.kind),
+        }
+    }
+}
+
+/// A SAM header and record validator.
+#[derive(Debug)]
+pub struct Validator<'a> {
+    header: &'a Header,
+    strictness: Strictness,
+}
+
+impl<'a> Validator<'a> {
+    /// Creates a validator for records described by the given header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, validation::{Strictness, Validator}};
+    /// let header = sam::Header::default();
+    /// let validator = Validator::new(&header, Strictness::Strict);
+    /// ```
+    pub fn new(header: &'a Header, strictness: Strictness) -> Self {
+        Self { header, strictness }
+    }
+
+    /// Returns the header used for validation.
+    pub fn header(&self) -> &Header {
+        self.header
+    }
+
+    /// Returns the strictness of the validator.
+    pub fn strictness(&self) -> Strictness {
+        self.strictness
+    }
+
+    /// Validates the header and all the records from the given iterator.
+    ///
+    /// Records are indexed by their position in the iterator. This stops at and returns the first
+    /// I/O error. Both `sam::Reader::records` and, after conversion, BAM records are supported.
+    pub fn validate<I>(&self, records: I) -> io::Result<Vec<Diagnostic>>
+    where
+        I: IntoIterator<Item = io::Result<Record>>,
+    {
+        let mut diagnostics = self.validate_header();
+
+        for (i, result) in records.into_iter().enumerate() {
+            let record = result?;
+            diagnostics.extend(self.validate_record(i, &record));
+        }
+
+        Ok(diagnostics)
+    }
+
+    /// Validates the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, header::ReferenceSequence, validation::{Strictness, Validator}};
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 0))
+    ///     .build();
+    ///
+    /// let validator = Validator::new(&header, Strictness::default());
+    /// assert_eq!(validator.validate_header().len(), 1);
+    /// ```
+    pub fn validate_header(&self) -> Vec<Diagnostic> {
+        let mut kinds = Vec::new();
+
+        for reference_sequence in self.header.reference_sequences().values() {
+            if reference_sequence.len() <= 0 {
+                kinds.push(Kind::InvalidReferenceSequenceLength(
+                    reference_sequence.name().into(),
+                    reference_sequence.len(),
+                ));
+            }
+        }
+
+        let programs = self.header.programs();
+
+        for program in programs.values() {
+            if let Some(previous_id) = program.previous_id() {
+                if !programs.contains_key(previous_id) {
+                    kinds.push(Kind::InvalidProgramPreviousId(
+                        program.id().into(),
+                        previous_id.into(),
+                    ));
+                }
+            }
+        }
+
+        self.build_diagnostics(None, kinds)
+    }
+
+    /// Validates a single record.
+    ///
+    /// `i` is the 0-based index of the record, which is attached to each returned diagnostic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, validation::{Kind, Strictness, Validator}};
+    ///
+    /// let header = sam::Header::default();
+    /// let validator = Validator::new(&header, Strictness::default());
+    ///
+    /// let record = sam::Record::builder().set_flags(sam::record::Flags::empty()).build();
+    /// let diagnostics = validator.validate_record(0, &record);
+    ///
+    /// assert_eq!(diagnostics.len(), 3);
+    /// assert_eq!(diagnostics[0].kind(), &Kind::MissingReferenceSequenceName);
+    /// ```
+    pub fn validate_record(&self, i: usize, record: &Record) -> Vec<Diagnostic> {
+        let mut kinds = Vec::new();
+
+        validate_lengths(record, &mut kinds);
+        self.validate_alignment(record, &mut kinds);
+        self.validate_mate(record, &mut kinds);
+        self.validate_read_group(record, &mut kinds);
+
+        self.build_diagnostics(Some(i), kinds)
+    }
+
+    fn validate_alignment(&self, record: &Record, kinds: &mut Vec<Kind>) {
+        let flags = record.flags();
+
+        if flags.is_unmapped() {
+            if let Some(mapq) = *record.mapping_quality() {
+                if mapq != 0 {
+                    kinds.push(Kind::UnmappedRecordWithMappingQuality(mapq));
+                }
+            }
+        } else {
+            if record.reference_sequence_name().is_none() {
+                kinds.push(Kind::MissingReferenceSequenceName);
+            }
+
+            if record.position().is_none() {
+                kinds.push(Kind::MissingPosition);
+            }
+
+            if record.cigar().is_empty() {
+                kinds.push(Kind::MissingCigar);
+            }
+        }
+
+        let reference_sequence_name = match record.reference_sequence_name() {
+            Some(name) => name,
+            None => return,
+        };
+
+        let reference_sequence = match self
+            .header
+            .reference_sequences()
+            .get(reference_sequence_name.as_str())
+        {
+            Some(rs) => rs,
+            None => {
+                kinds.push(Kind::InvalidReferenceSequenceName(
+                    reference_sequence_name.to_string(),
+                ));
+                return;
+            }
+        };
+
+        if let Some(position) = record.position().map(i32::from) {
+            let len = reference_sequence.len();
+
+            if position > len {
+                kinds.push(Kind::PositionOutOfBounds(position, len));
+            } else if !flags.is_unmapped() {
+                let reference_len = record.cigar().reference_len() as i32;
+                let end = position + reference_len - 1;
+
+                if end > len {
+                    kinds.push(Kind::AlignmentEndOutOfBounds(end, len));
+                }
+            }
+        }
+    }
+
+    fn validate_mate(&self, record: &Record, kinds: &mut Vec<Kind>) {
+        let flags = record.flags();
+
+        if flags.is_paired() {
+            let segment_flags = flags & (Flags::READ_1 | Flags::READ_2);
+
+            if segment_flags.is_empty() || segment_flags == Flags::READ_1 | Flags::READ_2 {
+                kinds.push(Kind::InvalidSegmentFlags(flags));
+            }
+
+            if !flags.is_mate_unmapped()
+                && (record.mate_reference_sequence_name().is_none()
+                    || record.mate_position().is_none())
+            {
+                kinds.push(Kind::MissingMateFields);
+            }
+        } else {
+            let paired_flags = flags
+                & (Flags::PROPER_PAIR
+                    | Flags::MATE_UNMAPPED
+                    | Flags::MATE_REVERSE_COMPLEMENTED
+                    | Flags::READ_1
+                    | Flags::READ_2);
+
+            if !paired_flags.is_empty() {
+                kinds.push(Kind::InvalidFlagsForUnpairedRecord(paired_flags));
+            }
+
+            if record.mate_reference_sequence_name().is_some() || record.mate_position().is_some() {
+                kinds.push(Kind::MateFieldsForUnpairedRecord);
+            }
+        }
+
+        let mate_reference_sequence_name = match record.mate_reference_sequence_name() {
+            Some(name) => name,
+            None => return,
+        };
+
+        match self
+            .header
+            .reference_sequences()
+            .get(mate_reference_sequence_name.as_str())
+        {
+            Some(reference_sequence) => {
+                if let Some(mate_position) = record.mate_position().map(i32::from) {
+                    let len = reference_sequence.len();
+
+                    if mate_position > len {
+                        kinds.push(Kind::MatePositionOutOfBounds(mate_position, len));
+                    }
+                }
+            }
+            None => kinds.push(Kind::InvalidMateReferenceSequenceName(
+                mate_reference_sequence_name.to_string(),
+            )),
+        }
+    }
+
+    fn validate_read_group(&self, record: &Record, kinds: &mut Vec<Kind>) {
+        let field = match record
+            .data()
+            .iter()
+            .find(|field| field.tag() == &Tag::ReadGroup)
+        {
+            Some(field) => field,
+            None => return,
+        };
+
+        match field.value() {
+            Value::String(id) => {
+                if !self.header.read_groups().contains_key(id) {
+                    kinds.push(Kind::InvalidReadGroup(id.clone()));
+                }
+            }
+            value => kinds.push(Kind::InvalidReadGroupType(value.clone())),
+        }
+    }
+
+    fn build_diagnostics(&self, record_index: Option<usize>, kinds: Vec<Kind>) -> Vec<Diagnostic> {
+        kinds
+            .into_iter()
+            .filter_map(|kind| {
+                let severity = match (self.strictness, kind.severity()) {
+                    (Strictness::Strict, _) => Severity::Error,
+                    (Strictness::Silent, Severity::Warning) => return None,
+                    (_, severity) => severity,
+                };
+
+                Some(Diagnostic::new(severity, record_index, kind))
+            })
+            .collect()
+    }
+}
+
+fn validate_lengths(record: &Record, kinds: &mut Vec<Kind>) {
+    let sequence_len = record.sequence().len();
+
+    if sequence_len == 0 {
+        return;
+    }
+
+    let cigar = record.cigar();
+
+    if !cigar.is_empty() {
+        let read_len = cigar.read_len();
+
+        if read_len as usize != sequence_len {
+            kinds.push(Kind::CigarReadLengthMismatch(read_len, sequence_len));
+        }
+    }
+
+    let quality_scores_len = record.quality_scores().len();
+
+    if quality_scores_len > 0 && quality_scores_len != sequence_len {
+        kinds.push(Kind::QualityScoresLengthMismatch(
+            quality_scores_len,
+            sequence_len,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{
+        header::{Program, ReadGroup, ReferenceSequence},
+        record::{MappingQuality, Position},
+    };
+
+    use super::*;
+
+    fn build_header() -> Header {
+        Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), 8))
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq1"), 13))
+            .add_read_group(ReadGroup::new(String::from("rg0")))
+            .build()
+    }
+
+    fn kinds(diagnostics: &[Diagnostic]) -> Vec<&Kind> {
+        diagnostics.iter().map(|d| d.kind()).collect()
+    }
+
+    #[test]
+    fn test_validate_header() {
+        let header = Header::builder()
+            .add_reference_sequence(ReferenceSequence::new(String::from("sq0"), -1))
+            .add_program(
+                Program::builder()
+                    .set_id("pg1")
+                    .set_previous_id("pg0")
+                    .build(),
+            )
+            .build();
+
+        let validator = Validator::new(&header, Strictness::Lenient);
+
+        assert_eq!(
+            validator.validate_header(),
+            [
+                Diagnostic::new(
+                    Severity::Error,
+                    None,
+                    Kind::InvalidReferenceSequenceLength(String::from("sq0"), -1)
+                ),
+                Diagnostic::new(
+                    Severity::Warning,
+                    None,
+                    Kind::InvalidProgramPreviousId(String::from("pg1"), String::from("pg0"))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let validator = Validator::new(&header, Strictness::Lenient);
+
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(1)?)
+            .set_cigar("4M".parse()?)
+            .set_sequence("ACGT".parse()?)
+            .set_quality_scores("NDLS".parse()?)
+            .set_data("RG:Z:rg0".parse()?)
+            .build();
+
+        assert!(validator.validate_record(0, &record).is_empty());
+
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(7)?)
+            .set_cigar("4M".parse()?)
+            .set_sequence("ACG".parse()?)
+            .set_quality_scores("ND".parse()?)
+            .set_data("RG:Z:rg1".parse()?)
+            .build();
+
+        let diagnostics = validator.validate_record(3, &record);
+
+        assert_eq!(
+            kinds(&diagnostics),
+            [
+                &Kind::CigarReadLengthMismatch(4, 3),
+                &Kind::QualityScoresLengthMismatch(2, 3),
+                &Kind::AlignmentEndOutOfBounds(10, 8),
+                &Kind::InvalidReadGroup(String::from("rg1")),
+            ]
+        );
+
+        assert!(diagnostics.iter().all(|d| d.record_index() == Some(3)));
+
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq2".parse()?)
+            .set_position(Position::try_from(21)?)
+            .set_cigar("4M".parse()?)
+            .build();
+
+        assert_eq!(
+            kinds(&validator.validate_record(0, &record)),
+            [&Kind::InvalidReferenceSequenceName(String::from("sq2"))]
+        );
+
+        let record = Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(21)?)
+            .set_cigar("4M".parse()?)
+            .build();
+
+        assert_eq!(
+            kinds(&validator.validate_record(0, &record)),
+            [&Kind::PositionOutOfBounds(21, 8)]
+        );
+
+        let record = Record::builder()
+            .set_mapping_quality(MappingQuality::from(8))
+            .build();
+
+        assert_eq!(
+            kinds(&validator.validate_record(0, &record)),
+            [&Kind::UnmappedRecordWithMappingQuality(8)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_record_with_mate_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let validator = Validator::new(&header, Strictness::Lenient);
+
+        let record = Record::builder()
+            .set_flags(Flags::UNMAPPED | Flags::PAIRED | Flags::READ_1)
+            .set_mate_reference_sequence_name("sq1".parse()?)
+            .set_mate_position(Position::try_from(13)?)
+            .build();
+
+        assert!(validator.validate_record(0, &record).is_empty());
+
+        let record = Record::builder()
+            .set_flags(Flags::UNMAPPED | Flags::PAIRED)
+            .set_mate_reference_sequence_name("sq1".parse()?)
+            .set_mate_position(Position::try_from(21)?)
+            .build();
+
+        assert_eq!(
+            kinds(&validator.validate_record(0, &record)),
+            [
+                &Kind::InvalidSegmentFlags(Flags::UNMAPPED | Flags::PAIRED),
+                &Kind::MatePositionOutOfBounds(21, 13),
+            ]
+        );
+
+        let flags = Flags::UNMAPPED | Flags::PAIRED | Flags::READ_1 | Flags::READ_2;
+
+        let record = Record::builder()
+            .set_flags(flags)
+            .set_mate_reference_sequence_name("sq1".parse()?)
+            .set_mate_position(Position::try_from(13)?)
+            .build();
+
+        assert_eq!(
+            kinds(&validator.validate_record(0, &record)),
+            [&Kind::InvalidSegmentFlags(flags)]
+        );
+
+        let record = Record::builder()
+            .set_flags(Flags::UNMAPPED | Flags::PAIRED | Flags::READ_2)
+            .set_mate_reference_sequence_name("sq2".parse()?)
+            .build();
+
+        assert_eq!(
+            kinds(&validator.validate_record(0, &record)),
+            [
+                &Kind::MissingMateFields,
+                &Kind::InvalidMateReferenceSequenceName(String::from("sq2")),
+            ]
+        );
+
+        let record = Record::builder()
+            .set_flags(Flags::UNMAPPED | Flags::MATE_UNMAPPED | Flags::READ_1)
+            .set_mate_reference_sequence_name("sq1".parse()?)
+            .build();
+
+        assert_eq!(
+            kinds(&validator.validate_record(0, &record)),
+            [
+                &Kind::InvalidFlagsForUnpairedRecord(Flags::MATE_UNMAPPED | Flags::READ_1),
+                &Kind::MateFieldsForUnpairedRecord,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_record_with_strictness() {
+        let header = build_header();
+        let record = Record::builder()
+            .set_mapping_quality(MappingQuality::from(8))
+            .build();
+
+        let validator = Validator::new(&header, Strictness::Strict);
+        let diagnostics = validator.validate_record(0, &record);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity(), Severity::Error);
+
+        let validator = Validator::new(&header, Strictness::Lenient);
+        let diagnostics = validator.validate_record(0, &record);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity(), Severity::Warning);
+
+        let validator = Validator::new(&header, Strictness::Silent);
+        assert!(validator.validate_record(0, &record).is_empty());
+    }
+}
@@ -7,8 +7,11 @@ pub use self::bin::Bin;
 
 pub(crate) use self::builder::Builder;
 
+use bit_vec::BitVec;
 use noodles_bgzf as bgzf;
 
+const WINDOW_SIZE: i32 = 16384;
+
 /// A tabix index reference sequence.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct ReferenceSequence {
@@ -58,4 +61,141 @@ impl ReferenceSequence {
     pub fn intervals(&self) -> &[bgzf::VirtualPosition] {
         &self.intervals
     }
+
+    /// Returns a list of bins in this reference sequence that intersect the given range.
+    ///
+    /// `start` and `end` are 1-based, inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new());
+    /// let query_bins = reference_sequence.query(8, 13);
+    /// assert!(query_bins.is_empty());
+    /// ```
+    pub fn query(&self, start: i32, end: i32) -> Vec<&Bin> {
+        query_bins(self.bins(), start, end, Bin::id)
+    }
+
+    /// Finds in minimum start offset in the linear index for a given start position.
+    ///
+    /// `start` is 1-based.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix::index::ReferenceSequence;
+    /// let reference_sequence = ReferenceSequence::new(Vec::new(), Vec::new());
+    /// assert_eq!(reference_sequence.min_offset(13), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn min_offset(&self, start: i32) -> bgzf::VirtualPosition {
+        let i = ((start - 1) / WINDOW_SIZE) as usize;
+        self.intervals.get(i).copied().unwrap_or_default()
+    }
+}
+
+/// Returns the bins in the given list that intersect the given range.
+///
+/// This uses the binning scheme shared by tabix and BAM indices, where `bin_id` returns the ID of
+/// a bin. Pseudo-bins, i.e., bins with IDs past the last bin, never intersect the range.
+///
+/// `start` and `end` are 1-based, inclusive.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_tabix::index::reference_sequence::{self, Bin};
+///
+/// let bins = [
+///     Bin::new(0, Vec::new()),
+///     Bin::new(4681, Vec::new()),
+///     Bin::new(4682, Vec::new()),
+/// ];
+///
+/// let query_bins = reference_sequence::query_bins(&bins, 8, 13, Bin::id);
+/// let ids: Vec<_> = query_bins.iter().map(|bin| bin.id()).collect();
+/// assert_eq!(ids, [0, 4681]);
+/// ```
+pub fn query_bins<B, F>(bins: &[B], start: i32, end: i32, bin_id: F) -> Vec<&B>
+where
+    F: Fn(&B) -> u32,
+{
+    let region_bins = region_to_bins((start - 1) as usize, end as usize);
+
+    bins.iter()
+        .filter(|b| region_bins.get(bin_id(b) as usize).unwrap_or(false))
+        .collect()
+}
+
+// 0-based, [start, end)
+fn region_to_bins(start: usize, mut end: usize) -> BitVec {
+    end -= 1;
+
+    let mut bins = BitVec::from_elem(bin::MAX_ID, false);
+    bins.set(0, true);
+
+    for k in (1 + (start >> 26))..=(1 + (end >> 26)) {
+        bins.set(k, true);
+    }
+
+    for k in (9 + (start >> 23))..=(9 + (end >> 23)) {
+        bins.set(k, true);
+    }
+
+    for k in (73 + (start >> 20))..=(73 + (end >> 20)) {
+        bins.set(k, true);
+    }
+
+    for k in (585 + (start >> 17))..=(585 + (end >> 17)) {
+        bins.set(k, true);
+    }
+
+    for k in (4681 + (start >> 14))..=(4681 + (end >> 14)) {
+        bins.set(k, true);
+    }
+
+    bins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        let reference_sequence = ReferenceSequence::new(
+            vec![
+                Bin::new(0, Vec::new()),
+                Bin::new(4681, Vec::new()),
+                Bin::new(4682, Vec::new()),
+                Bin::new(37450, Vec::new()),
+            ],
+            Vec::new(),
+        );
+
+        let query_bins = reference_sequence.query(8, 13);
+        let ids: Vec<_> = query_bins.iter().map(|bin| bin.id()).collect();
+        assert_eq!(ids, [0, 4681]);
+    }
+
+    #[test]
+    fn test_region_to_bins() {
+        // [8, 13]
+        let actual = region_to_bins(7, 13);
+        let mut expected = BitVec::from_elem(bin::MAX_ID, false);
+        for &k in &[0, 1, 9, 73, 585, 4681] {
+            expected.set(k, true);
+        }
+        assert_eq!(actual, expected);
+
+        // [63245986, 63245986]
+        let actual = region_to_bins(63245985, 63255986);
+        let mut expected = BitVec::from_elem(bin::MAX_ID, false);
+        for &k in &[0, 1, 16, 133, 1067, 8541] {
+            expected.set(k, true);
+        }
+        assert_eq!(actual, expected);
+    }
 }
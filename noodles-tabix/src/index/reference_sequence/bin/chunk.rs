@@ -1,6 +1,8 @@
 use noodles_bgzf as bgzf;
 
 /// A tabix index reference sequence bin chunk.
+///
+/// This is also the chunk of a BAM index (BAI) bin.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Chunk {
     start: bgzf::VirtualPosition,
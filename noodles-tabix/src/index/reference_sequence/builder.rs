@@ -17,6 +17,10 @@ pub struct Builder {
 
 impl Builder {
     pub fn add_record(&mut self, start: u32, end: u32, chunk: Chunk) -> &mut Self {
+        // A start of 0, e.g., a VCF telomere, is clamped to the first position.
+        let end = end.max(start).max(1);
+        let start = start.max(1);
+
         self.update_bins(start, end, chunk);
         self.update_linear_index(start, end, chunk);
         self
@@ -33,7 +37,7 @@ impl Builder {
     }
 
     fn update_bins(&mut self, start: u32, end: u32, chunk: Chunk) {
-        let bin_id = region_to_bin(start, end);
+        let bin_id = region_to_bin(start - 1, end);
 
         let builder = self.bin_builders.entry(bin_id).or_insert_with(|| {
             let mut builder = Bin::builder();
@@ -55,8 +59,12 @@ impl Builder {
             );
         }
 
-        for i in linear_index_start_offset..=linear_index_end_offset {
-            self.intervals[i] = chunk.start();
+        // Records are added in coordinate order, so the first chunk to overlap a window has its
+        // minimum offset.
+        for interval in &mut self.intervals[linear_index_start_offset..=linear_index_end_offset] {
+            if *interval == bgzf::VirtualPosition::default() {
+                *interval = chunk.start();
+            }
         }
     }
 }
@@ -93,6 +101,67 @@ mod tests {
         assert_eq!(reference_sequence, ReferenceSequence::default());
     }
 
+    #[test]
+    fn test_add_record() {
+        let mut builder = Builder::default();
+
+        builder
+            .add_record(
+                8,
+                13,
+                Chunk::new(
+                    bgzf::VirtualPosition::from(144),
+                    bgzf::VirtualPosition::from(233),
+                ),
+            )
+            .add_record(
+                16385,
+                16390,
+                Chunk::new(
+                    bgzf::VirtualPosition::from(233),
+                    bgzf::VirtualPosition::from(377),
+                ),
+            );
+
+        let reference_sequence = builder.build();
+
+        assert_eq!(
+            reference_sequence.intervals(),
+            &[
+                bgzf::VirtualPosition::from(144),
+                bgzf::VirtualPosition::from(233),
+            ]
+        );
+
+        let mut bin_ids: Vec<_> = reference_sequence.bins().iter().map(|b| b.id()).collect();
+        bin_ids.sort_unstable();
+        assert_eq!(bin_ids, [4681, 4682]);
+    }
+
+    #[test]
+    fn test_add_record_with_zero_start() {
+        let mut builder = Builder::default();
+
+        builder.add_record(
+            0,
+            0,
+            Chunk::new(
+                bgzf::VirtualPosition::from(144),
+                bgzf::VirtualPosition::from(233),
+            ),
+        );
+
+        let reference_sequence = builder.build();
+
+        assert_eq!(
+            reference_sequence.intervals(),
+            &[bgzf::VirtualPosition::from(144)]
+        );
+
+        let bin_ids: Vec<_> = reference_sequence.bins().iter().map(|b| b.id()).collect();
+        assert_eq!(bin_ids, [4681]);
+    }
+
     #[test]
     fn test_region_to_bin() {
         // [8, 13]
@@ -15,13 +15,17 @@ pub use self::{
 };
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::TryFrom,
     error, fmt,
     str::{FromStr, Lines},
 };
 
 static FILE_FORMAT: &str = "VCFv4.3";
+static FILE_FORMAT_PREFIX: &str = "VCFv";
+
+// The contig field that holds the index of a contig in a BCF string map.
+static CONTIG_INDEX_KEY: &str = "IDX";
 
 /// A VCF header.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -30,6 +34,9 @@ pub struct Header {
     infos: Vec<Info>,
     filters: Vec<Filter>,
     formats: Vec<Format>,
+    // Indices of the first INFO and FORMAT definitions by ID.
+    info_indices: HashMap<String, usize>,
+    format_indices: HashMap<String, usize>,
     alternative_alleles: Vec<AlternativeAllele>,
     assembly: Option<String>,
     contigs: Vec<Contig>,
@@ -144,6 +151,28 @@ impl Header {
         &self.formats
     }
 
+    /// Returns the information record (`INFO`) with the given ID.
+    pub(crate) fn get_info(&self, id: &str) -> Option<&Info> {
+        self.info_indices.get(id).map(|&i| &self.infos[i])
+    }
+
+    /// Returns the genotype format record (`FORMAT`) with the given ID.
+    pub(crate) fn get_format(&self, id: &str) -> Option<&Format> {
+        self.format_indices.get(id).map(|&i| &self.formats[i])
+    }
+
+    fn push_info(&mut self, info: Info) {
+        let id = info.id().as_ref().into();
+        self.info_indices.entry(id).or_insert(self.infos.len());
+        self.infos.push(info);
+    }
+
+    fn push_format(&mut self, format: Format) {
+        let id = format.id().as_ref().into();
+        self.format_indices.entry(id).or_insert(self.formats.len());
+        self.formats.push(format);
+    }
+
     /// Returns a list of symbolic alternate alleles (`ALT`).
     ///
     /// # Examples
@@ -248,6 +277,25 @@ impl Header {
         &self.samples_names
     }
 
+    /// Returns a mutable reference to the sample names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut header = vcf::Header::builder().add_sample_name("sample0").build();
+    /// header.sample_names_mut().push(String::from("sample1"));
+    ///
+    /// assert_eq!(header.sample_names(), [
+    ///     String::from("sample0"),
+    ///     String::from("sample1"),
+    /// ]);
+    /// ```
+    pub fn sample_names_mut(&mut self) -> &mut Vec<String> {
+        &mut self.samples_names
+    }
+
     /// Returns a header record with the given key.
     ///
     /// This includes all records other than `fileformat`, `INFO`, `FILTER`, `FORMAT`, `ALT`,
@@ -295,6 +343,325 @@ impl Header {
     pub fn insert(&mut self, record: Record) {
         self.map.insert(record.key().to_string(), record);
     }
+
+    /// Validates the header.
+    ///
+    /// This checks that the file format is a VCF version; that there are no duplicate IDs in the
+    /// information, filter, genotype format, alternative allele, and contig records; that contig
+    /// records with an index (`IDX`) are in index order; that there are no duplicate sample
+    /// names; and that the definitions of reserved information and genotype format keys match
+    /// their reserved number and type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, header::{Contig, ValidateError}};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_contig(Contig::new(String::from("sq0")))
+    ///     .build();
+    /// assert!(header.validate().is_ok());
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_contig(Contig::new(String::from("sq0")))
+    ///     .add_contig(Contig::new(String::from("sq0")))
+    ///     .build();
+    /// assert_eq!(
+    ///     header.validate(),
+    ///     Err(ValidateError::DuplicateContig(String::from("sq0")))
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidateError> {
+        if !self.file_format.starts_with(FILE_FORMAT_PREFIX) {
+            return Err(ValidateError::InvalidFileFormat(self.file_format.clone()));
+        }
+
+        let mut ids = HashSet::new();
+
+        for info in self.infos() {
+            let id = info.id();
+
+            if !ids.insert(id.as_ref()) {
+                return Err(ValidateError::DuplicateInfo(id.to_string()));
+            }
+
+            if !matches!(id, crate::record::info::field::Key::Other(..))
+                && (info.number() != id.number() || info.ty() != id.ty())
+            {
+                return Err(ValidateError::InfoDefinitionMismatch(id.to_string()));
+            }
+        }
+
+        let mut ids = HashSet::new();
+
+        for filter in self.filters() {
+            if !ids.insert(filter.id()) {
+                return Err(ValidateError::DuplicateFilter(filter.id().into()));
+            }
+        }
+
+        let mut ids = HashSet::new();
+
+        for format in self.formats() {
+            let id = format.id();
+
+            if !ids.insert(id.as_ref()) {
+                return Err(ValidateError::DuplicateFormat(id.to_string()));
+            }
+
+            if !matches!(id, crate::record::genotype::field::Key::Other(..))
+                && (format.number() != id.number() || format.ty() != id.ty())
+            {
+                return Err(ValidateError::FormatDefinitionMismatch(id.to_string()));
+            }
+        }
+
+        let mut ids = HashSet::new();
+
+        for alternative_allele in self.alternative_alleles() {
+            let id = alternative_allele.id().to_string();
+
+            if !ids.insert(id.clone()) {
+                return Err(ValidateError::DuplicateAlternativeAllele(id));
+            }
+        }
+
+        let mut ids = HashSet::new();
+        let mut last_index = None;
+
+        for contig in self.contigs() {
+            if !ids.insert(contig.id()) {
+                return Err(ValidateError::DuplicateContig(contig.id().into()));
+            }
+
+            if let Some(i) = contig
+                .get(CONTIG_INDEX_KEY)
+                .and_then(|s| s.parse::<usize>().ok())
+            {
+                if last_index.map(|j| i <= j).unwrap_or(false) {
+                    return Err(ValidateError::InvalidContigOrder(contig.id().into()));
+                }
+
+                last_index = Some(i);
+            }
+        }
+
+        let mut sample_names = HashSet::new();
+
+        for sample_name in self.sample_names() {
+            if !sample_names.insert(sample_name) {
+                return Err(ValidateError::DuplicateSampleName(sample_name.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Merges another header into a copy of this header.
+    ///
+    /// The information, filter, genotype format, alternative allele, contig, and other records
+    /// are unioned by ID. Records of this header take precedence, and new records of the other
+    /// header are appended. Information and genotype format records with the same ID must have
+    /// the same number and type, and contig records with the same ID, the same length.
+    ///
+    /// Contigs in both headers must be in the same order. A contig only in the other header is
+    /// inserted after the contig preceding it in the other header, or, if it comes before all
+    /// shared contigs, before the first shared contig. If there are no shared contigs, the new
+    /// contigs are appended.
+    ///
+    /// The file format of the merged header is the newer of the two VCF versions. Sample names
+    /// are combined as given by `sample_name_merge`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, header::{Contig, SampleNameMerge}};
+    ///
+    /// let header0 = vcf::Header::builder()
+    ///     .add_contig(Contig::new(String::from("sq0")))
+    ///     .add_sample_name("sample0")
+    ///     .build();
+    ///
+    /// let header1 = vcf::Header::builder()
+    ///     .add_contig(Contig::new(String::from("sq0")))
+    ///     .add_contig(Contig::new(String::from("sq1")))
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let header = header0.merge(&header1, SampleNameMerge::Concatenate)?;
+    ///
+    /// assert_eq!(header.contigs().len(), 2);
+    /// assert_eq!(header.sample_names(), [String::from("sample0"), String::from("sample1")]);
+    /// # Ok::<(), vcf::header::MergeError>(())
+    /// ```
+    pub fn merge(
+        &self,
+        other: &Self,
+        sample_name_merge: SampleNameMerge,
+    ) -> Result<Self, MergeError> {
+        let mut header = self.clone();
+
+        if self.file_format != other.file_format {
+            match (
+                parse_file_format_version(&self.file_format),
+                parse_file_format_version(&other.file_format),
+            ) {
+                (Some(a), Some(b)) => {
+                    if b > a {
+                        header.file_format = other.file_format.clone();
+                    }
+                }
+                _ => {
+                    return Err(MergeError::FileFormatMismatch(
+                        self.file_format.clone(),
+                        other.file_format.clone(),
+                    ))
+                }
+            }
+        }
+
+        for info in other.infos() {
+            let id = info.id().as_ref();
+
+            match header.get_info(id) {
+                Some(i) => {
+                    if i.number() != info.number() || i.ty() != info.ty() {
+                        return Err(MergeError::InfoDefinitionConflict(id.into()));
+                    }
+                }
+                None => header.push_info(info.clone()),
+            }
+        }
+
+        let mut filter_ids: HashSet<&str> = self.filters().iter().map(|f| f.id()).collect();
+
+        for filter in other.filters() {
+            if filter_ids.insert(filter.id()) {
+                header.filters.push(filter.clone());
+            }
+        }
+
+        for format in other.formats() {
+            let id = format.id().as_ref();
+
+            match header.get_format(id) {
+                Some(f) => {
+                    if f.number() != format.number() || f.ty() != format.ty() {
+                        return Err(MergeError::FormatDefinitionConflict(id.into()));
+                    }
+                }
+                None => header.push_format(format.clone()),
+            }
+        }
+
+        let mut alternative_allele_ids: HashSet<String> = self
+            .alternative_alleles()
+            .iter()
+            .map(|a| a.id().to_string())
+            .collect();
+
+        for alternative_allele in other.alternative_alleles() {
+            if alternative_allele_ids.insert(alternative_allele.id().to_string()) {
+                header.alternative_alleles.push(alternative_allele.clone());
+            }
+        }
+
+        if header.assembly.is_none() {
+            header.assembly = other.assembly.clone();
+        }
+
+        header.contigs = merge_contigs(self.contigs(), other.contigs())?;
+
+        if header.pedigree_db.is_none() {
+            header.pedigree_db = other.pedigree_db.clone();
+        }
+
+        for (key, record) in &other.map {
+            header
+                .map
+                .entry(key.clone())
+                .or_insert_with(|| record.clone());
+        }
+
+        let mut sample_names: HashSet<&str> =
+            self.sample_names().iter().map(|s| s.as_str()).collect();
+
+        for sample_name in other.sample_names() {
+            if !sample_names.insert(sample_name.as_str()) {
+                match sample_name_merge {
+                    SampleNameMerge::Concatenate => {
+                        return Err(MergeError::DuplicateSampleName(sample_name.clone()));
+                    }
+                    SampleNameMerge::Deduplicate => continue,
+                }
+            }
+
+            header.samples_names.push(sample_name.clone());
+        }
+
+        Ok(header)
+    }
+}
+
+// Merges the contigs of `other` into `contigs`.
+//
+// Contigs shared by both lists must be in the same relative order. New contigs are placed after
+// the last shared contig that precedes them in `other`, or before the first shared contig if none
+// does. If no contigs are shared, new contigs are appended.
+fn merge_contigs(contigs: &[Contig], other: &[Contig]) -> Result<Vec<Contig>, MergeError> {
+    let indices: HashMap<&str, usize> = contigs
+        .iter()
+        .enumerate()
+        .map(|(i, contig)| (contig.id(), i))
+        .collect();
+
+    // The new contigs to place before and after each contig in `contigs`.
+    let mut before = vec![Vec::new(); contigs.len()];
+    let mut after = vec![Vec::new(); contigs.len()];
+
+    // The index of the last shared contig, and new contigs not yet preceded by one.
+    let mut last_index: Option<usize> = None;
+    let mut leading_contigs = Vec::new();
+
+    for contig in other {
+        match indices.get(contig.id()) {
+            Some(&i) => {
+                if contigs[i].len() != contig.len() {
+                    return Err(MergeError::ContigLengthConflict(contig.id().into()));
+                }
+
+                if last_index.map(|j| i < j).unwrap_or(false) {
+                    return Err(MergeError::ContigOrderMismatch(contig.id().into()));
+                }
+
+                before[i].append(&mut leading_contigs);
+                last_index = Some(i);
+            }
+            None => match last_index {
+                Some(i) => after[i].push(contig.clone()),
+                None => leading_contigs.push(contig.clone()),
+            },
+        }
+    }
+
+    let mut merged_contigs = Vec::with_capacity(contigs.len() + other.len());
+
+    for ((contig, before), after) in contigs.iter().zip(before).zip(after) {
+        merged_contigs.extend(before);
+        merged_contigs.push(contig.clone());
+        merged_contigs.extend(after);
+    }
+
+    merged_contigs.extend(leading_contigs);
+
+    Ok(merged_contigs)
+}
+
+// Returns the major and minor version of a VCF file format, e.g., `VCFv4.3`.
+fn parse_file_format_version(s: &str) -> Option<(u32, u32)> {
+    let version = s.strip_prefix(FILE_FORMAT_PREFIX)?;
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
 }
 
 impl Default for Header {
@@ -317,6 +684,10 @@ impl fmt::Display for Header {
             writeln!(f, "{}", info)?;
         }
 
+        for filter in self.filters() {
+            writeln!(f, "{}", filter)?;
+        }
+
         for format in self.formats() {
             writeln!(f, "{}", format)?;
         }
@@ -415,6 +786,101 @@ impl fmt::Display for ParseError {
     }
 }
 
+/// An error returned when a VCF header fails to validate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidateError {
+    /// The file format (`fileformat`) is not a VCF version.
+    InvalidFileFormat(String),
+    /// An information record (`INFO`) ID is duplicated.
+    DuplicateInfo(String),
+    /// An information record (`INFO`) of a reserved key has a different number or type.
+    InfoDefinitionMismatch(String),
+    /// A filter record (`FILTER`) ID is duplicated.
+    DuplicateFilter(String),
+    /// A genotype format record (`FORMAT`) ID is duplicated.
+    DuplicateFormat(String),
+    /// A genotype format record (`FORMAT`) of a reserved key has a different number or type.
+    FormatDefinitionMismatch(String),
+    /// A symbolic alternate allele record (`ALT`) ID is duplicated.
+    DuplicateAlternativeAllele(String),
+    /// A contig record (`contig`) ID is duplicated.
+    DuplicateContig(String),
+    /// A contig record (`contig`) is not in index (`IDX`) order.
+    InvalidContigOrder(String),
+    /// A sample name is duplicated.
+    DuplicateSampleName(String),
+}
+
+impl error::Error for ValidateError {}
+
+impl fmt::Display for ValidateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFileFormat(file_format) => {
+                write!(f, "invalid file format: {}", file_format)
+            }
+            Self::DuplicateInfo(id) => write!(f, "duplicate info: {}", id),
+            Self::InfoDefinitionMismatch(id) => write!(f, "info definition mismatch: {}", id),
+            Self::DuplicateFilter(id) => write!(f, "duplicate filter: {}", id),
+            Self::DuplicateFormat(id) => write!(f, "duplicate format: {}", id),
+            Self::FormatDefinitionMismatch(id) => {
+                write!(f, "format definition mismatch: {}", id)
+            }
+            Self::DuplicateAlternativeAllele(id) => {
+                write!(f, "duplicate alternative allele: {}", id)
+            }
+            Self::DuplicateContig(id) => write!(f, "duplicate contig: {}", id),
+            Self::InvalidContigOrder(id) => write!(f, "invalid contig order: {}", id),
+            Self::DuplicateSampleName(name) => write!(f, "duplicate sample name: {}", name),
+        }
+    }
+}
+
+/// How sample names are combined when merging VCF headers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SampleNameMerge {
+    /// The sample names of the other header are appended and must all be new, e.g., when merging
+    /// VCFs of different samples.
+    Concatenate,
+    /// Only new sample names of the other header are appended, e.g., when merging VCFs of
+    /// different regions of the same samples.
+    Deduplicate,
+}
+
+/// An error returned when VCF headers fail to merge.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MergeError {
+    /// The file formats (`fileformat`) differ.
+    FileFormatMismatch(String, String),
+    /// Information records (`INFO`) with the same ID have a different number or type.
+    InfoDefinitionConflict(String),
+    /// Genotype format records (`FORMAT`) with the same ID have a different number or type.
+    FormatDefinitionConflict(String),
+    /// Contig records (`contig`) with the same ID have different lengths.
+    ContigLengthConflict(String),
+    /// Contig records (`contig`) are in a different order.
+    ContigOrderMismatch(String),
+    /// A sample name is in both headers.
+    DuplicateSampleName(String),
+}
+
+impl error::Error for MergeError {}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FileFormatMismatch(a, b) => write!(f, "file format mismatch: {} != {}", a, b),
+            Self::InfoDefinitionConflict(id) => write!(f, "info definition conflict: {}", id),
+            Self::FormatDefinitionConflict(id) => {
+                write!(f, "format definition conflict: {}", id)
+            }
+            Self::ContigLengthConflict(id) => write!(f, "contig length conflict: {}", id),
+            Self::ContigOrderMismatch(id) => write!(f, "contig order mismatch: {}", id),
+            Self::DuplicateSampleName(name) => write!(f, "duplicate sample name: {}", name),
+        }
+    }
+}
+
 impl FromStr for Header {
     type Err = ParseError;
 
@@ -524,6 +990,10 @@ mod tests {
     fn test_fmt() {
         let header = Header::builder()
             .set_file_format("VCFv4.3")
+            .add_filter(Filter::new(
+                String::from("q10"),
+                String::from("Quality below 10"),
+            ))
             .set_assembly("file:///assemblies.fasta")
             .insert(Record::new(
                 record::Key::Other(String::from("fileDate")),
@@ -533,6 +1003,7 @@ mod tests {
 
         let expected = "\
 ##fileformat=VCFv4.3
+##FILTER=<ID=q10,Description=\"Quality below 10\">
 ##assembly=file:///assemblies.fasta
 ##fileDate=20200514
 #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
@@ -644,4 +1115,167 @@ mod tests {
 
         assert_eq!(s.parse::<Header>(), Err(ParseError::UnexpectedFileFormat));
     }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::record::info::field::Key as InfoKey;
+
+        let header = Header::builder()
+            .add_info(Info::new(
+                InfoKey::AlleleCount,
+                Number::A,
+                info::Type::Integer,
+                String::from("Allele count"),
+            ))
+            .add_contig(Contig::new(String::from("sq0")))
+            .add_contig(Contig::new(String::from("sq1")))
+            .add_sample_name("sample0")
+            .build();
+
+        assert_eq!(header.validate(), Ok(()));
+
+        let header = Header::builder().set_file_format("BCFv2.2").build();
+        assert_eq!(
+            header.validate(),
+            Err(ValidateError::InvalidFileFormat(String::from("BCFv2.2")))
+        );
+
+        let header = Header::builder()
+            .add_info(Info::new(
+                InfoKey::AlleleCount,
+                Number::Count(1),
+                info::Type::Integer,
+                String::from("Allele count"),
+            ))
+            .build();
+        assert_eq!(
+            header.validate(),
+            Err(ValidateError::InfoDefinitionMismatch(String::from("AC")))
+        );
+
+        let header = Header::builder()
+            .add_filter(Filter::new(String::from("q10"), String::new()))
+            .add_filter(Filter::new(String::from("q10"), String::new()))
+            .build();
+        assert_eq!(
+            header.validate(),
+            Err(ValidateError::DuplicateFilter(String::from("q10")))
+        );
+
+        let header = Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample0")
+            .build();
+        assert_eq!(
+            header.validate(),
+            Err(ValidateError::DuplicateSampleName(String::from("sample0")))
+        );
+
+        let header: Header = "##fileformat=VCFv4.3
+##contig=<ID=sq0,IDX=1>
+##contig=<ID=sq1,IDX=0>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+"
+        .parse()?;
+        assert_eq!(
+            header.validate(),
+            Err(ValidateError::InvalidContigOrder(String::from("sq1")))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() -> Result<(), Box<dyn std::error::Error>> {
+        let header0: Header = "##fileformat=VCFv4.3
+##INFO=<ID=NS,Number=1,Type=Integer,Description=\"Number of samples with data\">
+##contig=<ID=sq0,length=8>
+##contig=<ID=sq1,length=13>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+"
+        .parse()?;
+
+        let header1: Header = "##fileformat=VCFv4.3
+##INFO=<ID=NS,Number=1,Type=Integer,Description=\"Number of samples\">
+##INFO=<ID=DP,Number=1,Type=Integer,Description=\"Combined depth across samples\">
+##FILTER=<ID=q10,Description=\"Quality below 10\">
+##contig=<ID=sq1,length=13>
+##contig=<ID=sq2,length=21>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1
+"
+        .parse()?;
+
+        let header = header0.merge(&header1, SampleNameMerge::Deduplicate)?;
+
+        let ids: Vec<_> = header.infos().iter().map(|i| i.id().to_string()).collect();
+        assert_eq!(ids, ["NS", "DP"]);
+        assert_eq!(
+            header.infos()[0].description(),
+            "Number of samples with data"
+        );
+        assert_eq!(header.filters().len(), 1);
+
+        let ids: Vec<_> = header.contigs().iter().map(|c| c.id()).collect();
+        assert_eq!(ids, ["sq0", "sq1", "sq2"]);
+
+        let header5: Header = "##fileformat=VCFv4.3
+##contig=<ID=sq3>
+##contig=<ID=sq0,length=8>
+##contig=<ID=sq4>
+##contig=<ID=sq1,length=13>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+"
+        .parse()?;
+        let merged_header = header0.merge(&header5, SampleNameMerge::Concatenate)?;
+        let ids: Vec<_> = merged_header.contigs().iter().map(|c| c.id()).collect();
+        assert_eq!(ids, ["sq3", "sq0", "sq4", "sq1"]);
+
+        assert_eq!(
+            header.sample_names(),
+            [String::from("sample0"), String::from("sample1")]
+        );
+
+        assert_eq!(
+            header0.merge(&header1, SampleNameMerge::Concatenate),
+            Err(MergeError::DuplicateSampleName(String::from("sample0")))
+        );
+
+        let header2: Header = "##fileformat=VCFv4.3
+##INFO=<ID=NS,Number=.,Type=Integer,Description=\"Number of samples with data\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+"
+        .parse()?;
+        assert_eq!(
+            header0.merge(&header2, SampleNameMerge::Concatenate),
+            Err(MergeError::InfoDefinitionConflict(String::from("NS")))
+        );
+
+        let header3: Header = "##fileformat=VCFv4.3
+##contig=<ID=sq1,length=13>
+##contig=<ID=sq0,length=8>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+"
+        .parse()?;
+        assert_eq!(
+            header0.merge(&header3, SampleNameMerge::Concatenate),
+            Err(MergeError::ContigOrderMismatch(String::from("sq0")))
+        );
+
+        let header4 = Header::builder().set_file_format("VCFv4.2").build();
+        let header = header4.merge(&header0, SampleNameMerge::Concatenate)?;
+        assert_eq!(header.file_format(), "VCFv4.3");
+        let header = header0.merge(&header4, SampleNameMerge::Concatenate)?;
+        assert_eq!(header.file_format(), "VCFv4.3");
+
+        let header6 = Header::builder().set_file_format("BCFv2.2").build();
+        assert_eq!(
+            header0.merge(&header6, SampleNameMerge::Concatenate),
+            Err(MergeError::FileFormatMismatch(
+                String::from("VCFv4.3"),
+                String::from("BCFv2.2")
+            ))
+        );
+
+        Ok(())
+    }
 }
@@ -264,18 +264,30 @@ impl Builder {
     /// let header = vcf::Header::builder().build();
     /// ```
     pub fn build(self) -> Header {
-        Header {
+        let mut header = Header {
             file_format: self.file_format,
-            infos: self.infos,
+            infos: Vec::new(),
             filters: self.filters,
-            formats: self.formats,
+            formats: Vec::new(),
+            info_indices: HashMap::new(),
+            format_indices: HashMap::new(),
             alternative_alleles: self.alternative_alleles,
             assembly: self.assembly,
             contigs: self.contigs,
             pedigree_db: self.pedigree_db,
             samples_names: self.sample_names,
             map: self.map,
+        };
+
+        for info in self.infos {
+            header.push_info(info);
         }
+
+        for format in self.formats {
+            header.push_format(format);
+        }
+
+        header
     }
 }
 
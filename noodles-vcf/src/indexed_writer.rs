@@ -0,0 +1,234 @@
+use std::io::{self, Write};
+
+use noodles_bgzf as bgzf;
+use noodles_tabix::{self as tabix, index::reference_sequence::bin::Chunk};
+
+use super::{Header, Record, Writer};
+
+/// A bgzipped VCF writer that builds a tabix index of the written records.
+///
+/// Records must be written in coordinate order.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_vcf::{self as vcf, header::Contig};
+///
+/// let mut writer = vcf::IndexedWriter::new(Vec::new());
+///
+/// let header = vcf::Header::builder()
+///     .add_contig(Contig::new(String::from("sq0")))
+///     .build();
+///
+/// writer.write_header(&header)?;
+///
+/// let record = vcf::Record::builder()
+///     .set_chromosome("sq0".parse()?)
+///     .set_position(1)
+///     .set_reference_bases("A".parse()?)
+///     .build()?;
+///
+/// writer.write_record(&record)?;
+///
+/// let (_, index) = writer.finish()?;
+/// assert_eq!(index.reference_sequence_names(), [String::from("sq0")]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::Writer<W>>,
+    indexer: tabix::index::Indexer,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexed VCF writer.
+    ///
+    /// The given writer receives the bgzipped VCF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let writer = vcf::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        let mut indexer = tabix::Index::indexer();
+        indexer.set_header(tabix::index::header::Builder::vcf().build());
+
+        Self {
+            inner: Writer::new(bgzf::Writer::new(inner)),
+            indexer,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let writer = vcf::IndexedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref().get_ref()
+    }
+
+    /// Writes a VCF header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut writer = vcf::IndexedWriter::new(Vec::new());
+    ///
+    /// let header = vcf::Header::default();
+    /// writer.write_header(&header)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_header(&mut self, header: &Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a VCF record and adds it to the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(1)
+    ///     .set_reference_bases("A".parse()?)
+    ///     .build()?;
+    ///
+    /// let mut writer = vcf::IndexedWriter::new(Vec::new());
+    /// writer.write_record(&record)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        let start = self.inner.get_ref().virtual_position();
+        self.inner.write_record(record)?;
+        let end = self.inner.get_ref().virtual_position();
+
+        crate::add_record(&mut self.indexer, record, Chunk::new(start, end))
+    }
+
+    /// Finishes the output stream and returns the underlying writer and the built tabix index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf as vcf;
+    /// let writer = vcf::IndexedWriter::new(Vec::new());
+    /// let (data, index) = writer.finish()?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn finish(self) -> io::Result<(W, tabix::Index)> {
+        let inner = self.inner.into_inner().finish()?;
+        let index = self.indexer.build();
+        Ok((inner, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles::Region;
+
+    use crate::{header::Contig, Reader};
+
+    use super::*;
+
+    #[test]
+    fn test_finish() -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = IndexedWriter::new(Vec::new());
+
+        let header = Header::builder()
+            .add_contig(Contig::new(String::from("sq0")))
+            .add_contig(Contig::new(String::from("sq1")))
+            .build();
+
+        writer.write_header(&header)?;
+
+        for (chromosome, position) in &[("sq0", 8), ("sq0", 13), ("sq0", 100000), ("sq1", 5)] {
+            let record = Record::builder()
+                .set_chromosome(chromosome.parse()?)
+                .set_position(*position)
+                .set_reference_bases("A".parse()?)
+                .build()?;
+
+            writer.write_record(&record)?;
+        }
+
+        let (data, index) = writer.finish()?;
+
+        assert_eq!(
+            index.reference_sequence_names(),
+            [String::from("sq0"), String::from("sq1")]
+        );
+        assert_eq!(index.reference_sequences()[0].intervals().len(), 7);
+
+        let mut reader = Reader::new(bgzf::Reader::new(Cursor::new(data)));
+        reader.read_header()?;
+
+        let region = Region::mapped("sq0", 10, 100000);
+        let positions = reader
+            .query(&index, &region)?
+            .map(|result| result.map(|record| record.position()))
+            .collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(positions, [13, 100000]);
+
+        let region = Region::mapped("sq1", 1, 10);
+        assert_eq!(reader.query(&index, &region)?.count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finish_with_telomere() -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = IndexedWriter::new(Vec::new());
+
+        let header = Header::builder()
+            .add_contig(Contig::new(String::from("sq0")))
+            .build();
+
+        writer.write_header(&header)?;
+
+        for position in &[0, 8] {
+            let record = Record::builder()
+                .set_chromosome("sq0".parse()?)
+                .set_position(*position)
+                .set_reference_bases("N".parse()?)
+                .build()?;
+
+            writer.write_record(&record)?;
+        }
+
+        let (data, index) = writer.finish()?;
+
+        let mut reader = Reader::new(bgzf::Reader::new(Cursor::new(data)));
+        reader.read_header()?;
+
+        let region = Region::mapped("sq0", 1, 10);
+        let positions = reader
+            .query(&index, &region)?
+            .map(|result| result.map(|record| record.position()))
+            .collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(positions, [8]);
+
+        Ok(())
+    }
+}
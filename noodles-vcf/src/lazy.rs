@@ -0,0 +1,13 @@
+//! Lazily decoded VCF record and sample selection.
+//!
+//! A [`Record`] keeps the raw text of a VCF record. Only the bounds of the site fields are
+//! located when it is read. Fields, including the samples, are decoded on demand, which avoids
+//! parsing every genotype field of every sample when only some are used.
+//!
+//! A [`Selection`] is a subset of samples and genotype fields. Selecting from a record copies the
+//! raw text of the selected values, so untouched values are written back out as read.
+
+pub mod record;
+pub mod selection;
+
+pub use self::{record::Record, selection::Selection};
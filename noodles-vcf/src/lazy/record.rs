@@ -0,0 +1,469 @@
+//! Lazy VCF record and samples.
+
+pub mod samples;
+
+pub use self::samples::Samples;
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    record::{Field, ParseError},
+    Header,
+};
+
+use super::Selection;
+
+const FIELD_DELIMITER: char = '\t';
+const FORMAT_DELIMITER: char = ':';
+const MISSING_VALUE: &str = ".";
+
+static SITE_FIELDS: [Field; 8] = [
+    Field::Chromosome,
+    Field::Position,
+    Field::Ids,
+    Field::ReferenceBases,
+    Field::AlternateBases,
+    Field::QualityScore,
+    Field::FilterStatus,
+    Field::Info,
+];
+
+const FORMAT_FIELD_INDEX: usize = SITE_FIELDS.len();
+
+/// A lazily decoded VCF record.
+///
+/// This holds the raw text of a record and the bounds of its site fields and genotype format.
+/// No field is validated until it is decoded.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Record {
+    buf: String,
+    bounds: Vec<usize>,
+}
+
+impl Record {
+    pub(crate) fn buf_mut(&mut self) -> &mut String {
+        &mut self.buf
+    }
+
+    // Locates the end of each site field and, if present, the genotype format.
+    pub(crate) fn index(&mut self) -> Result<(), ParseError> {
+        self.bounds.clear();
+
+        self.bounds.extend(
+            self.buf
+                .match_indices(FIELD_DELIMITER)
+                .map(|(i, _)| i)
+                .take(FORMAT_FIELD_INDEX + 1),
+        );
+
+        if self.bounds.len() <= FORMAT_FIELD_INDEX {
+            self.bounds.push(self.buf.len());
+        }
+
+        if self.bounds.len() < SITE_FIELDS.len() {
+            let field = SITE_FIELDS[self.bounds.len()];
+            self.bounds.clear();
+            return Err(ParseError::MissingField(field));
+        }
+
+        Ok(())
+    }
+
+    fn field(&self, i: usize) -> &str {
+        let start = if i == 0 { 0 } else { self.bounds[i - 1] + 1 };
+        &self.buf[start..self.bounds[i]]
+    }
+
+    fn site(&self) -> &str {
+        &self.buf[..self.bounds[FORMAT_FIELD_INDEX - 1]]
+    }
+
+    /// Returns the raw chromosome (`CHROM`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.".parse()?;
+    /// assert_eq!(record.chromosome(), "sq0");
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn chromosome(&self) -> &str {
+        self.field(0)
+    }
+
+    /// Returns the raw position (`POS`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.".parse()?;
+    /// assert_eq!(record.position(), "1");
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn position(&self) -> &str {
+        self.field(1)
+    }
+
+    /// Returns the raw IDs (`ID`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\tnd0\tA\t.\t.\tPASS\t.".parse()?;
+    /// assert_eq!(record.ids(), "nd0");
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn ids(&self) -> &str {
+        self.field(2)
+    }
+
+    /// Returns the raw reference bases (`REF`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.".parse()?;
+    /// assert_eq!(record.reference_bases(), "A");
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn reference_bases(&self) -> &str {
+        self.field(3)
+    }
+
+    /// Returns the raw alternate bases (`ALT`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\t.\tA\tC,G\t.\tPASS\t.".parse()?;
+    /// assert_eq!(record.alternate_bases(), "C,G");
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn alternate_bases(&self) -> &str {
+        self.field(4)
+    }
+
+    /// Returns the raw quality score (`QUAL`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t13\tPASS\t.".parse()?;
+    /// assert_eq!(record.quality_score(), "13");
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn quality_score(&self) -> &str {
+        self.field(5)
+    }
+
+    /// Returns the raw filter status (`FILTER`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.".parse()?;
+    /// assert_eq!(record.filter_status(), "PASS");
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn filter_status(&self) -> &str {
+        self.field(6)
+    }
+
+    /// Returns the raw additional information (`INFO`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\tNS=2".parse()?;
+    /// assert_eq!(record.info(), "NS=2");
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn info(&self) -> &str {
+        self.field(7)
+    }
+
+    /// Returns the raw genotype format (`FORMAT`), if present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:GQ\t0|0:13".parse()?;
+    /// assert_eq!(record.format(), Some("GT:GQ"));
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.".parse()?;
+    /// assert!(record.format().is_none());
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn format(&self) -> Option<&str> {
+        if self.bounds.len() > FORMAT_FIELD_INDEX {
+            Some(self.field(FORMAT_FIELD_INDEX))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the raw samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:GQ\t0|0:13".parse()?;
+    /// assert_eq!(record.samples().get(0), Some("0|0:13"));
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn samples(&self) -> Samples<'_> {
+        let buf = match self.bounds.get(FORMAT_FIELD_INDEX) {
+            Some(&end) if end < self.buf.len() => &self.buf[end + 1..],
+            _ => "",
+        };
+
+        Samples::new(buf)
+    }
+
+    /// Decodes the site fields, i.e., all fields but the genotype format and samples, using the
+    /// definitions of a VCF header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, lazy};
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0".parse()?;
+    /// let site_record = record.try_into_site_record(&vcf::Header::default())?;
+    ///
+    /// assert_eq!(site_record.position(), 1);
+    /// assert!(site_record.genotypes().is_empty());
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn try_into_site_record(&self, header: &Header) -> Result<crate::Record, ParseError> {
+        crate::Record::try_from_str_header(self.site(), header)
+    }
+
+    /// Decodes all fields, including the samples, using the definitions of a VCF header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, lazy};
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0".parse()?;
+    /// let vcf_record = record.try_into_vcf_record(&vcf::Header::default())?;
+    ///
+    /// assert_eq!(vcf_record.genotypes().len(), 1);
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn try_into_vcf_record(&self, header: &Header) -> Result<crate::Record, ParseError> {
+        crate::Record::try_from_str_header(&self.buf, header)
+    }
+
+    /// Creates a record with only the selected samples and genotype fields.
+    ///
+    /// Site fields and, if all genotype fields are selected, the genotype format and selected
+    /// samples are copied as is. Otherwise, only the raw values of the selected keys are copied,
+    /// in the order of the selection. A key that is not in the genotype format is skipped, and a
+    /// value missing from a sample (i.e., a dropped trailing field) is written as missing (`.`).
+    ///
+    /// If the record has no genotype format or none of the selected keys are in it, the result
+    /// only has site fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, lazy, record::genotype::field::Key};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:GQ\t0|0:13\t1/1:8".parse()?;
+    ///
+    /// let selection = lazy::Selection::try_from_header(&header, &["sample1"])?;
+    /// assert_eq!(
+    ///     record.select(&selection).to_string(),
+    ///     "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:GQ\t1/1:8"
+    /// );
+    ///
+    /// let selection = selection.set_keys(vec![Key::Genotype]);
+    /// assert_eq!(
+    ///     record.select(&selection).to_string(),
+    ///     "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t1/1"
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn select(&self, selection: &Selection) -> Self {
+        let mut buf = String::from(self.site());
+        let mut bounds = self.bounds[..FORMAT_FIELD_INDEX].to_vec();
+
+        let raw_format = match self.format() {
+            Some(raw_format) => raw_format,
+            None => return Self { buf, bounds },
+        };
+
+        let samples: Vec<_> = self.samples().iter().collect();
+
+        let selected_samples = selection
+            .sample_indices()
+            .iter()
+            .map(|&i| samples.get(i).copied().unwrap_or(MISSING_VALUE));
+
+        match selection.keys() {
+            None => {
+                buf.push(FIELD_DELIMITER);
+                buf.push_str(raw_format);
+                bounds.push(buf.len());
+
+                for sample in selected_samples {
+                    buf.push(FIELD_DELIMITER);
+                    buf.push_str(sample);
+                }
+            }
+            Some(keys) => {
+                let raw_keys: Vec<_> = raw_format.split(FORMAT_DELIMITER).collect();
+
+                let positions: Vec<_> = keys
+                    .iter()
+                    .filter_map(|key| raw_keys.iter().position(|k| *k == key.as_ref()))
+                    .collect();
+
+                if positions.is_empty() {
+                    return Self { buf, bounds };
+                }
+
+                buf.push(FIELD_DELIMITER);
+                push_values(&mut buf, &raw_keys, &positions);
+                bounds.push(buf.len());
+
+                for sample in selected_samples {
+                    let values: Vec<_> = sample.split(FORMAT_DELIMITER).collect();
+                    buf.push(FIELD_DELIMITER);
+                    push_values(&mut buf, &values, &positions);
+                }
+            }
+        }
+
+        Self { buf, bounds }
+    }
+}
+
+fn push_values(buf: &mut String, values: &[&str], positions: &[usize]) {
+    for (i, &position) in positions.iter().enumerate() {
+        if i > 0 {
+            buf.push(FORMAT_DELIMITER);
+        }
+
+        buf.push_str(values.get(position).copied().unwrap_or(MISSING_VALUE));
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.buf)
+    }
+}
+
+impl FromStr for Record {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut record = Self {
+            buf: s.into(),
+            bounds: Vec::new(),
+        };
+
+        record.index()?;
+
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::record::genotype::field::Key;
+
+    use super::*;
+
+    #[test]
+    fn test_from_str() -> Result<(), ParseError> {
+        let record: Record = "sq0\t1\t.\tA\tC\t13\tPASS\tNS=2".parse()?;
+        assert_eq!(record.chromosome(), "sq0");
+        assert_eq!(record.position(), "1");
+        assert_eq!(record.ids(), ".");
+        assert_eq!(record.reference_bases(), "A");
+        assert_eq!(record.alternate_bases(), "C");
+        assert_eq!(record.quality_score(), "13");
+        assert_eq!(record.filter_status(), "PASS");
+        assert_eq!(record.info(), "NS=2");
+        assert!(record.format().is_none());
+        assert!(record.samples().is_empty());
+
+        let record: Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT".parse()?;
+        assert_eq!(record.format(), Some("GT"));
+        assert!(record.samples().is_empty());
+
+        let record: Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0\t0/1".parse()?;
+        assert_eq!(record.info(), ".");
+        assert_eq!(record.format(), Some("GT"));
+        assert_eq!(record.samples().len(), 2);
+
+        assert_eq!(
+            "sq0\t1\t.\tA".parse::<Record>(),
+            Err(ParseError::MissingField(Field::AlternateBases))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_select() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .add_sample_name("sample2")
+            .build();
+
+        let record: Record =
+            "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:AD:GQ\t0|0:5,0:13\t0/1:3,2\t1/1:0,8:21".parse()?;
+
+        let selection = Selection::try_from_header(&header, &["sample2", "sample0"])?;
+        let actual = record.select(&selection);
+        assert_eq!(
+            actual.to_string(),
+            "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:AD:GQ\t1/1:0,8:21\t0|0:5,0:13"
+        );
+        assert_eq!(actual.format(), Some("GT:AD:GQ"));
+        assert_eq!(actual.samples().len(), 2);
+
+        let selection = Selection::try_from_header(&header, &["sample1"])?
+            .set_keys(vec![Key::Genotype, Key::ConditionalGenotypeQuality]);
+        let actual = record.select(&selection);
+        assert_eq!(
+            actual.to_string(),
+            "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:GQ\t0/1:."
+        );
+        assert_eq!(actual.format(), Some("GT:GQ"));
+
+        let selection = Selection::try_from_header(&header, &["sample0"])?
+            .set_keys(vec![Key::GenotypeCopyNumber]);
+        let actual = record.select(&selection);
+        assert_eq!(actual.to_string(), "sq0\t1\t.\tA\tC\t.\tPASS\t.");
+        assert!(actual.format().is_none());
+
+        let record: Record = "sq0\t1\t.\tA\tC\t.\tPASS\t.".parse()?;
+        let selection = Selection::try_from_header(&header, &["sample0"])?;
+        assert_eq!(record.select(&selection), record);
+
+        Ok(())
+    }
+}
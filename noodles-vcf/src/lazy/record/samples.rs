@@ -0,0 +1,237 @@
+//! Lazy VCF record samples.
+
+use std::iter;
+
+use crate::record::{
+    format::Format,
+    genotype::{self, field::value::genotype as gt},
+    Genotype,
+};
+
+const DELIMITER: char = '\t';
+const FIELD_DELIMITER: char = ':';
+const GENOTYPE_KEY: &str = "GT";
+
+/// The raw samples of a lazy VCF record.
+///
+/// The bounds of the samples are located once on creation, so each sample can be accessed by index
+/// without scanning the preceding samples.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Samples<'a> {
+    buf: &'a str,
+    ends: Vec<usize>,
+}
+
+impl<'a> Samples<'a> {
+    pub(crate) fn new(buf: &'a str) -> Self {
+        let ends = if buf.is_empty() {
+            Vec::new()
+        } else {
+            buf.match_indices(DELIMITER)
+                .map(|(i, _)| i)
+                .chain(iter::once(buf.len()))
+                .collect()
+        };
+
+        Self { buf, ends }
+    }
+
+    /// Returns the number of samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0\t1/1".parse()?;
+    /// assert_eq!(record.samples().len(), 2);
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns whether there are any samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.".parse()?;
+    /// assert!(record.samples().is_empty());
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the raw sample at the given index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:GQ\t0|0:13\t1/1:8".parse()?;
+    /// let samples = record.samples();
+    ///
+    /// assert_eq!(samples.get(1), Some("1/1:8"));
+    /// assert!(samples.get(2).is_none());
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn get(&self, i: usize) -> Option<&'a str> {
+        let end = *self.ends.get(i)?;
+
+        let start = match i.checked_sub(1) {
+            Some(j) => self.ends[j] + 1,
+            None => 0,
+        };
+
+        Some(&self.buf[start..end])
+    }
+
+    /// Returns an iterator over the raw samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0\t1/1".parse()?;
+    /// let samples: Vec<_> = record.samples().iter().collect();
+    ///
+    /// assert_eq!(samples, ["0|0", "1/1"]);
+    /// # Ok::<(), noodles_vcf::record::ParseError>(())
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &'a str> {
+        let buf = self.buf;
+        let samples = if buf.is_empty() {
+            None
+        } else {
+            Some(buf.split(DELIMITER))
+        };
+
+        samples.into_iter().flatten()
+    }
+
+    /// Decodes the sample at the given index for the given genotype format.
+    ///
+    /// This returns `None` if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:GQ\t0|0:13\t1/1:8".parse()?;
+    /// let format = record.format().expect("missing format").parse()?;
+    ///
+    /// let genotype = record.samples().get_genotype(1, &format).transpose()?;
+    /// assert_eq!(genotype.map(|g| g.len()), Some(2));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_genotype(
+        &self,
+        i: usize,
+        format: &Format,
+    ) -> Option<Result<Genotype, genotype::ParseError>> {
+        self.get(i)
+            .map(|sample| Genotype::from_str_format(sample, format))
+    }
+
+    /// Decodes only the genotype (`GT`) value of the sample at the given index for the given raw
+    /// genotype format.
+    ///
+    /// If present, the genotype is always the first field of a sample, so this does not parse any
+    /// other field of the sample. This returns `None` if the index is out of bounds or the format
+    /// does not start with the genotype (`GT`) key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::lazy;
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT:GQ\t0|0:13\t1/1:8".parse()?;
+    /// let format = record.format().expect("missing format");
+    ///
+    /// let genotype = record.samples().get_genotype_value(1, format).transpose()?;
+    /// assert_eq!(genotype, Some("1/1".parse()?));
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGQ:DP\t13:5".parse()?;
+    /// let format = record.format().expect("missing format");
+    /// assert!(record.samples().get_genotype_value(0, format).is_none());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_genotype_value(
+        &self,
+        i: usize,
+        format: &str,
+    ) -> Option<Result<gt::Genotype, gt::ParseError>> {
+        if format.split(FIELD_DELIMITER).next() != Some(GENOTYPE_KEY) {
+            return None;
+        }
+
+        self.get(i).map(|sample| {
+            sample
+                .split(FIELD_DELIMITER)
+                .next()
+                .unwrap_or_default()
+                .parse()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter() {
+        let samples = Samples::new("");
+        assert_eq!(samples.iter().count(), 0);
+
+        let samples = Samples::new("0|0:13\t.\t1/1");
+        let actual: Vec<_> = samples.iter().collect();
+        assert_eq!(actual, ["0|0:13", ".", "1/1"]);
+    }
+
+    #[test]
+    fn test_get() {
+        let samples = Samples::new("");
+        assert!(samples.get(0).is_none());
+
+        let samples = Samples::new("0|0:13\t.\t1/1");
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples.get(0), Some("0|0:13"));
+        assert_eq!(samples.get(1), Some("."));
+        assert_eq!(samples.get(2), Some("1/1"));
+        assert!(samples.get(3).is_none());
+    }
+
+    #[test]
+    fn test_get_genotype_value() -> Result<(), gt::ParseError> {
+        let samples = Samples::new("1/1:13\t.\t0|1\t:13");
+
+        assert_eq!(
+            samples.get_genotype_value(0, "GT:GQ").transpose()?,
+            Some("1/1".parse()?)
+        );
+        assert_eq!(
+            samples.get_genotype_value(1, "GT:GQ").transpose()?,
+            Some(".".parse()?)
+        );
+        assert_eq!(
+            samples.get_genotype_value(2, "GT").transpose()?,
+            Some("0|1".parse()?)
+        );
+        assert_eq!(
+            samples.get_genotype_value(3, "GT:GQ"),
+            Some(Err(gt::ParseError::Empty))
+        );
+        assert!(samples.get_genotype_value(4, "GT:GQ").is_none());
+
+        assert!(samples.get_genotype_value(0, "GQ:GT").is_none());
+        assert!(samples.get_genotype_value(0, "GQ").is_none());
+
+        Ok(())
+    }
+}
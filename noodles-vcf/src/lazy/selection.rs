@@ -0,0 +1,279 @@
+//! Lazy VCF record sample selection.
+
+use std::{
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+use crate::{record::genotype::field::Key, Header};
+
+/// A selection of samples and, optionally, genotype fields.
+///
+/// Samples are selected by name and resolved to their indices using the sample names of a VCF
+/// header. Selected samples are kept in the given order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Selection {
+    sample_names: Vec<String>,
+    sample_indices: Vec<usize>,
+    keys: Option<Vec<Key>>,
+}
+
+/// An error returned when a selection fails to resolve using a VCF header.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TryFromHeaderError {
+    /// A sample name is not in the header.
+    MissingSampleName(String),
+    /// A sample name is selected more than once.
+    DuplicateSampleName(String),
+}
+
+impl error::Error for TryFromHeaderError {}
+
+impl fmt::Display for TryFromHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSampleName(name) => write!(f, "missing sample name: {}", name),
+            Self::DuplicateSampleName(name) => write!(f, "duplicate sample name: {}", name),
+        }
+    }
+}
+
+impl Selection {
+    /// Creates a selection of the given samples using the sample names of a VCF header.
+    ///
+    /// All genotype fields are selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, lazy};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let selection = lazy::Selection::try_from_header(&header, &["sample1"])?;
+    /// assert_eq!(selection.sample_indices(), [1]);
+    ///
+    /// assert!(lazy::Selection::try_from_header(&header, &["sample2"]).is_err());
+    /// # Ok::<(), lazy::selection::TryFromHeaderError>(())
+    /// ```
+    pub fn try_from_header<S>(
+        header: &Header,
+        sample_names: &[S],
+    ) -> Result<Self, TryFromHeaderError>
+    where
+        S: AsRef<str>,
+    {
+        let mut header_indices: HashMap<&str, usize> =
+            HashMap::with_capacity(header.sample_names().len());
+
+        for (i, name) in header.sample_names().iter().enumerate() {
+            header_indices.entry(name.as_str()).or_insert(i);
+        }
+
+        let mut selected_names = HashSet::new();
+        let mut names = Vec::with_capacity(sample_names.len());
+        let mut indices = Vec::with_capacity(sample_names.len());
+
+        for sample_name in sample_names {
+            let name = sample_name.as_ref();
+
+            if !selected_names.insert(name) {
+                return Err(TryFromHeaderError::DuplicateSampleName(name.into()));
+            }
+
+            let i = header_indices
+                .get(name)
+                .copied()
+                .ok_or_else(|| TryFromHeaderError::MissingSampleName(name.into()))?;
+
+            names.push(name.into());
+            indices.push(i);
+        }
+
+        Ok(Self {
+            sample_names: names,
+            sample_indices: indices,
+            keys: None,
+        })
+    }
+
+    /// Sets the selected genotype fields.
+    ///
+    /// Fields are written in the given order, except the genotype (`GT`), which is moved to be
+    /// the first key if selected, as it must lead a genotype format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, lazy, record::genotype::field::Key};
+    ///
+    /// let header = vcf::Header::builder().add_sample_name("sample0").build();
+    ///
+    /// let selection = lazy::Selection::try_from_header(&header, &["sample0"])?
+    ///     .set_keys(vec![Key::ConditionalGenotypeQuality, Key::Genotype]);
+    ///
+    /// assert_eq!(
+    ///     selection.keys(),
+    ///     Some(&[Key::Genotype, Key::ConditionalGenotypeQuality][..])
+    /// );
+    /// # Ok::<(), lazy::selection::TryFromHeaderError>(())
+    /// ```
+    pub fn set_keys(mut self, mut keys: Vec<Key>) -> Self {
+        if let Some(i) = keys.iter().position(|key| key == &Key::Genotype) {
+            keys[..=i].rotate_right(1);
+        }
+
+        self.keys = Some(keys);
+        self
+    }
+
+    /// Returns the selected sample names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, lazy};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let selection = lazy::Selection::try_from_header(&header, &["sample1", "sample0"])?;
+    ///
+    /// assert_eq!(selection.sample_names(), [
+    ///     String::from("sample1"),
+    ///     String::from("sample0"),
+    /// ]);
+    /// # Ok::<(), lazy::selection::TryFromHeaderError>(())
+    /// ```
+    pub fn sample_names(&self) -> &[String] {
+        &self.sample_names
+    }
+
+    /// Returns the indices of the selected samples in the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, lazy};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let selection = lazy::Selection::try_from_header(&header, &["sample1", "sample0"])?;
+    /// assert_eq!(selection.sample_indices(), [1, 0]);
+    /// # Ok::<(), lazy::selection::TryFromHeaderError>(())
+    /// ```
+    pub fn sample_indices(&self) -> &[usize] {
+        &self.sample_indices
+    }
+
+    /// Returns the selected genotype fields.
+    ///
+    /// This returns `None` if all genotype fields are selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, lazy};
+    /// let header = vcf::Header::builder().add_sample_name("sample0").build();
+    /// let selection = lazy::Selection::try_from_header(&header, &["sample0"])?;
+    /// assert!(selection.keys().is_none());
+    /// # Ok::<(), lazy::selection::TryFromHeaderError>(())
+    /// ```
+    pub fn keys(&self) -> Option<&[Key]> {
+        self.keys.as_deref()
+    }
+
+    /// Returns a copy of the given header with only the selected sample names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, lazy};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let selection = lazy::Selection::try_from_header(&header, &["sample1"])?;
+    /// let selected_header = selection.header(&header);
+    ///
+    /// assert_eq!(selected_header.sample_names(), [String::from("sample1")]);
+    /// # Ok::<(), lazy::selection::TryFromHeaderError>(())
+    /// ```
+    pub fn header(&self, header: &Header) -> Header {
+        let mut header = header.clone();
+        *header.sample_names_mut() = self.sample_names.clone();
+        header
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_header() {
+        let header = Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .build();
+
+        let selection = Selection::try_from_header(&header, &["sample1", "sample0"]);
+        assert_eq!(
+            selection.map(|s| s.sample_indices().to_vec()),
+            Ok(vec![1, 0])
+        );
+
+        let names: [&str; 0] = [];
+        let selection = Selection::try_from_header(&header, &names);
+        assert_eq!(selection.map(|s| s.sample_indices().is_empty()), Ok(true));
+
+        assert_eq!(
+            Selection::try_from_header(&header, &["sample2"]),
+            Err(TryFromHeaderError::MissingSampleName(String::from(
+                "sample2"
+            )))
+        );
+
+        assert_eq!(
+            Selection::try_from_header(&header, &["sample0", "sample0"]),
+            Err(TryFromHeaderError::DuplicateSampleName(String::from(
+                "sample0"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_set_keys() {
+        let header = Header::builder().add_sample_name("sample0").build();
+        let selection = Selection::try_from_header(&header, &["sample0"]).unwrap();
+
+        let actual = selection.clone().set_keys(vec![
+            Key::ConditionalGenotypeQuality,
+            Key::ReadDepth,
+            Key::Genotype,
+        ]);
+        assert_eq!(
+            actual.keys(),
+            Some(
+                &[
+                    Key::Genotype,
+                    Key::ConditionalGenotypeQuality,
+                    Key::ReadDepth
+                ][..]
+            )
+        );
+
+        let actual = selection.set_keys(vec![Key::ConditionalGenotypeQuality]);
+        assert_eq!(actual.keys(), Some(&[Key::ConditionalGenotypeQuality][..]));
+    }
+}
@@ -21,8 +21,83 @@
 //! ```
 
 pub mod header;
+mod indexed_writer;
+pub mod lazy;
+pub mod merge;
+pub mod normalization;
 mod reader;
 pub mod record;
+pub mod structural_variant;
 mod writer;
 
-pub use self::{header::Header, reader::Reader, record::Record, writer::Writer};
+pub use self::{
+    header::Header, indexed_writer::IndexedWriter, reader::Reader, record::Record, writer::Writer,
+};
+
+use std::{convert::TryFrom, fs::File, io, path::Path};
+
+use noodles_bgzf as bgzf;
+use noodles_tabix::{self as tabix, index::reference_sequence::bin::Chunk};
+
+/// Indexes a bgzipped VCF file.
+///
+/// The resulting tabix index uses the VCF preset, i.e., reference sequence names are in column
+/// 1, start positions in column 2, and lines starting with `#` are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_vcf as vcf;
+/// let index = vcf::index("sample.vcf.gz")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P) -> io::Result<tabix::Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(bgzf::Reader::new).map(Reader::new)?;
+
+    reader.read_header()?;
+
+    let mut indexer = tabix::Index::indexer();
+    indexer.set_header(tabix::index::header::Builder::vcf().build());
+
+    let mut buf = String::new();
+
+    loop {
+        buf.clear();
+
+        let start = reader.virtual_position();
+
+        if reader.read_record(&mut buf)? == 0 {
+            break;
+        }
+
+        let end = reader.virtual_position();
+
+        let record: Record = buf
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        add_record(&mut indexer, &record, Chunk::new(start, end))?;
+    }
+
+    Ok(indexer.build())
+}
+
+fn add_record(
+    indexer: &mut tabix::index::Indexer,
+    record: &Record,
+    chunk: Chunk,
+) -> io::Result<()> {
+    let start = u32::try_from(record.position())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let end =
+        u32::try_from(record.end()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    indexer.add_record(&record.chromosome().to_string(), start, end, chunk);
+
+    Ok(())
+}
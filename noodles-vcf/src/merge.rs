@@ -0,0 +1,1064 @@
+//! Multi-sample VCF merging.
+//!
+//! A [`Merger`] combines position-sorted VCFs of different samples into a single VCF, similar to
+//! `bcftools merge`. Records of each input at the same chromosome and position with compatible
+//! reference bases, i.e., one is a prefix of the other, are merged into one record. For a merged
+//! record,
+//!
+//!   * the reference bases are the longest of the merged records, and the alternate bases of
+//!     shorter records are extended to match;
+//!   * the alternate bases are the union of the alternate bases of the merged records, in order of
+//!     appearance;
+//!   * genotypes (`GT`) and values with a number of `A`, `R`, or `G` are remapped to the merged
+//!     alleles; and
+//!   * samples of inputs without a record at the position are set to missing (`./.`).
+//!
+//! The allele count (`AC`) and total allele count (`AN`) info fields are recomputed from the
+//! merged genotypes. Otherwise, the first info value of a key is kept.
+//!
+//! # Examples
+//!
+//! ## Merge VCFs of different samples
+//!
+//! ```no_run
+//! # use std::{fs::File, io::{self, BufReader}};
+//! use noodles_vcf::{self as vcf, merge::Merger};
+//!
+//! let readers = ["sample0.vcf", "sample1.vcf"]
+//!     .iter()
+//!     .map(|src| File::open(src).map(BufReader::new).map(vcf::Reader::new))
+//!     .collect::<io::Result<_>>()?;
+//!
+//! let mut merger = Merger::new(readers)?;
+//!
+//! let mut writer = vcf::Writer::new(io::stdout());
+//! writer.write_header(merger.header())?;
+//!
+//! for result in merger.records() {
+//!     let record = result?;
+//!     writer.write_record(&record)?;
+//! }
+//! # Ok::<(), io::Error>(())
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    convert::TryFrom,
+    io, iter,
+};
+
+use crate::{
+    header::{Number, SampleNameMerge},
+    record::{
+        alternate_bases::Allele,
+        genotype::{
+            self,
+            field::value::{
+                genotype::{allele::Phasing, Allele as GenotypeAllele},
+                Genotype as GenotypeValue,
+            },
+            genotype_count, genotype_index, genotype_value, ploidy,
+        },
+        info,
+        reference_bases::Base,
+        AlternateBases, FilterStatus, Format, Genotype, Ids, Info, QualityScore, ReferenceBases,
+    },
+    Header, Reader, Record,
+};
+
+const MISSING_GENOTYPE_PLOIDY: usize = 2;
+
+/// A VCF merger.
+///
+/// A merger reads records from multiple VCF readers and merges the records at the same site. The
+/// records of each reader must be sorted by position, and the records of a chromosome must be
+/// contiguous. Chromosomes with a contig (`contig`) in the merged header must be in the same order
+/// as the contigs. Other chromosomes are merged in the order they are first read.
+pub struct Merger<R> {
+    readers: Vec<Reader<R>>,
+    headers: Vec<Header>,
+    header: Header,
+    chromosomes: HashMap<String, usize>,
+    contig_count: usize,
+    pending: Vec<Option<Record>>,
+    queue: BinaryHeap<Reverse<(usize, i32, usize)>>,
+    last_keys: Vec<Option<(usize, i32)>>,
+    seen_chromosomes: Vec<HashSet<usize>>,
+    line_buf: String,
+}
+
+impl<R> Merger<R>
+where
+    R: io::BufRead,
+{
+    /// Creates a VCF merger.
+    ///
+    /// This reads the headers of the given readers and merges them. The sample names of the
+    /// readers must be distinct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, merge::Merger};
+    ///
+    /// let data0 = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+    /// ";
+    ///
+    /// let data1 = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+    /// ";
+    ///
+    /// let readers = vec![vcf::Reader::new(&data0[..]), vcf::Reader::new(&data1[..])];
+    /// let merger = Merger::new(readers)?;
+    ///
+    /// assert_eq!(
+    ///     merger.header().sample_names(),
+    ///     [String::from("sample0"), String::from("sample1")]
+    /// );
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn new(mut readers: Vec<Reader<R>>) -> io::Result<Self> {
+        let mut headers = Vec::with_capacity(readers.len());
+
+        for reader in &mut readers {
+            let header: Header = reader
+                .read_header()?
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            headers.push(header);
+        }
+
+        let mut header_iter = headers.iter();
+
+        let header = match header_iter.next() {
+            Some(first_header) => header_iter.try_fold(first_header.clone(), |header, other| {
+                header
+                    .merge(other, SampleNameMerge::Concatenate)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })?,
+            None => Header::default(),
+        };
+
+        let chromosomes: HashMap<_, _> = header
+            .contigs()
+            .iter()
+            .enumerate()
+            .map(|(i, contig)| (contig.id().into(), i))
+            .collect();
+
+        let contig_count = chromosomes.len();
+
+        let reader_count = readers.len();
+
+        let mut merger = Self {
+            readers,
+            headers,
+            header,
+            chromosomes,
+            contig_count,
+            pending: vec![None; reader_count],
+            queue: BinaryHeap::with_capacity(reader_count),
+            last_keys: vec![None; reader_count],
+            seen_chromosomes: vec![HashSet::new(); reader_count],
+            line_buf: String::new(),
+        };
+
+        for i in 0..reader_count {
+            merger.advance(i)?;
+        }
+
+        Ok(merger)
+    }
+
+    /// Returns the merged VCF header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, merge::Merger};
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// ";
+    ///
+    /// let merger = Merger::new(vec![vcf::Reader::new(&data[..])])?;
+    /// assert_eq!(merger.header().file_format(), "VCFv4.3");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Reads and merges the records at the next site.
+    ///
+    /// This returns `None` when all readers are exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, merge::Merger};
+    ///
+    /// let data0 = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+    /// sq0\t8\t.\tA\tC\t.\tPASS\t.\tGT\t0/1
+    /// ";
+    ///
+    /// let data1 = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+    /// sq0\t8\t.\tA\tG\t.\tPASS\t.\tGT\t1/1
+    /// ";
+    ///
+    /// let readers = vec![vcf::Reader::new(&data0[..]), vcf::Reader::new(&data1[..])];
+    /// let mut merger = Merger::new(readers)?;
+    ///
+    /// let record = merger.read_record()?.expect("missing record");
+    /// assert_eq!(record.alternate_bases().to_string(), "C,G");
+    /// assert_eq!(record.genotypes()[1].to_string(), "2/2");
+    ///
+    /// assert!(merger.read_record()?.is_none());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_record(&mut self) -> io::Result<Option<Record>> {
+        let (chromosome_index, position, i) = match self.queue.pop() {
+            Some(Reverse(entry)) => entry,
+            None => return Ok(None),
+        };
+
+        let mut candidates = vec![i];
+
+        while let Some(Reverse((c, p, j))) = self.queue.peek().copied() {
+            if (c, p) != (chromosome_index, position) {
+                break;
+            }
+
+            self.queue.pop();
+            candidates.push(j);
+        }
+
+        candidates.sort_unstable();
+
+        let mut reference_bases: Option<&ReferenceBases> = None;
+        let mut indices = Vec::new();
+
+        for &i in &candidates {
+            let record = match &self.pending[i] {
+                Some(record) => record,
+                None => continue,
+            };
+
+            let bases = record.reference_bases();
+
+            match reference_bases {
+                Some(merged_bases) if bases.starts_with(merged_bases) => {
+                    reference_bases = Some(bases);
+                }
+                Some(merged_bases) if merged_bases.starts_with(bases) => {}
+                Some(_) => continue,
+                None => reference_bases = Some(bases),
+            }
+
+            indices.push(i);
+        }
+
+        for &i in &candidates {
+            if !indices.contains(&i) {
+                self.queue.push(Reverse((chromosome_index, position, i)));
+            }
+        }
+
+        let mut records = vec![None; self.readers.len()];
+
+        for &i in &indices {
+            records[i] = self.pending[i].take();
+            self.advance(i)?;
+        }
+
+        self.merge_records(&records).map(Some)
+    }
+
+    /// Returns an iterator over merged records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, merge::Merger};
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t8\t.\tA\tC\t.\tPASS\t.
+    /// sq0\t13\t.\tG\tT\t.\tPASS\t.
+    /// ";
+    ///
+    /// let mut merger = Merger::new(vec![vcf::Reader::new(&data[..])])?;
+    /// let records: Vec<_> = merger.records().collect::<io::Result<_>>()?;
+    /// assert_eq!(records.len(), 2);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn records(&mut self) -> impl Iterator<Item = io::Result<Record>> + '_ {
+        iter::from_fn(move || self.read_record().transpose())
+    }
+
+    fn advance(&mut self, i: usize) -> io::Result<()> {
+        self.line_buf.clear();
+
+        if self.readers[i].read_record(&mut self.line_buf)? == 0 {
+            self.pending[i] = None;
+            return Ok(());
+        }
+
+        let record = Record::try_from_str_header(&self.line_buf, &self.headers[i])
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let chromosome_count = self.chromosomes.len();
+
+        let chromosome_index = *self
+            .chromosomes
+            .entry(record.chromosome().to_string())
+            .or_insert(chromosome_count);
+
+        let position = record.position();
+
+        if let Some((last_chromosome_index, last_position)) = self.last_keys[i] {
+            let is_unsorted = if chromosome_index == last_chromosome_index {
+                position < last_position
+            } else {
+                self.seen_chromosomes[i].contains(&chromosome_index)
+                    || (chromosome_index < self.contig_count
+                        && last_chromosome_index < self.contig_count
+                        && chromosome_index < last_chromosome_index)
+            };
+
+            if is_unsorted {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unsorted input: {}:{}", record.chromosome(), position),
+                ));
+            }
+        }
+
+        self.seen_chromosomes[i].insert(chromosome_index);
+        self.last_keys[i] = Some((chromosome_index, position));
+        self.pending[i] = Some(record);
+        self.queue.push(Reverse((chromosome_index, position, i)));
+
+        Ok(())
+    }
+
+    fn merge_records(&self, records: &[Option<Record>]) -> io::Result<Record> {
+        let present_records: Vec<_> = records.iter().flatten().collect();
+
+        let first_record = present_records
+            .first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no records to merge"))?;
+
+        let reference_bases = present_records
+            .iter()
+            .map(|record| record.reference_bases())
+            .max_by_key(|bases| bases.len())
+            .cloned()
+            .unwrap_or_else(|| first_record.reference_bases().clone());
+
+        let mut alternate_bases: Vec<Allele> = Vec::new();
+
+        let allele_maps: Vec<Option<Vec<usize>>> = records
+            .iter()
+            .map(|record| {
+                record.as_ref().map(|record| {
+                    let suffix = &reference_bases[record.reference_bases().len()..];
+                    let mut allele_map = vec![0];
+
+                    for allele in record.alternate_bases().iter() {
+                        let allele = extend_allele(allele, suffix);
+
+                        let j = match alternate_bases.iter().position(|a| a == &allele) {
+                            Some(j) => j,
+                            None => {
+                                alternate_bases.push(allele);
+                                alternate_bases.len() - 1
+                            }
+                        };
+
+                        allele_map.push(j + 1);
+                    }
+
+                    allele_map
+                })
+            })
+            .collect();
+
+        let allele_count = alternate_bases.len() + 1;
+
+        let mut builder = Record::builder()
+            .set_chromosome(first_record.chromosome().clone())
+            .set_position(first_record.position())
+            .set_ids(merge_ids(&present_records))
+            .set_reference_bases(reference_bases)
+            .set_alternate_bases(AlternateBases::from(alternate_bases))
+            .set_quality_score(merge_quality_scores(&present_records))
+            .set_filter_status(merge_filter_statuses(&present_records));
+
+        let genotypes = self.merge_genotypes(records, &allele_maps, allele_count)?;
+
+        if let Some((format, genotypes)) = genotypes {
+            builder = builder.set_format(format).set_genotypes(genotypes);
+        }
+
+        let mut record = builder
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        *record.info_mut() = merge_info(records, &allele_maps, allele_count, record.genotypes());
+
+        Ok(record)
+    }
+
+    fn merge_genotypes(
+        &self,
+        records: &[Option<Record>],
+        allele_maps: &[Option<Vec<usize>>],
+        allele_count: usize,
+    ) -> io::Result<Option<(Format, Vec<Genotype>)>> {
+        if self.header.sample_names().is_empty() {
+            return Ok(None);
+        }
+
+        let mut keys = vec![genotype::field::Key::Genotype];
+
+        for format in records
+            .iter()
+            .flatten()
+            .filter_map(|record| record.format())
+        {
+            for key in format.iter() {
+                if !keys.contains(key) {
+                    keys.push(key.clone());
+                }
+            }
+        }
+
+        let mut genotypes = Vec::with_capacity(self.header.sample_names().len());
+
+        for ((header, record), allele_map) in self.headers.iter().zip(records).zip(allele_maps) {
+            let sample_count = header.sample_names().len();
+
+            for j in 0..sample_count {
+                let fields = match (record, allele_map) {
+                    (Some(record), Some(allele_map)) => {
+                        let genotype = record.genotypes().get(j);
+                        merge_genotype_fields(&keys, genotype, allele_map, allele_count)
+                    }
+                    _ => missing_genotype_fields(&keys),
+                };
+
+                let genotype = Genotype::try_from(fields)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+                genotypes.push(genotype);
+            }
+        }
+
+        let format =
+            Format::try_from(keys).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Some((format, genotypes)))
+    }
+}
+
+fn extend_allele(allele: &Allele, suffix: &[Base]) -> Allele {
+    match allele {
+        Allele::Bases(bases) if !suffix.is_empty() => {
+            let mut bases = bases.clone();
+            bases.extend_from_slice(suffix);
+            Allele::Bases(bases)
+        }
+        _ => allele.clone(),
+    }
+}
+
+fn merge_ids(records: &[&Record]) -> Ids {
+    let mut ids: Vec<String> = Vec::new();
+
+    for id in records.iter().flat_map(|record| record.ids().iter()) {
+        if !ids.contains(id) {
+            ids.push(id.clone());
+        }
+    }
+
+    Ids::from(ids)
+}
+
+fn merge_quality_scores(records: &[&Record]) -> QualityScore {
+    records
+        .iter()
+        .filter_map(|record| *record.quality_score())
+        .fold(None, |max: Option<f32>, score| {
+            Some(max.map_or(score, |m| m.max(score)))
+        })
+        .and_then(|score| QualityScore::try_from(score).ok())
+        .unwrap_or_default()
+}
+
+fn merge_filter_statuses(records: &[&Record]) -> FilterStatus {
+    let mut filters: Vec<String> = Vec::new();
+    let mut is_pass = false;
+
+    for record in records {
+        match record.filter_status() {
+            FilterStatus::Missing => {}
+            FilterStatus::Pass => is_pass = true,
+            FilterStatus::Fail(ids) => {
+                for id in ids {
+                    if !filters.contains(id) {
+                        filters.push(id.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    if !filters.is_empty() {
+        FilterStatus::Fail(filters)
+    } else if is_pass {
+        FilterStatus::Pass
+    } else {
+        FilterStatus::Missing
+    }
+}
+
+fn merge_info(
+    records: &[Option<Record>],
+    allele_maps: &[Option<Vec<usize>>],
+    allele_count: usize,
+    genotypes: &[Genotype],
+) -> Info {
+    use info::field::{Key, Value};
+
+    let mut keys: Vec<&Key> = Vec::new();
+
+    for field in records
+        .iter()
+        .flatten()
+        .flat_map(|record| record.info().iter())
+    {
+        if !keys.contains(&field.key()) {
+            keys.push(field.key());
+        }
+    }
+
+    let allele_counts = count_alleles(genotypes, allele_count);
+
+    let mut fields = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        let value = match (key, &allele_counts) {
+            (Key::AlleleCount, Some(counts)) => Some(Value::IntegerArray(
+                counts[1..].iter().copied().map(Some).collect(),
+            )),
+            (Key::TotalAlleleCount, Some(counts)) => Some(Value::Integer(counts.iter().sum())),
+            _ => merge_info_value(key, records, allele_maps, allele_count),
+        };
+
+        fields.push(info::Field::new(key.clone(), value));
+    }
+
+    Info::from(fields)
+}
+
+fn merge_info_value(
+    key: &info::field::Key,
+    records: &[Option<Record>],
+    allele_maps: &[Option<Vec<usize>>],
+    allele_count: usize,
+) -> Option<info::field::Value> {
+    use info::field::Value;
+
+    let number = key.number();
+
+    let values: Vec<_> = records
+        .iter()
+        .zip(allele_maps)
+        .filter_map(|(record, allele_map)| {
+            let record = record.as_ref()?;
+            let allele_map = allele_map.as_ref()?;
+            let field = record.info().iter().find(|field| field.key() == key)?;
+            Some((field.value()?, allele_map))
+        })
+        .collect();
+
+    if !is_allele_number(number) {
+        return values.first().map(|(value, _)| (*value).clone());
+    }
+
+    macro_rules! merge_array {
+        ($variant:ident) => {{
+            let mut merged_values = None;
+
+            for (value, allele_map) in &values {
+                if let Value::$variant(vs) = value {
+                    let remapped_values = remap_values(number, allele_map, allele_count, vs)?
+                        .into_iter()
+                        .map(Option::flatten)
+                        .collect();
+
+                    fill(&mut merged_values, remapped_values);
+                }
+            }
+
+            merged_values.map(Value::$variant)
+        }};
+    }
+
+    match values.first()?.0 {
+        Value::IntegerArray(_) => merge_array!(IntegerArray),
+        Value::FloatArray(_) => merge_array!(FloatArray),
+        Value::CharacterArray(_) => merge_array!(CharacterArray),
+        Value::StringArray(_) => merge_array!(StringArray),
+        value => Some(value.clone()),
+    }
+}
+
+// Returns the number of called alleles of each allele index or `None` if no sample has a
+// genotype.
+fn count_alleles(genotypes: &[Genotype], allele_count: usize) -> Option<Vec<i32>> {
+    let mut counts = vec![0; allele_count];
+    let mut has_genotype = false;
+
+    for genotype in genotypes {
+        let genotype_value = match genotype.genotype() {
+            Some(Ok(value)) => value,
+            _ => continue,
+        };
+
+        has_genotype = true;
+
+        for i in genotype_value.iter().filter_map(|allele| allele.position()) {
+            if let Some(count) = counts.get_mut(i) {
+                *count += 1;
+            }
+        }
+    }
+
+    if has_genotype {
+        Some(counts)
+    } else {
+        None
+    }
+}
+
+fn merge_genotype_fields(
+    keys: &[genotype::field::Key],
+    genotype: Option<&Genotype>,
+    allele_map: &[usize],
+    allele_count: usize,
+) -> Vec<genotype::Field> {
+    keys.iter()
+        .map(|key| {
+            let value = genotype
+                .and_then(|g| g.iter().find(|field| field.key() == key))
+                .and_then(|field| field.value())
+                .and_then(|value| {
+                    if key == &genotype::field::Key::Genotype {
+                        remap_genotype_value(value, allele_map)
+                    } else {
+                        remap_genotype_field_value(key.number(), allele_map, allele_count, value)
+                    }
+                });
+
+            let value = if value.is_none() && key == &genotype::field::Key::Genotype {
+                Some(missing_genotype_value())
+            } else {
+                value
+            };
+
+            genotype::Field::new(key.clone(), value)
+        })
+        .collect()
+}
+
+fn missing_genotype_fields(keys: &[genotype::field::Key]) -> Vec<genotype::Field> {
+    keys.iter()
+        .map(|key| {
+            let value = if key == &genotype::field::Key::Genotype {
+                Some(missing_genotype_value())
+            } else {
+                None
+            };
+
+            genotype::Field::new(key.clone(), value)
+        })
+        .collect()
+}
+
+fn missing_genotype_value() -> genotype::field::Value {
+    let alleles = vec![GenotypeAllele::new(None, Phasing::Unphased); MISSING_GENOTYPE_PLOIDY];
+    GenotypeValue::from(alleles).into()
+}
+
+fn remap_genotype_value(
+    value: &genotype::field::Value,
+    allele_map: &[usize],
+) -> Option<genotype::field::Value> {
+    let genotype = genotype_value(value)?;
+
+    let alleles: Vec<_> = genotype
+        .iter()
+        .map(|allele| {
+            let position = allele.position().and_then(|i| allele_map.get(i).copied());
+            GenotypeAllele::new(position, allele.phasing())
+        })
+        .collect();
+
+    Some(GenotypeValue::from(alleles).into())
+}
+
+fn remap_genotype_field_value(
+    number: Number,
+    allele_map: &[usize],
+    allele_count: usize,
+    value: &genotype::field::Value,
+) -> Option<genotype::field::Value> {
+    use genotype::field::Value;
+
+    if !is_allele_number(number) {
+        return Some(value.clone());
+    }
+
+    macro_rules! remap_array {
+        ($variant:ident, $values:expr) => {
+            remap_values(number, allele_map, allele_count, $values)
+                .map(|vs| vs.into_iter().map(Option::flatten).collect())
+                .map(Value::$variant)
+        };
+    }
+
+    match value {
+        Value::IntegerArray(values) => remap_array!(IntegerArray, values),
+        Value::FloatArray(values) => remap_array!(FloatArray, values),
+        Value::CharacterArray(values) => remap_array!(CharacterArray, values),
+        Value::StringArray(values) => remap_array!(StringArray, values),
+        _ => Some(value.clone()),
+    }
+}
+
+fn is_allele_number(number: Number) -> bool {
+    matches!(number, Number::A | Number::R | Number::G)
+}
+
+// Moves values of a record into the merged record, keeping the values already set.
+fn fill<T>(merged_values: &mut Option<Vec<Option<T>>>, values: Vec<Option<T>>) {
+    match merged_values {
+        Some(merged_values) => {
+            for (merged_value, value) in merged_values.iter_mut().zip(values) {
+                if merged_value.is_none() {
+                    *merged_value = value;
+                }
+            }
+        }
+        None => *merged_values = Some(values),
+    }
+}
+
+// Reorders the values of a field by the allele indices of the merged record. `allele_map` maps
+// the allele indices of the record to the allele indices of the merged record.
+fn remap_values<T>(
+    number: Number,
+    allele_map: &[usize],
+    allele_count: usize,
+    values: &[T],
+) -> Option<Vec<Option<T>>>
+where
+    T: Clone,
+{
+    let record_allele_count = allele_map.len();
+
+    match number {
+        Number::A => {
+            if values.len() != record_allele_count - 1 {
+                return None;
+            }
+
+            let mut remapped_values = vec![None; allele_count - 1];
+
+            for (value, &j) in values.iter().zip(&allele_map[1..]) {
+                remapped_values[j - 1] = Some(value.clone());
+            }
+
+            Some(remapped_values)
+        }
+        Number::R => {
+            if values.len() != record_allele_count {
+                return None;
+            }
+
+            let mut remapped_values = vec![None; allele_count];
+
+            for (value, &j) in values.iter().zip(allele_map) {
+                remapped_values[j] = Some(value.clone());
+            }
+
+            Some(remapped_values)
+        }
+        Number::G => {
+            let p = ploidy(record_allele_count, values.len())?;
+            let mut remapped_values = vec![None; genotype_count(allele_count, p)];
+
+            for mut alleles in genotypes(record_allele_count, p) {
+                let mut remapped_alleles: Vec<_> = alleles.iter().map(|&a| allele_map[a]).collect();
+
+                let i = genotype_index(&mut alleles);
+                let j = genotype_index(&mut remapped_alleles);
+
+                remapped_values[j] = values.get(i).cloned();
+            }
+
+            Some(remapped_values)
+        }
+        _ => None,
+    }
+}
+
+// Returns all genotypes of the given allele count and ploidy as sorted allele indices.
+fn genotypes(allele_count: usize, ploidy: usize) -> Vec<Vec<usize>> {
+    let mut genotypes = vec![Vec::new()];
+
+    for _ in 0..ploidy {
+        genotypes = genotypes
+            .into_iter()
+            .flat_map(|alleles: Vec<usize>| {
+                let start = alleles.last().copied().unwrap_or(0);
+
+                (start..allele_count).map(move |a| {
+                    let mut alleles = alleles.clone();
+                    alleles.push(a);
+                    alleles
+                })
+            })
+            .collect();
+    }
+
+    genotypes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_merger(data: &[&'static [u8]]) -> io::Result<Merger<&'static [u8]>> {
+        let readers = data.iter().map(|d| Reader::new(*d)).collect();
+        Merger::new(readers)
+    }
+
+    #[test]
+    fn test_read_record() -> Result<(), Box<dyn std::error::Error>> {
+        let data0 = b"##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count\">
+##INFO=<ID=AN,Number=1,Type=Integer,Description=\"Total allele count\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=AD,Number=R,Type=Integer,Description=\"Read depths\">
+##FORMAT=<ID=PL,Number=G,Type=Integer,Description=\"Phred-scaled genotype likelihoods\">
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+sq0\t8\trs0\tA\tC\t13\tPASS\tAC=1;AN=2\tGT:AD:PL\t0/1:5,3:30,0,40
+sq0\t13\t.\tG\tT\t.\tPASS\t.\tGT\t1/1
+sq1\t5\t.\tCA\tC\t5\tq10\t.\tGT\t0/1
+";
+
+        let data1 = b"##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description=\"Allele count\">
+##INFO=<ID=AN,Number=1,Type=Integer,Description=\"Total allele count\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=AD,Number=R,Type=Integer,Description=\"Read depths\">
+##FORMAT=<ID=PL,Number=G,Type=Integer,Description=\"Phred-scaled genotype likelihoods\">
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+sq0\t8\trs0\tA\tG,C\t21\tPASS\tAC=1,1;AN=2\tGT:AD:PL\t1/2:0,4,6:50,20,60,10,0,70
+sq1\t5\t.\tC\tT\t8\tPASS\t.\tGT\t1/1
+";
+
+        let mut merger = build_merger(&[data0, data1])?;
+
+        let actual = merger.read_record()?;
+        let expected = "sq0\t8\trs0\tA\tC,G\t21\tPASS\tAC=2,1;AN=4\tGT:AD:PL\t0/1:5,3,.:30,0,40,.,.,.\t2/1:0,6,4:50,10,70,20,0,60".parse()?;
+        assert_eq!(actual, Some(expected));
+
+        let actual = merger.read_record()?;
+        let expected = "sq0\t13\t.\tG\tT\t.\tPASS\t.\tGT\t1/1\t./.".parse()?;
+        assert_eq!(actual, Some(expected));
+
+        let actual = merger.read_record()?;
+        let expected = "sq1\t5\t.\tCA\tC,TA\t8\tq10\t.\tGT\t0/1\t2/2".parse()?;
+        assert_eq!(actual, Some(expected));
+
+        assert!(merger.read_record()?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_with_incompatible_reference_bases() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let data0 = b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+sq0\t8\t.\tAC\tA\t.\tPASS\t.\tGT\t0/1
+";
+
+        let data1 = b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+sq0\t8\t.\tAG\tA\t.\tPASS\t.\tGT\t1/1
+";
+
+        let mut merger = build_merger(&[data0, data1])?;
+
+        let actual = merger.read_record()?;
+        let expected = "sq0\t8\t.\tAC\tA\t.\tPASS\t.\tGT\t0/1\t./.".parse()?;
+        assert_eq!(actual, Some(expected));
+
+        let actual = merger.read_record()?;
+        let expected = "sq0\t8\t.\tAG\tA\t.\tPASS\t.\tGT\t./.\t1/1".parse()?;
+        assert_eq!(actual, Some(expected));
+
+        assert!(merger.read_record()?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_with_unsorted_input() -> io::Result<()> {
+        let data = b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t13\t.\tA\tC\t.\tPASS\t.
+sq0\t8\t.\tG\tT\t.\tPASS\t.
+";
+
+        let mut merger = build_merger(&[data])?;
+
+        assert!(matches!(
+            merger.read_record(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_with_chromosome_order_per_reader() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let data0 = b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq1\t8\t.\tA\tC\t.\tPASS\t.
+sq0\t5\t.\tG\tT\t.\tPASS\t.
+";
+
+        let data1 = b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t5\t.\tG\tA\t.\tPASS\t.
+";
+
+        let mut merger = build_merger(&[data0, data1])?;
+
+        let actual = merger.read_record()?;
+        let expected = "sq1\t8\t.\tA\tC\t.\tPASS\t.".parse()?;
+        assert_eq!(actual, Some(expected));
+
+        let actual = merger.read_record()?;
+        let expected = "sq0\t5\t.\tG\tT,A\t.\tPASS\t.".parse()?;
+        assert_eq!(actual, Some(expected));
+
+        assert!(merger.read_record()?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_with_unsorted_chromosomes() -> io::Result<()> {
+        let data = b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t8\t.\tA\tC\t.\tPASS\t.
+sq1\t5\t.\tG\tT\t.\tPASS\t.
+sq0\t13\t.\tG\tT\t.\tPASS\t.
+";
+
+        let mut merger = build_merger(&[data])?;
+        assert!(merger.read_record()?.is_some());
+        assert!(matches!(
+            merger.read_record(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let data = b"##fileformat=VCFv4.3
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq1\t5\t.\tG\tT\t.\tPASS\t.
+sq0\t8\t.\tA\tC\t.\tPASS\t.
+";
+
+        let mut merger = build_merger(&[data])?;
+        assert!(matches!(
+            merger.read_record(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_with_duplicate_sample_names() {
+        let data = b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+";
+
+        assert!(matches!(
+            build_merger(&[data, data]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_remap_values() {
+        // REF=A, ALT=G -> REF=A, ALT=C,G
+        let allele_map = [0, 2];
+
+        assert_eq!(
+            remap_values(Number::A, &allele_map, 3, &[1]),
+            Some(vec![None, Some(1)])
+        );
+
+        assert_eq!(
+            remap_values(Number::R, &allele_map, 3, &[5, 3]),
+            Some(vec![Some(5), None, Some(3)])
+        );
+
+        assert_eq!(
+            remap_values(Number::G, &allele_map, 3, &[30, 0, 40]),
+            Some(vec![Some(30), None, None, Some(0), None, Some(40)])
+        );
+
+        assert_eq!(remap_values(Number::Count(1), &allele_map, 3, &[1]), None);
+        assert_eq!(remap_values(Number::A, &allele_map, 3, &[1, 2]), None);
+    }
+
+    #[test]
+    fn test_genotypes() {
+        assert_eq!(
+            genotypes(3, 2),
+            [
+                vec![0, 0],
+                vec![0, 1],
+                vec![0, 2],
+                vec![1, 1],
+                vec![1, 2],
+                vec![2, 2]
+            ]
+        );
+    }
+}
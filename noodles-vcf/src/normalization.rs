@@ -0,0 +1,923 @@
+//! VCF record normalization.
+//!
+//! Normalization gives variants a consistent representation, similar to `bcftools norm`. A
+//! record can be
+//!
+//!   * trimmed of bases shared by all alleles ([`trim`]),
+//!   * left-aligned against a reference sequence ([`left_align`]),
+//!   * split into biallelic records ([`split`]), and
+//!   * joined from biallelic records ([`join`]).
+//!
+//! Only records with all alternate bases as a list of bases are trimmed or left-aligned. Other
+//! records, e.g., those with symbolic alleles or breakends, are returned unchanged.
+//!
+//! # Examples
+//!
+//! ## Left-align records using an indexed FASTA
+//!
+//! ```no_run
+//! # use std::{fs::File, io::{self, BufReader}};
+//! use noodles::Region;
+//! use noodles_fasta as fasta;
+//! use noodles_vcf::{self as vcf, normalization};
+//!
+//! let index = fasta::index("reference.fa")?;
+//! let mut reference_reader = File::open("reference.fa")
+//!     .map(BufReader::new)
+//!     .map(fasta::Reader::new)?;
+//!
+//! let record: vcf::Record = "sq0\t8\t.\tCA\tC\t.\tPASS\t.".parse()
+//!     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+//!
+//! let index_record = index
+//!     .iter()
+//!     .find(|r| r.reference_sequence_name() == record.chromosome().to_string())
+//!     .expect("missing reference sequence");
+//!
+//! let region = Region::mapped(
+//!     index_record.reference_sequence_name(),
+//!     1,
+//!     index_record.len() as i32,
+//! );
+//! let reference_sequence = reference_reader.query(&index, &region)?;
+//!
+//! let record = normalization::left_align(&record, reference_sequence.sequence())
+//!     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+//! # Ok::<(), io::Error>(())
+//! ```
+
+use std::{convert::TryFrom, error, fmt};
+
+use crate::{
+    header::Number,
+    record::{
+        alternate_bases::Allele,
+        genotype::{
+            self,
+            field::value::{genotype::Allele as GenotypeAllele, Genotype as GenotypeValue},
+            genotype_count, genotype_index, genotype_value, ploidy,
+        },
+        info,
+        reference_bases::Base,
+        AlternateBases, Info, ReferenceBases,
+    },
+    Record,
+};
+
+/// An error returned when a VCF record fails to left-align.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LeftAlignError {
+    /// The reference bases are outside the reference sequence.
+    InvalidPosition(i32),
+    /// The reference bases do not match the reference sequence.
+    ReferenceBasesMismatch,
+    /// A reference sequence base is invalid.
+    InvalidReferenceSequenceBase(char),
+}
+
+impl error::Error for LeftAlignError {}
+
+impl fmt::Display for LeftAlignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPosition(position) => write!(f, "invalid position: {}", position),
+            Self::ReferenceBasesMismatch => {
+                f.write_str("reference bases do not match reference sequence")
+            }
+            Self::InvalidReferenceSequenceBase(c) => {
+                write!(f, "invalid reference sequence base: {}", c)
+            }
+        }
+    }
+}
+
+/// An error returned when VCF records fail to join.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JoinError {
+    /// The input is empty.
+    Empty,
+    /// A record is not biallelic.
+    NotBiallelic,
+    /// The records differ in chromosome, position, or reference bases.
+    SiteMismatch,
+    /// The records differ in number of samples.
+    SampleCountMismatch,
+}
+
+impl error::Error for JoinError {}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::NotBiallelic => f.write_str("record is not biallelic"),
+            Self::SiteMismatch => f.write_str("site mismatch"),
+            Self::SampleCountMismatch => f.write_str("sample count mismatch"),
+        }
+    }
+}
+
+/// Trims bases shared by all alleles.
+///
+/// Shared trailing bases are trimmed first and then shared leading bases, moving the position
+/// forward. At least one base is always kept for each allele.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, normalization};
+///
+/// let record: vcf::Record = "sq0\t1\t.\tGCAT\tGTGAT,GCGT\t.\tPASS\t.".parse()?;
+/// let actual = normalization::trim(&record);
+///
+/// assert_eq!(actual.position(), 2);
+/// assert_eq!(actual.reference_bases().to_string(), "CA");
+/// assert_eq!(actual.alternate_bases().to_string(), "TGA,CG");
+/// # Ok::<(), vcf::record::ParseError>(())
+/// ```
+pub fn trim(record: &Record) -> Record {
+    let mut alleles = match allele_bases(record) {
+        Some(alleles) => alleles,
+        None => return record.clone(),
+    };
+
+    let mut position = record.position();
+
+    while all_longer_than_one(&alleles) && have_equal_last_bases(&alleles) {
+        for bases in &mut alleles {
+            bases.pop();
+        }
+    }
+
+    position += trim_leading_bases(&mut alleles);
+
+    set_allele_bases(record, position, alleles)
+}
+
+/// Left-aligns and trims the alleles of a record using a reference sequence.
+///
+/// The given reference sequence is the entire sequence of the record's chromosome, e.g., the
+/// sequence of a FASTA record. The reference bases of the record must match it.
+///
+/// Indels are shifted left while the alleles end with the same base. The result is the same
+/// parsimonious representation as produced by `bcftools norm` or `vt normalize`.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, normalization};
+///
+/// let reference_sequence = b"GCACAT";
+///
+/// let record: vcf::Record = "sq0\t3\t.\tACA\tA\t.\tPASS\t.".parse()?;
+/// let actual = normalization::left_align(&record, reference_sequence)?;
+///
+/// assert_eq!(actual.position(), 1);
+/// assert_eq!(actual.reference_bases().to_string(), "GCA");
+/// assert_eq!(actual.alternate_bases().to_string(), "G");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn left_align(record: &Record, reference_sequence: &[u8]) -> Result<Record, LeftAlignError> {
+    let mut alleles = match allele_bases(record) {
+        Some(alleles) => alleles,
+        None => return Ok(record.clone()),
+    };
+
+    let mut position = record.position();
+
+    let start =
+        usize::try_from(position - 1).map_err(|_| LeftAlignError::InvalidPosition(position))?;
+    let end = start + alleles[0].len();
+
+    let reference_bases = reference_sequence
+        .get(start..end)
+        .ok_or(LeftAlignError::InvalidPosition(position))?;
+
+    for (&b, &base) in reference_bases.iter().zip(alleles[0].iter()) {
+        if reference_sequence_base(b)? != base {
+            return Err(LeftAlignError::ReferenceBasesMismatch);
+        }
+    }
+
+    loop {
+        let mut is_changed = false;
+
+        if have_equal_last_bases(&alleles) && (position > 1 || all_longer_than_one(&alleles)) {
+            for bases in &mut alleles {
+                bases.pop();
+            }
+
+            is_changed = true;
+        }
+
+        if position > 1 && alleles.iter().any(|bases| bases.is_empty()) {
+            let i = (position - 2) as usize;
+            let base = reference_sequence_base(reference_sequence[i])?;
+
+            for bases in &mut alleles {
+                bases.insert(0, base);
+            }
+
+            position -= 1;
+            is_changed = true;
+        }
+
+        if !is_changed {
+            break;
+        }
+    }
+
+    position += trim_leading_bases(&mut alleles);
+
+    Ok(set_allele_bases(record, position, alleles))
+}
+
+/// Splits a multiallelic record into biallelic records.
+///
+/// Each alternate allele gets its own record with the same reference bases. The values of INFO
+/// and genotype fields with a number of `A`, `R`, or `G` are subset to the alleles of the new
+/// record. Genotype (`GT`) calls of other alternate alleles become reference calls.
+///
+/// The number of a field is that of its key in the record. Reserved keys have the number given
+/// by the VCF specification, but other keys only have the number of their header definition when
+/// the record is parsed with a header, e.g., using [`Record::try_from_str_header`]. Otherwise,
+/// they have a number of 1, and their values are copied as is to each split record.
+///
+/// Records with at most one alternate allele are returned as is.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, normalization};
+///
+/// let record: vcf::Record =
+///     "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=3,1\tGT:AD:PL\t1/2:2,5,3:90,12,50,30,0,60".parse()?;
+///
+/// let records = normalization::split(&record);
+/// assert_eq!(records.len(), 2);
+///
+/// let actual = &records[1];
+/// assert_eq!(actual.alternate_bases().to_string(), "G");
+/// assert_eq!(actual.info().to_string(), "AC=1");
+/// assert_eq!(actual.genotypes()[0].to_string(), "0/1:2,3:90,30,60");
+/// # Ok::<(), vcf::record::ParseError>(())
+/// ```
+pub fn split(record: &Record) -> Vec<Record> {
+    let alternate_bases = record.alternate_bases();
+
+    if alternate_bases.len() <= 1 {
+        return vec![record.clone()];
+    }
+
+    let allele_count = alternate_bases.len() + 1;
+
+    (1..allele_count)
+        .map(|k| split_allele(record, allele_count, k))
+        .collect()
+}
+
+/// Joins biallelic records into a multiallelic record.
+///
+/// This is the inverse of [`split`]. All records must be biallelic and have the same chromosome,
+/// position, and reference bases. The alternate alleles are in the order of the records.
+///
+/// The values of INFO and genotype fields with a number of `A`, `R`, or `G` are merged. Genotype
+/// field values not given by any record, e.g., likelihoods of genotypes with two different
+/// alternate alleles, are missing. INFO fields cannot have missing values and are dropped in this
+/// case. As with [`split`], the number of a field is that of its key in the record.
+///
+/// All other fields are taken from the first record, and the values of the other records are
+/// ignored. This includes the IDs, quality score (`QUAL`), filter status (`FILTER`), INFO fields
+/// with other numbers, genotype format, and genotype fields with other numbers.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, normalization};
+///
+/// let records: Vec<vcf::Record> = vec![
+///     "sq0\t1\t.\tA\tC\t.\tPASS\tAC=3\tGT:AD:PL\t0/1:2,5:90,12,50".parse()?,
+///     "sq0\t1\t.\tA\tG\t.\tPASS\tAC=1\tGT:AD:PL\t0/0:2,3:90,30,60".parse()?,
+/// ];
+///
+/// let actual = normalization::join(&records)?;
+///
+/// assert_eq!(actual.alternate_bases().to_string(), "C,G");
+/// assert_eq!(actual.info().to_string(), "AC=3,1");
+/// assert_eq!(actual.genotypes()[0].to_string(), "0/1:2,5,3:90,12,50,30,.,60");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn join(records: &[Record]) -> Result<Record, JoinError> {
+    let first = records.first().ok_or(JoinError::Empty)?;
+
+    for record in records {
+        if record.alternate_bases().len() != 1 {
+            return Err(JoinError::NotBiallelic);
+        }
+
+        if record.chromosome() != first.chromosome()
+            || record.position() != first.position()
+            || record.reference_bases() != first.reference_bases()
+        {
+            return Err(JoinError::SiteMismatch);
+        }
+
+        if record.genotypes().len() != first.genotypes().len() {
+            return Err(JoinError::SampleCountMismatch);
+        }
+    }
+
+    let allele_count = records.len() + 1;
+    let mut joined_record = first.clone();
+
+    let alternate_bases: Vec<_> = records
+        .iter()
+        .map(|record| record.alternate_bases()[0].clone())
+        .collect();
+
+    *joined_record.alternate_bases_mut() = AlternateBases::from(alternate_bases);
+
+    let mut fields = Vec::with_capacity(first.info().len());
+
+    for field in first.info().iter() {
+        let key = field.key();
+
+        if !is_allele_number(key.number()) {
+            fields.push(field.clone());
+            continue;
+        }
+
+        let values: Vec<_> = records
+            .iter()
+            .map(|record| {
+                record
+                    .info()
+                    .iter()
+                    .find(|f| f.key() == key)
+                    .and_then(|f| f.value())
+            })
+            .collect();
+
+        if let Some(value) = join_info_value(key.number(), allele_count, &values) {
+            fields.push(info::Field::new(key.clone(), Some(value)));
+        }
+    }
+
+    *joined_record.info_mut() = Info::from(fields);
+
+    for (i, genotype) in joined_record.genotypes_mut().iter_mut().enumerate() {
+        for field in genotype.fields_mut() {
+            let key = field.key().clone();
+
+            let values: Vec<_> = records
+                .iter()
+                .map(|record| {
+                    record.genotypes()[i]
+                        .iter()
+                        .find(|f| f.key() == &key)
+                        .and_then(|f| f.value())
+                })
+                .collect();
+
+            let value = if key == genotype::field::Key::Genotype {
+                join_genotype_value(&values).or_else(|| field.value().cloned())
+            } else if is_allele_number(key.number()) {
+                join_genotype_field_value(key.number(), allele_count, &values)
+            } else {
+                continue;
+            };
+
+            *field = genotype::Field::new(key, value);
+        }
+    }
+
+    Ok(joined_record)
+}
+
+fn allele_bases(record: &Record) -> Option<Vec<Vec<Base>>> {
+    let alternate_bases = record.alternate_bases();
+
+    if alternate_bases.is_empty() {
+        return None;
+    }
+
+    let mut alleles = Vec::with_capacity(alternate_bases.len() + 1);
+    alleles.push(record.reference_bases().to_vec());
+
+    for allele in alternate_bases.iter() {
+        match allele {
+            Allele::Bases(bases) => alleles.push(bases.clone()),
+            _ => return None,
+        }
+    }
+
+    Some(alleles)
+}
+
+fn set_allele_bases(record: &Record, position: i32, mut alleles: Vec<Vec<Base>>) -> Record {
+    let mut record = record.clone();
+
+    let alternate_bases: Vec<_> = alleles
+        .split_off(1)
+        .into_iter()
+        .map(Allele::Bases)
+        .collect();
+    let reference_bases = alleles.pop().unwrap_or_default();
+
+    // Alleles always keep at least one base.
+    if let Ok(reference_bases) = ReferenceBases::try_from(reference_bases) {
+        *record.position_mut() = position;
+        *record.reference_bases_mut() = reference_bases;
+        *record.alternate_bases_mut() = AlternateBases::from(alternate_bases);
+    }
+
+    record
+}
+
+fn all_longer_than_one(alleles: &[Vec<Base>]) -> bool {
+    alleles.iter().all(|bases| bases.len() > 1)
+}
+
+fn have_equal_last_bases(alleles: &[Vec<Base>]) -> bool {
+    let last = alleles[0].last();
+    last.is_some() && alleles.iter().all(|bases| bases.last() == last)
+}
+
+// Trims shared leading bases and returns the number of bases trimmed.
+fn trim_leading_bases(alleles: &mut [Vec<Base>]) -> i32 {
+    let mut n = 0;
+
+    while all_longer_than_one(alleles) && alleles.iter().all(|bases| bases[0] == alleles[0][0]) {
+        for bases in alleles.iter_mut() {
+            bases.remove(0);
+        }
+
+        n += 1;
+    }
+
+    n
+}
+
+fn reference_sequence_base(b: u8) -> Result<Base, LeftAlignError> {
+    let c = char::from(b.to_ascii_uppercase());
+    Base::try_from(c).map_err(|_| LeftAlignError::InvalidReferenceSequenceBase(char::from(b)))
+}
+
+fn is_allele_number(number: Number) -> bool {
+    matches!(number, Number::A | Number::R | Number::G)
+}
+
+// Returns the genotype indices of the biallelic genotypes of the given alternate allele, ordered
+// by the number of copies of the alternate allele.
+fn biallelic_genotype_indices(k: usize, ploidy: usize) -> Vec<usize> {
+    (0..=ploidy)
+        .map(|n| {
+            let mut alleles = vec![0; ploidy - n];
+            alleles.resize(ploidy, k);
+            genotype_index(&mut alleles)
+        })
+        .collect()
+}
+
+// Returns the indices of the values of a field for the given alternate allele.
+fn allele_indices(number: Number, allele_count: usize, k: usize, len: usize) -> Option<Vec<usize>> {
+    match number {
+        Number::A => Some(vec![k - 1]),
+        Number::R => Some(vec![0, k]),
+        Number::G => ploidy(allele_count, len).map(|p| biallelic_genotype_indices(k, p)),
+        _ => None,
+    }
+}
+
+fn select<T>(values: &[T], indices: &[usize]) -> Option<Vec<T>>
+where
+    T: Clone,
+{
+    indices.iter().map(|&i| values.get(i).cloned()).collect()
+}
+
+fn split_allele(record: &Record, allele_count: usize, k: usize) -> Record {
+    let mut split_record = record.clone();
+
+    let allele = record.alternate_bases()[k - 1].clone();
+    *split_record.alternate_bases_mut() = AlternateBases::from(vec![allele]);
+
+    let fields = record
+        .info()
+        .iter()
+        .map(|field| {
+            field
+                .value()
+                .and_then(|value| split_info_value(field.key().number(), allele_count, k, value))
+                .map(|value| info::Field::new(field.key().clone(), Some(value)))
+                .unwrap_or_else(|| field.clone())
+        })
+        .collect::<Vec<_>>();
+
+    *split_record.info_mut() = Info::from(fields);
+
+    for genotype in split_record.genotypes_mut() {
+        for field in genotype.fields_mut() {
+            let key = field.key();
+
+            let value = match field.value() {
+                Some(value) => value,
+                None => continue,
+            };
+
+            let split_value = if key == &genotype::field::Key::Genotype {
+                split_genotype_value(value, k)
+            } else {
+                split_genotype_field_value(key.number(), allele_count, k, value)
+            };
+
+            if let Some(value) = split_value {
+                *field = genotype::Field::new(key.clone(), Some(value));
+            }
+        }
+    }
+
+    split_record
+}
+
+fn split_info_value(
+    number: Number,
+    allele_count: usize,
+    k: usize,
+    value: &info::field::Value,
+) -> Option<info::field::Value> {
+    use info::field::Value;
+
+    match value {
+        Value::IntegerArray(values) => allele_indices(number, allele_count, k, values.len())
+            .and_then(|indices| select(values, &indices))
+            .map(Value::IntegerArray),
+        Value::FloatArray(values) => allele_indices(number, allele_count, k, values.len())
+            .and_then(|indices| select(values, &indices))
+            .map(Value::FloatArray),
+        Value::CharacterArray(values) => allele_indices(number, allele_count, k, values.len())
+            .and_then(|indices| select(values, &indices))
+            .map(Value::CharacterArray),
+        Value::StringArray(values) => allele_indices(number, allele_count, k, values.len())
+            .and_then(|indices| select(values, &indices))
+            .map(Value::StringArray),
+        _ => None,
+    }
+}
+
+fn split_genotype_field_value(
+    number: Number,
+    allele_count: usize,
+    k: usize,
+    value: &genotype::field::Value,
+) -> Option<genotype::field::Value> {
+    use genotype::field::Value;
+
+    match value {
+        Value::IntegerArray(values) => allele_indices(number, allele_count, k, values.len())
+            .and_then(|indices| select(values, &indices))
+            .map(Value::IntegerArray),
+        Value::FloatArray(values) => allele_indices(number, allele_count, k, values.len())
+            .and_then(|indices| select(values, &indices))
+            .map(Value::FloatArray),
+        Value::CharacterArray(values) => allele_indices(number, allele_count, k, values.len())
+            .and_then(|indices| select(values, &indices))
+            .map(Value::CharacterArray),
+        Value::StringArray(values) => allele_indices(number, allele_count, k, values.len())
+            .and_then(|indices| select(values, &indices))
+            .map(Value::StringArray),
+        _ => None,
+    }
+}
+
+fn split_genotype_value(
+    value: &genotype::field::Value,
+    k: usize,
+) -> Option<genotype::field::Value> {
+    let genotype = genotype_value(value)?;
+
+    let alleles: Vec<_> = genotype
+        .iter()
+        .map(|allele| {
+            let position = allele.position().map(|i| if i == k { 1 } else { 0 });
+            GenotypeAllele::new(position, allele.phasing())
+        })
+        .collect();
+
+    Some(GenotypeValue::from(alleles).into())
+}
+
+// Returns, for each value of a biallelic record, the index of the value in the joined record.
+fn join_indices(number: Number, k: usize, len: usize) -> Option<Vec<usize>> {
+    match number {
+        Number::A => Some(vec![k - 1]),
+        Number::R => Some(vec![0, k]),
+        Number::G => ploidy(2, len).map(|p| biallelic_genotype_indices(k, p)),
+        _ => None,
+    }
+}
+
+fn joined_len(number: Number, allele_count: usize, len: usize) -> Option<usize> {
+    match number {
+        Number::A => Some(allele_count - 1),
+        Number::R => Some(allele_count),
+        Number::G => ploidy(2, len).map(|p| genotype_count(allele_count, p)),
+        _ => None,
+    }
+}
+
+// Merges the values of each biallelic record into a list of optional values.
+fn join_values<'a, T, V, F>(
+    number: Number,
+    allele_count: usize,
+    values: &[Option<&'a V>],
+    f: F,
+) -> Option<Vec<Option<T>>>
+where
+    T: Clone + 'a,
+    F: Fn(&'a V) -> Option<&'a [T]>,
+{
+    let first_len = values.iter().flatten().find_map(|&v| f(v))?.len();
+    let len = joined_len(number, allele_count, first_len)?;
+
+    let mut joined_values = vec![None; len];
+
+    for (i, value) in values.iter().enumerate() {
+        let vs = match value.and_then(&f) {
+            Some(vs) => vs,
+            None => continue,
+        };
+
+        let indices = match join_indices(number, i + 1, vs.len()) {
+            Some(indices) => indices,
+            None => continue,
+        };
+
+        for (v, j) in vs.iter().zip(indices) {
+            if let Some(joined_value @ None) = joined_values.get_mut(j) {
+                *joined_value = Some(v.clone());
+            }
+        }
+    }
+
+    Some(joined_values)
+}
+
+fn join_info_value(
+    number: Number,
+    allele_count: usize,
+    values: &[Option<&info::field::Value>],
+) -> Option<info::field::Value> {
+    use info::field::Value;
+
+    let first = values.iter().flatten().next()?;
+
+    macro_rules! join_array {
+        ($variant:ident) => {
+            join_values(number, allele_count, values, |v| match v {
+                Value::$variant(vs) => Some(&vs[..]),
+                _ => None,
+            })
+            .map(|vs| vs.into_iter().map(Option::flatten).collect())
+            .map(Value::$variant)
+        };
+    }
+
+    match first {
+        Value::IntegerArray(_) => join_array!(IntegerArray),
+        Value::FloatArray(_) => join_array!(FloatArray),
+        Value::CharacterArray(_) => join_array!(CharacterArray),
+        Value::StringArray(_) => join_array!(StringArray),
+        _ => None,
+    }
+}
+
+fn join_genotype_field_value(
+    number: Number,
+    allele_count: usize,
+    values: &[Option<&genotype::field::Value>],
+) -> Option<genotype::field::Value> {
+    use genotype::field::Value;
+
+    let first = values.iter().flatten().next()?;
+
+    macro_rules! join_array {
+        ($variant:ident) => {
+            join_values(number, allele_count, values, |v| match v {
+                Value::$variant(vs) => Some(&vs[..]),
+                _ => None,
+            })
+            .map(|vs| vs.into_iter().map(Option::flatten).collect())
+            .map(Value::$variant)
+        };
+    }
+
+    match first {
+        Value::IntegerArray(_) => join_array!(IntegerArray),
+        Value::FloatArray(_) => join_array!(FloatArray),
+        Value::CharacterArray(_) => join_array!(CharacterArray),
+        Value::StringArray(_) => join_array!(StringArray),
+        _ => None,
+    }
+}
+
+fn join_genotype_value(
+    values: &[Option<&genotype::field::Value>],
+) -> Option<genotype::field::Value> {
+    let genotypes: Vec<_> = values
+        .iter()
+        .map(|value| value.and_then(genotype_value))
+        .collect();
+
+    let first = genotypes.first().cloned().flatten()?;
+    let mut positions: Vec<_> = first.iter().map(|allele| allele.position()).collect();
+
+    for (i, genotype) in genotypes.iter().enumerate().skip(1) {
+        let genotype = match genotype {
+            Some(genotype) => genotype,
+            None => continue,
+        };
+
+        for (position, allele) in positions.iter_mut().zip(genotype.iter()) {
+            if allele.position() == Some(1) && *position == Some(0) {
+                *position = Some(i + 1);
+            }
+        }
+    }
+
+    let alleles: Vec<_> = first
+        .iter()
+        .zip(positions)
+        .map(|(allele, position)| GenotypeAllele::new(position, allele.phasing()))
+        .collect();
+
+    Some(GenotypeValue::from(alleles).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trim() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "sq0\t5\t.\tCAA\tCA\t.\tPASS\t.".parse()?;
+        let actual = trim(&record);
+        assert_eq!(actual.position(), 5);
+        assert_eq!(actual.reference_bases().to_string(), "CA");
+        assert_eq!(actual.alternate_bases().to_string(), "C");
+
+        let record: Record = "sq0\t5\t.\tACGT\tATGT\t.\tPASS\t.".parse()?;
+        let actual = trim(&record);
+        assert_eq!(actual.position(), 6);
+        assert_eq!(actual.reference_bases().to_string(), "C");
+        assert_eq!(actual.alternate_bases().to_string(), "T");
+
+        let record: Record = "sq0\t5\t.\tA\t<DEL>\t.\tPASS\t.".parse()?;
+        assert_eq!(trim(&record), record);
+
+        let record: Record = "sq0\t5\t.\tAA\t.\t.\tPASS\t.".parse()?;
+        assert_eq!(trim(&record), record);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_left_align() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = b"ttcacacag";
+
+        // deletion of a repeat unit
+        let record: Record = "sq0\t6\t.\tACA\tA\t.\tPASS\t.".parse()?;
+        let actual = left_align(&record, reference_sequence)?;
+        assert_eq!(actual.position(), 2);
+        assert_eq!(actual.reference_bases().to_string(), "TCA");
+        assert_eq!(actual.alternate_bases().to_string(), "T");
+
+        // insertion shifted to the start of the reference sequence
+        let reference_sequence = b"AAAC";
+        let record: Record = "sq0\t3\t.\tA\tAA\t.\tPASS\t.".parse()?;
+        let actual = left_align(&record, reference_sequence)?;
+        assert_eq!(actual.position(), 1);
+        assert_eq!(actual.reference_bases().to_string(), "A");
+        assert_eq!(actual.alternate_bases().to_string(), "AA");
+
+        // SNV
+        let record: Record = "sq0\t4\t.\tC\tG\t.\tPASS\t.".parse()?;
+        assert_eq!(left_align(&record, reference_sequence)?, record);
+
+        let record: Record = "sq0\t4\t.\tA\tAA\t.\tPASS\t.".parse()?;
+        assert_eq!(
+            left_align(&record, reference_sequence),
+            Err(LeftAlignError::ReferenceBasesMismatch)
+        );
+
+        let record: Record = "sq0\t4\t.\tCA\tC\t.\tPASS\t.".parse()?;
+        assert_eq!(
+            left_align(&record, reference_sequence),
+            Err(LeftAlignError::InvalidPosition(4))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_with_header_definitions() -> Result<(), Box<dyn std::error::Error>> {
+        let header: crate::Header = r#"##fileformat=VCFv4.3
+##INFO=<ID=ZA,Number=A,Type=Integer,Description="noodles">
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#
+        .parse()?;
+
+        let s = "sq0\t1\t.\tA\tC,G\t.\tPASS\tZA=3,1";
+
+        let record = Record::try_from_str_header(s, &header)?;
+        let actual: Vec<_> = split(&record)
+            .iter()
+            .map(|r| r.info().to_string())
+            .collect();
+        assert_eq!(actual, ["ZA=3", "ZA=1"]);
+
+        let record: Record = s.parse()?;
+        let actual: Vec<_> = split(&record)
+            .iter()
+            .map(|r| r.info().to_string())
+            .collect();
+        assert_eq!(actual, ["ZA=3,1", "ZA=3,1"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "sq0\t1\t.\tA\tC,G,T\t.\tPASS\tAC=1,2,3;AD=5,1,2,3\tGT:AD:PL\t2|3:5,1,2,3:0,1,2,3,4,5,6,7,8,9\t1:4,1,0,0:0,1,2,3"
+            .parse()?;
+
+        let records = split(&record);
+        assert_eq!(records.len(), 3);
+
+        let actual: Vec<_> = records
+            .iter()
+            .map(|r| {
+                let genotypes: Vec<_> = r.genotypes().iter().map(|g| g.to_string()).collect();
+                (
+                    r.alternate_bases().to_string(),
+                    r.info().to_string(),
+                    genotypes,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            actual,
+            [
+                (
+                    String::from("C"),
+                    String::from("AC=1;AD=5,1"),
+                    vec![String::from("0|0:5,1:0,1,2"), String::from("1:4,1:0,1")]
+                ),
+                (
+                    String::from("G"),
+                    String::from("AC=2;AD=5,2"),
+                    vec![String::from("1|0:5,2:0,3,5"), String::from("0:4,0:0,2")]
+                ),
+                (
+                    String::from("T"),
+                    String::from("AC=3;AD=5,3"),
+                    vec![String::from("0|1:5,3:0,6,9"), String::from("0:4,0:0,3")]
+                ),
+            ]
+        );
+
+        let record: Record = "sq0\t1\t.\tA\tC\t.\tPASS\tAC=1".parse()?;
+        assert_eq!(split(&record), [record]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record =
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=1,2;AD=5,1,2\tGT:AD:PL\t1|2:5,1,2:0,1,2,3,4,5"
+                .parse()?;
+
+        let actual = join(&split(&record))?;
+        assert_eq!(actual.alternate_bases(), record.alternate_bases());
+        assert_eq!(actual.info(), record.info());
+        assert_eq!(actual.genotypes()[0].to_string(), "1|2:5,1,2:0,1,2,3,.,5");
+
+        assert_eq!(join(&[]), Err(JoinError::Empty));
+
+        let records: Vec<Record> = vec![
+            "sq0\t1\t.\tA\tC\t.\tPASS\t.".parse()?,
+            "sq0\t2\t.\tA\tG\t.\tPASS\t.".parse()?,
+        ];
+        assert_eq!(join(&records), Err(JoinError::SiteMismatch));
+
+        let records: Vec<Record> = vec![
+            "sq0\t1\t.\tA\tC\t.\tPASS\t.".parse()?,
+            "sq0\t1\t.\tA\tG,T\t.\tPASS\t.".parse()?,
+        ];
+        assert_eq!(join(&records), Err(JoinError::NotBiallelic));
+
+        Ok(())
+    }
+}
@@ -1,12 +1,18 @@
 //! VCF reader and iterators.
 
+mod query;
 mod records;
+mod records_with_header;
 
-pub use self::records::Records;
+pub use self::{query::Query, records::Records, records_with_header::RecordsWithHeader};
 
-use std::io::{self, BufRead, BufReader, Read, Seek};
+use std::io::{self, BufRead, Read, Seek};
 
+use noodles::Region;
 use noodles_bgzf as bgzf;
+use noodles_tabix as tabix;
+
+use crate::{lazy, record::Strictness, Header};
 
 const LINE_FEED: char = '\n';
 const CARRIAGE_RETURN: char = '\r';
@@ -105,7 +111,7 @@ where
                 Err(e) => return Err(e),
             };
 
-            if eol && !buf.is_empty() && buf[0] != HEADER_PREFIX {
+            if buf.is_empty() || (eol && buf[0] != HEADER_PREFIX) {
                 break;
             }
 
@@ -165,6 +171,53 @@ where
         read_line(&mut self.inner, buf)
     }
 
+    /// Reads a single record without decoding its fields.
+    ///
+    /// Only the bounds of the site fields and genotype format are located. Fields, including the
+    /// samples, are decoded on demand (see [`lazy::Record`]).
+    ///
+    /// The stream is expected to be directly after the header or at the start of another record.
+    ///
+    /// If successful, the number of bytes read is returned. If the number of bytes read is 0,
+    /// the stream reached EOF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, lazy};
+    ///
+    /// let data = b"##fileformat=VCFv4.3
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+    /// sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0
+    /// ";
+    ///
+    /// let mut reader = vcf::Reader::new(&data[..]);
+    /// reader.read_header()?;
+    ///
+    /// let mut record = lazy::Record::default();
+    /// reader.read_lazy_record(&mut record)?;
+    ///
+    /// assert_eq!(record.chromosome(), "sq0");
+    /// assert_eq!(record.samples().get(0), Some("0|0"));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_lazy_record(&mut self, record: &mut lazy::Record) -> io::Result<usize> {
+        let buf = record.buf_mut();
+        buf.clear();
+
+        match read_line(&mut self.inner, buf)? {
+            0 => Ok(0),
+            n => {
+                record
+                    .index()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+                Ok(n)
+            }
+        }
+    }
+
     /// Returns an iterator over records starting from the current stream position.
     ///
     /// The stream is expected to be directly after the header or at the start of another record.
@@ -193,9 +246,49 @@ where
     pub fn records(&mut self) -> Records<'_, R> {
         Records::new(self)
     }
+
+    /// Returns an iterator over records starting from the current stream position, parsing
+    /// each with the INFO and FORMAT definitions of the given header.
+    ///
+    /// Each record is returned with its parse warnings. See
+    /// [`crate::Record::try_from_str_header_with_strictness`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_vcf::{self as vcf, record::{info::field::Value, Strictness}};
+    ///
+    /// let data = b"##fileformat=VCFv4.3\n##INFO=<ID=NDLS,Number=R,Type=Integer,Description=\"noodles\">
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t1\t.\tA\tC\t.\tPASS\tNDLS=8,13
+    /// ";
+    ///
+    /// let mut reader = vcf::Reader::new(&data[..]);
+    /// let header: vcf::Header = reader.read_header()?.parse()?;
+    ///
+    /// let mut records = reader.records_with_header(&header, Strictness::Strict);
+    ///
+    /// let (record, warnings) = records.next().transpose()?.expect("missing record");
+    /// assert_eq!(
+    ///     record.info()[0].value(),
+    ///     Some(&Value::IntegerArray(vec![Some(8), Some(13)]))
+    /// );
+    /// assert!(warnings.is_empty());
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn records_with_header<'a>(
+        &'a mut self,
+        header: &'a Header,
+        strictness: Strictness,
+    ) -> RecordsWithHeader<'a, R> {
+        RecordsWithHeader::new(self, header, strictness)
+    }
 }
 
-impl<R> Reader<BufReader<bgzf::Reader<R>>>
+impl<R> Reader<bgzf::Reader<R>>
 where
     R: Read,
 {
@@ -204,12 +297,12 @@ where
     /// # Examples
     ///
     /// ```
-    /// # use std::io::{self, BufReader};
+    /// # use std::io;
     /// use noodles_bgzf as bgzf;
     /// use noodles_vcf as vcf;
     ///
     /// let data = Vec::new();
-    /// let reader = vcf::Reader::new(BufReader::new(bgzf::Reader::new(&data[..])));
+    /// let reader = vcf::Reader::new(bgzf::Reader::new(&data[..]));
     /// let virtual_position = reader.virtual_position();
     ///
     /// assert_eq!(virtual_position.compressed(), 0);
@@ -217,11 +310,11 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn virtual_position(&self) -> bgzf::VirtualPosition {
-        self.inner.get_ref().virtual_position()
+        self.inner.virtual_position()
     }
 }
 
-impl<R> Reader<BufReader<bgzf::Reader<R>>>
+impl<R> Reader<bgzf::Reader<R>>
 where
     R: Read + Seek,
 {
@@ -232,13 +325,12 @@ where
     /// # Examples
     ///
     /// ```no_run
-    /// # use std::{fs::File, io::{self, BufReader}};
+    /// # use std::{fs::File, io};
     /// use noodles_bgzf as bgzf;
     /// use noodles_vcf as vcf;
     ///
     /// let mut reader = File::open("sample.vcf.gz")
     ///     .map(bgzf::Reader::new)
-    ///     .map(BufReader::new)
     ///     .map(vcf::Reader::new)?;
     ///
     /// let virtual_position = bgzf::VirtualPosition::from(102334155);
@@ -246,7 +338,97 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
-        self.inner.get_mut().seek(pos)
+        self.inner.seek(pos)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// The region's reference sequence name is resolved using the reference sequence names in
+    /// the tabix index. A record intersects the region if its span, i.e., from its start position
+    /// to its end position (see [`crate::Record::end`]), overlaps the region's interval.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles::Region;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut reader = File::open("sample.vcf.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(vcf::Reader::new)?;
+    ///
+    /// let index = tabix::read("sample.vcf.gz.tbi")?;
+    /// let region = Region::mapped("sq0", 8, 13);
+    ///
+    /// for result in reader.query(&index, &region)? {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn query(&mut self, index: &tabix::Index, region: &Region) -> io::Result<Query<'_, R>> {
+        let (i, start, end) = resolve_region(index, region)?;
+
+        let index_reference_sequence = index.reference_sequences().get(i).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "could not find reference in index: {} >= {}",
+                    i,
+                    index.reference_sequences().len()
+                ),
+            )
+        })?;
+
+        let query_bins = index_reference_sequence.query(start, end);
+
+        let chunks: Vec<_> = query_bins
+            .iter()
+            .flat_map(|bin| bin.chunks())
+            .cloned()
+            .collect();
+
+        let min_offset = index_reference_sequence.min_offset(start);
+        let merged_chunks = tabix::optimize_chunks(&chunks, min_offset);
+
+        let reference_sequence_name = index.reference_sequence_names()[i].clone();
+
+        Ok(Query::new(
+            self,
+            merged_chunks,
+            reference_sequence_name,
+            start,
+            end,
+        ))
+    }
+}
+
+fn resolve_region(index: &tabix::Index, region: &Region) -> io::Result<(usize, i32, i32)> {
+    match region {
+        Region::Mapped { name, start, end } => {
+            let i = index
+                .reference_sequence_names()
+                .iter()
+                .position(|n| n == name)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "region reference sequence does not exist in reference sequences: {:?}",
+                            region
+                        ),
+                    )
+                })?;
+
+            Ok((i, *start, *end))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "region is not mapped",
+        )),
     }
 }
 
@@ -302,6 +484,17 @@ sq0\t13
         Ok(())
     }
 
+    #[test]
+    fn test_read_header_with_no_records() -> io::Result<()> {
+        let data = b"##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n";
+        let mut reader = Reader::new(&data[..]);
+
+        let actual = reader.read_header()?;
+        assert_eq!(actual.as_bytes(), &data[..]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_record() -> io::Result<()> {
         let mut reader = Reader::new(DATA);
@@ -325,6 +518,108 @@ sq0\t13
         Ok(())
     }
 
+    #[test]
+    fn test_read_lazy_record() -> io::Result<()> {
+        let data = b"##fileformat=VCFv4.3
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0
+sq0\t2
+";
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_header()?;
+
+        let mut record = lazy::Record::default();
+        let bytes_read = reader.read_lazy_record(&mut record)?;
+        assert_eq!(bytes_read, 28);
+        assert_eq!(record.position(), "1");
+        assert_eq!(record.format(), Some("GT"));
+        assert_eq!(record.samples().get(0), Some("0|0"));
+
+        assert!(reader.read_lazy_record(&mut record).is_err());
+
+        let bytes_read = reader.read_lazy_record(&mut record)?;
+        assert_eq!(bytes_read, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::{Cursor, Write};
+
+        use tabix::index::{
+            reference_sequence::{bin::Chunk, Bin},
+            ReferenceSequence,
+        };
+
+        fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+            let mut writer = bgzf::Writer::new(Vec::new());
+            writer.write_all(data)?;
+            writer.finish()
+        }
+
+        fn chunk(start: usize, end: usize) -> Chunk {
+            Chunk::new(
+                bgzf::VirtualPosition::from((start as u64) << 16),
+                bgzf::VirtualPosition::from((end as u64) << 16),
+            )
+        }
+
+        // Each part is a separate BGZF member, so each starts at a known compressed position.
+        let parts = [
+            compress(b"##fileformat=VCFv4.3\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n")?,
+            compress(b"sq0\t8\t.\tA\t.\t.\tPASS\t.\nsq0\t13\t.\tACGT\t.\t.\tPASS\t.\n")?,
+            compress(b"sq0\t21\t.\tA\t<DEL>\t.\tPASS\tSVLEN=-13\n")?,
+            compress(b"sq1\t8\t.\tA\t.\t.\tPASS\t.\n")?,
+        ];
+
+        let mut offsets = vec![0];
+
+        for part in &parts {
+            offsets.push(offsets.last().unwrap() + part.len());
+        }
+
+        let index = tabix::Index::builder()
+            .set_reference_sequence_names(vec![String::from("sq0"), String::from("sq1")])
+            .set_reference_sequences(vec![
+                ReferenceSequence::new(
+                    vec![Bin::new(
+                        4681,
+                        vec![chunk(offsets[1], offsets[2]), chunk(offsets[2], offsets[3])],
+                    )],
+                    vec![bgzf::VirtualPosition::from((offsets[1] as u64) << 16)],
+                ),
+                ReferenceSequence::new(
+                    vec![Bin::new(4681, vec![chunk(offsets[3], offsets[4])])],
+                    vec![bgzf::VirtualPosition::from((offsets[3] as u64) << 16)],
+                ),
+            ])
+            .build();
+
+        let data = parts.concat();
+        let mut reader = Reader::new(bgzf::Reader::new(Cursor::new(data)));
+        reader.read_header()?;
+
+        let region = Region::mapped("sq0", 14, 30);
+        let positions = reader
+            .query(&index, &region)?
+            .map(|result| result.map(|record| record.position()))
+            .collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(positions, [13, 21]);
+
+        let region = Region::mapped("sq0", 9, 12);
+        assert_eq!(reader.query(&index, &region)?.count(), 0);
+
+        let region = Region::mapped("sq1", 1, 8);
+        assert_eq!(reader.query(&index, &region)?.count(), 1);
+
+        let region = Region::mapped("sq2", 1, 8);
+        assert!(reader.query(&index, &region).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_line() -> io::Result<()> {
         let mut buf = String::new();
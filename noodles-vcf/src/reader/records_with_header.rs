@@ -0,0 +1,62 @@
+use std::io::{self, BufRead};
+
+use crate::{
+    record::{ParseError, Strictness},
+    Header, Record,
+};
+
+use super::Reader;
+
+/// An iterator over records of a VCF reader parsed with the definitions of a VCF header.
+///
+/// Each record is returned with its parse warnings.
+///
+/// This is created by calling [`Reader::records_with_header`].
+pub struct RecordsWithHeader<'a, R> {
+    inner: &'a mut Reader<R>,
+    header: &'a Header,
+    strictness: Strictness,
+    line_buf: String,
+}
+
+impl<'a, R> RecordsWithHeader<'a, R>
+where
+    R: BufRead,
+{
+    pub(crate) fn new(
+        inner: &'a mut Reader<R>,
+        header: &'a Header,
+        strictness: Strictness,
+    ) -> Self {
+        Self {
+            inner,
+            header,
+            strictness,
+            line_buf: String::new(),
+        }
+    }
+}
+
+impl<'a, R> Iterator for RecordsWithHeader<'a, R>
+where
+    R: BufRead,
+{
+    type Item = io::Result<(Record, Vec<ParseError>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.line_buf.clear();
+
+        match self.inner.read_record(&mut self.line_buf) {
+            Ok(0) => None,
+            Ok(_) => Some(
+                Record::try_from_str_header_with_strictness(
+                    &self.line_buf,
+                    self.header,
+                    self.strictness,
+                )
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            ),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
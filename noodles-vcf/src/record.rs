@@ -21,6 +21,10 @@ pub use self::{
 
 use std::{error, fmt, num, str::FromStr};
 
+use crate::{header::Number, Header};
+
+use self::genotype::genotype_count;
+
 pub(crate) const MISSING_FIELD: &str = ".";
 pub(crate) const FIELD_DELIMITER: char = '\t';
 
@@ -257,8 +261,8 @@ impl Record {
     ///     .build()?;
     ///
     /// assert_eq!(record.info(), &Info::from(vec![
-    ///     Field::new(Key::SamplesWithDataCount, Value::Integer(3)),
-    ///     Field::new(Key::AlleleFrequencies, Value::FloatArray(vec![0.5])),
+    ///     Field::new(Key::SamplesWithDataCount, Some(Value::Integer(3))),
+    ///     Field::new(Key::AlleleFrequencies, Some(Value::FloatArray(vec![Some(0.5)]))),
     /// ]));
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
@@ -320,7 +324,7 @@ impl Record {
     ///
     /// assert_eq!(record.genotypes(), [
     ///     Genotype::try_from(vec![
-    ///         Field::new(Key::Genotype, Some(Value::String(String::from("0|0")))),
+    ///         Field::new(Key::Genotype, Some(Value::Genotype("0|0".parse()?))),
     ///         Field::new(Key::ConditionalGenotypeQuality, Some(Value::Integer(13))),
     ///     ])?,
     /// ]);
@@ -329,6 +333,292 @@ impl Record {
     pub fn genotypes(&self) -> &[Genotype] {
         &self.genotypes
     }
+
+    /// Returns the end position of the record.
+    ///
+    /// This is the end position (`END`) info field, if set. Otherwise, if the first alternate
+    /// allele is a symbolic deletion (`<DEL>`), duplication (`<DUP>`), inversion (`<INV>`), or
+    /// copy number variation (`<CNV>`), it is the start position plus the absolute value of the
+    /// first structural variant length (`SVLEN`), if set. Otherwise, it is the position of the last
+    /// base of the reference bases, e.g., an insertion (`<INS>`) ends at its start position. The
+    /// position is 1-based, inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(8)
+    ///     .set_reference_bases("ACGT".parse()?)
+    ///     .build()?;
+    /// assert_eq!(record.end(), 11);
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(8)
+    ///     .set_reference_bases("A".parse()?)
+    ///     .set_alternate_bases("<DEL>".parse()?)
+    ///     .set_info("SVLEN=-5".parse()?)
+    ///     .build()?;
+    /// assert_eq!(record.end(), 13);
+    ///
+    /// let record = vcf::Record::builder()
+    ///     .set_chromosome("sq0".parse()?)
+    ///     .set_position(8)
+    ///     .set_reference_bases("A".parse()?)
+    ///     .set_alternate_bases("<INS>".parse()?)
+    ///     .set_info("SVLEN=21".parse()?)
+    ///     .build()?;
+    /// assert_eq!(record.end(), 8);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn end(&self) -> i32 {
+        use info::field::{Key, Value};
+
+        if let Some(field) = self.info.iter().find(|f| f.key() == &Key::EndPosition) {
+            if let Some(Value::Integer(n)) = field.value() {
+                return *n;
+            }
+        }
+
+        if !self.spans_sv_len() {
+            return self.position + self.reference_bases.len() as i32 - 1;
+        }
+
+        if let Some(field) = self.info.iter().find(|f| f.key() == &Key::SvLengths) {
+            let sv_len = match field.value() {
+                Some(Value::Integer(n)) => Some(*n),
+                Some(Value::IntegerArray(values)) => values.first().copied().flatten(),
+                _ => None,
+            };
+
+            if let Some(n) = sv_len {
+                return self.position + n.abs();
+            }
+        }
+
+        self.position + self.reference_bases.len() as i32 - 1
+    }
+
+    // Returns whether the structural variant length (`SVLEN`) is the length of the reference
+    // bases covered by the first alternate allele.
+    fn spans_sv_len(&self) -> bool {
+        use alternate_bases::allele::{symbol::structural_variant::Type, Allele, Symbol};
+
+        matches!(
+            self.alternate_bases.first(),
+            Some(Allele::Symbol(Symbol::StructuralVariant(sv)))
+                if matches!(
+                    sv.ty(),
+                    Type::Deletion | Type::Duplication | Type::Inversion | Type::CopyNumberVariation
+                )
+        )
+    }
+
+    pub(crate) fn position_mut(&mut self) -> &mut i32 {
+        &mut self.position
+    }
+
+    pub(crate) fn reference_bases_mut(&mut self) -> &mut ReferenceBases {
+        &mut self.reference_bases
+    }
+
+    pub(crate) fn alternate_bases_mut(&mut self) -> &mut AlternateBases {
+        &mut self.alternate_bases
+    }
+
+    pub(crate) fn info_mut(&mut self) -> &mut Info {
+        &mut self.info
+    }
+
+    pub(crate) fn genotypes_mut(&mut self) -> &mut [Genotype] {
+        &mut self.genotypes
+    }
+
+    /// Parses a raw VCF record using the INFO and FORMAT definitions of a VCF header.
+    ///
+    /// This is the same as [`Self::try_from_str_header_with_strictness`] using
+    /// [`Strictness::Lenient`], discarding any warnings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::{info::Type, Info, Number},
+    ///     record::info::field::Value,
+    /// };
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_info(Info::new(
+    ///         "NDLS".parse()?,
+    ///         Number::A,
+    ///         Type::Integer,
+    ///         String::from("noodles"),
+    ///     ))
+    ///     .build();
+    ///
+    /// let record = vcf::Record::try_from_str_header("sq0\t1\t.\tA\tC,G\t.\tPASS\tNDLS=8,13", &header)?;
+    /// assert_eq!(
+    ///     record.info()[0].value(),
+    ///     Some(&Value::IntegerArray(vec![Some(8), Some(13)]))
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_str_header(s: &str, header: &Header) -> Result<Self, ParseError> {
+        Self::try_from_str_header_with_strictness(s, header, Strictness::default())
+            .map(|(record, _)| record)
+    }
+
+    /// Parses a raw VCF record using the INFO and FORMAT definitions of a VCF header.
+    ///
+    /// Keys declared in the header take the number and type of their definitions. The number of
+    /// values of each field with a declared key is checked against its declared number, where `A`,
+    /// `R`, and `G` are relative to the number of alternate bases. The ploidy for `G` is taken from
+    /// the genotype (`GT`) of the sample or is otherwise assumed to be 2.
+    ///
+    /// When using [`Strictness::Strict`], keys not declared in the header and value count
+    /// mismatches are errors. Otherwise, they are returned as warnings along with the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::{info::Type, Info, Number},
+    ///     record::{ParseError, Strictness},
+    /// };
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_info(Info::new(
+    ///         "NDLS".parse()?,
+    ///         Number::A,
+    ///         Type::Integer,
+    ///         String::from("noodles"),
+    ///     ))
+    ///     .build();
+    ///
+    /// let s = "sq0\t1\t.\tA\tC\t.\tPASS\tNDLS=8,13";
+    ///
+    /// let (_, warnings) =
+    ///     vcf::Record::try_from_str_header_with_strictness(s, &header, Strictness::Lenient)?;
+    /// assert!(matches!(warnings[..], [ParseError::InvalidInfoValueCount(..)]));
+    ///
+    /// assert!(matches!(
+    ///     vcf::Record::try_from_str_header_with_strictness(s, &header, Strictness::Strict),
+    ///     Err(ParseError::InvalidInfoValueCount(..))
+    /// ));
+    ///
+    /// let s = "sq0\t1\t.\tA\tC\t.\tPASS\tZS=ndls";
+    /// assert!(matches!(
+    ///     vcf::Record::try_from_str_header_with_strictness(s, &header, Strictness::Strict),
+    ///     Err(ParseError::UndeclaredInfoKey(_))
+    /// ));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_str_header_with_strictness(
+        s: &str,
+        header: &Header,
+        strictness: Strictness,
+    ) -> Result<(Self, Vec<ParseError>), ParseError> {
+        let record = parse(s, Some(header))?;
+
+        let mut warnings = Vec::new();
+
+        let mut warn = |e: ParseError| {
+            if strictness == Strictness::Strict {
+                Err(e)
+            } else {
+                warnings.push(e);
+                Ok(())
+            }
+        };
+
+        let alternate_bases_count = record.alternate_bases().len();
+
+        for field in record.info().iter() {
+            let key = field.key();
+
+            let info = match header.get_info(key.as_ref()) {
+                Some(info) => info,
+                None => {
+                    warn(ParseError::UndeclaredInfoKey(key.clone()))?;
+                    continue;
+                }
+            };
+
+            let actual = match field.value() {
+                Some(value) => info_value_count(value),
+                None => continue,
+            };
+
+            if let Some(expected) = expected_value_count(info.number(), alternate_bases_count, 2) {
+                if actual != expected {
+                    warn(ParseError::InvalidInfoValueCount(
+                        key.clone(),
+                        expected,
+                        actual,
+                    ))?;
+                }
+            }
+        }
+
+        if let Some(format) = record.format() {
+            for key in format.iter() {
+                if header.get_format(key.as_ref()).is_none() {
+                    warn(ParseError::UndeclaredFormatKey(key.clone()))?;
+                }
+            }
+        }
+
+        for genotype in record.genotypes() {
+            let ploidy = genotype_ploidy(genotype).unwrap_or(2);
+
+            for field in genotype.iter() {
+                let key = field.key();
+
+                if key == &genotype::field::Key::Genotype {
+                    continue;
+                }
+
+                let format = match header.get_format(key.as_ref()) {
+                    Some(format) => format,
+                    None => continue,
+                };
+
+                let actual = match field.value() {
+                    Some(value) => genotype_value_count(value),
+                    None => continue,
+                };
+
+                if let Some(expected) =
+                    expected_value_count(format.number(), alternate_bases_count, ploidy)
+                {
+                    if actual != expected {
+                        warn(ParseError::InvalidGenotypeValueCount(
+                            key.clone(),
+                            expected,
+                            actual,
+                        ))?;
+                    }
+                }
+            }
+        }
+
+        Ok((record, warnings))
+    }
+}
+
+/// The handling of INFO and FORMAT keys that are not declared in the header.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Strictness {
+    /// Undeclared keys are parsed without a definition and reported as warnings.
+    #[default]
+    Lenient,
+    /// Undeclared keys are an error.
+    Strict,
 }
 
 /// An error returned when a raw VCF record fails to parse.
@@ -356,6 +646,18 @@ pub enum ParseError {
     InvalidFormat(format::ParseError),
     /// A genotype is invalid.
     InvalidGenotype(genotype::ParseError),
+    /// An info key is not declared in the header.
+    UndeclaredInfoKey(info::field::Key),
+    /// A format key is not declared in the header.
+    UndeclaredFormatKey(genotype::field::Key),
+    /// The number of values of an info field does not match its declared number.
+    ///
+    /// This holds the key, the expected count, and the actual count.
+    InvalidInfoValueCount(info::field::Key, usize, usize),
+    /// The number of values of a genotype field does not match its declared number.
+    ///
+    /// This holds the key, the expected count, and the actual count.
+    InvalidGenotypeValueCount(genotype::field::Key, usize, usize),
 }
 
 impl error::Error for ParseError {}
@@ -374,6 +676,20 @@ impl fmt::Display for ParseError {
             Self::InvalidInfo(e) => write!(f, "{}", e),
             Self::InvalidFormat(e) => write!(f, "{}", e),
             Self::InvalidGenotype(e) => write!(f, "{}", e),
+            Self::UndeclaredInfoKey(key) => write!(f, "undeclared info key: {}", key),
+            Self::UndeclaredFormatKey(key) => write!(f, "undeclared format key: {}", key.as_ref()),
+            Self::InvalidInfoValueCount(key, expected, actual) => write!(
+                f,
+                "invalid info value count for {}: expected {}, got {}",
+                key, expected, actual
+            ),
+            Self::InvalidGenotypeValueCount(key, expected, actual) => write!(
+                f,
+                "invalid genotype value count for {}: expected {}, got {}",
+                key.as_ref(),
+                expected,
+                actual
+            ),
         }
     }
 }
@@ -382,59 +698,123 @@ impl FromStr for Record {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut fields = s.split(FIELD_DELIMITER);
+        parse(s, None)
+    }
+}
 
-        let chrom = parse_string(&mut fields, Field::Chromosome)
-            .and_then(|s| s.parse().map_err(ParseError::InvalidChromosome))?;
+fn parse(s: &str, header: Option<&Header>) -> Result<Record, ParseError> {
+    let mut fields = s.split(FIELD_DELIMITER);
 
-        let pos = parse_string(&mut fields, Field::Position)
-            .and_then(|s| s.parse().map_err(ParseError::InvalidPosition))?;
+    let chrom = parse_string(&mut fields, Field::Chromosome)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidChromosome))?;
 
-        let ids = parse_string(&mut fields, Field::Ids)
-            .and_then(|s| s.parse().map_err(ParseError::InvalidIds))?;
+    let pos = parse_string(&mut fields, Field::Position)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidPosition))?;
 
-        let r#ref = parse_string(&mut fields, Field::ReferenceBases)
-            .and_then(|s| s.parse().map_err(ParseError::InvalidReferenceBases))?;
+    let ids = parse_string(&mut fields, Field::Ids)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidIds))?;
 
-        let alt = parse_string(&mut fields, Field::AlternateBases)
-            .and_then(|s| s.parse().map_err(ParseError::InvalidAlternateBases))?;
+    let r#ref = parse_string(&mut fields, Field::ReferenceBases)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidReferenceBases))?;
 
-        let qual = parse_string(&mut fields, Field::QualityScore)
-            .and_then(|s| s.parse().map_err(ParseError::InvalidQualityScore))?;
+    let alt = parse_string(&mut fields, Field::AlternateBases)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidAlternateBases))?;
 
-        let filter = parse_string(&mut fields, Field::FilterStatus)
-            .and_then(|s| s.parse().map_err(ParseError::InvalidFilterStatus))?;
+    let qual = parse_string(&mut fields, Field::QualityScore)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidQualityScore))?;
 
-        let info = parse_string(&mut fields, Field::Info)
-            .and_then(|s| s.parse().map_err(ParseError::InvalidInfo))?;
+    let filter = parse_string(&mut fields, Field::FilterStatus)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidFilterStatus))?;
 
-        let format = match fields.next() {
-            Some(s) => s.parse().map(Some).map_err(ParseError::InvalidFormat)?,
-            None => None,
-        };
+    let info = parse_string(&mut fields, Field::Info).and_then(|s| {
+        match header {
+            Some(h) => Info::try_from_str_header(s, h),
+            None => s.parse(),
+        }
+        .map_err(ParseError::InvalidInfo)
+    })?;
+
+    let format = match fields.next() {
+        Some(s) => match header {
+            Some(h) => Format::try_from_str_header(s, h),
+            None => s.parse(),
+        }
+        .map(Some)
+        .map_err(ParseError::InvalidFormat)?,
+        None => None,
+    };
 
-        let genotypes = format
-            .as_ref()
-            .map(|f| {
-                fields
-                    .map(|s| Genotype::from_str_format(s, f))
-                    .collect::<Result<_, _>>()
-                    .map_err(ParseError::InvalidGenotype)
-            })
-            .unwrap_or_else(|| Ok(Vec::new()))?;
-
-        Ok(Self {
-            chromosome: chrom,
-            position: pos,
-            ids,
-            reference_bases: r#ref,
-            alternate_bases: alt,
-            quality_score: qual,
-            filter_status: filter,
-            info,
-            format,
-            genotypes,
+    let genotypes = format
+        .as_ref()
+        .map(|f| {
+            fields
+                .map(|s| Genotype::from_str_format(s, f))
+                .collect::<Result<_, _>>()
+                .map_err(ParseError::InvalidGenotype)
         })
+        .unwrap_or_else(|| Ok(Vec::new()))?;
+
+    Ok(Record {
+        chromosome: chrom,
+        position: pos,
+        ids,
+        reference_bases: r#ref,
+        alternate_bases: alt,
+        quality_score: qual,
+        filter_status: filter,
+        info,
+        format,
+        genotypes,
+    })
+}
+
+fn expected_value_count(
+    number: Number,
+    alternate_bases_count: usize,
+    ploidy: usize,
+) -> Option<usize> {
+    match number {
+        Number::Count(n) => Some(n),
+        Number::A => Some(alternate_bases_count),
+        Number::R => Some(alternate_bases_count + 1),
+        Number::G => Some(genotype_count(alternate_bases_count + 1, ploidy)),
+        Number::Unknown => None,
+    }
+}
+
+fn genotype_ploidy(genotype: &Genotype) -> Option<usize> {
+    genotype
+        .genotype()
+        .and_then(|result| result.ok())
+        .map(|gt| gt.ploidy())
+}
+
+fn info_value_count(value: &info::field::Value) -> usize {
+    use info::field::Value;
+
+    match value {
+        Value::Flag => 0,
+        Value::Integer(_) | Value::Float(_) | Value::Character(_) | Value::String(_) => 1,
+        Value::IntegerArray(values) => values.len(),
+        Value::FloatArray(values) => values.len(),
+        Value::CharacterArray(values) => values.len(),
+        Value::StringArray(values) => values.len(),
+    }
+}
+
+fn genotype_value_count(value: &genotype::field::Value) -> usize {
+    use genotype::field::Value;
+
+    match value {
+        Value::Integer(_)
+        | Value::Float(_)
+        | Value::Character(_)
+        | Value::String(_)
+        | Value::Genotype(_) => 1,
+        Value::IntegerArray(values) => values.len(),
+        Value::FloatArray(values) => values.len(),
+        Value::CharacterArray(values) => values.len(),
+        Value::StringArray(values) => values.len(),
     }
 }
 
@@ -478,7 +858,27 @@ mod tests {
     }
 
     #[test]
-    fn test_from_str_with_genotype_info() -> Result<(), ParseError> {
+    fn test_end() -> Result<(), ParseError> {
+        let record: Record = "sq0\t8\t.\tACGT\tA\t.\tPASS\t.".parse()?;
+        assert_eq!(record.end(), 11);
+
+        let record: Record = "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tEND=21;SVLEN=-5".parse()?;
+        assert_eq!(record.end(), 21);
+
+        let record: Record = "sq0\t8\t.\tA\t<DUP>\t.\tPASS\tSVLEN=5".parse()?;
+        assert_eq!(record.end(), 13);
+
+        let record: Record = "sq0\t8\t.\tA\t<INS>\t.\tPASS\tSVLEN=21".parse()?;
+        assert_eq!(record.end(), 8);
+
+        let record: Record = "sq0\t8\t.\tA\tACGT\t.\tPASS\tSVLEN=3".parse()?;
+        assert_eq!(record.end(), 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str_with_genotype_info() -> Result<(), Box<dyn std::error::Error>> {
         let s = "chr1\t13\tnd0\tATCG\tA\t5.8\tPASS\tSVTYPE=DEL\tGT:GQ\t0|1:13";
         let record: Record = s.parse()?;
 
@@ -493,7 +893,7 @@ mod tests {
         let expected = vec![
             genotype::Field::new(
                 genotype::field::Key::Genotype,
-                Some(genotype::field::Value::String(String::from("0|1"))),
+                Some(genotype::field::Value::Genotype("0|1".parse()?)),
             ),
             genotype::Field::new(
                 genotype::field::Key::ConditionalGenotypeQuality,
@@ -506,4 +906,125 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_try_from_str_header_with_strictness() -> Result<(), Box<dyn std::error::Error>> {
+        let header: Header = r#"##fileformat=VCFv4.3
+##INFO=<ID=ZR,Number=R,Type=Integer,Description="noodles">
+##FORMAT=<ID=GT,Number=1,Type=String,Description="Genotype">
+##FORMAT=<ID=ZG,Number=G,Type=Float,Description="noodles">
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO	FORMAT	sample0	sample1
+"#
+        .parse()?;
+
+        let s = "sq0\t1\t.\tA\tC\t.\tPASS\tZR=8,13\tGT:ZG\t0/1:0.1,0.2,0.7\t1:0.5,0.5";
+        let (record, warnings) =
+            Record::try_from_str_header_with_strictness(s, &header, Strictness::Strict)?;
+        assert!(warnings.is_empty());
+        assert_eq!(
+            record.info()[0].value(),
+            Some(&info::field::Value::IntegerArray(vec![Some(8), Some(13)]))
+        );
+        assert_eq!(
+            record.genotypes()[1][1].value(),
+            Some(&genotype::field::Value::FloatArray(vec![
+                Some(0.5),
+                Some(0.5)
+            ]))
+        );
+
+        let s = "sq0\t1\t.\tA\tC\t.\tPASS\tZR=8";
+        let expected = ParseError::InvalidInfoValueCount(
+            info::field::Key::Other(
+                String::from("ZR"),
+                Number::R,
+                crate::header::info::Type::Integer,
+            ),
+            2,
+            1,
+        );
+        assert_eq!(
+            Record::try_from_str_header_with_strictness(s, &header, Strictness::Strict),
+            Err(expected.clone())
+        );
+        let (_, warnings) =
+            Record::try_from_str_header_with_strictness(s, &header, Strictness::Lenient)?;
+        assert_eq!(warnings, [expected]);
+
+        let s = "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:ZG\t0/1:0.1,0.9";
+        assert!(matches!(
+            Record::try_from_str_header_with_strictness(s, &header, Strictness::Strict),
+            Err(ParseError::InvalidGenotypeValueCount(_, 3, 2))
+        ));
+
+        let (_, warnings) =
+            Record::try_from_str_header_with_strictness(s, &header, Strictness::Lenient)?;
+        assert_eq!(warnings.len(), 1);
+
+        let s = "sq0\t1\t.\tA\tC\t.\tPASS\tZB;AC=1,2\tGT:ZH\t0/1:8,13";
+        let (record, warnings) =
+            Record::try_from_str_header_with_strictness(s, &header, Strictness::Lenient)?;
+        assert_eq!(record.info()[0].value(), Some(&info::field::Value::Flag));
+        assert!(matches!(
+            warnings[..],
+            [
+                ParseError::UndeclaredInfoKey(_),
+                ParseError::UndeclaredInfoKey(info::field::Key::AlleleCount),
+                ParseError::UndeclaredFormatKey(_),
+            ]
+        ));
+
+        let s = "sq0\t1\t.\tA\tC\t.\tPASS\tZS=ndls\tGT:ZH\t0/1:8";
+        let record = Record::try_from_str_header(s, &header)?;
+        assert_eq!(
+            record.info()[0].value(),
+            Some(&info::field::Value::String(String::from("ndls")))
+        );
+
+        assert!(matches!(
+            Record::try_from_str_header_with_strictness(s, &header, Strictness::Strict),
+            Err(ParseError::UndeclaredInfoKey(_))
+        ));
+
+        let s = "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT:ZH\t0/1:8";
+        assert!(matches!(
+            Record::try_from_str_header_with_strictness(s, &header, Strictness::Strict),
+            Err(ParseError::UndeclaredFormatKey(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_str_header_with_missing_info_values() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let header: Header = r#"##fileformat=VCFv4.3
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth across samples">
+##INFO=<ID=AF,Number=A,Type=Float,Description="Allele frequency">
+##INFO=<ID=ZZ,Number=A,Type=Integer,Description="noodles">
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#
+        .parse()?;
+
+        let s = "sq0\t1\t.\tA\tC,G\t.\tPASS\tDP=.;AF=.;ZZ=1,.";
+
+        for strictness in [Strictness::Lenient, Strictness::Strict] {
+            let (record, warnings) =
+                Record::try_from_str_header_with_strictness(s, &header, strictness)?;
+
+            assert!(warnings.is_empty());
+
+            let info = record.info();
+            assert_eq!(info[0].value(), None);
+            assert_eq!(info[1].value(), None);
+            assert_eq!(
+                info[2].value(),
+                Some(&info::field::Value::IntegerArray(vec![Some(1), None]))
+            );
+
+            assert_eq!(info.to_string(), "DP=.;AF=.;ZZ=1,.");
+        }
+
+        Ok(())
+    }
 }
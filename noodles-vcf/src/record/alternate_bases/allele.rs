@@ -1,8 +1,9 @@
-//! VCF record alternate bases allele and symbol.
+//! VCF record alternate bases allele, breakend, and symbol.
 
+pub mod breakend;
 pub mod symbol;
 
-pub use self::symbol::Symbol;
+pub use self::{breakend::Breakend, symbol::Symbol};
 
 use std::{convert::TryFrom, error, fmt, str::FromStr};
 
@@ -16,7 +17,7 @@ pub enum Allele {
     /// A symbolic allele (e.g., `<DEL>`, `<CN:0>`, etc.).
     Symbol(Symbol),
     /// A breakend (e.g., `]sq0:5]A`, `G.`, etc.).
-    Breakend(String),
+    Breakend(Breakend),
     /// An overlapping deletion, i.e., a missing allele (`*`).
     OverlappingDeletion,
 }
@@ -32,7 +33,7 @@ impl fmt::Display for Allele {
                 Ok(())
             }
             Self::Symbol(symbol) => write!(f, "<{}>", symbol),
-            Self::Breakend(breakend) => f.write_str(breakend),
+            Self::Breakend(breakend) => write!(f, "{}", breakend),
             Self::OverlappingDeletion => f.write_str("*"),
         }
     }
@@ -45,6 +46,8 @@ pub enum ParseError {
     Empty,
     /// The symbol is invalid.
     InvalidSymbol(symbol::ParseError),
+    /// The breakend is invalid.
+    InvalidBreakend(breakend::ParseError),
     /// A base is invalid.
     InvalidBase(base::TryFromCharError),
 }
@@ -56,6 +59,7 @@ impl fmt::Display for ParseError {
         match self {
             Self::Empty => f.write_str("empty input"),
             Self::InvalidSymbol(e) => write!(f, "invalid symbol: {}", e),
+            Self::InvalidBreakend(e) => write!(f, "invalid breakend: {}", e),
             Self::InvalidBase(e) => write!(f, "invalid base: {}", e),
         }
     }
@@ -74,9 +78,12 @@ impl FromStr for Allele {
                         .parse()
                         .map(Self::Symbol)
                         .map_err(ParseError::InvalidSymbol)
-                } else if s.contains(|c| c == '[' || c == ']') || (s.len() == 2 && s.contains('.'))
+                } else if s.contains(&['[', ']'][..])
+                    || (s.len() > 1 && (s.starts_with('.') || s.ends_with('.')))
                 {
-                    Ok(Self::Breakend(s.into()))
+                    s.parse()
+                        .map(Self::Breakend)
+                        .map_err(ParseError::InvalidBreakend)
                 } else {
                     s.chars()
                         .map(|c| c.to_ascii_uppercase())
@@ -95,7 +102,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_fmt() {
+    fn test_fmt() -> Result<(), breakend::ParseError> {
         let allele = Allele::Bases(vec![Base::G]);
         assert_eq!(allele.to_string(), "G");
 
@@ -113,21 +120,23 @@ mod tests {
         let allele = Allele::Symbol(Symbol::NonstructuralVariant(String::from("CN:0")));
         assert_eq!(allele.to_string(), "<CN:0>");
 
-        let allele = Allele::Breakend(String::from("]sq0:5]A"));
+        let allele = Allele::Breakend("]sq0:5]A".parse()?);
         assert_eq!(allele.to_string(), "]sq0:5]A");
 
-        let allele = Allele::Breakend(String::from("C[sq1:13["));
+        let allele = Allele::Breakend("C[sq1:13[".parse()?);
         assert_eq!(allele.to_string(), "C[sq1:13[");
 
-        let allele = Allele::Breakend(String::from("G."));
+        let allele = Allele::Breakend("G.".parse()?);
         assert_eq!(allele.to_string(), "G.");
 
-        let allele = Allele::Breakend(String::from(".A"));
+        let allele = Allele::Breakend(".A".parse()?);
         assert_eq!(allele.to_string(), ".A");
+
+        Ok(())
     }
 
     #[test]
-    fn test_from_str() -> Result<(), ParseError> {
+    fn test_from_str() -> Result<(), Box<dyn std::error::Error>> {
         assert_eq!("G".parse::<Allele>()?, Allele::Bases(vec![Base::G]));
 
         assert_eq!(
@@ -154,26 +163,25 @@ mod tests {
 
         assert_eq!(
             "]sq0:5]A".parse::<Allele>()?,
-            Allele::Breakend(String::from("]sq0:5]A"))
+            Allele::Breakend("]sq0:5]A".parse()?)
         );
 
         assert_eq!(
             "C[sq1:13[".parse::<Allele>()?,
-            Allele::Breakend(String::from("C[sq1:13["))
+            Allele::Breakend("C[sq1:13[".parse()?)
         );
 
-        assert_eq!(
-            "G.".parse::<Allele>()?,
-            Allele::Breakend(String::from("G."))
-        );
+        assert_eq!("G.".parse::<Allele>()?, Allele::Breakend("G.".parse()?));
 
-        assert_eq!(
-            ".A".parse::<Allele>()?,
-            Allele::Breakend(String::from(".A"))
-        );
+        assert_eq!(".A".parse::<Allele>()?, Allele::Breakend(".A".parse()?));
 
         assert!("".parse::<Allele>().is_err());
 
+        assert!(matches!(
+            "C[sq0[".parse::<Allele>(),
+            Err(ParseError::InvalidBreakend(_))
+        ));
+
         Ok(())
     }
 }
@@ -0,0 +1,482 @@
+//! VCF record alternate bases allele breakend and mate.
+
+use std::{convert::TryFrom, error, fmt, num, str::FromStr};
+
+use crate::record::{
+    chromosome,
+    reference_bases::{base, Base},
+    Chromosome,
+};
+
+const SINGLE_BREAKEND: char = '.';
+const FORWARD_MATE_DELIMITER: char = ']';
+const REVERSE_MATE_DELIMITER: char = '[';
+const MATE_DELIMITERS: [char; 2] = [FORWARD_MATE_DELIMITER, REVERSE_MATE_DELIMITER];
+const POSITION_DELIMITER: char = ':';
+
+/// A VCF record alternate bases allele breakend orientation.
+///
+/// This is the side of a breakend that is joined to another sequence.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Orientation {
+    /// The sequence is joined after the breakend, i.e., the sequence to its left is kept (e.g.,
+    /// `t[p[`, `t]p]`, `t.`).
+    Forward,
+    /// The sequence is joined before the breakend, i.e., the sequence to its right is kept (e.g.,
+    /// `]p]t`, `[p[t`, `.t`).
+    Reverse,
+}
+
+/// A VCF record alternate bases allele breakend mate.
+///
+/// This is the position `p` of a breakend (`chr:pos`) that is joined to the breakend.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mate {
+    chromosome: Chromosome,
+    position: i32,
+    orientation: Orientation,
+}
+
+impl Mate {
+    /// Creates a breakend mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::breakend::{Mate, Orientation};
+    /// let mate = Mate::new("sq0".parse()?, 5, Orientation::Forward);
+    /// # Ok::<(), noodles_vcf::record::chromosome::ParseError>(())
+    /// ```
+    pub fn new(chromosome: Chromosome, position: i32, orientation: Orientation) -> Self {
+        Self {
+            chromosome,
+            position,
+            orientation,
+        }
+    }
+
+    /// Returns the chromosome of the mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::breakend::{Mate, Orientation},
+    ///     Chromosome,
+    /// };
+    ///
+    /// let mate = Mate::new("sq0".parse()?, 5, Orientation::Forward);
+    /// assert_eq!(mate.chromosome(), &Chromosome::Name(String::from("sq0")));
+    /// # Ok::<(), noodles_vcf::record::chromosome::ParseError>(())
+    /// ```
+    pub fn chromosome(&self) -> &Chromosome {
+        &self.chromosome
+    }
+
+    /// Returns the position of the mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::breakend::{Mate, Orientation};
+    /// let mate = Mate::new("sq0".parse()?, 5, Orientation::Forward);
+    /// assert_eq!(mate.position(), 5);
+    /// # Ok::<(), noodles_vcf::record::chromosome::ParseError>(())
+    /// ```
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+
+    /// Returns the orientation of the mate.
+    ///
+    /// A mate in forward orientation (`]p]`) joins the sequence to the left of its position; and
+    /// reverse orientation (`[p[`), the sequence to the right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::breakend::{Mate, Orientation};
+    /// let mate = Mate::new("sq0".parse()?, 5, Orientation::Forward);
+    /// assert_eq!(mate.orientation(), Orientation::Forward);
+    /// # Ok::<(), noodles_vcf::record::chromosome::ParseError>(())
+    /// ```
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+}
+
+impl fmt::Display for Mate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let delimiter = match self.orientation {
+            Orientation::Forward => FORWARD_MATE_DELIMITER,
+            Orientation::Reverse => REVERSE_MATE_DELIMITER,
+        };
+
+        write!(
+            f,
+            "{}{}{}{}{}",
+            delimiter, self.chromosome, POSITION_DELIMITER, self.position, delimiter
+        )
+    }
+}
+
+/// A VCF record alternate bases allele breakend.
+///
+/// A breakend is a replacement string of bases `t` joined to a mate (see § 5.4 "Specifying
+/// complex rearrangements with breakends" of VCF 4.3). A single breakend (e.g., `G.`) does not
+/// have a mate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Breakend {
+    bases: Vec<Base>,
+    orientation: Orientation,
+    mate: Option<Mate>,
+}
+
+impl Breakend {
+    /// Creates a breakend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{
+    ///     alternate_bases::allele::{breakend::{Mate, Orientation}, Breakend},
+    ///     reference_bases::Base,
+    /// };
+    ///
+    /// let mate = Mate::new("sq1".parse()?, 13, Orientation::Reverse);
+    /// let breakend = Breakend::new(vec![Base::C], Orientation::Forward, Some(mate));
+    ///
+    /// assert_eq!(breakend.to_string(), "C[sq1:13[");
+    /// # Ok::<(), noodles_vcf::record::chromosome::ParseError>(())
+    /// ```
+    pub fn new(bases: Vec<Base>, orientation: Orientation, mate: Option<Mate>) -> Self {
+        Self {
+            bases,
+            orientation,
+            mate,
+        }
+    }
+
+    /// Returns the replacement bases of the breakend.
+    ///
+    /// This includes the reference base and any inserted bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{alternate_bases::allele::Breakend, reference_bases::Base};
+    /// let breakend: Breakend = "CAG[sq1:13[".parse()?;
+    /// assert_eq!(breakend.bases(), [Base::C, Base::A, Base::G]);
+    /// # Ok::<(), noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn bases(&self) -> &[Base] {
+        &self.bases
+    }
+
+    /// Returns the orientation of the breakend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::{breakend::Orientation, Breakend};
+    ///
+    /// let breakend: Breakend = "C[sq1:13[".parse()?;
+    /// assert_eq!(breakend.orientation(), Orientation::Forward);
+    ///
+    /// let breakend: Breakend = "]sq0:5]A".parse()?;
+    /// assert_eq!(breakend.orientation(), Orientation::Reverse);
+    /// # Ok::<(), noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Returns the mate of the breakend.
+    ///
+    /// This is `None` for a single breakend.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::{breakend::{Mate, Orientation}, Breakend};
+    ///
+    /// let breakend: Breakend = "]sq0:5]A".parse()?;
+    /// assert_eq!(
+    ///     breakend.mate(),
+    ///     Some(&Mate::new("sq0".parse()?, 5, Orientation::Forward))
+    /// );
+    ///
+    /// let breakend: Breakend = "G.".parse()?;
+    /// assert!(breakend.mate().is_none());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn mate(&self) -> Option<&Mate> {
+        self.mate.as_ref()
+    }
+
+    /// Returns whether the breakend is a single breakend, i.e., it has no mate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::alternate_bases::allele::Breakend;
+    /// let breakend: Breakend = ".A".parse()?;
+    /// assert!(breakend.is_single());
+    /// # Ok::<(), noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn is_single(&self) -> bool {
+        self.mate.is_none()
+    }
+
+    /// Returns the bases inserted at the junction.
+    ///
+    /// These are the replacement bases without the reference base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::{alternate_bases::allele::Breakend, reference_bases::Base};
+    ///
+    /// let breakend: Breakend = "CAG[sq1:13[".parse()?;
+    /// assert_eq!(breakend.inserted_bases(), [Base::A, Base::G]);
+    ///
+    /// let breakend: Breakend = "]sq0:5]TCA".parse()?;
+    /// assert_eq!(breakend.inserted_bases(), [Base::T, Base::C]);
+    /// # Ok::<(), noodles_vcf::record::alternate_bases::allele::breakend::ParseError>(())
+    /// ```
+    pub fn inserted_bases(&self) -> &[Base] {
+        if self.bases.is_empty() {
+            return &self.bases;
+        }
+
+        match self.orientation {
+            Orientation::Forward => &self.bases[1..],
+            Orientation::Reverse => &self.bases[..self.bases.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Breakend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.orientation == Orientation::Reverse {
+            match &self.mate {
+                Some(mate) => write!(f, "{}", mate)?,
+                None => write!(f, "{}", SINGLE_BREAKEND)?,
+            }
+        }
+
+        for &base in &self.bases {
+            write!(f, "{}", char::from(base))?;
+        }
+
+        if self.orientation == Orientation::Forward {
+            match &self.mate {
+                Some(mate) => write!(f, "{}", mate)?,
+                None => write!(f, "{}", SINGLE_BREAKEND)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when a raw VCF record alternate bases allele breakend fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid,
+    /// The replacement bases are missing.
+    MissingBases,
+    /// A replacement base is invalid.
+    InvalidBase(base::TryFromCharError),
+    /// The mate is invalid.
+    InvalidMate,
+    /// The mate chromosome is invalid.
+    InvalidChromosome(chromosome::ParseError),
+    /// The mate position is invalid.
+    InvalidPosition(num::ParseIntError),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid => f.write_str("invalid input"),
+            Self::MissingBases => f.write_str("missing bases"),
+            Self::InvalidBase(e) => write!(f, "invalid base: {}", e),
+            Self::InvalidMate => f.write_str("invalid mate"),
+            Self::InvalidChromosome(e) => write!(f, "invalid chromosome: {}", e),
+            Self::InvalidPosition(e) => write!(f, "invalid position: {}", e),
+        }
+    }
+}
+
+impl FromStr for Breakend {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let (raw_bases, orientation, mate) = if let Some(i) = s.find(&MATE_DELIMITERS[..]) {
+            if i == 0 {
+                let j = s[1..]
+                    .find(&MATE_DELIMITERS[..])
+                    .map(|j| j + 2)
+                    .ok_or(ParseError::InvalidMate)?;
+
+                let mate = parse_mate(&s[..j])?;
+                (&s[j..], Orientation::Reverse, Some(mate))
+            } else {
+                let mate = parse_mate(&s[i..])?;
+                (&s[..i], Orientation::Forward, Some(mate))
+            }
+        } else if let Some(t) = s.strip_prefix(SINGLE_BREAKEND) {
+            (t, Orientation::Reverse, None)
+        } else if let Some(t) = s.strip_suffix(SINGLE_BREAKEND) {
+            (t, Orientation::Forward, None)
+        } else {
+            return Err(ParseError::Invalid);
+        };
+
+        if raw_bases.is_empty() {
+            return Err(ParseError::MissingBases);
+        }
+
+        let bases = raw_bases
+            .chars()
+            .map(|c| c.to_ascii_uppercase())
+            .map(Base::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(ParseError::InvalidBase)?;
+
+        Ok(Self::new(bases, orientation, mate))
+    }
+}
+
+// Parses a mate delimited by brackets, e.g., `]sq0:5]`.
+fn parse_mate(s: &str) -> Result<Mate, ParseError> {
+    let orientation = if s.starts_with(FORWARD_MATE_DELIMITER) {
+        Orientation::Forward
+    } else {
+        Orientation::Reverse
+    };
+
+    let delimiter = match orientation {
+        Orientation::Forward => FORWARD_MATE_DELIMITER,
+        Orientation::Reverse => REVERSE_MATE_DELIMITER,
+    };
+
+    let t = s
+        .strip_prefix(delimiter)
+        .and_then(|t| t.strip_suffix(delimiter))
+        .ok_or(ParseError::InvalidMate)?;
+
+    let i = t.rfind(POSITION_DELIMITER).ok_or(ParseError::InvalidMate)?;
+
+    let chromosome = t[..i].parse().map_err(ParseError::InvalidChromosome)?;
+    let position = t[i + 1..].parse().map_err(ParseError::InvalidPosition)?;
+
+    Ok(Mate::new(chromosome, position, orientation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), chromosome::ParseError> {
+        let mate = Mate::new("sq1".parse()?, 13, Orientation::Reverse);
+        let breakend = Breakend::new(vec![Base::C], Orientation::Forward, Some(mate));
+        assert_eq!(breakend.to_string(), "C[sq1:13[");
+
+        let mate = Mate::new("sq1".parse()?, 13, Orientation::Forward);
+        let breakend = Breakend::new(vec![Base::C], Orientation::Forward, Some(mate));
+        assert_eq!(breakend.to_string(), "C]sq1:13]");
+
+        let mate = Mate::new("sq0".parse()?, 5, Orientation::Forward);
+        let breakend = Breakend::new(vec![Base::A], Orientation::Reverse, Some(mate));
+        assert_eq!(breakend.to_string(), "]sq0:5]A");
+
+        let mate = Mate::new("<ctg1>".parse()?, 5, Orientation::Reverse);
+        let breakend = Breakend::new(vec![Base::A, Base::C], Orientation::Reverse, Some(mate));
+        assert_eq!(breakend.to_string(), "[<ctg1>:5[AC");
+
+        let breakend = Breakend::new(vec![Base::G], Orientation::Forward, None);
+        assert_eq!(breakend.to_string(), "G.");
+
+        let breakend = Breakend::new(vec![Base::A], Orientation::Reverse, None);
+        assert_eq!(breakend.to_string(), ".A");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), chromosome::ParseError> {
+        let mate = Mate::new("sq1".parse()?, 13, Orientation::Reverse);
+        assert_eq!(
+            "C[sq1:13[".parse(),
+            Ok(Breakend::new(
+                vec![Base::C],
+                Orientation::Forward,
+                Some(mate)
+            ))
+        );
+
+        let mate = Mate::new("sq0".parse()?, 5, Orientation::Forward);
+        assert_eq!(
+            "]sq0:5]ca".parse(),
+            Ok(Breakend::new(
+                vec![Base::C, Base::A],
+                Orientation::Reverse,
+                Some(mate)
+            ))
+        );
+
+        let mate = Mate::new("<ctg1>".parse()?, 5, Orientation::Reverse);
+        assert_eq!(
+            "[<ctg1>:5[A".parse(),
+            Ok(Breakend::new(
+                vec![Base::A],
+                Orientation::Reverse,
+                Some(mate)
+            ))
+        );
+
+        assert_eq!(
+            "GT.".parse(),
+            Ok(Breakend::new(
+                vec![Base::G, Base::T],
+                Orientation::Forward,
+                None
+            ))
+        );
+
+        assert_eq!(
+            ".A".parse(),
+            Ok(Breakend::new(vec![Base::A], Orientation::Reverse, None))
+        );
+
+        assert_eq!("".parse::<Breakend>(), Err(ParseError::Empty));
+        assert_eq!("A".parse::<Breakend>(), Err(ParseError::Invalid));
+        assert_eq!(".".parse::<Breakend>(), Err(ParseError::MissingBases));
+        assert_eq!("[sq0:5[".parse::<Breakend>(), Err(ParseError::MissingBases));
+        assert_eq!("C[sq0:5]".parse::<Breakend>(), Err(ParseError::InvalidMate));
+        assert_eq!("C[sq0[".parse::<Breakend>(), Err(ParseError::InvalidMate));
+        assert!(matches!(
+            "C[sq0:x[".parse::<Breakend>(),
+            Err(ParseError::InvalidPosition(_))
+        ));
+        assert!(matches!(
+            "Z.".parse::<Breakend>(),
+            Err(ParseError::InvalidBase(_))
+        ));
+
+        Ok(())
+    }
+}
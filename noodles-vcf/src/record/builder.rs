@@ -253,8 +253,8 @@ impl Builder {
     ///     .build()?;
     ///
     /// assert_eq!(record.info(), &Info::from(vec![
-    ///     Field::new(Key::SamplesWithDataCount, Value::Integer(3)),
-    ///     Field::new(Key::AlleleFrequencies, Value::FloatArray(vec![0.5])),
+    ///     Field::new(Key::SamplesWithDataCount, Some(Value::Integer(3))),
+    ///     Field::new(Key::AlleleFrequencies, Some(Value::FloatArray(vec![Some(0.5)]))),
     /// ]));
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
@@ -318,7 +318,7 @@ impl Builder {
     ///
     /// assert_eq!(record.genotypes(), [
     ///     Genotype::try_from(vec![
-    ///         Field::new(Key::Genotype, Some(Value::String(String::from("0|0")))),
+    ///         Field::new(Key::Genotype, Some(Value::Genotype("0|0".parse()?))),
     ///         Field::new(Key::ConditionalGenotypeQuality, Some(Value::Integer(13))),
     ///     ])?,
     /// ]);
@@ -352,7 +352,7 @@ impl Builder {
     ///
     /// assert_eq!(record.genotypes(), [
     ///     Genotype::try_from(vec![
-    ///         Field::new(Key::Genotype, Some(Value::String(String::from("0|0")))),
+    ///         Field::new(Key::Genotype, Some(Value::Genotype("0|0".parse()?))),
     ///         Field::new(Key::ConditionalGenotypeQuality, Some(Value::Integer(13))),
     ///     ])?,
     /// ]);
@@ -439,4 +439,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_add_genotype_with_genotype_value() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::record::genotype::field::value;
+
+        let genotype: value::Genotype = "0|1".parse()?;
+
+        let record = Builder::default()
+            .set_chromosome("sq0".parse()?)
+            .set_position(1)
+            .set_reference_bases("A".parse()?)
+            .set_format("GT".parse()?)
+            .add_genotype(Genotype::from(genotype.clone()))
+            .build()?;
+
+        assert_eq!(record.genotypes()[0].genotype(), Some(Ok(genotype)));
+        assert_eq!(record.genotypes()[0].to_string(), "0|1");
+
+        Ok(())
+    }
 }
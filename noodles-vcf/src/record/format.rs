@@ -2,6 +2,8 @@
 
 use std::{convert::TryFrom, error, fmt, ops::Deref, str::FromStr};
 
+use crate::Header;
+
 use super::genotype::field::{key, Key};
 
 const DELIMITER: char = ':';
@@ -10,6 +12,53 @@ const DELIMITER: char = ':';
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Format(Vec<Key>);
 
+impl Format {
+    /// Parses a raw VCF record genotype format using the FORMAT definitions of a VCF header.
+    ///
+    /// A key declared in the header takes the number and type of its definition. Undeclared keys
+    /// are parsed the same as [`FromStr`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::{format::Type, Number},
+    ///     record::{genotype::field::Key, Format},
+    /// };
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_format(vcf::header::Format::new(
+    ///         "NDLS".parse()?,
+    ///         Number::R,
+    ///         Type::Integer,
+    ///         String::from("noodles"),
+    ///     ))
+    ///     .build();
+    ///
+    /// let format = Format::try_from_str_header("GT:NDLS", &header)?;
+    /// assert_eq!(
+    ///     &format[..],
+    ///     [
+    ///         Key::Genotype,
+    ///         Key::Other(String::from("NDLS"), Number::R, Type::Integer)
+    ///     ]
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_str_header(s: &str, header: &Header) -> Result<Self, ParseError> {
+        let format: Self = s.parse()?;
+
+        let keys = format
+            .0
+            .into_iter()
+            .map(|key| find_declared_key(header, &key).unwrap_or(key))
+            .collect();
+
+        Ok(Self(keys))
+    }
+}
+
 impl Deref for Format {
     type Target = [Key];
 
@@ -71,6 +120,15 @@ impl FromStr for Format {
     }
 }
 
+fn find_declared_key(header: &Header, key: &Key) -> Option<Key> {
+    header
+        .get_format(key.as_ref())
+        .map(|format| match format.id() {
+            Key::Other(id, ..) => Key::Other(id.clone(), format.number(), format.ty()),
+            id => id.clone(),
+        })
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 /// An error returned when a vector of keys fails to convert to a format.
 pub enum TryFromKeyVectorError {
@@ -4,7 +4,7 @@ pub mod field;
 
 pub use self::field::Field;
 
-use std::{convert::TryFrom, error, fmt, ops::Deref};
+use std::{borrow::Cow, convert::TryFrom, error, fmt, ops::Deref};
 
 use super::{Format, MISSING_FIELD};
 
@@ -51,7 +51,7 @@ impl Genotype {
     /// assert_eq!(
     ///     Genotype::from_str_format("0|0:13", &format),
     ///     Ok(Genotype::try_from(vec![
-    ///         Field::new(Key::Genotype, Some(Value::String(String::from("0|0")))),
+    ///         Field::new(Key::Genotype, Some(Value::Genotype("0|0".parse()?))),
     ///         Field::new(Key::ConditionalGenotypeQuality, Some(Value::Integer(13))),
     ///     ])?)
     /// );
@@ -73,6 +73,36 @@ impl Genotype {
             }
         }
     }
+
+    /// Parses and returns the genotype (`GT`) value, if present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::Genotype;
+    ///
+    /// let format = "GT:GQ".parse()?;
+    /// let genotype = Genotype::from_str_format("0|1:13", &format)?;
+    ///
+    /// let gt = genotype.genotype().transpose()?.expect("missing GT");
+    /// assert!(gt.is_het());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn genotype(
+        &self,
+    ) -> Option<Result<field::value::Genotype, field::value::genotype::ParseError>> {
+        self.first()
+            .filter(|field| field.key() == &field::Key::Genotype)
+            .map(|field| match field.value() {
+                Some(field::Value::Genotype(genotype)) => Ok(genotype.clone()),
+                Some(value) => value.to_string().parse(),
+                None => MISSING_FIELD.parse(),
+            })
+    }
+
+    pub(crate) fn fields_mut(&mut self) -> &mut [Field] {
+        &mut self.0
+    }
 }
 
 impl Deref for Genotype {
@@ -101,6 +131,15 @@ impl fmt::Display for Genotype {
     }
 }
 
+impl From<field::value::Genotype> for Genotype {
+    fn from(genotype: field::value::Genotype) -> Self {
+        Self(vec![Field::new(
+            field::Key::Genotype,
+            Some(field::Value::from(genotype)),
+        )])
+    }
+}
+
 /// An error returned when VCF genotype fields fail to convert.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TryFromFieldsError {
@@ -137,6 +176,43 @@ impl TryFrom<Vec<Field>> for Genotype {
     }
 }
 
+// Returns the genotype (`GT`) of a field value, parsing it if it is not already typed.
+pub(crate) fn genotype_value(value: &field::Value) -> Option<Cow<'_, field::value::Genotype>> {
+    match value {
+        field::Value::Genotype(genotype) => Some(Cow::Borrowed(genotype)),
+        _ => value.to_string().parse().ok().map(Cow::Owned),
+    }
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+
+    (0..k).fold(1, |c, i| c * (n - i) / (i + 1))
+}
+
+// Returns the index of the genotype with the given allele indices (see § 1.6.2 "Genotype fields"
+// of VCF 4.3).
+pub(crate) fn genotype_index(alleles: &mut [usize]) -> usize {
+    alleles.sort_unstable();
+
+    alleles
+        .iter()
+        .enumerate()
+        .map(|(m, &a)| binomial(a + m, m + 1))
+        .sum()
+}
+
+pub(crate) fn genotype_count(allele_count: usize, ploidy: usize) -> usize {
+    binomial(allele_count + ploidy - 1, ploidy)
+}
+
+// Returns the ploidy of a list of genotype values, e.g., likelihoods, from its length.
+pub(crate) fn ploidy(allele_count: usize, len: usize) -> Option<usize> {
+    (1..=len).find(|&p| genotype_count(allele_count, p) == len)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,13 +243,13 @@ mod tests {
     }
 
     #[test]
-    fn test_fmt() {
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
         let genotype = Genotype::default();
         assert_eq!(genotype.to_string(), ".");
 
         let genotype = Genotype(vec![Field::new(
             field::Key::Genotype,
-            Some(field::Value::String(String::from("0|0"))),
+            Some(field::Value::Genotype("0|0".parse()?)),
         )]);
 
         assert_eq!(genotype.to_string(), "0|0");
@@ -181,7 +257,7 @@ mod tests {
         let genotype = Genotype(vec![
             Field::new(
                 field::Key::Genotype,
-                Some(field::Value::String(String::from("0|0"))),
+                Some(field::Value::Genotype("0|0".parse()?)),
             ),
             Field::new(
                 field::Key::ConditionalGenotypeQuality,
@@ -190,13 +266,49 @@ mod tests {
         ]);
 
         assert_eq!(genotype.to_string(), "0|0:13");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_genotype() -> Result<(), Box<dyn std::error::Error>> {
+        let format = "GT:GQ".parse()?;
+
+        let genotype = Genotype::from_str_format("0/1:13", &format)?;
+        assert_eq!(genotype.genotype(), Some("0/1".parse()));
+
+        let genotype = Genotype::from_str_format("./.:13", &format)?;
+        assert_eq!(genotype.genotype(), Some("./.".parse()));
+
+        let genotype = Genotype::from_str_format(".:13", &format)?;
+        assert_eq!(genotype.genotype(), Some(".".parse()));
+
+        let genotype = Genotype::default();
+        assert!(genotype.genotype().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_genotype_value_for_genotype() -> Result<(), field::value::genotype::ParseError> {
+        let genotype = Genotype::from("0|1".parse::<field::value::Genotype>()?);
+
+        assert_eq!(
+            genotype,
+            Genotype(vec![Field::new(
+                field::Key::Genotype,
+                Some(field::Value::Genotype("0|1".parse()?)),
+            )])
+        );
+
+        Ok(())
     }
 
     #[test]
-    fn test_try_from_fields_for_genotype() {
+    fn test_try_from_fields_for_genotype() -> Result<(), Box<dyn std::error::Error>> {
         let fields = vec![Field::new(
             field::Key::Genotype,
-            Some(field::Value::String(String::from("0|0"))),
+            Some(field::Value::Genotype("0|0".parse()?)),
         )];
         assert!(Genotype::try_from(fields).is_ok());
 
@@ -208,5 +320,29 @@ mod tests {
             Genotype::try_from(fields),
             Err(TryFromFieldsError::MissingLeadingGenotypeField)
         );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_genotype_index() {
+        assert_eq!(genotype_index(&mut [0, 0]), 0);
+        assert_eq!(genotype_index(&mut [0, 1]), 1);
+        assert_eq!(genotype_index(&mut [1, 1]), 2);
+        assert_eq!(genotype_index(&mut [2, 0]), 3);
+        assert_eq!(genotype_index(&mut [1, 2]), 4);
+        assert_eq!(genotype_index(&mut [2, 2]), 5);
+
+        assert_eq!(genotype_index(&mut [2]), 2);
+        assert_eq!(genotype_index(&mut [0, 0, 1]), 1);
+        assert_eq!(genotype_index(&mut [1, 1, 1]), 3);
+    }
+
+    #[test]
+    fn test_genotype_count() {
+        assert_eq!(genotype_count(2, 1), 2);
+        assert_eq!(genotype_count(2, 2), 3);
+        assert_eq!(genotype_count(3, 2), 6);
+        assert_eq!(genotype_count(2, 3), 4);
     }
 }
@@ -47,7 +47,8 @@ impl Field {
     /// );
     /// ```
     pub fn from_str_key(s: &str, key: &Key) -> Result<Self, ParseError> {
-        if s == MISSING_VALUE {
+        // A missing genotype (`GT`) is a call with one missing allele, e.g., a haploid `.`.
+        if s == MISSING_VALUE && key != &Key::Genotype {
             Ok(Self::new(key.clone(), None))
         } else {
             Value::from_str_key(s, key)
@@ -126,7 +127,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_from_str_key() -> Result<(), ParseError> {
+    fn test_from_str_key() -> Result<(), Box<dyn std::error::Error>> {
         let key = Key::MappingQuality;
         let actual = Field::from_str_key(".", &key)?;
         assert_eq!(actual.key(), &key);
@@ -145,7 +146,10 @@ mod tests {
         let key = Key::Genotype;
         let actual = Field::from_str_key("0|0", &key)?;
         assert_eq!(actual.key(), &key);
-        assert_eq!(actual.value(), Some(&Value::String(String::from("0|0"))));
+        assert_eq!(actual.value(), Some(&Value::Genotype("0|0".parse()?)));
+
+        let actual = Field::from_str_key(".", &key)?;
+        assert_eq!(actual.value(), Some(&Value::Genotype(".".parse()?)));
 
         Ok(())
     }
@@ -162,7 +166,7 @@ mod tests {
         let field = Field::new(key, Some(Value::Float(8.333)));
         assert_eq!(field.to_string(), "8.333");
 
-        let field = Field::new(Key::Genotype, Some(Value::String(String::from("0|0"))));
+        let field = Field::new(Key::Genotype, Some(Value::Genotype("0|0".parse().unwrap())));
         assert_eq!(field.to_string(), "0|0");
     }
 }
@@ -1,5 +1,9 @@
 //! VCF record genotype field value.
 
+pub mod genotype;
+
+pub use self::genotype::Genotype;
+
 use std::{error, fmt, num};
 
 use crate::{
@@ -31,6 +35,8 @@ pub enum Value {
     CharacterArray(Vec<Option<char>>),
     /// An array of strings.
     StringArray(Vec<Option<String>>),
+    /// A genotype (`GT`).
+    Genotype(Genotype),
 }
 
 impl fmt::Display for Value {
@@ -40,6 +46,7 @@ impl fmt::Display for Value {
             Self::Float(n) => write!(f, "{}", n),
             Self::Character(c) => write!(f, "{}", c),
             Self::String(s) => write!(f, "{}", s),
+            Self::Genotype(genotype) => write!(f, "{}", genotype),
             Self::IntegerArray(values) => {
                 for (i, value) in values.iter().enumerate() {
                     if i > 0 {
@@ -104,6 +111,12 @@ impl fmt::Display for Value {
     }
 }
 
+impl From<Genotype> for Value {
+    fn from(genotype: Genotype) -> Self {
+        Self::Genotype(genotype)
+    }
+}
+
 /// An error returned when a raw VCF record genotype field value fails to parse.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseError {
@@ -115,6 +128,8 @@ pub enum ParseError {
     InvalidFloat(num::ParseFloatError),
     /// The character is invalid.
     InvalidCharacter,
+    /// The genotype (`GT`) is invalid.
+    InvalidGenotype(genotype::ParseError),
 }
 
 impl error::Error for ParseError {}
@@ -128,6 +143,7 @@ impl fmt::Display for ParseError {
             Self::InvalidInteger(e) => write!(f, "invalid integer: {}", e),
             Self::InvalidFloat(e) => write!(f, "invalid float: {}", e),
             Self::InvalidCharacter => f.write_str("invalid character"),
+            Self::InvalidGenotype(e) => write!(f, "invalid genotype: {}", e),
         }
     }
 }
@@ -147,6 +163,13 @@ impl Value {
 
     /// ```
     pub fn from_str_key(s: &str, key: &Key) -> Result<Self, ParseError> {
+        if key == &Key::Genotype {
+            return s
+                .parse()
+                .map(Self::Genotype)
+                .map_err(ParseError::InvalidGenotype);
+        }
+
         match key.ty() {
             Type::Integer => match key.number() {
                 Number::Count(0) => Err(ParseError::InvalidNumberForType(key.number(), key.ty())),
@@ -0,0 +1,361 @@
+//! VCF record genotype field genotype (`GT`) value and allele.
+
+pub mod allele;
+
+pub use self::allele::Allele;
+
+use std::{error, fmt, ops::Deref, str::FromStr};
+
+use self::allele::Phasing;
+
+/// A VCF record genotype field genotype (`GT`) value.
+///
+/// This is the list of called alleles of a sample. The number of alleles is the ploidy.
+///
+/// The phasing of the first allele is typically implicit. When parsed, it is phased if all other
+/// alleles are phased, and unphased otherwise (see § 1.6.2 "Genotype fields" of VCF 4.4). A
+/// leading separator, e.g., `|0/1`, explicitly sets it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Genotype(Vec<Allele>);
+
+impl Genotype {
+    /// Returns the ploidy, i.e., the number of alleles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::Genotype;
+    /// let genotype: Genotype = "0|1".parse()?;
+    /// assert_eq!(genotype.ploidy(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn ploidy(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns whether all alleles are phased.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "0|1".parse()?;
+    /// assert!(genotype.is_phased());
+    ///
+    /// let genotype: Genotype = "0/1".parse()?;
+    /// assert!(!genotype.is_phased());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn is_phased(&self) -> bool {
+        self.iter()
+            .all(|allele| allele.phasing() == Phasing::Phased)
+    }
+
+    /// Returns whether all alleles are missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "./.".parse()?;
+    /// assert!(genotype.is_missing());
+    ///
+    /// let genotype: Genotype = "0/.".parse()?;
+    /// assert!(!genotype.is_missing());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn is_missing(&self) -> bool {
+        self.iter().all(|allele| allele.position().is_none())
+    }
+
+    /// Returns whether all alleles are the reference allele.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "0|0".parse()?;
+    /// assert!(genotype.is_hom_ref());
+    ///
+    /// let genotype: Genotype = "0|.".parse()?;
+    /// assert!(!genotype.is_hom_ref());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn is_hom_ref(&self) -> bool {
+        !self.is_empty() && self.iter().all(|allele| allele.position() == Some(0))
+    }
+
+    /// Returns whether all alleles are called and are not all the same allele.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "0|1".parse()?;
+    /// assert!(genotype.is_het());
+    ///
+    /// let genotype: Genotype = "1/2".parse()?;
+    /// assert!(genotype.is_het());
+    ///
+    /// let genotype: Genotype = "0/.".parse()?;
+    /// assert!(!genotype.is_het());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn is_het(&self) -> bool {
+        match self.called_positions() {
+            Some(positions) => positions.windows(2).any(|w| w[0] != w[1]),
+            None => false,
+        }
+    }
+
+    /// Returns whether all alleles are the same alternate allele.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::Genotype;
+    ///
+    /// let genotype: Genotype = "1/1".parse()?;
+    /// assert!(genotype.is_hom_alt());
+    ///
+    /// let genotype: Genotype = "1/2".parse()?;
+    /// assert!(!genotype.is_hom_alt());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn is_hom_alt(&self) -> bool {
+        match self.called_positions() {
+            Some(positions) => positions[0] > 0 && positions.iter().all(|&p| p == positions[0]),
+            None => false,
+        }
+    }
+
+    /// Returns the number of called alternate alleles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::Genotype;
+    /// let genotype: Genotype = "0/1/2/.".parse()?;
+    /// assert_eq!(genotype.alt_allele_count(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn alt_allele_count(&self) -> usize {
+        self.iter()
+            .filter(|allele| matches!(allele.position(), Some(p) if p > 0))
+            .count()
+    }
+
+    // Returns the allele positions if there is at least one allele and all are called.
+    fn called_positions(&self) -> Option<Vec<usize>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.iter().map(|allele| allele.position()).collect()
+    }
+}
+
+impl Deref for Genotype {
+    type Target = [Allele];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for Genotype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, allele) in self.iter().enumerate() {
+            if i > 0 || allele.phasing() != first_allele_phasing(&self[1..]) {
+                write!(f, "{}", char::from(allele.phasing()))?;
+            }
+
+            write!(f, "{}", allele)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Vec<Allele>> for Genotype {
+    fn from(alleles: Vec<Allele>) -> Self {
+        Self(alleles)
+    }
+}
+
+/// An error returned when a raw VCF record genotype field genotype value fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// An allele is invalid.
+    InvalidAllele(allele::ParseError),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::InvalidAllele(e) => write!(f, "invalid allele: {}", e),
+        }
+    }
+}
+
+impl FromStr for Genotype {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut alleles = Vec::new();
+        let mut start = 0;
+
+        for (i, c) in s.char_indices().skip(1) {
+            if Phasing::from_char(c).is_some() {
+                alleles.push(s[start..i].parse().map_err(ParseError::InvalidAllele)?);
+                start = i;
+            }
+        }
+
+        alleles.push(s[start..].parse().map_err(ParseError::InvalidAllele)?);
+
+        let has_explicit_first_phasing = s.chars().next().and_then(Phasing::from_char).is_some();
+
+        if !has_explicit_first_phasing {
+            let phasing = first_allele_phasing(&alleles[1..]);
+            alleles[0] = Allele::new(alleles[0].position(), phasing);
+        }
+
+        Ok(Self(alleles))
+    }
+}
+
+// The implicit phasing of the first allele is phased if all other alleles are phased.
+fn first_allele_phasing(other_alleles: &[Allele]) -> Phasing {
+    if other_alleles
+        .iter()
+        .all(|allele| allele.phasing() == Phasing::Phased)
+    {
+        Phasing::Phased
+    } else {
+        Phasing::Unphased
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let genotype = Genotype(vec![
+            Allele::new(Some(0), Phasing::Phased),
+            Allele::new(Some(1), Phasing::Phased),
+        ]);
+        assert_eq!(genotype.to_string(), "0|1");
+
+        let genotype = Genotype(vec![
+            Allele::new(Some(0), Phasing::Unphased),
+            Allele::new(None, Phasing::Unphased),
+        ]);
+        assert_eq!(genotype.to_string(), "0/.");
+
+        let genotype = Genotype(vec![Allele::new(Some(1), Phasing::Phased)]);
+        assert_eq!(genotype.to_string(), "1");
+
+        let genotype = Genotype(vec![
+            Allele::new(Some(0), Phasing::Unphased),
+            Allele::new(Some(1), Phasing::Phased),
+        ]);
+        assert_eq!(genotype.to_string(), "/0|1");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "0|1".parse(),
+            Ok(Genotype(vec![
+                Allele::new(Some(0), Phasing::Phased),
+                Allele::new(Some(1), Phasing::Phased),
+            ]))
+        );
+
+        assert_eq!(
+            "./1".parse(),
+            Ok(Genotype(vec![
+                Allele::new(None, Phasing::Unphased),
+                Allele::new(Some(1), Phasing::Unphased),
+            ]))
+        );
+
+        assert_eq!(
+            "0/1|2".parse(),
+            Ok(Genotype(vec![
+                Allele::new(Some(0), Phasing::Unphased),
+                Allele::new(Some(1), Phasing::Unphased),
+                Allele::new(Some(2), Phasing::Phased),
+            ]))
+        );
+
+        assert_eq!(
+            "/0|1".parse(),
+            Ok(Genotype(vec![
+                Allele::new(Some(0), Phasing::Unphased),
+                Allele::new(Some(1), Phasing::Phased),
+            ]))
+        );
+
+        assert_eq!(
+            ".".parse(),
+            Ok(Genotype(vec![Allele::new(None, Phasing::Phased)]))
+        );
+
+        assert_eq!("".parse::<Genotype>(), Err(ParseError::Empty));
+        assert_eq!(
+            "0||1".parse::<Genotype>(),
+            Err(ParseError::InvalidAllele(allele::ParseError::Empty))
+        );
+        assert!(matches!(
+            "0/n".parse::<Genotype>(),
+            Err(ParseError::InvalidAllele(_))
+        ));
+    }
+
+    #[test]
+    fn test_zygosity() -> Result<(), ParseError> {
+        let genotype: Genotype = "0/0".parse()?;
+        assert!(genotype.is_hom_ref());
+        assert!(!genotype.is_het());
+        assert!(!genotype.is_hom_alt());
+        assert_eq!(genotype.alt_allele_count(), 0);
+
+        let genotype: Genotype = "0|1".parse()?;
+        assert!(!genotype.is_hom_ref());
+        assert!(genotype.is_het());
+        assert!(!genotype.is_hom_alt());
+        assert_eq!(genotype.alt_allele_count(), 1);
+
+        let genotype: Genotype = "2/2".parse()?;
+        assert!(!genotype.is_hom_ref());
+        assert!(!genotype.is_het());
+        assert!(genotype.is_hom_alt());
+        assert_eq!(genotype.alt_allele_count(), 2);
+
+        let genotype: Genotype = "./.".parse()?;
+        assert!(genotype.is_missing());
+        assert!(!genotype.is_hom_ref());
+        assert!(!genotype.is_het());
+        assert!(!genotype.is_hom_alt());
+        assert_eq!(genotype.alt_allele_count(), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+//! VCF record genotype allele and phasing.
+
+use std::{error, fmt, num, str::FromStr};
+
+const MISSING_VALUE: &str = ".";
+
+/// A VCF record genotype allele phasing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Phasing {
+    /// Phased (`|`).
+    Phased,
+    /// Unphased (`/`).
+    Unphased,
+}
+
+impl Phasing {
+    /// Returns the phasing for the given separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::genotype::allele::Phasing;
+    /// assert_eq!(Phasing::from_char('|'), Some(Phasing::Phased));
+    /// assert_eq!(Phasing::from_char('/'), Some(Phasing::Unphased));
+    /// assert_eq!(Phasing::from_char(':'), None);
+    /// ```
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '|' => Some(Self::Phased),
+            '/' => Some(Self::Unphased),
+            _ => None,
+        }
+    }
+}
+
+impl From<Phasing> for char {
+    fn from(phasing: Phasing) -> Self {
+        match phasing {
+            Phasing::Phased => '|',
+            Phasing::Unphased => '/',
+        }
+    }
+}
+
+/// A VCF record genotype allele.
+///
+/// An allele is the index of the called allele, where 0 is the reference allele and 1 is the
+/// first alternate allele, etc., or missing (`.`). It is paired with the phasing given by the
+/// separator preceding it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Allele {
+    position: Option<usize>,
+    phasing: Phasing,
+}
+
+impl Allele {
+    /// Creates a VCF record genotype allele.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::genotype::{allele::Phasing, Allele};
+    /// let allele = Allele::new(Some(1), Phasing::Phased);
+    /// ```
+    pub fn new(position: Option<usize>, phasing: Phasing) -> Self {
+        Self { position, phasing }
+    }
+
+    /// Returns the allele position, i.e., the index of the called allele.
+    ///
+    /// This returns `None` if the allele is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::genotype::{allele::Phasing, Allele};
+    ///
+    /// let allele = Allele::new(Some(1), Phasing::Phased);
+    /// assert_eq!(allele.position(), Some(1));
+    ///
+    /// let allele = Allele::new(None, Phasing::Phased);
+    /// assert!(allele.position().is_none());
+    /// ```
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    /// Returns the allele phasing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::record::genotype::field::value::genotype::{allele::Phasing, Allele};
+    /// let allele = Allele::new(Some(1), Phasing::Phased);
+    /// assert_eq!(allele.phasing(), Phasing::Phased);
+    /// ```
+    pub fn phasing(&self) -> Phasing {
+        self.phasing
+    }
+}
+
+impl fmt::Display for Allele {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{}", position),
+            None => f.write_str(MISSING_VALUE),
+        }
+    }
+}
+
+/// An error returned when a raw VCF record genotype allele fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The position is invalid.
+    InvalidPosition(num::ParseIntError),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::InvalidPosition(e) => write!(f, "invalid position: {}", e),
+        }
+    }
+}
+
+impl FromStr for Allele {
+    type Err = ParseError;
+
+    /// Parses a raw allele, with or without a leading phasing separator.
+    ///
+    /// An allele without a separator is unphased.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        let (phasing, t) = match chars.next().and_then(Phasing::from_char) {
+            Some(phasing) => (phasing, chars.as_str()),
+            None => (Phasing::Unphased, s),
+        };
+
+        match t {
+            "" => Err(ParseError::Empty),
+            MISSING_VALUE => Ok(Self::new(None, phasing)),
+            _ => t
+                .parse()
+                .map(|position| Self::new(Some(position), phasing))
+                .map_err(ParseError::InvalidPosition),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Allele::new(Some(0), Phasing::Phased).to_string(), "0");
+        assert_eq!(Allele::new(Some(13), Phasing::Unphased).to_string(), "13");
+        assert_eq!(Allele::new(None, Phasing::Phased).to_string(), ".");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("0".parse(), Ok(Allele::new(Some(0), Phasing::Unphased)));
+        assert_eq!("|1".parse(), Ok(Allele::new(Some(1), Phasing::Phased)));
+        assert_eq!("/.".parse(), Ok(Allele::new(None, Phasing::Unphased)));
+
+        assert_eq!("".parse::<Allele>(), Err(ParseError::Empty));
+        assert_eq!("|".parse::<Allele>(), Err(ParseError::Empty));
+        assert!(matches!(
+            "n".parse::<Allele>(),
+            Err(ParseError::InvalidPosition(_))
+        ));
+    }
+}
@@ -6,6 +6,8 @@ pub use self::field::Field;
 
 use std::{error, fmt, ops::Deref, str::FromStr};
 
+use crate::Header;
+
 use super::MISSING_FIELD;
 
 const DELIMITER: char = ';';
@@ -14,6 +16,42 @@ const DELIMITER: char = ';';
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Info(Vec<Field>);
 
+impl Info {
+    /// Parses raw VCF record info using the INFO definitions of a VCF header.
+    ///
+    /// See [`Field::try_from_str_header`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::{info::Type, Info, Number},
+    ///     record::{self, info::field::Value},
+    /// };
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_info(Info::new(
+    ///         "NDLS".parse()?,
+    ///         Number::Count(2),
+    ///         Type::Integer,
+    ///         String::from("noodles"),
+    ///     ))
+    ///     .build();
+    ///
+    /// let info = record::Info::try_from_str_header("NS=2;NDLS=8,13", &header)?;
+    /// assert_eq!(info.len(), 2);
+    /// assert_eq!(
+    ///     info[1].value(),
+    ///     Some(&Value::IntegerArray(vec![Some(8), Some(13)]))
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_str_header(s: &str, header: &Header) -> Result<Self, ParseError> {
+        parse(s, |t| Field::try_from_str_header(t, header))
+    }
+}
+
 impl Deref for Info {
     type Target = [Field];
 
@@ -70,16 +108,23 @@ impl FromStr for Info {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "" => Err(ParseError::Empty),
-            MISSING_FIELD => Ok(Self::default()),
-            _ => s
-                .split(DELIMITER)
-                .map(|s| s.parse())
-                .collect::<Result<_, _>>()
-                .map(Self)
-                .map_err(ParseError::InvalidField),
-        }
+        parse(s, |t| t.parse())
+    }
+}
+
+fn parse<F>(s: &str, parse_field: F) -> Result<Info, ParseError>
+where
+    F: Fn(&str) -> Result<Field, field::ParseError>,
+{
+    match s {
+        "" => Err(ParseError::Empty),
+        MISSING_FIELD => Ok(Info::default()),
+        _ => s
+            .split(DELIMITER)
+            .map(parse_field)
+            .collect::<Result<_, _>>()
+            .map(Info)
+            .map_err(ParseError::InvalidField),
     }
 }
 
@@ -94,18 +139,30 @@ mod tests {
 
         let info = Info(vec![Field::new(
             field::Key::SamplesWithDataCount,
-            field::Value::Integer(2),
+            Some(field::Value::Integer(2)),
         )]);
         assert_eq!(info.to_string(), "NS=2");
 
         let info = Info(vec![
-            Field::new(field::Key::SamplesWithDataCount, field::Value::Integer(2)),
+            Field::new(
+                field::Key::SamplesWithDataCount,
+                Some(field::Value::Integer(2)),
+            ),
             Field::new(
                 field::Key::AlleleFrequencies,
-                field::Value::FloatArray(vec![0.333, 0.667]),
+                Some(field::Value::FloatArray(vec![Some(0.333), Some(0.667)])),
             ),
         ]);
         assert_eq!(info.to_string(), "NS=2;AF=0.333,0.667");
+
+        let info = Info(vec![
+            Field::new(field::Key::SamplesWithDataCount, None),
+            Field::new(
+                field::Key::AlleleFrequencies,
+                Some(field::Value::FloatArray(vec![Some(0.333), None])),
+            ),
+        ]);
+        assert_eq!(info.to_string(), "NS=.;AF=0.333,.");
     }
 
     #[test]
@@ -7,16 +7,17 @@ pub use self::{key::Key, value::Value};
 
 use std::{error, fmt, str::FromStr};
 
-use crate::header::info::Type;
+use crate::{header::info::Type, Header};
 
 const SEPARATOR: char = '=';
 const MAX_COMPONENTS: usize = 2;
+const MISSING_VALUE: &str = ".";
 
 /// A VCF record info field.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Field {
     key: Key,
-    value: Value,
+    value: Option<Value>,
 }
 
 impl Field {
@@ -26,19 +27,58 @@ impl Field {
     ///
     /// ```
     /// use noodles_vcf::record::info::{field::{Key, Value}, Field};
-    /// let field = Field::new(Key::SamplesWithDataCount, Value::Integer(1));
+    /// let field = Field::new(Key::SamplesWithDataCount, Some(Value::Integer(1)));
     /// ```
-    pub fn new(key: Key, value: Value) -> Self {
+    pub fn new(key: Key, value: Option<Value>) -> Self {
         Self { key, value }
     }
 
+    /// Parses a raw VCF record info field using the INFO definitions of a VCF header.
+    ///
+    /// A key declared in the header takes the number and type of its definition. Undeclared keys
+    /// are parsed the same as [`FromStr`]. A missing value (`.`) is `None`, and missing elements
+    /// of an array are `None` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::{info::Type, Info, Number},
+    ///     record::info::{field::{Key, Value}, Field},
+    /// };
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_info(Info::new(
+    ///         "NDLS".parse()?,
+    ///         Number::Count(2),
+    ///         Type::Integer,
+    ///         String::from("noodles"),
+    ///     ))
+    ///     .build();
+    ///
+    /// let field = Field::try_from_str_header("NDLS=8,13", &header)?;
+    /// assert_eq!(
+    ///     field.key(),
+    ///     &Key::Other(String::from("NDLS"), Number::Count(2), Type::Integer)
+    /// );
+    /// assert_eq!(field.value(), Some(&Value::IntegerArray(vec![Some(8), Some(13)])));
+    ///
+    /// let field = Field::try_from_str_header("NDLS=8,.", &header)?;
+    /// assert_eq!(field.value(), Some(&Value::IntegerArray(vec![Some(8), None])));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_str_header(s: &str, header: &Header) -> Result<Self, ParseError> {
+        parse(s, Some(header))
+    }
+
     /// Returns the field key.
     ///
     /// # Examples
     ///
     /// ```
     /// use noodles_vcf::record::info::{field::{Key, Value}, Field};
-    /// let field = Field::new(Key::SamplesWithDataCount, Value::Integer(1));
+    /// let field = Field::new(Key::SamplesWithDataCount, Some(Value::Integer(1)));
     /// assert_eq!(field.key(), &Key::SamplesWithDataCount);
     /// ```
     pub fn key(&self) -> &Key {
@@ -51,19 +91,20 @@ impl Field {
     ///
     /// ```
     /// use noodles_vcf::record::info::{field::{Key, Value}, Field};
-    /// let field = Field::new(Key::SamplesWithDataCount, Value::Integer(1));
-    /// assert_eq!(field.value(), &Value::Integer(1));
+    /// let field = Field::new(Key::SamplesWithDataCount, Some(Value::Integer(1)));
+    /// assert_eq!(field.value(), Some(&Value::Integer(1)));
     /// ```
-    pub fn value(&self) -> &Value {
-        &self.value
+    pub fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
     }
 }
 
 impl fmt::Display for Field {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.value() {
-            Value::Flag => write!(f, "{}", self.key),
-            value => write!(f, "{}{}{}", self.key, SEPARATOR, value),
+            Some(Value::Flag) => write!(f, "{}", self.key),
+            Some(value) => write!(f, "{}{}{}", self.key, SEPARATOR, value),
+            None => write!(f, "{}{}{}", self.key, SEPARATOR, MISSING_VALUE),
         }
     }
 }
@@ -100,33 +141,57 @@ impl FromStr for Field {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut components = s.splitn(MAX_COMPONENTS, SEPARATOR);
+        parse(s, None)
+    }
+}
 
-        let key: Key = components
+fn parse(s: &str, header: Option<&Header>) -> Result<Field, ParseError> {
+    let mut components = s.splitn(MAX_COMPONENTS, SEPARATOR);
+
+    let key: Key = components
+        .next()
+        .ok_or(ParseError::MissingKey)
+        .and_then(|s| s.parse().map_err(ParseError::InvalidKey))?;
+
+    let (key, is_declared) = match header.and_then(|h| find_declared_key(h, &key)) {
+        Some(declared_key) => (declared_key, true),
+        None => (key, false),
+    };
+
+    let value = if let Type::Flag = key.ty() {
+        let s = components.next().unwrap_or_default();
+        Value::from_str_key(s, &key)
+            .map(Some)
+            .map_err(ParseError::InvalidValue)?
+    } else if let (Key::Other(..), false, None) = (&key, is_declared, components.clone().next()) {
+        Some(Value::Flag)
+    } else {
+        components
             .next()
-            .ok_or(ParseError::MissingKey)
-            .and_then(|s| s.parse().map_err(ParseError::InvalidKey))?;
-
-        let value = if let Type::Flag = key.ty() {
-            let s = components.next().unwrap_or_default();
-            Value::from_str_key(s, &key).map_err(ParseError::InvalidValue)?
-        } else if let Key::Other(..) = key {
-            if let Some(s) = components.next() {
-                Value::from_str_key(s, &key).map_err(ParseError::InvalidValue)?
-            } else {
-                Value::Flag
-            }
-        } else {
-            components
-                .next()
-                .ok_or(ParseError::MissingValue)
-                .and_then(|s| Value::from_str_key(s, &key).map_err(ParseError::InvalidValue))?
-        };
-
-        Ok(Self::new(key, value))
+            .ok_or(ParseError::MissingValue)
+            .and_then(|s| parse_value(s, &key))?
+    };
+
+    Ok(Field::new(key, value))
+}
+
+fn parse_value(s: &str, key: &Key) -> Result<Option<Value>, ParseError> {
+    if s == MISSING_VALUE {
+        Ok(None)
+    } else {
+        Value::from_str_key(s, key)
+            .map(Some)
+            .map_err(ParseError::InvalidValue)
     }
 }
 
+fn find_declared_key(header: &Header, key: &Key) -> Option<Key> {
+    header.get_info(key.as_ref()).map(|info| match info.id() {
+        Key::Other(id, ..) => Key::Other(id.clone(), info.number(), info.ty()),
+        id => id.clone(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::header::Number;
@@ -135,53 +200,112 @@ mod tests {
 
     #[test]
     fn test_fmt() {
-        let field = Field::new(Key::SamplesWithDataCount, Value::Integer(2));
+        let field = Field::new(Key::SamplesWithDataCount, Some(Value::Integer(2)));
         assert_eq!(field.to_string(), "NS=2");
 
-        let field = Field::new(Key::BaseQuality, Value::Float(1.333));
+        let field = Field::new(Key::BaseQuality, Some(Value::Float(1.333)));
         assert_eq!(field.to_string(), "BQ=1.333");
 
-        let field = Field::new(Key::IsSomaticMutation, Value::Flag);
+        let field = Field::new(Key::IsSomaticMutation, Some(Value::Flag));
         assert_eq!(field.to_string(), "SOMATIC");
 
         let field = Field::new(
             Key::Other(String::from("SVTYPE"), Number::Count(1), Type::String),
-            Value::String(String::from("DEL")),
+            Some(Value::String(String::from("DEL"))),
         );
         assert_eq!(field.to_string(), "SVTYPE=DEL");
+
+        let field = Field::new(Key::TotalDepth, None);
+        assert_eq!(field.to_string(), "DP=.");
     }
 
     #[test]
     fn test_from_str() -> Result<(), ParseError> {
         let actual: Field = "NS=2".parse()?;
         assert_eq!(actual.key(), &Key::SamplesWithDataCount);
-        assert_eq!(actual.value(), &Value::Integer(2));
+        assert_eq!(actual.value(), Some(&Value::Integer(2)));
 
         let actual: Field = "BQ=1.333".parse()?;
         assert_eq!(actual.key(), &Key::BaseQuality);
-        assert_eq!(actual.value(), &Value::Float(1.333));
+        assert_eq!(actual.value(), Some(&Value::Float(1.333)));
 
         let actual: Field = "SOMATIC".parse()?;
         assert_eq!(actual.key(), &Key::IsSomaticMutation);
-        assert_eq!(actual.value(), &Value::Flag);
+        assert_eq!(actual.value(), Some(&Value::Flag));
 
         let actual: Field = "EVENT=INV0".parse()?;
         assert_eq!(actual.key(), &Key::BreakendEventId);
-        assert_eq!(actual.value(), &Value::String(String::from("INV0")));
+        assert_eq!(actual.value(), Some(&Value::String(String::from("INV0"))));
 
         let actual: Field = "NDLS=VCF".parse()?;
         assert_eq!(
             actual.key(),
             &Key::Other(String::from("NDLS"), Number::Count(1), Type::String)
         );
-        assert_eq!(actual.value(), &Value::String(String::from("VCF")));
+        assert_eq!(actual.value(), Some(&Value::String(String::from("VCF"))));
 
         let actual: Field = "FLG".parse()?;
         assert_eq!(
             actual.key(),
             &Key::Other(String::from("FLG"), Number::Count(1), Type::String)
         );
-        assert_eq!(actual.value(), &Value::Flag);
+        assert_eq!(actual.value(), Some(&Value::Flag));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_str_header() -> Result<(), Box<dyn std::error::Error>> {
+        let header: Header = r#"##fileformat=VCFv4.3
+##INFO=<ID=NS,Number=1,Type=Integer,Description="Number of samples with data">
+##INFO=<ID=ZF,Number=A,Type=Float,Description="noodles">
+##INFO=<ID=ZB,Number=0,Type=Flag,Description="noodles">
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#
+        .parse()?;
+
+        let actual = Field::try_from_str_header("NS=2", &header)?;
+        assert_eq!(actual.key(), &Key::SamplesWithDataCount);
+        assert_eq!(actual.value(), Some(&Value::Integer(2)));
+
+        let actual = Field::try_from_str_header("ZF=0.5", &header)?;
+        assert_eq!(
+            actual.key(),
+            &Key::Other(String::from("ZF"), Number::A, Type::Float)
+        );
+        assert_eq!(actual.value(), Some(&Value::FloatArray(vec![Some(0.5)])));
+
+        let actual = Field::try_from_str_header("ZF=0.5,.", &header)?;
+        assert_eq!(
+            actual.value(),
+            Some(&Value::FloatArray(vec![Some(0.5), None]))
+        );
+
+        let actual = Field::try_from_str_header("NS=.", &header)?;
+        assert_eq!(actual.value(), None);
+
+        let actual = Field::try_from_str_header("ZB", &header)?;
+        assert_eq!(
+            actual.key(),
+            &Key::Other(String::from("ZB"), Number::Count(0), Type::Flag)
+        );
+        assert_eq!(actual.value(), Some(&Value::Flag));
+
+        let actual = Field::try_from_str_header("ZS=ndls", &header)?;
+        assert_eq!(
+            actual.key(),
+            &Key::Other(String::from("ZS"), Number::Count(1), Type::String)
+        );
+
+        assert_eq!(
+            Field::try_from_str_header("ZF", &header),
+            Err(ParseError::MissingValue)
+        );
+
+        assert!(matches!(
+            Field::try_from_str_header("ZF=ndls", &header),
+            Err(ParseError::InvalidValue(_))
+        ));
 
         Ok(())
     }
@@ -10,6 +10,7 @@ use crate::{
 use super::Key;
 
 const DELIMITER: char = ',';
+const MISSING_VALUE: &str = ".";
 
 /// A VCF record info field value.
 #[derive(Clone, Debug, PartialEq)]
@@ -25,13 +26,13 @@ pub enum Value {
     /// A string.
     String(String),
     /// An array of 32-bit integers.
-    IntegerArray(Vec<i32>),
+    IntegerArray(Vec<Option<i32>>),
     /// An array of single-precision floating-points.
-    FloatArray(Vec<f32>),
+    FloatArray(Vec<Option<f32>>),
     /// An array of characters.
-    CharacterArray(Vec<char>),
+    CharacterArray(Vec<Option<char>>),
     /// An array of strings.
-    StringArray(Vec<String>),
+    StringArray(Vec<Option<String>>),
 }
 
 impl fmt::Display for Value {
@@ -48,7 +49,11 @@ impl fmt::Display for Value {
                         write!(f, "{}", DELIMITER)?;
                     }
 
-                    write!(f, "{}", value)?;
+                    if let Some(v) = value {
+                        write!(f, "{}", v)?;
+                    } else {
+                        f.write_str(MISSING_VALUE)?;
+                    }
                 }
 
                 Ok(())
@@ -59,7 +64,11 @@ impl fmt::Display for Value {
                         write!(f, "{}", DELIMITER)?;
                     }
 
-                    write!(f, "{}", value)?;
+                    if let Some(v) = value {
+                        write!(f, "{}", v)?;
+                    } else {
+                        f.write_str(MISSING_VALUE)?;
+                    }
                 }
 
                 Ok(())
@@ -70,7 +79,11 @@ impl fmt::Display for Value {
                         write!(f, "{}", DELIMITER)?;
                     }
 
-                    write!(f, "{}", value)?;
+                    if let Some(v) = value {
+                        write!(f, "{}", v)?;
+                    } else {
+                        f.write_str(MISSING_VALUE)?;
+                    }
                 }
 
                 Ok(())
@@ -81,7 +94,11 @@ impl fmt::Display for Value {
                         write!(f, "{}", DELIMITER)?;
                     }
 
-                    write!(f, "{}", value)?;
+                    if let Some(v) = value {
+                        write!(f, "{}", v)?;
+                    } else {
+                        f.write_str(MISSING_VALUE)?;
+                    }
                 }
 
                 Ok(())
@@ -168,7 +185,13 @@ fn parse_i32(s: &str) -> Result<Value, ParseError> {
 
 fn parse_i32_array(s: &str) -> Result<Value, ParseError> {
     s.split(DELIMITER)
-        .map(|s| s.parse().map_err(ParseError::InvalidInteger))
+        .map(|t| {
+            if t == MISSING_VALUE {
+                Ok(None)
+            } else {
+                t.parse().map(Some).map_err(ParseError::InvalidInteger)
+            }
+        })
         .collect::<Result<_, _>>()
         .map(Value::IntegerArray)
 }
@@ -181,7 +204,15 @@ fn parse_f32(s: &str) -> Result<Value, ParseError> {
 
 fn parse_f32_array(s: &str) -> Result<Value, ParseError> {
     s.split(DELIMITER)
-        .map(|s| parse_f32_case_insensitive_extended(s).map_err(ParseError::InvalidFloat))
+        .map(|t| {
+            if t == MISSING_VALUE {
+                Ok(None)
+            } else {
+                parse_f32_case_insensitive_extended(t)
+                    .map(Some)
+                    .map_err(ParseError::InvalidFloat)
+            }
+        })
         .collect::<Result<_, _>>()
         .map(Value::FloatArray)
 }
@@ -212,7 +243,13 @@ fn parse_char(s: &str) -> Result<Value, ParseError> {
 
 fn parse_char_array(s: &str) -> Result<Value, ParseError> {
     s.split(DELIMITER)
-        .map(parse_raw_char)
+        .map(|t| {
+            if t == MISSING_VALUE {
+                Ok(None)
+            } else {
+                parse_raw_char(t).map(Some)
+            }
+        })
         .collect::<Result<_, _>>()
         .map(Value::CharacterArray)
 }
@@ -222,7 +259,16 @@ fn parse_string(s: &str) -> Value {
 }
 
 fn parse_string_array(s: &str) -> Value {
-    let values = s.split(DELIMITER).map(|t| t.into()).collect();
+    let values = s
+        .split(DELIMITER)
+        .map(|t| {
+            if t == MISSING_VALUE {
+                None
+            } else {
+                Some(t.into())
+            }
+        })
+        .collect();
     Value::StringArray(values)
 }
 
@@ -246,28 +292,34 @@ mod tests {
         let value = Value::String(String::from("noodles"));
         assert_eq!(value.to_string(), "noodles");
 
-        let value = Value::IntegerArray(vec![2]);
+        let value = Value::IntegerArray(vec![Some(2)]);
         assert_eq!(value.to_string(), "2");
 
-        let value = Value::IntegerArray(vec![2, 5]);
+        let value = Value::IntegerArray(vec![Some(2), Some(5)]);
         assert_eq!(value.to_string(), "2,5");
 
-        let value = Value::FloatArray(vec![0.333]);
+        let value = Value::IntegerArray(vec![Some(2), None]);
+        assert_eq!(value.to_string(), "2,.");
+
+        let value = Value::FloatArray(vec![Some(0.333)]);
         assert_eq!(value.to_string(), "0.333");
 
-        let value = Value::FloatArray(vec![0.333, 0.667]);
+        let value = Value::FloatArray(vec![Some(0.333), Some(0.667)]);
         assert_eq!(value.to_string(), "0.333,0.667");
 
-        let value = Value::CharacterArray(vec!['n']);
+        let value = Value::CharacterArray(vec![Some('n')]);
         assert_eq!(value.to_string(), "n");
 
-        let value = Value::CharacterArray(vec!['n', 'd', 'l', 's']);
+        let value = Value::CharacterArray(vec![Some('n'), Some('d'), Some('l'), Some('s')]);
         assert_eq!(value.to_string(), "n,d,l,s");
 
-        let value = Value::StringArray(vec![String::from("noodles")]);
+        let value = Value::StringArray(vec![Some(String::from("noodles"))]);
         assert_eq!(value.to_string(), "noodles");
 
-        let value = Value::StringArray(vec![String::from("noodles"), String::from("vcf")]);
+        let value = Value::StringArray(vec![
+            Some(String::from("noodles")),
+            Some(String::from("vcf")),
+        ]);
         assert_eq!(value.to_string(), "noodles,vcf");
     }
 
@@ -288,7 +340,11 @@ mod tests {
         let key = Key::Other(String::from("I32"), Number::Count(2), Type::Integer);
         assert_eq!(
             Value::from_str_key("8,13", &key),
-            Ok(Value::IntegerArray(vec![8, 13])),
+            Ok(Value::IntegerArray(vec![Some(8), Some(13)])),
+        );
+        assert_eq!(
+            Value::from_str_key("8,.", &key),
+            Ok(Value::IntegerArray(vec![Some(8), None])),
         );
     }
 
@@ -309,7 +365,11 @@ mod tests {
         let key = Key::Other(String::from("F32"), Number::Count(2), Type::Float);
         assert_eq!(
             Value::from_str_key("0.333,0.667", &key),
-            Ok(Value::FloatArray(vec![0.333, 0.667]))
+            Ok(Value::FloatArray(vec![Some(0.333), Some(0.667)]))
+        );
+        assert_eq!(
+            Value::from_str_key(".,0.667", &key),
+            Ok(Value::FloatArray(vec![None, Some(0.667)]))
         );
     }
 
@@ -351,7 +411,12 @@ mod tests {
         let key = Key::Other(String::from("CHAR"), Number::Count(2), Type::Character);
         assert_eq!(
             Value::from_str_key("n,d,l,s", &key),
-            Ok(Value::CharacterArray(vec!['n', 'd', 'l', 's']))
+            Ok(Value::CharacterArray(vec![
+                Some('n'),
+                Some('d'),
+                Some('l'),
+                Some('s')
+            ]))
         );
     }
 
@@ -376,8 +441,15 @@ mod tests {
         assert_eq!(
             Value::from_str_key("noodles,vcf", &key),
             Ok(Value::StringArray(vec![
-                String::from("noodles"),
-                String::from("vcf")
+                Some(String::from("noodles")),
+                Some(String::from("vcf"))
+            ]))
+        );
+        assert_eq!(
+            Value::from_str_key("noodles,.", &key),
+            Ok(Value::StringArray(vec![
+                Some(String::from("noodles")),
+                None
             ]))
         );
     }
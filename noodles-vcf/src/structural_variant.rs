@@ -0,0 +1,213 @@
+//! VCF structural variant intervals and breakend mates.
+//!
+//! Structural variants are described by their alternate alleles (e.g., `<DEL>` or a
+//! [`crate::record::alternate_bases::allele::Breakend`]) and the reserved INFO fields `END`,
+//! `SVLEN`, `CIPOS`, `CIEND`, and `MATEID` (see § 3 "INFO keys used for structural variants" of
+//! VCF 4.3).
+
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use crate::{
+    record::info::field::{Key, Value},
+    Record,
+};
+
+/// Returns the interval on the reference sequence covered by the variant of a record.
+///
+/// The interval starts at the record position and ends at [`Record::end`], i.e., the end
+/// position (`END`), if set; the start position plus the structural variant length (`SVLEN`) of
+/// a symbolic deletion, duplication, inversion, or copy number variation; or the last base of the
+/// reference bases. Insertions (`<INS>`) do not span any bases past the position.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, structural_variant};
+///
+/// let record: vcf::Record = "sq0\t8\t.\tACGT\tA\t.\tPASS\t.".parse()?;
+/// assert_eq!(structural_variant::reference_interval(&record), 8..=11);
+///
+/// let record: vcf::Record = "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tSVLEN=-5".parse()?;
+/// assert_eq!(structural_variant::reference_interval(&record), 8..=13);
+///
+/// let record: vcf::Record = "sq0\t8\t.\tA\t<INS>\t.\tPASS\tSVLEN=21".parse()?;
+/// assert_eq!(structural_variant::reference_interval(&record), 8..=8);
+/// # Ok::<(), vcf::record::ParseError>(())
+/// ```
+pub fn reference_interval(record: &Record) -> RangeInclusive<i32> {
+    record.position()..=record.end()
+}
+
+/// Returns the confidence interval around the position of a record (`CIPOS`).
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, structural_variant};
+///
+/// let record: vcf::Record = "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tSVLEN=-5;CIPOS=-2,3".parse()?;
+/// assert_eq!(structural_variant::position_confidence_interval(&record), Some(6..=11));
+///
+/// let record: vcf::Record = "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tSVLEN=-5".parse()?;
+/// assert!(structural_variant::position_confidence_interval(&record).is_none());
+/// # Ok::<(), vcf::record::ParseError>(())
+/// ```
+pub fn position_confidence_interval(record: &Record) -> Option<RangeInclusive<i32>> {
+    confidence_interval(
+        record,
+        &Key::PositionConfidenceIntervals,
+        *reference_interval(record).start(),
+    )
+}
+
+/// Returns the confidence interval around the end position of a record (`CIEND`).
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, structural_variant};
+///
+/// let record: vcf::Record = "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tSVLEN=-5;CIEND=-1,1".parse()?;
+/// assert_eq!(structural_variant::end_confidence_interval(&record), Some(12..=14));
+/// # Ok::<(), vcf::record::ParseError>(())
+/// ```
+pub fn end_confidence_interval(record: &Record) -> Option<RangeInclusive<i32>> {
+    confidence_interval(
+        record,
+        &Key::EndConfidenceIntervals,
+        *reference_interval(record).end(),
+    )
+}
+
+/// Pairs records that are breakend mates.
+///
+/// Records are linked by their IDs and the IDs in their mate breakend IDs (`MATEID`). A link in
+/// either direction pairs the records, i.e., only one of the mates needs to list the other. Each
+/// pair is returned once, in input order. Records with mates not in the given records are not
+/// paired.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{self as vcf, structural_variant};
+///
+/// let records: Vec<vcf::Record> = vec![
+///     "sq0\t8\tbnd0\tA\tA[sq1:13[\t.\tPASS\tSVTYPE=BND;MATEID=bnd1".parse()?,
+///     "sq0\t21\tbnd2\tC\tC.\t.\tPASS\tSVTYPE=BND".parse()?,
+///     "sq1\t13\tbnd1\tG\t]sq0:8]G\t.\tPASS\tSVTYPE=BND;MATEID=bnd0".parse()?,
+/// ];
+///
+/// let pairs = structural_variant::pair_mates(&records);
+///
+/// assert_eq!(pairs.len(), 1);
+/// assert_eq!(pairs[0], (&records[0], &records[2]));
+/// # Ok::<(), vcf::record::ParseError>(())
+/// ```
+pub fn pair_mates(records: &[Record]) -> Vec<(&Record, &Record)> {
+    let mut indices = HashMap::new();
+
+    for (i, record) in records.iter().enumerate() {
+        for id in record.ids().iter() {
+            indices.entry(id.as_str()).or_insert(i);
+        }
+    }
+
+    let mut pairs = Vec::new();
+
+    for (i, record) in records.iter().enumerate() {
+        for mate_id in mate_ids(record) {
+            if let Some(&j) = indices.get(mate_id) {
+                if i != j {
+                    pairs.push((i.min(j), i.max(j)));
+                }
+            }
+        }
+    }
+
+    pairs.sort_unstable();
+    pairs.dedup();
+
+    pairs
+        .into_iter()
+        .map(|(i, j)| (&records[i], &records[j]))
+        .collect()
+}
+
+fn confidence_interval(record: &Record, key: &Key, position: i32) -> Option<RangeInclusive<i32>> {
+    let field = record.info().iter().find(|field| field.key() == key)?;
+
+    match field.value() {
+        Some(Value::IntegerArray(values)) => match values[..] {
+            [Some(start), Some(end)] => Some(position + start..=position + end),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn mate_ids(record: &Record) -> Vec<&str> {
+    let field = record
+        .info()
+        .iter()
+        .find(|field| field.key() == &Key::MateBreakendIds);
+
+    match field.and_then(|f| f.value()) {
+        Some(Value::String(id)) => vec![id.as_str()],
+        Some(Value::StringArray(ids)) => ids.iter().flatten().map(|id| id.as_str()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "sq0\t8\t.\tA\tC\t.\tPASS\t.".parse()?;
+        assert_eq!(reference_interval(&record), 8..=8);
+
+        let record: Record = "sq0\t8\t.\tA\t<DUP>\t.\tPASS\tEND=21;SVLEN=5".parse()?;
+        assert_eq!(reference_interval(&record), 8..=21);
+
+        let record: Record = "sq0\t8\t.\tA\t<INS>\t.\tPASS\tSVLEN=5".parse()?;
+        assert_eq!(reference_interval(&record), 8..=8);
+
+        let record: Record = "sq0\t8\t.\tA\tA[sq1:13[\t.\tPASS\tSVTYPE=BND".parse()?;
+        assert_eq!(reference_interval(&record), 8..=8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pair_mates() -> Result<(), Box<dyn std::error::Error>> {
+        let records: Vec<Record> = vec![
+            "sq0\t8\tbnd0\tA\tA[sq1:13[,A]sq2:5]\t.\tPASS\tMATEID=bnd1,bnd2".parse()?,
+            "sq1\t13\tbnd1\tG\t]sq0:8]G\t.\tPASS\tMATEID=bnd0".parse()?,
+            "sq2\t5\tbnd2\tT\tT[sq0:8[\t.\tPASS\tMATEID=bnd0".parse()?,
+            "sq2\t21\tbnd3\tC\tC[sq3:1[\t.\tPASS\tMATEID=bnd4".parse()?,
+        ];
+
+        let actual = pair_mates(&records);
+        let expected = [(&records[0], &records[1]), (&records[0], &records[2])];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pair_mates_with_one_sided_mate_id() -> Result<(), Box<dyn std::error::Error>> {
+        let records: Vec<Record> = vec![
+            "sq0\t8\tbnd0\tA\tA[sq1:13[\t.\tPASS\tSVTYPE=BND".parse()?,
+            "sq0\t21\tbnd2\tC\tC[sq2:5[\t.\tPASS\tSVTYPE=BND;MATEID=bnd3".parse()?,
+            "sq1\t13\tbnd1\tG\t]sq0:8]G\t.\tPASS\tSVTYPE=BND;MATEID=bnd0".parse()?,
+            "sq2\t5\tbnd3\tT\t]sq0:21]T\t.\tPASS\tSVTYPE=BND;MATEID=bnd2".parse()?,
+        ];
+
+        let actual = pair_mates(&records);
+        let expected = [(&records[0], &records[2]), (&records[1], &records[3])];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use std::io::{self, Write};
 
-use super::{Header, Record};
+use super::{lazy, Header, Record};
 
 /// A VCF writer.
 ///
@@ -69,6 +69,32 @@ where
         &self.inner
     }
 
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let mut writer = vcf::Writer::new(Vec::new());
+    /// assert!(writer.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps and returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let writer = vcf::Writer::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
     /// Writes a VCF header.
     ///
     /// # Examples
@@ -128,6 +154,27 @@ where
 
         writeln!(self.inner)
     }
+
+    /// Writes a lazy VCF record.
+    ///
+    /// The raw record is written as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, lazy};
+    ///
+    /// let record: lazy::Record = "sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0".parse()?;
+    ///
+    /// let mut writer = vcf::Writer::new(Vec::new());
+    /// writer.write_lazy_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"sq0\t1\t.\tA\t.\t.\tPASS\t.\tGT\t0|0\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_lazy_record(&mut self, record: &lazy::Record) -> io::Result<()> {
+        writeln!(self.inner, "{}", record)
+    }
 }
 
 #[cfg(test)]